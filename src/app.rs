@@ -1,10 +1,65 @@
 use anyhow::Result;
-use std::fs;
-use std::time::Instant;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::config::MonitorDatabase;
-use crate::monitor::{fetch_monitors, identify_monitors, MonitorConfig, Rotation};
-use crate::state::{DialogType, DragState, FocusPanel, MainTab, SettingField};
+use crate::backups::{self, Backup};
+use crate::clipboard;
+use crate::clock;
+use crate::compare::{self, FieldDiff};
+use crate::config::{
+    self, suggest_workspace_names, MonitorDatabase, SavedMonitor, MAX_NICKNAME,
+    MAX_WORKSPACE_NAME,
+};
+use crate::diagram;
+use crate::drm;
+use crate::edid::{self, Edid};
+use crate::exec::{self, Executor};
+use crate::history::{HistoryEntry, HistoryLog, MonitorChange};
+use crate::hypr_ipc::AddedMonitor;
+use crate::i18n::{set_lang, tr, tr_fill, Lang};
+use crate::keymap::KeyMap;
+use crate::monitor::{
+    config_errors, fetch_monitor_details, fetch_monitors, fetch_physical_sizes, fetch_workspaces,
+    focus_monitor, focused_monitor, identify_monitors, identify_targets, monitor_colors, reload_hyprland, set_dpms,
+    set_monitor_rule, swap_active_workspaces, workspaces_on, ConfigError,
+    HyprWorkspace, KeyBy, ModePreset, MonitorConfig, MonitorDetails, Rotation, RELOAD_ECHO,
+};
+use crate::prefs::{IdentifyPrefs, Prefs};
+use crate::presentation;
+use crate::presets::{self, Preset, Presets};
+use crate::safe;
+use crate::session::Session;
+use crate::state::{
+    ApplyStatus, DialogButton, DialogType, DragState, FocusPanel, MainTab, SettingField,
+    SettingGroup,
+};
+use crate::toast::Toasts;
+use crate::ui::theme::{set_theme, Theme};
+use crate::ui::{HitMap, TextInput, BOX_GAP, BOX_WIDTH};
+use crate::undo::UndoStack;
+
+/// Last dropdown option of Resolution and Scale; picking it opens a typed entry.
+pub const CUSTOM_OPTION: &str = "Custom…";
+
+/// Joins a refresh rate offered only at another resolution to that
+/// resolution in the Refresh Rate dropdown: `165Hz @ 2560x1440`.
+pub const OTHER_RES: &str = " @ ";
+
+/// Entry for a workspace name: never blank, at most `MAX_WORKSPACE_NAME` graphemes.
+pub fn workspace_name_input(name: &str) -> TextInput {
+    TextInput::new(name, MAX_WORKSPACE_NAME).validated(|s| {
+        if s.trim().is_empty() {
+            anyhow::bail!("Name cannot be empty");
+        }
+        Ok(())
+    })
+}
+
+/// Draws `App` as it stands; see [`App::progress`].
+pub type Progress = Box<dyn FnMut(&App)>;
 
 pub struct App {
     // Live panel state
@@ -13,6 +68,34 @@ pub struct App {
     pub selected_monitor: usize,
     pub focus_panel: FocusPanel,
     pub selected_setting: usize,
+    /// Settings groups folded down to their heading.
+    pub collapsed_groups: Vec<SettingGroup>,
+    pub undo_stack: UndoStack<Vec<MonitorConfig>>,
+    /// Layout most recently written to Hyprland; the "before" side of the
+    /// audit log entry recorded on the next apply.
+    pub applied_monitors: Vec<MonitorConfig>,
+    /// What the last apply changed, listed while it waits for confirmation.
+    pub applied_changes: Vec<MonitorChange>,
+    /// Monitors Hyprland runs otherwise than the last apply asked, and how,
+    /// from [`compare::achieved_mismatches`]; flagged in the same prompt.
+    pub apply_mismatches: Vec<(String, String)>,
+    /// Each managed monitor's outcome in the last apply, by connector.
+    pub apply_status: Vec<(String, ApplyStatus)>,
+    /// What Hyprland ran at the last poll, to tell changes made outside
+    /// hyprmon from its own. `None` after hyprmon changed something itself:
+    /// the next poll only takes a fresh look.
+    pub hypr_seen: Option<Vec<MonitorConfig>>,
+    /// Changes made outside hyprmon, offered for adopting or undoing.
+    pub external_changes: Vec<MonitorChange>,
+    /// Cached Hyprland state: monitors as `hyprctl` last reported them, before
+    /// any saved settings are laid over, kept current by hotplug events.
+    /// Drives the out-of-sync badge and rebuilds the Live layout on hotplug.
+    pub hypr_monitors: Vec<MonitorConfig>,
+    /// Hyprland workspaces and the monitors they are on, refreshed when
+    /// workspaces or windows come, go or move.
+    pub hypr_workspaces: Vec<HyprWorkspace>,
+    /// Connector Hyprland has focus on, kept current by `focusedmon` events.
+    pub focused_monitor: Option<String>,
 
     // Saved panel state
     pub saved_monitors: Vec<MonitorConfig>,
@@ -23,39 +106,155 @@ pub struct App {
     // Common state
     pub main_tab: MainTab,
     pub dialog: DialogType,
+    /// User key rebindings for the main view.
+    pub keymap: KeyMap,
+    /// What was drawn where last frame, for mouse clicks.
+    pub hits: HitMap,
     pub dropdown_selection: usize,
+    /// Rows ticked in the import picker, as indices into
+    /// [`App::import_candidates`].
+    pub import_picks: Vec<usize>,
     pub has_changes: bool,
-    pub message: String,
+    /// Status messages: on-screen toasts plus their history.
+    pub toasts: Toasts,
+    /// Whether the Hyprland event socket is still being listened to.
+    pub ipc_connected: bool,
     pub drag_state: DragState,
     pub monitor_db: MonitorDatabase,
+    /// Type-ahead filter of the dropdown and query of the command palette.
     pub input_buffer: String,
+    /// Leave [`config::is_exotic_resolution`] modes out of the Resolution
+    /// dropdown (`Tab` there); for this session only.
+    pub hide_exotic_modes: bool,
+    /// Text being edited in the open input dialog.
+    pub text_input: TextInput,
+    /// Name candidates offered when creating a workspace; Tab cycles them.
+    pub name_suggestions: Vec<String>,
+    pub suggestion_idx: usize,
+    pub history_log: HistoryLog,
+    /// Hyprland config file hyprmon writes its managed block into.
+    pub hypr_conf_path: PathBuf,
+    /// Database as it was before a Saved-tab apply, restored on revert.
+    pub pre_apply_db: Option<MonitorDatabase>,
+    /// Entries shown by the history viewer, newest first.
+    pub history_entries: Vec<HistoryEntry>,
+    /// What Hyprland complained about after the last apply.
+    pub config_errors: Vec<ConfigError>,
+    /// Facts shown by the monitor details dialog while it is open.
+    pub monitor_details: Option<MonitorDetails>,
+    /// Lines of the generated-config viewer while it is open, each prefixed
+    /// with `+` when monitors.conf on disk lacks it, else a space.
+    pub config_preview: Vec<String>,
+    /// Kept monitors.conf versions, newest first, while the rollback picker
+    /// is open.
+    pub backups: Vec<Backup>,
+    pub identify_prefs: IdentifyPrefs,
+    /// Live selection as of the last `identify_selection_change` call.
+    last_selected_monitor: usize,
+    /// Runs `hyprctl` and friends; tests put a [`exec::Replay`] here.
+    pub exec: Arc<dyn Executor>,
+    /// Connector of the monitor in game mode, until monitors.conf is reloaded.
+    pub game_mode: Option<String>,
+    /// Connector mirrored everywhere in presentation mode, until monitors.conf
+    /// is reloaded.
+    pub presenting: Option<String>,
+    /// Connector forced to [`presentation::PROJECTOR_MODE`], until it is
+    /// unplugged or monitors.conf is reloaded.
+    pub projector: Option<String>,
+    /// When hyprmon last asked Hyprland to reload; see [`RELOAD_ECHO`].
+    pub reloaded_at: Option<Instant>,
+    /// Apply saved settings when a monitor is plugged in. Off for this
+    /// session only, with `Ctrl+p` or `--no-auto-apply`; never saved.
+    pub auto_apply: bool,
+    /// Known-good settings per monitor model, suggested with `s`.
+    pub presets: Presets,
+    /// Draws the screen mid-apply, so [`ApplyStatus`] shows each step as it
+    /// happens; the TUI sets it, tests and plain mode leave it empty.
+    pub progress: Option<Progress>,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
+        // The language goes first so every message below comes in it
+        let prefs = Prefs::load();
+        let lang = Lang::from_prefs(prefs.as_ref().unwrap_or(&Prefs::default()));
+        set_lang(*lang.as_ref().unwrap_or(&Lang::En));
+        let exec = exec::system();
         let mut monitor_db = MonitorDatabase::load().unwrap_or_default();
-        let mut monitors = fetch_monitors()?;
+        let mut hypr_monitors = fetch_monitors(&*exec)?;
+        monitor_db.assign_keys(&mut hypr_monitors);
+        let mut monitors = hypr_monitors.clone();
 
-        // Find best matching workspace for connected monitors
-        if let Some(ws_idx) = monitor_db.find_best_workspace(&monitors) {
-            monitor_db.active_workspace = ws_idx;
-        }
+        // Find best matching workspace for connected monitors (unless locked)
+        monitor_db.auto_select(&monitors);
 
         // Apply saved configs to connected monitors
         for monitor in &mut monitors {
             monitor_db.apply_saved_config(monitor);
         }
 
-        let original = monitors.clone();
-        let selected_workspace = monitor_db.active_workspace;
+        let mut app = Self::from_parts(monitors, monitor_db, exec);
+        app.hypr_monitors = hypr_monitors;
+        app.refresh_workspaces();
+        app.focused_monitor = focused_monitor(&*app.exec);
+        let prefs = prefs.unwrap_or_else(|e| {
+            app.toasts.warn(tr_fill("Config: {} (using defaults)", &[&format!("{e:#}")]));
+            Prefs::default()
+        });
+        match KeyMap::from_prefs(&prefs) {
+            Ok(keymap) => app.keymap = keymap,
+            Err(e) => {
+                app.toasts.warn(tr_fill("Config: {} (using default keys)", &[&format!("{e:#}")]))
+            }
+        }
+        app.identify_prefs = prefs.identify;
+        match Theme::from_prefs(&prefs) {
+            Ok(theme) => set_theme(theme),
+            Err(e) => {
+                app.toasts.warn(tr_fill("Config: {} (using default theme)", &[&format!("{e:#}")]))
+            }
+        }
+        if let Err(e) = lang {
+            app.toasts.warn(tr_fill("Config: {} (using English)", &[&format!("{e:#}")]));
+        }
+        match Presets::load() {
+            Ok(presets) => app.presets = presets,
+            Err(e) => app.toasts.warn(tr_fill(
+                "Presets: {} (using the bundled ones)",
+                &[&format!("{e:#}")],
+            )),
+        }
+        Ok(app)
+    }
+
+    /// Assemble an `App` from already-loaded monitors and database. Shared by
+    /// `new()` and the test constructors so every field has one default.
+    fn from_parts(
+        monitors: Vec<MonitorConfig>,
+        monitor_db: MonitorDatabase,
+        exec: Arc<dyn Executor>,
+    ) -> Self {
+        let selected_workspace = monitor_db.active_workspace();
         let saved_monitors = monitor_db.get_workspace_monitors(selected_workspace);
 
-        Ok(Self {
+        Self {
+            original_monitors: monitors.clone(),
+            applied_monitors: monitors.clone(),
+            applied_changes: Vec::new(),
+            apply_mismatches: Vec::new(),
+            apply_status: Vec::new(),
+            progress: None,
+            hypr_seen: None,
+            external_changes: Vec::new(),
+            hypr_monitors: monitors.clone(),
+            hypr_workspaces: Vec::new(),
+            focused_monitor: None,
             monitors,
-            original_monitors: original,
             selected_monitor: 0,
             focus_panel: FocusPanel::Arrangement,
             selected_setting: 0,
+            collapsed_groups: Vec::new(),
+            undo_stack: UndoStack::default(),
 
             saved_monitors,
             saved_selected_monitor: 0,
@@ -64,19 +263,45 @@ impl App {
 
             main_tab: MainTab::Live,
             dialog: DialogType::None,
+            keymap: KeyMap::default(),
+            hits: HitMap::default(),
             dropdown_selection: 0,
+            import_picks: Vec::new(),
             has_changes: false,
-            message: String::new(),
+            toasts: Toasts::default(),
+            ipc_connected: false,
             drag_state: DragState::None,
             monitor_db,
             input_buffer: String::new(),
-        })
+            text_input: TextInput::default(),
+            name_suggestions: Vec::new(),
+            suggestion_idx: 0,
+            history_log: HistoryLog::default(),
+            hypr_conf_path: MonitorDatabase::hypr_conf_path(),
+            pre_apply_db: None,
+            history_entries: Vec::new(),
+            config_errors: Vec::new(),
+            monitor_details: None,
+            config_preview: Vec::new(),
+            backups: Vec::new(),
+            identify_prefs: IdentifyPrefs::default(),
+            last_selected_monitor: 0,
+            exec,
+            game_mode: None,
+            presenting: None,
+            projector: None,
+            hide_exotic_modes: false,
+            reloaded_at: None,
+            auto_apply: true,
+            presets: Presets::bundled(),
+        }
     }
 
     /// Switch main tab
     pub fn switch_tab(&mut self, tab: MainTab) {
         // Dropdown is only valid in Live panel; close it when changing tabs.
         if self.main_tab != tab && matches!(self.dialog, DialogType::EditDropdown) {
+            self.input_buffer.clear();
             self.dialog = DialogType::None;
         }
         self.main_tab = tab;
@@ -85,6 +310,42 @@ impl App {
         }
     }
 
+    /// Where the UI is now, to be restored by the next run.
+    pub fn session(&self) -> Session {
+        Session {
+            tab: Some(self.main_tab),
+            focus: Some(self.focus_panel),
+            workspace: self
+                .monitor_db
+                .workspaces
+                .get(self.selected_workspace)
+                .map(|ws| ws.id.clone()),
+            monitor: self.current_monitor().map(|m| m.name.clone()),
+            saved_monitor: self.saved_selected_monitor,
+        }
+    }
+
+    /// Go back to where a previous run left off, skipping anything that no
+    /// longer exists. The active workspace still comes from the database.
+    pub fn restore_session(&mut self, session: &Session) {
+        if let Some(focus) = session.focus {
+            self.focus_panel = focus;
+        }
+        if let Some(name) = &session.monitor {
+            if let Some(idx) = self.monitors.iter().position(|m| &m.name == name) {
+                self.selected_monitor = idx;
+                self.last_selected_monitor = idx;
+            }
+        }
+        let workspace = session.workspace.as_deref();
+        if let Some(idx) = workspace.and_then(|w| self.monitor_db.find_workspace(w)) {
+            self.selected_workspace = idx;
+        }
+        self.saved_selected_monitor = session.saved_monitor;
+        self.switch_tab(session.tab.unwrap_or(self.main_tab));
+        self.refresh_saved_monitors();
+    }
+
     /// Refresh saved monitors from current workspace
     pub fn refresh_saved_monitors(&mut self) {
         self.saved_monitors = self
@@ -98,742 +359,3905 @@ impl App {
     /// Select next workspace
     pub fn next_workspace(&mut self) {
         if self.selected_workspace < self.monitor_db.workspaces.len() - 1 {
-            self.selected_workspace += 1;
-            self.monitor_db.active_workspace = self.selected_workspace;
-            self.refresh_saved_monitors();
+            self.select_workspace(self.selected_workspace + 1);
         }
     }
 
     /// Select previous workspace
     pub fn prev_workspace(&mut self) {
         if self.selected_workspace > 0 {
-            self.selected_workspace -= 1;
-            self.monitor_db.active_workspace = self.selected_workspace;
+            self.select_workspace(self.selected_workspace - 1);
+        }
+    }
+
+    /// Select workspace `idx`, as a click on its tab does
+    pub fn select_workspace(&mut self, idx: usize) {
+        if idx < self.monitor_db.workspaces.len() {
+            self.selected_workspace = idx;
+            self.monitor_db.set_active(idx);
             self.refresh_saved_monitors();
         }
     }
 
-    /// Create new workspace
-    pub fn create_workspace(&mut self, name: &str) {
-        let idx = self.monitor_db.add_workspace(name);
+    /// Create new workspace; fails on a blank or taken name
+    pub fn create_workspace(&mut self, name: &str) -> Result<()> {
+        let idx = self.monitor_db.add_workspace(name)?;
         self.selected_workspace = idx;
-        self.monitor_db.active_workspace = idx;
+        self.monitor_db.set_active(idx);
         let _ = self.monitor_db.save();
         self.refresh_saved_monitors();
-        self.message = format!("Created workspace: {}", name);
+        self.toasts.info(tr_fill("Created workspace: {}", &[&self.current_workspace_name()]));
+        Ok(())
     }
 
-    /// Delete current workspace
-    pub fn delete_current_workspace(&mut self) -> bool {
-        if self.monitor_db.delete_workspace(self.selected_workspace) {
-            self.selected_workspace = self
-                .selected_workspace
-                .min(self.monitor_db.workspaces.len().saturating_sub(1));
-            self.monitor_db.active_workspace = self.selected_workspace;
+    /// Open a workspace-name dialog pre-filled with a name derived from the
+    /// connected monitors.
+    pub fn begin_workspace_naming(&mut self, dialog: DialogType) {
+        let existing: Vec<String> = self
+            .monitor_db
+            .workspaces
+            .iter()
+            .map(|ws| ws.name.clone())
+            .collect();
+        self.name_suggestions = suggest_workspace_names(&self.monitors, &existing);
+        self.suggestion_idx = 0;
+        let first = self.name_suggestions.first().cloned().unwrap_or_default();
+        // Selected, so typing a name of one's own replaces the suggestion
+        self.text_input = workspace_name_input(&first);
+        self.text_input.select_all();
+        self.dialog = dialog;
+    }
+
+    /// Replace the typed name with the next suggestion.
+    pub fn cycle_name_suggestion(&mut self) {
+        if self.name_suggestions.is_empty() {
+            return;
+        }
+        self.suggestion_idx = (self.suggestion_idx + 1) % self.name_suggestions.len();
+        self.text_input.set_text(&self.name_suggestions[self.suggestion_idx]);
+        self.text_input.select_all();
+    }
+
+    /// Duplicate current workspace under `name` and select the copy
+    pub fn duplicate_current_workspace(&mut self, name: &str) -> Result<()> {
+        let source = self.current_workspace_name();
+        let idx = self
+            .monitor_db
+            .duplicate_workspace(self.selected_workspace, name)?;
+        self.selected_workspace = idx;
+        self.monitor_db.set_active(idx);
+        let _ = self.monitor_db.save();
+        self.refresh_saved_monitors();
+        self.toasts.info(tr_fill(
+            "Duplicated '{}' as '{}'",
+            &[&source, &self.current_workspace_name()],
+        ));
+        Ok(())
+    }
+
+    /// Move the selected workspace one tab left/right and persist the order
+    pub fn move_current_workspace(&mut self, delta: isize) {
+        let idx = self
+            .monitor_db
+            .move_workspace(self.selected_workspace, delta);
+        self.follow_reordered_workspace(idx);
+    }
+
+    /// Pin/unpin the selected workspace (pinned tabs sort to the front)
+    pub fn toggle_pin_current_workspace(&mut self) {
+        let idx = self.monitor_db.toggle_pin(self.selected_workspace);
+        let pinned = self.monitor_db.workspaces[idx].pinned;
+        self.follow_reordered_workspace(idx);
+        let text = if pinned { "Pinned '{}'" } else { "Unpinned '{}'" };
+        self.toasts.info(tr_fill(text, &[&self.current_workspace_name()]));
+    }
+
+    /// Lock or unlock the active workspace against startup/hotplug matching.
+    pub fn toggle_lock_active(&mut self) {
+        self.monitor_db.lock_active = !self.monitor_db.lock_active;
+        let _ = self.monitor_db.save();
+        if self.monitor_db.lock_active {
+            self.toasts.info(tr_fill(
+                "Locked on '{}' - monitor changes won't switch workspace",
+                &[&self.active_workspace_name()],
+            ));
+        } else {
+            self.toasts.info(tr("Unlocked - workspace follows connected monitors"));
+        }
+    }
+
+    /// Pause or resume applying saved settings on hotplug, for this session.
+    pub fn toggle_auto_apply(&mut self) {
+        self.auto_apply = !self.auto_apply;
+        if self.auto_apply {
+            self.toasts.info(tr("Auto-apply on - plugged-in monitors get their saved settings"));
+        } else {
+            self.toasts
+                .info(tr("Auto-apply paused - plugged-in monitors are left as they come up"));
+        }
+    }
+
+    /// Set the selected workspace's note (empty clears it).
+    pub fn set_current_workspace_description(&mut self, text: &str) {
+        if let Some(ws) = self.monitor_db.workspaces.get_mut(self.selected_workspace) {
+            ws.description = text.trim().to_string();
+            self.toasts.info(tr_fill("Updated notes for '{}'", &[&ws.name]));
             let _ = self.monitor_db.save();
-            self.refresh_saved_monitors();
-            self.message = "Workspace deleted".to_string();
-            true
+        }
+    }
+
+    /// Flip the selected workspace's "never auto-apply" flag.
+    pub fn toggle_manual_only_current_workspace(&mut self) {
+        let Some(ws) = self.monitor_db.workspaces.get_mut(self.selected_workspace) else {
+            return;
+        };
+        ws.manual_only = !ws.manual_only;
+        if ws.manual_only {
+            self.toasts.info(tr_fill("'{}' will only be applied manually", &[&ws.name]));
         } else {
-            self.message = "Cannot delete last workspace".to_string();
-            false
+            self.toasts.info(tr_fill("'{}' can be auto-applied again", &[&ws.name]));
         }
+        let _ = self.monitor_db.save();
     }
 
-    /// Rename current workspace
-    pub fn rename_current_workspace(&mut self, name: &str) {
-        self.monitor_db
-            .rename_workspace(self.selected_workspace, name);
+    fn follow_reordered_workspace(&mut self, idx: usize) {
+        self.selected_workspace = idx;
+        self.monitor_db.set_active(idx);
         let _ = self.monitor_db.save();
-        self.message = format!("Renamed to: {}", name);
     }
 
-    /// Get current workspace name
-    pub fn current_workspace_name(&self) -> String {
+    /// Move workspace `idx` to where its tab was dropped, on tab `over`.
+    /// Pinned workspaces stay ahead of the others, so a tab dropped across
+    /// that boundary stops at it.
+    fn drop_workspace(&mut self, idx: usize, over: usize) {
+        if idx == over {
+            return;
+        }
+        let moved = self.monitor_db.move_workspace_to(idx, over);
+        self.follow_reordered_workspace(moved);
+        if moved != over {
+            self.toasts.info(tr("Pinned workspaces stay ahead of the others"));
+        }
+    }
+
+    /// Picker entries for "Save as…": every workspace, then a create-new row.
+    pub fn save_as_options(&self) -> Vec<String> {
         self.monitor_db
             .workspaces
-            .get(self.selected_workspace)
+            .iter()
             .map(|ws| ws.name.clone())
-            .unwrap_or_default()
+            .chain(std::iter::once("+ New workspace…".to_string()))
+            .collect()
     }
 
-    pub fn current_monitor(&self) -> Option<&MonitorConfig> {
-        self.monitors.get(self.selected_monitor)
+    /// Capture the current Live layout into workspace `ws_idx` (persisted, not
+    /// applied) and make it the selected workspace, leaving other profiles
+    /// untouched.
+    pub fn save_live_as(&mut self, ws_idx: usize) {
+        if ws_idx >= self.monitor_db.workspaces.len() {
+            return;
+        }
+        for monitor in &self.monitors {
+            self.monitor_db.update_monitor_in(ws_idx, monitor);
+        }
+        self.selected_workspace = ws_idx;
+        self.monitor_db.set_active(ws_idx);
+        let _ = self.monitor_db.save();
+        self.refresh_saved_monitors();
+        self.toasts.info(tr_fill("Saved live layout to '{}'", &[&self.current_workspace_name()]));
     }
 
-    pub fn current_monitor_mut(&mut self) -> Option<&mut MonitorConfig> {
-        self.monitors.get_mut(self.selected_monitor)
+    /// Capture the current Live layout into a newly created workspace
+    pub fn save_live_as_new(&mut self, name: &str) -> Result<()> {
+        let idx = self.monitor_db.add_workspace(name)?;
+        self.save_live_as(idx);
+        Ok(())
     }
 
-    pub fn select_next_monitor(&mut self) {
-        if !self.monitors.is_empty() {
-            self.selected_monitor = (self.selected_monitor + 1) % self.monitors.len();
+    /// Delete current workspace
+    pub fn delete_current_workspace(&mut self) -> bool {
+        if self.monitor_db.is_bundled(self.selected_workspace) {
+            self.toasts.warn(tr("Bundled workspaces cannot be deleted"));
+            return false;
+        }
+        if self.monitor_db.delete_workspace(self.selected_workspace) {
+            self.selected_workspace = self
+                .selected_workspace
+                .min(self.monitor_db.workspaces.len().saturating_sub(1));
+            self.monitor_db.set_active(self.selected_workspace);
+            let _ = self.monitor_db.save();
+            self.refresh_saved_monitors();
+            self.toasts.info(tr("Workspace deleted"));
+            true
+        } else {
+            self.toasts.warn(tr("Cannot delete last workspace"));
+            false
         }
     }
 
-    pub fn select_prev_monitor(&mut self) {
-        if !self.monitors.is_empty() {
-            if self.selected_monitor == 0 {
-                self.selected_monitor = self.monitors.len() - 1;
-            } else {
-                self.selected_monitor -= 1;
-            }
+    /// Re-read what Hyprland is actually running, to catch changes made
+    /// outside hyprmon (e.g. `hyprctl keyword monitor …`).
+    pub fn poll_hyprland(&mut self) {
+        if let Ok(mut monitors) = fetch_monitors(&*self.exec) {
+            self.monitor_db.assign_keys(&mut monitors);
+            self.notice_external_changes(&monitors);
+            self.hypr_monitors = monitors;
         }
+        self.refresh_workspaces();
     }
 
-    pub fn move_monitor_left(&mut self) {
-        if self.selected_monitor == 0 || self.monitors.len() < 2 {
+    /// Offer to reconcile when the monitors Hyprland runs changed since the
+    /// last poll while hyprmon did nothing. Plugging and unplugging are the
+    /// hotplug handlers' business, and focus moving is no change; a change
+    /// seen while another dialog is open waits for it to close.
+    fn notice_external_changes(&mut self, now: &[MonitorConfig]) {
+        if self.dialog != DialogType::None {
             return;
         }
-        self.monitors
-            .swap(self.selected_monitor, self.selected_monitor - 1);
-        self.selected_monitor -= 1;
-        self.recalculate_positions();
-        self.has_changes = true;
-    }
-
-    pub fn move_monitor_right(&mut self) {
-        if self.selected_monitor >= self.monitors.len() - 1 {
+        let Some(seen) = self.hypr_seen.replace(now.to_vec()) else {
+            return;
+        };
+        let names = |list: &[MonitorConfig]| -> HashSet<String> {
+            list.iter().map(|m| m.name.clone()).collect()
+        };
+        if names(&seen) != names(now) {
             return;
         }
-        self.monitors
-            .swap(self.selected_monitor, self.selected_monitor + 1);
-        self.selected_monitor += 1;
-        self.recalculate_positions();
-        self.has_changes = true;
+        let unfocused = |list: &[MonitorConfig]| -> Vec<MonitorConfig> {
+            let mut list = list.to_vec();
+            list.iter_mut().for_each(|m| m.is_primary = false);
+            list
+        };
+        let changes = HistoryEntry::between("", &unfocused(&seen), &unfocused(now)).changes;
+        if !changes.is_empty() {
+            self.external_changes = changes;
+            self.dialog = DialogType::Reconcile { focus: DialogButton::Yes };
+        }
     }
 
-    pub fn recalculate_positions(&mut self) {
-        let mut x = 0i32;
-        for monitor in &mut self.monitors {
-            monitor.position_x = x;
-            monitor.position_y = 0;
-
-            if let Some((w, _)) = monitor.resolution.split_once('x') {
-                if let Ok(width) = w.parse::<i32>() {
-                    x += (width as f64 / monitor.scale) as i32;
+    /// Take the settings changed outside hyprmon into the Live layout and the
+    /// active workspace, and write monitors.conf so a reload keeps them.
+    pub fn adopt_external_changes(&mut self) -> Result<()> {
+        let changed: Vec<MonitorConfig> = self
+            .hypr_monitors
+            .iter()
+            .filter(|m| {
+                let key = MonitorDatabase::get_monitor_key(m);
+                self.external_changes.iter().any(|c| c.monitor == key)
+            })
+            .cloned()
+            .collect();
+        let ws = self.monitor_db.active_workspace();
+        for external in &changed {
+            let lists = [&mut self.monitors, &mut self.original_monitors];
+            for list in lists.into_iter().chain([&mut self.applied_monitors]) {
+                if let Some(m) = list.iter_mut().find(|m| m.name == external.name) {
+                    m.resolution = external.resolution.clone();
+                    m.refresh_rate = external.refresh_rate;
+                    m.mode_preset = None;
+                    m.scale = external.scale;
+                    m.rotation = external.rotation;
+                    m.position_x = external.position_x;
+                    m.position_y = external.position_y;
+                }
+            }
+            if self.monitor_db.is_managed(&MonitorDatabase::get_monitor_key(external)) {
+                if let Some(live) = self.monitors.iter().find(|m| m.name == external.name) {
+                    self.monitor_db.update_monitor_in(ws, live);
                 }
             }
         }
+        self.monitor_db.save()?;
+        self.refresh_saved_monitors();
+        self.monitor_db.write_hypr_config(&self.hypr_conf_path, &self.connected_keys())?;
+        let _ = self.reload();
+        self.dialog = DialogType::None;
+        self.toasts.info(tr_fill(
+            "Adopted the outside change into {}",
+            &[&self.current_workspace_name()],
+        ));
+        Ok(())
     }
 
-    pub fn set_primary(&mut self, index: usize) {
-        for (i, m) in self.monitors.iter_mut().enumerate() {
-            m.is_primary = i == index;
+    /// Put the active workspace back over a change made outside hyprmon:
+    /// monitors.conf still has it, so a reload is enough.
+    pub fn reapply_over_external_changes(&mut self) {
+        let _ = self.reload();
+        self.dialog = DialogType::None;
+        self.poll_hyprland();
+        self.toasts.info(tr_fill("Re-applied {}", &[&self.current_workspace_name()]));
+    }
+
+    /// Re-read which Hyprland workspaces are on which monitor. Keeps the old
+    /// list when `hyprctl` cannot be asked.
+    pub fn refresh_workspaces(&mut self) {
+        if let Ok(workspaces) = fetch_workspaces(&*self.exec) {
+            self.hypr_workspaces = workspaces;
         }
-        self.has_changes = true;
     }
 
-    pub fn toggle_primary(&mut self) {
-        let idx = self.selected_monitor;
-        if idx < self.monitors.len() {
-            let is_currently_primary = self.monitors[idx].is_primary;
+    /// Whether Hyprland's running layout differs from the active workspace.
+    pub fn out_of_sync(&self) -> bool {
+        compare::is_out_of_sync(&compare::diff_against_saved(
+            &self.hypr_monitors,
+            &self.monitor_db,
+        ))
+    }
 
-            if is_currently_primary {
-                // If unchecking, make the first other monitor primary
-                if self.monitors.len() > 1 {
-                    let new_primary = if idx == 0 { 1 } else { 0 };
-                    self.set_primary(new_primary);
-                }
-                // If only one monitor, keep it primary
-            } else {
-                self.set_primary(idx);
+    /// Where the live monitors deviate from the active workspace.
+    pub fn compare_rows(&self) -> Vec<FieldDiff> {
+        compare::diff_against_saved(&self.monitors, &self.monitor_db)
+    }
+
+    /// Write the live value of diff row `row` into the active workspace.
+    pub fn compare_push(&mut self, row: usize) {
+        let Some(diff) = self.compare_rows().into_iter().nth(row) else {
+            return;
+        };
+        let live = self.monitors[diff.monitor].clone();
+        let ws = self.monitor_db.active_workspace();
+        match diff.field {
+            Some(field) => {
+                let Some(saved) = self.monitor_db.get_saved_config(&live) else {
+                    return;
+                };
+                let mut merged = compare::saved_as_live(saved, &live);
+                compare::copy_field(&live, &mut merged, field);
+                self.monitor_db.update_monitor_in(ws, &merged);
             }
+            None => self.monitor_db.update_monitor_in(ws, &live),
         }
+        let _ = self.monitor_db.save();
+        self.refresh_saved_monitors();
+        self.toasts.info(tr_fill("Saved live {} to workspace", &[&diff.key]));
     }
 
-    pub fn get_dropdown_options(&self) -> Vec<String> {
-        let field = SettingField::all()[self.selected_setting];
-        let monitor = match self.current_monitor() {
-            Some(m) => m,
-            None => return vec![],
+    /// Copy the saved value of diff row `row` onto the live monitor as an
+    /// undoable pending edit.
+    pub fn compare_pull(&mut self, row: usize) {
+        let Some(diff) = self.compare_rows().into_iter().nth(row) else {
+            return;
+        };
+        let Some(field) = diff.field else {
+            self.toasts.warn(tr_fill("{} has nothing saved to pull", &[&diff.key]));
+            return;
         };
+        let live = &self.monitors[diff.monitor];
+        let Some(saved) = self.monitor_db.get_saved_config(live) else {
+            return;
+        };
+        let saved = compare::saved_as_live(saved, live);
+        self.checkpoint();
+        if field == SettingField::Primary && saved.is_primary {
+            self.set_primary(diff.monitor);
+        } else {
+            compare::copy_field(&saved, &mut self.monitors[diff.monitor], field);
+        }
+        self.has_changes = self.monitors != self.original_monitors;
+        self.toasts.info(tr_fill("Pulled saved {} into Live (apply to write)", &[&diff.key]));
+    }
 
-        match field {
-            SettingField::Resolution => {
-                let mut resolutions: Vec<String> = monitor
-                    .available_modes
-                    .iter()
-                    .filter_map(|m| m.split_once('@').map(|(res, _)| res.trim().to_string()))
-                    .collect();
-                resolutions.sort();
-                resolutions.dedup();
-                // Sort descending by total pixels (width * height)
-                resolutions.sort_by(|a, b| {
-                    let pixels_a = a.split('x')
-                        .filter_map(|s| s.parse::<u64>().ok())
-                        .product::<u64>();
-                    let pixels_b = b.split('x')
-                        .filter_map(|s| s.parse::<u64>().ok())
-                        .product::<u64>();
-                    pixels_b.cmp(&pixels_a)
-                });
-                resolutions
+    /// Replace the fallback rule from user input; takes effect on next apply.
+    pub fn set_fallback(&mut self, input: &str) {
+        match crate::config::parse_fallback(input) {
+            Ok(rule) => {
+                match &rule {
+                    Some(r) => self
+                        .toasts
+                        .info(tr_fill("Fallback set to monitor=,{} (apply to write)", &[r])),
+                    None => self.toasts.info(tr("Fallback removed (apply to write)")),
+                }
+                self.monitor_db.fallback = rule;
+                let _ = self.monitor_db.save();
             }
-            SettingField::RefreshRate => {
-                let current_res = monitor.resolution.trim();
-                let mut refresh_rates: Vec<u32> = monitor
-                    .available_modes
-                    .iter()
-                    .filter_map(|m| m.split_once('@'))
-                    .filter(|(res, _)| res.trim() == current_res)
-                    .filter_map(|(_, rate)| {
-                        // Parse and round the refresh rate
-                        rate.trim()
-                            .trim_end_matches("Hz")
-                            .trim()
-                            .parse::<f64>()
-                            .ok()
-                            .map(|r| r.round() as u32)
-                    })
-                    .collect();
+            Err(e) => self.toasts.error(tr_fill("Error: {}", &[&e])),
+        }
+    }
 
-                refresh_rates.sort_by(|a, b| b.cmp(a)); // Sort descending
-                refresh_rates.dedup();
+    /// Let the selected Live monitor be configured by hand (or hand it back).
+    pub fn toggle_selected_monitor_managed(&mut self) {
+        let Some(monitor) = self.monitors.get(self.selected_monitor) else {
+            return;
+        };
+        let key = MonitorDatabase::get_monitor_key(monitor);
+        let managed = self.monitor_db.toggle_managed(&key);
+        let _ = self.monitor_db.save();
+        if managed {
+            self.toasts.info(tr_fill("{} is managed by hyprmon again", &[&key]));
+        } else {
+            self.toasts
+                .info(tr_fill("{} is now unmanaged - configure it in hyprland.conf", &[&key]));
+        }
+    }
 
-                let result: Vec<String> = refresh_rates
-                    .into_iter()
-                    .map(|r| format!("{}Hz", r))
-                    .collect();
+    /// Ask for a nickname for the selected Live monitor, starting from the
+    /// one it has.
+    pub fn open_nickname(&mut self) {
+        let Some(monitor) = self.current_monitor() else {
+            return;
+        };
+        let nickname = self.monitor_db.nickname(monitor).unwrap_or_default().to_string();
+        self.text_input = TextInput::new(&nickname, MAX_NICKNAME);
+        self.dialog = DialogType::EditNickname;
+    }
 
-                if result.is_empty() {
-                    vec![format!("{}Hz", monitor.refresh_rate.round() as u32)]
-                } else {
-                    result
-                }
+    /// Call the selected Live monitor `input` from now on; blank removes
+    /// its nickname.
+    pub fn set_selected_nickname(&mut self, input: &str) {
+        let Some(monitor) = self.current_monitor() else {
+            return;
+        };
+        let name = monitor.name.clone();
+        let key = MonitorDatabase::get_monitor_key(monitor);
+        match self.monitor_db.set_nickname(&key, input) {
+            Some(nickname) => {
+                self.toasts.info(tr_fill("{} is now called '{}'", &[&name, &nickname]))
             }
-            SettingField::Scale => vec!["100%", "125%", "150%", "175%", "200%"]
-                .into_iter()
-                .map(String::from)
-                .collect(),
-            SettingField::Rotation => Rotation::all()
-                .iter()
-                .map(|r| r.as_str().to_string())
-                .collect(),
-            SettingField::Primary => vec![],
+            None => self.toasts.info(tr_fill("{} has no nickname now", &[&name])),
         }
+        let _ = self.monitor_db.save();
     }
 
-    pub fn apply_dropdown_selection(&mut self) {
-        let field = SettingField::all()[self.selected_setting];
-        let options = self.get_dropdown_options();
-        let dropdown_idx = self.dropdown_selection;
+    /// Key of the monitor highlighted in the Saved tab.
+    pub fn selected_saved_monitor_key(&self) -> Option<String> {
+        self.saved_monitors
+            .get(self.saved_selected_monitor)
+            .map(|m| m.name.clone())
+    }
 
-        if dropdown_idx >= options.len() {
+    /// Turn the monitor highlighted in the Saved tab off when the selected
+    /// workspace is applied, or stop doing so.
+    pub fn toggle_saved_monitor_dpms(&mut self) {
+        let Some(key) = self.selected_saved_monitor_key() else {
             return;
-        }
-
-        let selected_value = options[dropdown_idx].clone();
+        };
+        let Some(ws) = self.monitor_db.workspaces.get_mut(self.selected_workspace) else {
+            return;
+        };
+        let off = ws.toggle_dpms_off(&key);
+        let name = ws.name.clone();
+        let _ = self.monitor_db.save();
+        if off {
+            self.toasts.info(tr_fill("{} turns off when '{}' is applied", &[&key, &name]));
+        } else {
+            self.toasts.info(tr_fill("{} stays on when '{}' is applied", &[&key, &name]));
+        }
+    }
 
-        if let Some(monitor) = self.current_monitor_mut() {
-            match field {
-                SettingField::Resolution => {
-                    monitor.resolution = selected_value;
-                }
-                SettingField::RefreshRate => {
-                    if let Ok(rate) = selected_value.trim_end_matches("Hz").parse::<f64>() {
-                        monitor.refresh_rate = rate;
-                    }
-                }
-                SettingField::Scale => {
-                    if let Ok(scale) = selected_value.trim_end_matches('%').parse::<f64>() {
-                        monitor.scale = scale / 100.0;
-                    }
-                }
-                SettingField::Rotation => {
-                    monitor.rotation = match dropdown_idx {
-                        0 => Rotation::Normal,
-                        1 => Rotation::Left,
-                        2 => Rotation::Right,
-                        3 => Rotation::Inverted,
-                        _ => Rotation::Normal,
-                    };
-                }
-                SettingField::Primary => {}
-            }
-            self.has_changes = true;
+    /// Remove the highlighted monitor entry from the selected workspace.
+    pub fn delete_selected_saved_monitor(&mut self) {
+        let Some(key) = self.selected_saved_monitor_key() else {
+            return;
+        };
+        if self.monitor_db.remove_monitor(self.selected_workspace, &key) {
+            let _ = self.monitor_db.save();
+            self.refresh_saved_monitors();
+            self.toasts.info(tr_fill(
+                "Removed {} from '{}'",
+                &[&key, &self.current_workspace_name()],
+            ));
         }
+    }
 
-        self.recalculate_positions();
+    /// Monitor entries of the other workspaces, as (workspace, key), that
+    /// can be imported into the selected one.
+    pub fn import_candidates(&self) -> Vec<(usize, String)> {
+        (0..self.monitor_db.workspaces.len())
+            .filter(|&ws| ws != self.selected_workspace)
+            .flat_map(|ws| {
+                let monitors = self.monitor_db.get_workspace_monitors(ws);
+                monitors.into_iter().map(move |m| (ws, m.name))
+            })
+            .collect()
     }
 
-    #[allow(dead_code)]
-    pub fn generate_config(&self) -> String {
-        let mut config =
-            String::from("# Hyprland Monitor Configuration\n# Generated by hyprmon\n\n");
+    /// Picker rows for the import dialog: tick, source workspace, key, mode.
+    pub fn import_options(&self) -> Vec<String> {
+        let workspaces = &self.monitor_db.workspaces;
+        self.import_candidates()
+            .iter()
+            .enumerate()
+            .map(|(i, (ws, key))| {
+                let tick = if self.import_picks.contains(&i) { "[x]" } else { "[ ]" };
+                let saved = &workspaces[*ws].monitors[key];
+                let mode = format!("{}@{:.0}", saved.resolution, saved.refresh_rate);
+                format!("{tick} {}: {key}  {mode}", workspaces[*ws].name)
+            })
+            .collect()
+    }
 
-        for monitor in &self.monitors {
-            let is_laptop = monitor.name.starts_with("eDP");
-            let identifier = if is_laptop {
-                monitor.name.clone()
-            } else {
-                format!("desc:{}", monitor.description)
-            };
+    /// Ask which monitor entries of other workspaces to copy into the
+    /// selected one.
+    pub fn open_import_monitors(&mut self) {
+        if self.import_candidates().is_empty() {
+            self.toasts.warn(tr("No other workspace has monitors to import"));
+            return;
+        }
+        self.import_picks.clear();
+        self.dropdown_selection = 0;
+        self.dialog = DialogType::ImportMonitors;
+    }
 
-            let transform = monitor.rotation.transform();
-            let scale = if monitor.scale.fract() == 0.0 {
-                format!("{}", monitor.scale as i32)
-            } else {
-                format!("{:.2}", monitor.scale)
-            };
+    /// Tick or untick import candidate `idx`.
+    pub fn toggle_import_pick(&mut self, idx: usize) {
+        match self.import_picks.iter().position(|&i| i == idx) {
+            Some(pos) => drop(self.import_picks.remove(pos)),
+            None => self.import_picks.push(idx),
+        }
+    }
 
-            if transform == 0 {
-                config.push_str(&format!(
-                    "# {}\nmonitor={},{}@{:.2},{}x{},{}\n\n",
-                    monitor.model,
-                    identifier,
-                    monitor.resolution,
-                    monitor.refresh_rate,
-                    monitor.position_x,
-                    monitor.position_y,
-                    scale
-                ));
-            } else {
-                config.push_str(&format!(
-                    "# {}\nmonitor={},{}@{:.2},{}x{},{},transform,{}\n\n",
-                    monitor.model,
-                    identifier,
-                    monitor.resolution,
-                    monitor.refresh_rate,
-                    monitor.position_x,
-                    monitor.position_y,
-                    scale,
-                    transform
-                ));
+    /// Copy the ticked import candidates (or just the highlighted one when
+    /// none is ticked) into the selected workspace and save.
+    pub fn import_picked_monitors(&mut self) {
+        let candidates = self.import_candidates();
+        let picks = if self.import_picks.is_empty() {
+            vec![self.dropdown_selection]
+        } else {
+            std::mem::take(&mut self.import_picks)
+        };
+        let mut imported = Vec::new();
+        for (ws, key) in picks.iter().filter_map(|&i| candidates.get(i)) {
+            if self.monitor_db.import_monitor(*ws, key, self.selected_workspace) {
+                imported.push((*ws, key));
             }
         }
-
-        config.push_str("# Fallback\nmonitor=,preferred,auto,1\n");
-        config
+        let target = self.current_workspace_name();
+        let message = match imported.as_slice() {
+            [] => return,
+            [(ws, key)] => {
+                let source = &self.monitor_db.workspaces[*ws].name;
+                tr_fill("Imported {} from '{}' into '{}'", &[key, source, &target])
+            }
+            many => tr_fill("Imported {} monitors into '{}'", &[&many.len(), &target]),
+        };
+        let _ = self.monitor_db.save();
+        self.refresh_saved_monitors();
+        self.toasts.info(message);
     }
 
-    pub fn save_and_apply(&mut self) -> Result<()> {
-        // Sync workspace selection before saving
-        self.monitor_db.active_workspace = self.selected_workspace;
+    /// Rename current workspace; fails on a blank or taken name
+    pub fn rename_current_workspace(&mut self, name: &str) -> Result<()> {
+        self.monitor_db
+            .rename_workspace(self.selected_workspace, name)?;
+        let _ = self.monitor_db.save();
+        self.toasts.info(tr_fill("Renamed to: {}", &[&self.current_workspace_name()]));
+        Ok(())
+    }
 
-        // Update database with current monitor configs
-        for monitor in &self.monitors {
-            self.monitor_db.update_monitor(monitor);
-        }
-        self.monitor_db.save()?;
+    /// Get current workspace name
+    /// Name of the workspace Live is compared and applied against, which can
+    /// differ from the one browsed in the Saved tab.
+    pub fn active_workspace_name(&self) -> String {
+        self.monitor_db
+            .current_workspace()
+            .map(|ws| ws.name.clone())
+            .unwrap_or_default()
+    }
 
-        // Refresh saved monitors view
-        self.refresh_saved_monitors();
+    pub fn current_workspace_name(&self) -> String {
+        self.monitor_db
+            .workspaces
+            .get(self.selected_workspace)
+            .map(|ws| ws.name.clone())
+            .unwrap_or_default()
+    }
 
-        let config_path = dirs::home_dir().unwrap().join(".config/hypr/monitors.conf");
+    pub fn current_monitor(&self) -> Option<&MonitorConfig> {
+        self.monitors.get(self.selected_monitor)
+    }
 
-        let existing = if config_path.exists() {
-            let backup = config_path.with_extension("conf.bak");
-            fs::copy(&config_path, &backup)?;
-            fs::read_to_string(&config_path).unwrap_or_default()
-        } else {
-            String::new()
-        };
+    pub fn current_monitor_mut(&mut self) -> Option<&mut MonitorConfig> {
+        self.monitors.get_mut(self.selected_monitor)
+    }
 
-        // Rewrite only hyprmon's managed block so any user-authored lines in
-        // monitors.conf survive regeneration. Pass the keys of the currently
-        // connected monitors so the generator packs only them into a gap-free
-        // row — a saved-but-absent monitor must not reserve coordinate space, or
-        // its empty slot traps the cursor between the monitors that remain.
-        let connected: std::collections::HashSet<String> = self
-            .monitors
-            .iter()
-            .map(MonitorDatabase::get_monitor_key)
-            .collect();
-        let block = self.monitor_db.generate_full_config(&connected);
-        let config = crate::config::splice_managed_block(&existing, &block);
-        fs::write(&config_path, &config)?;
+    /// Live monitor `idx` as it was last applied (or loaded), matched by
+    /// connector so reordering does not count as a change.
+    fn original_of(&self, idx: usize) -> Option<&MonitorConfig> {
+        let name = &self.monitors.get(idx)?.name;
+        self.original_monitors.iter().find(|m| &m.name == name)
+    }
 
-        // Reload Hyprland to apply changes
-        std::process::Command::new("hyprctl")
-            .arg("reload")
-            .output()
-            .ok();
+    /// Whether `field` of live monitor `idx` differs from what was applied.
+    pub fn setting_changed(&self, idx: usize, field: SettingField) -> bool {
+        let value = |m| compare::field_value(m, field);
+        self.original_of(idx)
+            .is_some_and(|original| value(original) != value(&self.monitors[idx]))
+    }
 
-        self.message = "Applied! Check your monitors.".to_string();
-        self.dialog = DialogType::ConfirmApply {
-            countdown: 15,
-            started: Instant::now(),
-        };
+    /// Whether anything about live monitor `idx`, position included, would
+    /// change on apply. Monitors that appeared since then count as changed.
+    pub fn monitor_changed(&self, idx: usize) -> bool {
+        match self.original_of(idx) {
+            Some(original) => *original != self.monitors[idx],
+            None => true,
+        }
+    }
 
-        Ok(())
+    /// Color of each live monitor, as an index into `IDENTIFY_COLORS`, from
+    /// its database key so it stays the same across ports and sessions.
+    pub fn monitor_colors(&self) -> Vec<usize> {
+        let keys: Vec<String> =
+            self.monitors.iter().map(MonitorDatabase::get_monitor_key).collect();
+        monitor_colors(&keys)
     }
 
-    pub fn revert_changes(&mut self) {
-        self.monitors = self.original_monitors.clone();
-        self.has_changes = false;
-        self.message = "Changes reverted.".to_string();
+    /// Like [`App::monitor_colors`], for the saved workspace shown.
+    pub fn saved_monitor_colors(&self) -> Vec<usize> {
+        let keys: Vec<String> = self.saved_monitors.iter().map(|m| m.name.clone()).collect();
+        monitor_colors(&keys)
     }
 
-    pub fn confirm_changes(&mut self) {
-        self.original_monitors = self.monitors.clone();
-        self.has_changes = false;
-        self.dialog = DialogType::None;
-        self.message = "Configuration saved!".to_string();
+    /// Live monitors sharing one database key with another, whose settings
+    /// would overwrite each other's: the first such group, in layout order.
+    pub fn key_collision(&self) -> Vec<usize> {
+        let keys: Vec<String> =
+            self.monitors.iter().map(MonitorDatabase::get_monitor_key).collect();
+        keys.iter()
+            .enumerate()
+            .find(|&(i, key)| keys[i + 1..].contains(key))
+            .map(|(_, key)| (0..keys.len()).filter(|&j| keys[j] == *key).collect())
+            .unwrap_or_default()
     }
 
-    pub fn identify(&self) {
-        identify_monitors(&self.monitors);
+    /// Whether live monitor `idx` shares its database key with another.
+    pub fn key_collides(&self, idx: usize) -> bool {
+        let key = MonitorDatabase::get_monitor_key(&self.monitors[idx]);
+        self.monitors
+            .iter()
+            .enumerate()
+            .any(|(i, m)| i != idx && MonitorDatabase::get_monitor_key(m) == key)
     }
 
-    pub fn refresh(&mut self) -> Result<()> {
-        self.monitor_db = MonitorDatabase::load().unwrap_or_default();
-        self.monitors = fetch_monitors()?;
+    /// Ways to tell the colliding monitors apart, with a label listing what
+    /// each would key them by. Serials are offered only when every monitor
+    /// reports a different one.
+    pub fn key_choices(&self) -> Vec<(KeyBy, String)> {
+        let group: Vec<&MonitorConfig> =
+            self.key_collision().into_iter().map(|i| &self.monitors[i]).collect();
+        if group.is_empty() {
+            return Vec::new();
+        }
+        let list = |f: fn(&MonitorConfig) -> &str| -> String {
+            group.iter().map(|&m| f(m)).collect::<Vec<_>>().join(", ")
+        };
+        let mut serials: Vec<&str> = group.iter().map(|m| m.serial.as_str()).collect();
+        serials.sort_unstable();
+        serials.dedup();
+        let mut choices = Vec::new();
+        if serials.len() == group.len() && !serials.contains(&"") {
+            choices.push((KeyBy::Serial, format!("By serial ({})", list(|m| &m.serial))));
+        }
+        choices.push((KeyBy::Connector, format!("By connector ({})", list(|m| &m.name))));
+        choices
+    }
 
-        // Apply saved configs to connected monitors
-        for monitor in &mut self.monitors {
-            self.monitor_db.apply_saved_config(monitor);
+    /// Before an apply: when live monitors share a key, ask how to tell them
+    /// apart and return `true`, so the apply waits for the answer.
+    fn ask_to_tell_apart(&mut self, saved: bool) -> bool {
+        if self.key_collision().is_empty() {
+            return false;
         }
+        self.dropdown_selection = 0;
+        self.dialog = DialogType::TellApart { saved };
+        true
+    }
 
-        self.original_monitors = self.monitors.clone();
-        self.selected_monitor = self
-            .selected_monitor
-            .min(self.monitors.len().saturating_sub(1));
-        self.has_changes = false;
-        self.message = "Monitors refreshed.".to_string();
+    /// Key the colliding monitors by `by` from now on, carrying what was
+    /// saved under their shared key over to the new keys.
+    pub fn tell_apart(&mut self, by: KeyBy) -> Result<()> {
+        let group = self.key_collision();
+        let Some(&first) = group.first() else {
+            return Ok(());
+        };
+        let shared = MonitorDatabase::get_monitor_key(&self.monitors[first]);
+        for &i in &group {
+            self.monitors[i].key_by = by;
+        }
+        let keys: Vec<String> =
+            group.iter().map(|&i| MonitorDatabase::get_monitor_key(&self.monitors[i])).collect();
+        self.monitor_db.split_key(&shared, by, &keys);
+        self.monitor_db.save()?;
+        for monitors in [
+            &mut self.hypr_monitors,
+            &mut self.original_monitors,
+            &mut self.applied_monitors,
+        ] {
+            self.monitor_db.assign_keys(monitors);
+        }
+        self.refresh_saved_monitors();
+        self.toasts.info(tr_fill("Monitors now keyed as {}", &[&keys.join(", ")]));
         Ok(())
     }
 
-    /// Called when a monitor is added via IPC
-    pub fn on_monitor_added(&mut self, _name: &str) -> Result<()> {
-        self.refresh()?;
-
-        // Auto-apply if we have saved config
-        let has_saved = self
-            .monitors
+    /// Live monitors whose scale Hyprland would not take as is, with the
+    /// scale it would pick instead.
+    pub fn scale_fixes(&self) -> Vec<(usize, f64)> {
+        self.monitors
             .iter()
-            .any(|m| self.monitor_db.get_saved_config(m).is_some());
-        if has_saved {
-            self.message = "Monitor connected - applying saved config...".to_string();
-            self.save_and_apply()?;
-        } else {
-            self.message = "New monitor detected!".to_string();
+            .enumerate()
+            .filter_map(|(i, m)| Some((i, config::fixed_scale(&m.resolution, m.scale)?)))
+            .collect()
+    }
+
+    /// One `DP-1: 1.57 → 1.566667` line per scale fix, for the prompt.
+    pub fn scale_fix_lines(&self) -> Vec<String> {
+        self.scale_fixes()
+            .into_iter()
+            .map(|(i, fixed)| {
+                let m = &self.monitors[i];
+                format!(
+                    "{}: {} → {}",
+                    m.name,
+                    config::format_scale(m.scale),
+                    config::format_scale(fixed)
+                )
+            })
+            .collect()
+    }
+
+    /// Switch every scale Hyprland would round to the value it rounds to.
+    pub fn fix_scales(&mut self) {
+        let fixes = self.scale_fixes();
+        if fixes.is_empty() {
+            return;
         }
-        Ok(())
+        self.checkpoint();
+        for (i, fixed) in fixes {
+            self.monitors[i].scale = fixed;
+        }
+        self.has_changes = true;
     }
 
-    /// Called when a monitor is removed via IPC
-    pub fn on_monitor_removed(&mut self, _name: &str) -> Result<()> {
-        self.refresh()?;
-        self.message = "Monitor disconnected.".to_string();
-        Ok(())
+    /// Settings Up/Down stops at: each one of an open group, and the first
+    /// of a collapsed group, which stands for its heading.
+    pub fn setting_stops(&self) -> Vec<usize> {
+        SettingGroup::all()
+            .iter()
+            .flat_map(|group| {
+                let mut fields = group.fields();
+                if self.collapsed_groups.contains(group) {
+                    fields.truncate(1);
+                }
+                fields
+            })
+            .collect()
     }
-}
 
-#[cfg(test)]
-impl App {
-    /// Build an `App` around `monitors` with a fresh in-memory DB, bypassing the
-    /// `new()` path that shells out to `hyprctl`. Used by UI render tests.
-    pub fn for_test(monitors: Vec<MonitorConfig>) -> Self {
-        let db = MonitorDatabase::default();
-        let saved = db.get_workspace_monitors(db.active_workspace);
-        Self {
-            monitors: monitors.clone(),
-            original_monitors: monitors,
-            selected_monitor: 0,
-            focus_panel: FocusPanel::Arrangement,
-            selected_setting: 0,
-            saved_monitors: saved,
-            saved_selected_monitor: 0,
-            saved_selected_setting: 0,
-            selected_workspace: 0,
-            main_tab: MainTab::Live,
-            dialog: DialogType::None,
-            dropdown_selection: 0,
-            has_changes: false,
-            message: String::new(),
-            drag_state: DragState::None,
-            monitor_db: db,
-            input_buffer: String::new(),
+    /// Move the Live settings selection `delta` stops, stopping at the ends.
+    pub fn step_setting(&mut self, delta: isize) {
+        let stops = self.setting_stops();
+        let at = stops.iter().position(|&i| i == self.selected_setting).unwrap_or(0);
+        let to = at.saturating_add_signed(delta).min(stops.len().saturating_sub(1));
+        if let Some(&i) = stops.get(to) {
+            self.selected_setting = i;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Whether the selected setting is hidden in a collapsed group.
+    pub fn selected_setting_folded(&self) -> bool {
+        let group = SettingField::all()[self.selected_setting].group();
+        self.collapsed_groups.contains(&group)
+    }
 
-    fn mc(name: &str, make: &str, model: &str, res: &str, x: i32) -> MonitorConfig {
-        MonitorConfig {
-            name: name.into(),
-            description: format!("{make} {model}"),
-            make: make.into(),
-            model: model.into(),
-            resolution: res.into(),
-            refresh_rate: 60.0,
-            position_x: x,
-            position_y: 0,
-            scale: 1.0,
-            rotation: Rotation::Normal,
-            is_primary: false,
-            available_modes: vec![
-                "1920x1080@60.00Hz".into(),
-                "1920x1080@144.00Hz".into(),
-                "2560x1440@60.00Hz".into(),
-            ],
+    /// Fold or unfold `group`; folding it moves a selection inside it to its
+    /// heading.
+    pub fn toggle_setting_group(&mut self, group: SettingGroup) {
+        if let Some(at) = self.collapsed_groups.iter().position(|&g| g == group) {
+            self.collapsed_groups.remove(at);
+        } else {
+            self.collapsed_groups.push(group);
+            if SettingField::all()[self.selected_setting].group() == group {
+                self.selected_setting = group.fields()[0];
+            }
         }
     }
 
-    fn app_with(monitors: Vec<MonitorConfig>, db: MonitorDatabase) -> App {
-        let aw = db.active_workspace;
-        let saved = db.get_workspace_monitors(aw);
-        App {
-            monitors: monitors.clone(),
-            original_monitors: monitors,
-            selected_monitor: 0,
-            focus_panel: FocusPanel::Arrangement,
-            selected_setting: 0,
-            saved_monitors: saved,
-            saved_selected_monitor: 0,
-            saved_selected_setting: 0,
-            selected_workspace: aw,
-            main_tab: MainTab::Live,
-            dialog: DialogType::None,
-            dropdown_selection: 0,
-            has_changes: false,
-            message: String::new(),
-            drag_state: DragState::None,
-            monitor_db: db,
-            input_buffer: String::new(),
+    pub fn select_next_monitor(&mut self) {
+        if !self.monitors.is_empty() {
+            self.selected_monitor = (self.selected_monitor + 1) % self.monitors.len();
         }
     }
 
+    pub fn select_prev_monitor(&mut self) {
+        if !self.monitors.is_empty() {
+            if self.selected_monitor == 0 {
+                self.selected_monitor = self.monitors.len() - 1;
+            } else {
+                self.selected_monitor -= 1;
+            }
+        }
+    }
+
+    pub fn move_monitor_left(&mut self) {
+        if self.selected_monitor == 0 || self.monitors.len() < 2 {
+            return;
+        }
+        self.checkpoint();
+        self.monitors
+            .swap(self.selected_monitor, self.selected_monitor - 1);
+        self.selected_monitor -= 1;
+        self.recalculate_positions();
+        self.has_changes = true;
+    }
+
+    pub fn move_monitor_right(&mut self) {
+        if self.selected_monitor >= self.monitors.len() - 1 {
+            return;
+        }
+        self.checkpoint();
+        self.monitors
+            .swap(self.selected_monitor, self.selected_monitor + 1);
+        self.selected_monitor += 1;
+        self.recalculate_positions();
+        self.has_changes = true;
+    }
+
+    /// Snapshot the Live layout before an edit so it can be undone.
+    pub fn checkpoint(&mut self) {
+        self.undo_stack.record(self.monitors.clone());
+        // Once edited, the boxes no longer show what was applied
+        self.apply_status.clear();
+    }
+
+    /// How live monitor `name` fared in the last apply, if it took part.
+    pub fn apply_status_of(&self, name: &str) -> Option<&ApplyStatus> {
+        self.apply_status.iter().find(|(n, _)| n == name).map(|(_, status)| status)
+    }
+
+    /// Monitors the last apply failed on, as "name (why)".
+    pub fn apply_failures(&self) -> Vec<String> {
+        self.apply_status
+            .iter()
+            .filter_map(|(name, status)| match status {
+                ApplyStatus::Failed(why) => Some(format!("{name} ({why})")),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Move every monitor still at `from` in the last apply on to `to`.
+    fn advance_apply_status(&mut self, from: &ApplyStatus, to: ApplyStatus) {
+        for (_, status) in &mut self.apply_status {
+            if status == from {
+                *status = to.clone();
+            }
+        }
+    }
+
+    fn fail_apply_of(&mut self, name: &str, why: String) {
+        if let Some((_, status)) = self.apply_status.iter_mut().find(|(n, _)| n == name) {
+            *status = ApplyStatus::Failed(why);
+        }
+    }
+
+    /// Fail the live monitors a `monitor=` rule for `key` is about.
+    fn fail_apply_of_key(&mut self, key: &str, why: &str) {
+        self.set_apply_status_of_key(key, ApplyStatus::Failed(why.to_string()));
+    }
+
+    fn set_apply_status_of_key(&mut self, key: &str, to: ApplyStatus) {
+        let names: Vec<String> = self
+            .monitors
+            .iter()
+            .filter(|m| MonitorDatabase::get_monitor_key(m) == key)
+            .map(|m| m.name.clone())
+            .collect();
+        for (name, status) in &mut self.apply_status {
+            if names.contains(name) {
+                *status = to.clone();
+            }
+        }
+    }
+
+    /// Draw the screen now, from inside an apply; see [`App::progress`].
+    fn show_progress(&mut self) {
+        if let Some(mut draw) = self.progress.take() {
+            draw(self);
+            self.progress = Some(draw);
+        }
+    }
+
+    /// Revert the last Live layout edit (move, setting change, primary toggle).
+    pub fn undo(&mut self) {
+        match self.undo_stack.undo(self.monitors.clone()) {
+            Some(prev) => {
+                self.restore_layout(prev);
+                self.toasts.info(tr("Undone."));
+            }
+            None => self.toasts.warn(tr("Nothing to undo.")),
+        }
+    }
+
+    /// Re-apply the last undone Live layout edit.
+    pub fn redo(&mut self) {
+        match self.undo_stack.redo(self.monitors.clone()) {
+            Some(next) => {
+                self.restore_layout(next);
+                self.toasts.info(tr("Redone."));
+            }
+            None => self.toasts.warn(tr("Nothing to redo.")),
+        }
+    }
+
+    fn restore_layout(&mut self, monitors: Vec<MonitorConfig>) {
+        self.monitors = monitors;
+        self.selected_monitor = self
+            .selected_monitor
+            .min(self.monitors.len().saturating_sub(1));
+        self.has_changes = self.monitors != self.original_monitors;
+    }
+
+    pub fn recalculate_positions(&mut self) {
+        let mut x = 0i32;
+        for monitor in &mut self.monitors {
+            monitor.position_x = x;
+            monitor.position_y = 0;
+            x += row_width(monitor);
+        }
+    }
+
+    /// Where the monitor being dragged lands if released now: its new index
+    /// in the row and the x coordinate it gets there. `None` when no drag is
+    /// in progress. A drag shorter than half a box snaps back to its slot.
+    pub fn drag_target(&self) -> Option<(usize, i32)> {
+        let DragState::Dragging {
+            monitor_idx,
+            start_x,
+            current_x,
+            ..
+        } = self.drag_state
+        else {
+            return None;
+        };
+        let distance = current_x as i32 - start_x as i32;
+        let slot = (BOX_WIDTH + BOX_GAP) as i32;
+        let threshold = slot / 2;
+        let moved = if distance.abs() > threshold {
+            ((distance.abs() + threshold) / slot) as usize
+        } else {
+            0
+        };
+        let target = if distance > 0 {
+            (monitor_idx + moved).min(self.monitors.len().saturating_sub(1))
+        } else {
+            monitor_idx.saturating_sub(moved)
+        };
+        let mut row: Vec<&MonitorConfig> = self.monitors.iter().collect();
+        let dragged = row.remove(monitor_idx);
+        row.insert(target, dragged);
+        Some((target, row[..target].iter().map(|m| row_width(m)).sum()))
+    }
+
+    /// Release the drag, moving the monitor to [`Self::drag_target`] or the
+    /// workspace to the tab it is over.
+    pub fn finish_drag(&mut self) {
+        if let DragState::Workspace { idx, over } = self.drag_state {
+            self.drag_state = DragState::None;
+            self.drop_workspace(idx, over);
+            return;
+        }
+        let (Some((target, _)), DragState::Dragging { monitor_idx, .. }) =
+            (self.drag_target(), self.drag_state)
+        else {
+            return;
+        };
+        self.drag_state = DragState::None;
+        if target == monitor_idx {
+            return;
+        }
+        self.checkpoint();
+        let monitor = self.monitors.remove(monitor_idx);
+        self.monitors.insert(target, monitor);
+        self.selected_monitor = target;
+        self.recalculate_positions();
+        self.has_changes = true;
+    }
+
+    pub fn set_primary(&mut self, index: usize) {
+        for (i, m) in self.monitors.iter_mut().enumerate() {
+            m.is_primary = i == index;
+        }
+        self.has_changes = true;
+    }
+
+    pub fn toggle_primary(&mut self) {
+        let idx = self.selected_monitor;
+        if idx < self.monitors.len() {
+            let is_currently_primary = self.monitors[idx].is_primary;
+            if is_currently_primary && self.monitors.len() < 2 {
+                // Only monitor stays primary; nothing to record.
+                return;
+            }
+            self.checkpoint();
+
+            if is_currently_primary {
+                // If unchecking, make the first other monitor primary
+                if self.monitors.len() > 1 {
+                    let new_primary = if idx == 0 { 1 } else { 0 };
+                    self.set_primary(new_primary);
+                }
+                // If only one monitor, keep it primary
+            } else {
+                self.set_primary(idx);
+            }
+        }
+    }
+
+    /// Open the dropdown for the selected setting with an empty type-ahead filter.
+    pub fn open_dropdown(&mut self) {
+        self.dropdown_selection = 0;
+        self.set_dropdown_filter(String::new());
+        self.dialog = DialogType::EditDropdown;
+    }
+
+    /// Show or hide the exotic modes in the Resolution dropdown, keeping the
+    /// highlight on a shown option.
+    pub fn toggle_exotic_modes(&mut self) {
+        if SettingField::all()[self.selected_setting] != SettingField::Resolution {
+            return;
+        }
+        self.hide_exotic_modes = !self.hide_exotic_modes;
+        self.set_dropdown_filter(self.input_buffer.clone());
+    }
+
+    /// Open the typed entry for the selected setting (a position, or the
+    /// custom resolution or scale), pre-filled with the current value.
+    pub fn open_setting_input(&mut self) {
+        let field = SettingField::all()[self.selected_setting];
+        let Some(monitor) = self.current_monitor() else {
+            return;
+        };
+        self.text_input = match field {
+            SettingField::PositionX | SettingField::PositionY => {
+                TextInput::new(&compare::field_value(monitor, field), 6)
+                    .accepting(|c| c.is_ascii_digit() || c == '-')
+                    .validated(|s| config::parse_position(s).map(drop))
+            }
+            SettingField::Scale => TextInput::new(&format!("{:.0}", monitor.scale * 100.0), 7)
+                .accepting(|c| c.is_ascii_digit() || c == '.' || c == '%')
+                .validated(|s| config::parse_scale(s).map(drop)),
+            SettingField::Resolution => TextInput::new(&monitor.resolution, 11)
+                .accepting(|c| c.is_ascii_digit() || c == 'x' || c == 'X')
+                .validated(|s| config::parse_resolution(s).map(drop)),
+            _ => return,
+        };
+        self.dialog = DialogType::EditSetting;
+    }
+
+    /// Set the selected setting to the typed value. Invalid input stays in
+    /// the dialog with its error; returns whether it was accepted.
+    pub fn submit_setting_input(&mut self) -> bool {
+        let Some(text) = self.text_input.submit() else {
+            return false;
+        };
+        let field = SettingField::all()[self.selected_setting];
+        self.set_setting_from_text(self.selected_monitor, field, &text).is_ok()
+    }
+
+    /// Set `field` of live monitor `idx` from typed text: a position, or a
+    /// resolution or scale outside the dropdown's list.
+    pub fn set_setting_from_text(
+        &mut self,
+        idx: usize,
+        field: SettingField,
+        text: &str,
+    ) -> Result<()> {
+        if idx >= self.monitors.len() {
+            anyhow::bail!("no monitor {}", idx + 1);
+        }
+        match field {
+            SettingField::PositionX | SettingField::PositionY => {
+                let value = config::parse_position(text)?;
+                self.set_position(idx, field, value);
+                return Ok(());
+            }
+            SettingField::Scale => {
+                let scale = config::parse_scale(text)?;
+                self.checkpoint();
+                self.monitors[idx].scale = scale;
+            }
+            SettingField::Resolution => {
+                let resolution = config::parse_resolution(text)?;
+                self.checkpoint();
+                self.monitors[idx].resolution = resolution;
+                self.monitors[idx].mode_preset = None;
+                let fitted = self.monitors[idx].fit_refresh_rate();
+                self.note_refresh_fit(idx, fitted);
+            }
+            _ => anyhow::bail!("{} is picked, not typed", field.label().trim_end_matches(':')),
+        }
+        self.recalculate_positions();
+        self.has_changes = self.monitors != self.original_monitors;
+        Ok(())
+    }
+
+    /// Set one coordinate of live monitor `idx`. The rest of the row stays
+    /// put; a layout that no longer tiles is packed into a row on apply.
+    pub fn set_position(&mut self, idx: usize, field: SettingField, value: i32) {
+        if idx >= self.monitors.len() || !field.is_position() {
+            return;
+        }
+        self.checkpoint();
+        let monitor = &mut self.monitors[idx];
+        match field {
+            SettingField::PositionX => monitor.position_x = value,
+            _ => monitor.position_y = value,
+        }
+        self.has_changes = self.monitors != self.original_monitors;
+        let layout: Vec<SavedMonitor> =
+            self.monitors.iter().map(SavedMonitor::from_monitor).collect();
+        if !config::tiles(&layout) {
+            self.toasts.warn(tr_fill(
+                "{} now overlaps or is cut off from the others; apply will lay them out in a row",
+                &[&self.monitors[idx].name],
+            ));
+        }
+    }
+
+    /// Indices into `get_dropdown_options()` of the options whose label
+    /// contains the type-ahead filter (`input_buffer`), ignoring case, less
+    /// the exotic modes while those are hidden (the current one stays).
+    pub fn dropdown_matches(&self) -> Vec<usize> {
+        let filter = self.input_buffer.to_lowercase();
+        let current = self.current_monitor().map(|m| m.resolution.as_str()).unwrap_or_default();
+        let hide = self.hide_exotic_modes
+            && SettingField::all()[self.selected_setting] == SettingField::Resolution;
+        self.get_dropdown_options()
+            .iter()
+            .enumerate()
+            .filter(|(_, opt)| !hide || *opt == current || !config::is_exotic_resolution(opt))
+            .filter(|(_, opt)| self.dropdown_label(opt).to_lowercase().contains(&filter))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// How a dropdown option is shown: resolutions with their aspect ratio,
+    /// e.g. `3440x1440  21:9`, so typing `21:9` finds the ultrawide modes.
+    pub fn dropdown_label(&self, option: &str) -> String {
+        let field = SettingField::all()[self.selected_setting];
+        match config::aspect_ratio(option) {
+            Some(ratio) if field == SettingField::Resolution => format!("{option:<11} {ratio}"),
+            _ => option.to_string(),
+        }
+    }
+
+    /// Move the dropdown highlight `delta` matching options down (or up),
+    /// stopping at either end.
+    pub fn move_dropdown_selection(&mut self, delta: isize) {
+        let matches = self.dropdown_matches();
+        let Some(last) = matches.len().checked_sub(1) else {
+            return;
+        };
+        let pos = matches.iter().position(|&i| i == self.dropdown_selection).unwrap_or(0);
+        self.dropdown_selection = matches[pos.saturating_add_signed(delta).min(last)];
+    }
+
+    /// Narrow the dropdown to options containing `filter`, keeping the
+    /// highlight on a match.
+    pub fn set_dropdown_filter(&mut self, filter: String) {
+        self.input_buffer = filter;
+        let matches = self.dropdown_matches();
+        if !matches.contains(&self.dropdown_selection) {
+            if let Some(&first) = matches.first() {
+                self.dropdown_selection = first;
+            }
+        }
+    }
+
+    pub fn get_dropdown_options(&self) -> Vec<String> {
+        let field = SettingField::all()[self.selected_setting];
+        match self.current_monitor() {
+            Some(monitor) => Self::dropdown_options_for(monitor, field),
+            None => vec![],
+        }
+    }
+
+    /// Values offered for `field` of `monitor`, in dropdown order.
+    pub fn dropdown_options_for(monitor: &MonitorConfig, field: SettingField) -> Vec<String> {
+        match field {
+            SettingField::Resolution => {
+                let mut resolutions: Vec<String> = monitor
+                    .available_modes
+                    .iter()
+                    .filter_map(|m| m.split_once('@').map(|(res, _)| res.trim().to_string()))
+                    .collect();
+                resolutions.sort();
+                resolutions.dedup();
+                // Sort descending by total pixels (width * height)
+                resolutions.sort_by(|a, b| {
+                    let pixels_a = a.split('x')
+                        .filter_map(|s| s.parse::<u64>().ok())
+                        .product::<u64>();
+                    let pixels_b = b.split('x')
+                        .filter_map(|s| s.parse::<u64>().ok())
+                        .product::<u64>();
+                    pixels_b.cmp(&pixels_a)
+                });
+                resolutions.push(ModePreset::HighRes.keyword().to_string());
+                resolutions.push(CUSTOM_OPTION.to_string());
+                resolutions
+            }
+            SettingField::RefreshRate => {
+                let current_res = monitor.resolution.trim();
+                // Parse and round the refresh rate
+                let rounded = |rate: &str| {
+                    let rate = rate.trim().trim_end_matches("Hz").trim().parse::<f64>();
+                    rate.ok().map(|r| r.round() as u32)
+                };
+                let modes = monitor.available_modes.iter().filter_map(|m| m.split_once('@'));
+                let mut refresh_rates: Vec<u32> = modes
+                    .clone()
+                    .filter(|(res, _)| res.trim() == current_res)
+                    .filter_map(|(_, rate)| rounded(rate))
+                    .collect();
+
+                refresh_rates.sort_by(|a, b| b.cmp(a)); // Sort descending
+                refresh_rates.dedup();
+                if refresh_rates.is_empty() {
+                    refresh_rates.push(monitor.refresh_rate.round() as u32);
+                }
+
+                // Rates only another resolution offers, each at the largest one
+                // that does; picking one switches the resolution too
+                let mut elsewhere: Vec<(u32, &str)> = Vec::new();
+                for (res, rate) in modes {
+                    let (res, Some(rate)) = (res.trim(), rounded(rate)) else {
+                        continue;
+                    };
+                    if refresh_rates.contains(&rate) {
+                        continue;
+                    }
+                    let pixels = |res| option_rank(SettingField::Resolution, res).unwrap_or(0.0);
+                    match elsewhere.iter_mut().find(|(r, _)| *r == rate) {
+                        Some(entry) if pixels(res) > pixels(entry.1) => entry.1 = res,
+                        Some(_) => {}
+                        None => elsewhere.push((rate, res)),
+                    }
+                }
+                elsewhere.sort_by_key(|&(rate, _)| std::cmp::Reverse(rate));
+
+                let mut result: Vec<String> =
+                    refresh_rates.into_iter().map(|r| format!("{}Hz", r)).collect();
+                result.push(ModePreset::HighRr.keyword().to_string());
+                let elsewhere = elsewhere.into_iter();
+                result.extend(elsewhere.map(|(r, res)| format!("{r}Hz{OTHER_RES}{res}")));
+                result
+            }
+            SettingField::Scale => vec!["100%", "125%", "150%", "175%", "200%", CUSTOM_OPTION]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            SettingField::Rotation => Rotation::all()
+                .iter()
+                .map(|r| r.as_str().to_string())
+                .collect(),
+            SettingField::PositionX | SettingField::PositionY | SettingField::Primary => vec![],
+        }
+    }
+
+    pub fn apply_dropdown_selection(&mut self) {
+        let field = SettingField::all()[self.selected_setting];
+        let options = self.get_dropdown_options();
+        let dropdown_idx = self.dropdown_selection;
+
+        if dropdown_idx >= options.len() {
+            return;
+        }
+
+        let selected_value = options[dropdown_idx].clone();
+        if selected_value == CUSTOM_OPTION {
+            self.open_setting_input();
+            return;
+        }
+        self.checkpoint();
+        let preset = ModePreset::from_keyword(&selected_value);
+        let mut fitted = None;
+        let mut switched = false;
+
+        if let Some(monitor) = self.current_monitor_mut() {
+            match field {
+                // Show the mode Hyprland will pick; the keyword is what's saved
+                SettingField::Resolution | SettingField::RefreshRate if preset.is_some() => {
+                    if let Some((resolution, rate)) =
+                        preset.and_then(|p| p.pick(&monitor.available_modes))
+                    {
+                        monitor.resolution = resolution;
+                        monitor.refresh_rate = rate;
+                    }
+                    monitor.mode_preset = preset;
+                }
+                SettingField::Resolution => {
+                    monitor.resolution = selected_value;
+                    monitor.mode_preset = None;
+                    fitted = monitor.fit_refresh_rate();
+                }
+                SettingField::RefreshRate => {
+                    let (rate, resolution) = match selected_value.split_once(OTHER_RES) {
+                        Some((rate, res)) => (rate, Some(res)),
+                        None => (selected_value.as_str(), None),
+                    };
+                    if let Ok(rate) = rate.trim_end_matches("Hz").parse::<f64>() {
+                        monitor.refresh_rate = rate;
+                        monitor.mode_preset = None;
+                        if let Some(res) = resolution {
+                            monitor.resolution = res.to_string();
+                            // Back to the exact rate: 164.96 rather than 165
+                            let exact = monitor.rates_at(res).into_iter().min_by(|a, b| {
+                                (a - rate).abs().total_cmp(&(b - rate).abs())
+                            });
+                            monitor.refresh_rate = exact.unwrap_or(rate);
+                            switched = true;
+                        }
+                    }
+                }
+                SettingField::Scale => {
+                    if let Ok(scale) = selected_value.trim_end_matches('%').parse::<f64>() {
+                        monitor.scale = scale / 100.0;
+                    }
+                }
+                SettingField::Rotation => {
+                    monitor.rotation = match dropdown_idx {
+                        0 => Rotation::Normal,
+                        1 => Rotation::Left,
+                        2 => Rotation::Right,
+                        3 => Rotation::Inverted,
+                        _ => Rotation::Normal,
+                    };
+                }
+                SettingField::PositionX | SettingField::PositionY | SettingField::Primary => {}
+            }
+            self.has_changes = true;
+        }
+        if field == SettingField::Resolution && preset.is_none() {
+            self.note_refresh_fit(self.selected_monitor, fitted);
+        }
+        if let Some(monitor) = self.current_monitor().filter(|_| switched) {
+            self.toasts.info(tr_fill(
+                "Resolution set to {} for {} Hz",
+                &[&monitor.resolution, &format!("{:.0}", monitor.refresh_rate)],
+            ));
+        }
+
+        self.recalculate_positions();
+    }
+
+    /// After monitor `idx`'s resolution changed: say which refresh rate it
+    /// was moved to, or warn when the pair is not a mode it offers.
+    fn note_refresh_fit(&mut self, idx: usize, fitted: Option<f64>) {
+        let Some(monitor) = self.monitors.get(idx) else {
+            return;
+        };
+        let message = match fitted {
+            Some(rate) => tr_fill(
+                "Refresh rate set to {} Hz, the nearest {} offers at {}",
+                &[&format!("{rate:.0}"), &monitor.name, &monitor.resolution],
+            ),
+            None if !monitor.mode_advertised() => tr_fill(
+                "{}@{} Hz is not a mode {} advertises; Hyprland may pick another",
+                &[&monitor.resolution, &format!("{:.0}", monitor.refresh_rate), &monitor.name],
+            ),
+            None => return,
+        };
+        if fitted.is_some() {
+            self.toasts.info(message);
+        } else {
+            self.toasts.warn(message);
+        }
+    }
+
+    /// Step the selected Live setting to the next higher (`up`) or lower
+    /// option without opening the dropdown. Rotation wraps around; the other
+    /// fields stop at their ends.
+    pub fn cycle_selected_setting(&mut self, up: bool) {
+        let field = SettingField::all()[self.selected_setting];
+        let Some(monitor) = self.current_monitor() else {
+            return;
+        };
+        let options = self.get_dropdown_options();
+        let target = match field {
+            SettingField::Primary => None,
+            SettingField::Rotation => {
+                let all = Rotation::all();
+                let n = all.len();
+                let pos = all.iter().position(|r| *r == monitor.rotation).unwrap_or(0);
+                Some(if up { (pos + 1) % n } else { (pos + n - 1) % n })
+            }
+            _ => {
+                let current = match field {
+                    SettingField::RefreshRate => Some(monitor.refresh_rate.round()),
+                    SettingField::Scale => Some(monitor.scale * 100.0),
+                    _ => option_rank(field, &monitor.resolution),
+                };
+                // Nearest option strictly above / below, so values missing
+                // from the list (e.g. a 133% scale) still step sensibly
+                let ranked = options
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, opt)| Some((i, option_rank(field, opt)?)));
+                current.and_then(|cur| {
+                    if up {
+                        ranked.filter(|&(_, r)| r > cur).min_by(|a, b| a.1.total_cmp(&b.1))
+                    } else {
+                        ranked.filter(|&(_, r)| r < cur).max_by(|a, b| a.1.total_cmp(&b.1))
+                    }
+                    .map(|(i, _)| i)
+                })
+            }
+        };
+        if let Some(idx) = target {
+            self.dropdown_selection = idx;
+            self.apply_dropdown_selection();
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn generate_config(&self) -> String {
+        let mut config =
+            String::from("# Hyprland Monitor Configuration\n# Generated by hyprmon\n\n");
+
+        for monitor in &self.monitors {
+            let is_laptop = monitor.name.starts_with("eDP");
+            let identifier = if is_laptop {
+                monitor.name.clone()
+            } else {
+                format!("desc:{}", monitor.description)
+            };
+
+            let transform = monitor.rotation.transform();
+            let scale = config::format_scale(monitor.scale);
+
+            if transform == 0 {
+                config.push_str(&format!(
+                    "# {}\nmonitor={},{}@{:.2},{}x{},{}\n\n",
+                    monitor.model,
+                    identifier,
+                    monitor.resolution,
+                    monitor.refresh_rate,
+                    monitor.position_x,
+                    monitor.position_y,
+                    scale
+                ));
+            } else {
+                config.push_str(&format!(
+                    "# {}\nmonitor={},{}@{:.2},{}x{},{},transform,{}\n\n",
+                    monitor.model,
+                    identifier,
+                    monitor.resolution,
+                    monitor.refresh_rate,
+                    monitor.position_x,
+                    monitor.position_y,
+                    scale,
+                    transform
+                ));
+            }
+        }
+
+        if let Some(rule) = &self.monitor_db.fallback {
+            config.push_str(&format!("# Fallback\nmonitor=,{}\n", rule));
+        }
+        config
+    }
+
+    pub fn save_and_apply(&mut self) -> Result<()> {
+        if self.ask_to_tell_apart(false) {
+            return Ok(());
+        }
+        if !self.scale_fixes().is_empty() {
+            self.dialog = DialogType::FixScale { focus: DialogButton::Yes };
+            return Ok(());
+        }
+        self.save_and_apply_as_is()
+    }
+
+    /// [`App::save_and_apply`] without the pre-apply questions, for when
+    /// they were answered.
+    pub fn save_and_apply_as_is(&mut self) -> Result<()> {
+        // Sync workspace selection before saving
+        self.monitor_db.set_active(self.selected_workspace);
+
+        // Update database with current monitor configs
+        for monitor in &self.monitors {
+            self.monitor_db.update_monitor(monitor);
+        }
+        self.monitor_db.save()?;
+
+        // Refresh saved monitors view
+        self.refresh_saved_monitors();
+
+        self.write_config_and_reload()?;
+        if self.config_errors.is_empty() && self.apply_failures().is_empty() {
+            self.toasts.info(tr("Applied! Check your monitors."));
+        }
+        Ok(())
+    }
+
+    /// Apply the workspace selected in the Saved tab as-is: its stored settings
+    /// are pushed onto the matching connected monitors and written out, without
+    /// folding the current Live layout back into the profile. The previous
+    /// database is kept so the confirm countdown can revert to it.
+    pub fn apply_saved_workspace(&mut self) -> Result<()> {
+        let Some(ws) = self.monitor_db.workspaces.get(self.selected_workspace) else {
+            return Ok(());
+        };
+        let name = ws.name.clone();
+        if ws.matches_monitors(&self.monitors) == 0 {
+            self.toasts.warn(tr_fill("No connected monitors are saved in '{}'", &[&name]));
+            return Ok(());
+        }
+        if self.ask_to_tell_apart(true) {
+            return Ok(());
+        }
+
+        self.pre_apply_db = Some(self.monitor_db.clone());
+        self.monitor_db.set_active(self.selected_workspace);
+        for monitor in &mut self.monitors {
+            self.monitor_db.apply_saved_config(monitor);
+        }
+        self.monitor_db.save()?;
+
+        self.write_config_and_reload()?;
+        if self.config_errors.is_empty() && self.apply_failures().is_empty() {
+            self.toasts.info(tr_fill("Applied workspace '{}'. Check your monitors.", &[&name]));
+        }
+        Ok(())
+    }
+
+    /// Alt+N hotkey: select the Nth workspace (0-based `idx`) and apply it
+    /// through the usual confirm countdown.
+    pub fn apply_workspace_at(&mut self, idx: usize) -> Result<()> {
+        if idx >= self.monitor_db.workspaces.len() {
+            self.toasts.warn(tr_fill("No workspace #{}", &[&(idx + 1)]));
+            return Ok(());
+        }
+        self.selected_workspace = idx;
+        self.refresh_saved_monitors();
+        self.apply_saved_workspace()
+    }
+
+    /// Copy the selected workspace's settings onto the connected monitors as
+    /// pending Live edits (undoable, nothing written) and switch to Live so the
+    /// profile can be tweaked before applying.
+    pub fn load_workspace_into_live(&mut self) {
+        let name = self.current_workspace_name();
+        let mut staged = self.monitors.clone();
+        let mut loaded = 0;
+        for monitor in &mut staged {
+            if self
+                .monitor_db
+                .apply_saved_config_from(self.selected_workspace, monitor)
+            {
+                loaded += 1;
+            }
+        }
+        if loaded == 0 {
+            self.toasts.warn(tr_fill("No connected monitors are saved in '{}'", &[&name]));
+            return;
+        }
+
+        self.checkpoint();
+        self.monitors = staged;
+        self.has_changes = self.monitors != self.original_monitors;
+        self.switch_tab(MainTab::Live);
+        self.toasts.info(tr_fill(
+            "Loaded {} monitor(s) from '{}'. Review, then A to apply.",
+            &[&loaded, &name],
+        ));
+    }
+
+    /// Regenerate hyprmon's block in `monitors.conf` from the database, reload
+    /// Hyprland, record the change, and start the confirm countdown.
+    fn connected_keys(&self) -> HashSet<String> {
+        self.monitors.iter().map(MonitorDatabase::get_monitor_key).collect()
+    }
+
+    /// The `monitors.conf` that `a` would write right now: the Live layout on
+    /// the Live tab, the selected workspace on the Saved tab.
+    pub fn pending_config(&self) -> String {
+        let mut db = self.monitor_db.clone();
+        db.set_active(self.selected_workspace);
+        if self.main_tab == MainTab::Live {
+            for monitor in &self.monitors {
+                db.update_monitor(monitor);
+            }
+        }
+        db.hypr_config_text(&self.connected_keys())
+    }
+
+    /// Drop an in-progress drag without moving anything; the dragged box is
+    /// drawn back in its slot. Returns whether a drag was cancelled.
+    pub fn cancel_drag(&mut self) -> bool {
+        if self.drag_state == DragState::None {
+            return false;
+        }
+        self.drag_state = DragState::None;
+        self.toasts.info(tr("Drag cancelled."));
+        true
+    }
+
+    /// Put [`Self::pending_config`] on the clipboard without applying it.
+    pub fn copy_config(&mut self) {
+        let text = self.pending_config();
+        match clipboard::copy(&*self.exec, &text) {
+            Ok(used) => self.toasts.info(tr_fill(
+                "Copied monitors.conf ({} lines) via {}",
+                &[&text.lines().count(), &used.join(tr(" and "))],
+            )),
+            Err(e) => self.toasts.error(tr_fill("Copy failed: {}", &[&e])),
+        }
+    }
+
+    /// Put a diagram of the arrangement on the current tab (SVG or ASCII) on
+    /// the clipboard, for docs and layout questions.
+    pub fn copy_diagram(&mut self, svg: bool) {
+        let monitors = match self.main_tab {
+            MainTab::Live => &self.monitors,
+            MainTab::Saved => &self.saved_monitors,
+        };
+        let (text, kind) = if svg {
+            (diagram::svg(monitors), "SVG")
+        } else {
+            (diagram::ascii(monitors, diagram::ASCII_WIDTH), "ASCII")
+        };
+        match clipboard::copy(&*self.exec, &text) {
+            Ok(used) => self.toasts.info(tr_fill(
+                "Copied {} diagram of {} monitor(s) via {}",
+                &[&kind, &monitors.len(), &used.join(tr(" and "))],
+            )),
+            Err(e) => self.toasts.error(tr_fill("Copy failed: {}", &[&e])),
+        }
+    }
+
+    fn write_config_and_reload(&mut self) -> Result<()> {
+        // Hyprland keeps an output the GPU has no pipe left for black and
+        // says nothing, so say it here
+        let enabled: Vec<&str> = self.monitors.iter().map(|m| m.name.as_str()).collect();
+        for warning in drm::overcommitted(&drm::read_topology(), &enabled) {
+            self.toasts.warn(warning);
+        }
+
+        // Rewrite only hyprmon's managed block so any user-authored lines in
+        // monitors.conf survive regeneration. Pass the keys of the currently
+        // connected monitors so the generator packs only them into a gap-free
+        // row — a saved-but-absent monitor must not reserve coordinate space, or
+        // its empty slot traps the cursor between the monitors that remain.
+        let connected = self.connected_keys();
+        self.apply_status = self
+            .monitors
+            .iter()
+            .filter(|m| self.monitor_db.is_managed(&MonitorDatabase::get_monitor_key(m)))
+            .map(|m| (m.name.clone(), ApplyStatus::Queued))
+            .collect();
+        self.show_progress();
+        self.set_in_steps(&connected);
+        if let Err(e) = self.monitor_db.write_hypr_config(&self.hypr_conf_path, &connected) {
+            let why = ApplyStatus::Failed(format!("{e:#}"));
+            self.advance_apply_status(&ApplyStatus::Queued, why);
+            return Err(e);
+        }
+        let reloaded = match self.reload() {
+            Ok(()) => ApplyStatus::Applied,
+            Err(e) => ApplyStatus::Failed(format!("reload: {e:#}")),
+        };
+        self.advance_apply_status(&ApplyStatus::Queued, reloaded);
+        self.show_progress();
+        self.game_mode = None;
+        self.presenting = None;
+        self.projector = None;
+        for (name, on) in self.monitor_db.dpms_plan(&self.monitors) {
+            if let Err(e) = set_dpms(&*self.exec, &name, on) {
+                self.toasts.warn(tr_fill("DPMS of {}: {}", &[&name, &format!("{e:#}")]));
+            }
+        }
+        // Hyprland falls back quietly on a mode it cannot set; ask what it runs
+        let achieved = fetch_monitors(&*self.exec);
+        self.apply_mismatches = achieved
+            .as_ref()
+            .map(|achieved| compare::achieved_mismatches(&self.monitors, achieved))
+            .unwrap_or_default();
+        if achieved.is_ok() {
+            self.advance_apply_status(&ApplyStatus::Applied, ApplyStatus::Verified);
+        }
+        for (name, why) in self.apply_mismatches.clone() {
+            self.fail_apply_of(&name, why);
+        }
+
+        let entry = HistoryEntry::between(
+            &self.current_workspace_name(),
+            &self.applied_monitors,
+            &self.monitors,
+        );
+        let _ = self.history_log.append(&entry);
+        self.applied_changes = entry.changes;
+        self.applied_monitors = self.monitors.clone();
+        self.hypr_monitors = self.monitors.clone();
+
+        let active = self.monitor_db.active_workspace();
+        if let Some(ws) = self.monitor_db.workspaces.get_mut(active) {
+            ws.mark_applied(clock::now());
+            let _ = self.monitor_db.save();
+        }
+
+        self.show_config_errors(config_errors(&*self.exec));
+        // A rejected monitor= line fails the monitor it is for
+        for error in self.config_errors.clone() {
+            let Some(key) = error.source.as_deref().and_then(|line| {
+                line.strip_prefix("monitor=")?.split(',').next().map(str::to_string)
+            }) else {
+                continue;
+            };
+            self.fail_apply_of_key(&key, &error.message);
+        }
+        let failures = self.apply_failures();
+        if !failures.is_empty() {
+            self.toasts.warn(tr_fill("Not applied as asked: {}", &[&failures.join(", ")]));
+        }
+        Ok(())
+    }
+
+    /// Set the monitors one by one first when the active workspace asks for
+    /// it (see [`config::ApplySteps`]); the reload then finds them set.
+    fn set_in_steps(&mut self, connected: &HashSet<String>) {
+        let rules = self.monitor_db.step_rules(connected);
+        if rules.is_empty() {
+            return;
+        }
+        let pause = self.monitor_db.current_workspace().map(|ws| ws.apply_steps.delay());
+        for (i, rule) in rules.iter().enumerate() {
+            // Drawn before the pause, or the screen would sit still through it
+            let key = rule.split(',').next().unwrap_or_default();
+            self.set_apply_status_of_key(key, ApplyStatus::Setting);
+            self.show_progress();
+            if i > 0 {
+                thread::sleep(pause.unwrap_or_default());
+            }
+            // Back to waiting for the reload once set
+            let status = match set_monitor_rule(&*self.exec, rule) {
+                Ok(_) => ApplyStatus::Queued,
+                Err(e) => {
+                    let why = format!("{e:#}");
+                    self.toasts.warn(tr_fill("A step was refused: {}", &[&why]));
+                    ApplyStatus::Failed(why)
+                }
+            };
+            self.set_apply_status_of_key(key, status);
+        }
+    }
+
+    /// After a reload: list what Hyprland rejected, pointing at the offending
+    /// line when it is in the file just written, or go straight to the
+    /// keep-or-revert countdown when it took everything.
+    pub fn show_config_errors(&mut self, mut errors: Vec<ConfigError>) {
+        let path = self.monitor_db.monitor_rules_path(&self.hypr_conf_path);
+        let written = std::fs::read_to_string(&path).unwrap_or_default();
+        for error in &mut errors {
+            if let Some((file, line)) = &error.file {
+                if Path::new(file) == path {
+                    error.source = written.lines().nth(line.saturating_sub(1)).map(String::from);
+                }
+            }
+        }
+        self.config_errors = errors;
+        if self.config_errors.is_empty() {
+            self.start_apply_countdown();
+        } else {
+            self.toasts.error(tr_fill(
+                "Hyprland reported {} config error(s); see the error list",
+                &[&self.config_errors.len()],
+            ));
+            self.dialog = DialogType::ConfigErrors { scroll: 0 };
+        }
+    }
+
+    /// Open the keep-or-revert prompt with a fresh countdown.
+    pub fn start_apply_countdown(&mut self) {
+        self.dialog = DialogType::ConfirmApply {
+            countdown: 15,
+            started: Instant::now(),
+            focus: DialogButton::Yes,
+        };
+    }
+
+    /// Revert an apply nobody kept once its countdown has run out. The event
+    /// loop calls this on every pass.
+    pub fn expire_apply_countdown(&mut self) {
+        if let DialogType::ConfirmApply { countdown, started, .. } = self.dialog {
+            if started.elapsed().as_secs() >= u64::from(countdown) {
+                self.revert_changes();
+                let _ = self.save_and_apply_as_is();
+                self.dialog = DialogType::None;
+            }
+        }
+    }
+
+    pub fn revert_changes(&mut self) {
+        if let Some(db) = self.pre_apply_db.take() {
+            self.selected_workspace = db.active_workspace();
+            self.monitor_db = db;
+        }
+        self.monitors = self.original_monitors.clone();
+        self.has_changes = false;
+        self.toasts.info(tr("Changes reverted."));
+    }
+
+    pub fn confirm_changes(&mut self) {
+        self.pre_apply_db = None;
+        self.original_monitors = self.monitors.clone();
+        self.has_changes = false;
+        self.dialog = DialogType::None;
+        self.toasts.info(tr("Configuration saved!"));
+    }
+
+    /// Open the applied-change log viewer.
+    pub fn open_history(&mut self) {
+        self.history_entries = self.history_log.load();
+        self.history_entries.reverse();
+        self.dialog = DialogType::History { scroll: 0 };
+    }
+
+    /// Open the read-only view of [`Self::pending_config`], marking the
+    /// lines the monitors.conf on disk does not have yet. Nothing is written.
+    pub fn open_config_preview(&mut self) {
+        let path = self.monitor_db.monitor_rules_path(&self.hypr_conf_path);
+        let on_disk = std::fs::read_to_string(path).unwrap_or_default();
+        let written: HashSet<&str> = on_disk.lines().collect();
+        self.config_preview = self
+            .pending_config()
+            .lines()
+            .map(|line| format!("{} {line}", if written.contains(line) { ' ' } else { '+' }))
+            .collect();
+        self.dialog = DialogType::ConfigPreview { scroll: 0 };
+    }
+
+    pub fn identify(&self) {
+        self.identify_only(None);
+    }
+
+    fn identify_only(&self, only: Option<usize>) {
+        let duration = Duration::from_millis(self.identify_prefs.duration);
+        let targets = identify_targets(&self.monitors, &self.monitor_colors(), only);
+        identify_monitors(self.exec.clone(), targets, duration);
+    }
+
+    /// With `[identify] on_select`, flash the selected live monitor once the
+    /// selection has moved to it. Called after every input event.
+    pub fn identify_selection_change(&mut self) {
+        if self.selected_monitor == self.last_selected_monitor {
+            return;
+        }
+        self.last_selected_monitor = self.selected_monitor;
+        if self.identify_prefs.on_select && self.main_tab == MainTab::Live {
+            self.identify_only(Some(self.selected_monitor));
+        }
+    }
+
+    /// Move Hyprland's focus to the selected live monitor, so the arrangement
+    /// doubles as a monitor switcher.
+    pub fn focus_selected_monitor(&mut self) {
+        let Some(name) = self.current_monitor().map(|m| m.name.clone()) else {
+            return;
+        };
+        match focus_monitor(&*self.exec, &name) {
+            Ok(()) => self.focused_monitor = Some(name),
+            Err(e) => self.toasts.error(format!("hyprctl: {e:#}")),
+        }
+    }
+
+    /// Game mode: run the selected monitor at the fastest refresh rate it has
+    /// at its resolution with VRR on, and turn VRR off on the others so they
+    /// do not fight it. Only `hyprctl keyword` is used, so nothing is saved;
+    /// the second press reloads monitors.conf to put everything back.
+    pub fn toggle_game_mode(&mut self) {
+        if self.game_mode.take().is_some() {
+            self.projector = None;
+            let _ = self.reload();
+            self.poll_hyprland();
+            self.toasts.info(tr("Game mode off, monitors.conf restored"));
+            return;
+        }
+        let Some(name) = self.current_monitor().map(|m| m.name.clone()) else {
+            return;
+        };
+        // Start from what Hyprland runs, not from unapplied edits
+        let Some(game) = self.hypr_monitors.iter().find(|m| m.name == name) else {
+            return;
+        };
+        let fastest = game.rates_at(&game.resolution).first().copied();
+        let fastest = fastest.unwrap_or(game.refresh_rate);
+        // The game monitor goes first, so if Hyprland refuses it nothing changes
+        let mut rules = vec![game.keyword_rule(fastest, true)];
+        let others = self.hypr_monitors.iter().filter(|m| m.name != name);
+        rules.extend(others.map(|m| m.keyword_rule(m.refresh_rate, false)));
+        self.hypr_seen = None;
+        for (i, rule) in rules.iter().enumerate() {
+            if let Err(e) = set_monitor_rule(&*self.exec, rule) {
+                self.toasts.error(format!("hyprctl: {e:#}"));
+                // Past the first rule something changed, which b must undo
+                if i > 0 {
+                    self.game_mode = Some(name);
+                }
+                return;
+            }
+        }
+        self.game_mode = Some(name.clone());
+        self.presenting = None;
+        self.projector = None;
+        self.poll_hyprland();
+        self.toasts.info(tr_fill(
+            "Game mode: {} at {} Hz with VRR, VRR off elsewhere; b restores",
+            &[&name, &format!("{fastest:.0}")],
+        ));
+    }
+
+    /// Presentation mode (see [`presentation`]): the laptop panel mirrored on
+    /// every other output. The second press reloads monitors.conf to put the
+    /// workspace back.
+    pub fn toggle_presentation(&mut self) {
+        if self.presenting.take().is_some() {
+            self.projector = None;
+            let _ = self.reload();
+            self.poll_hyprland();
+            self.toasts.info(tr("Presentation over, monitors.conf restored"));
+            return;
+        }
+        // Mirror what Hyprland runs, not unapplied edits
+        let Some(mirror) = presentation::plan(&self.hypr_monitors) else {
+            self.toasts.warn(tr("Nothing to present on: only one monitor is connected"));
+            return;
+        };
+        self.hypr_seen = None;
+        for (i, rule) in mirror.rules.iter().enumerate() {
+            if let Err(e) = set_monitor_rule(&*self.exec, rule) {
+                self.toasts.error(format!("hyprctl: {e:#}"));
+                // Past the first rule something changed, which Ctrl+o must undo
+                if i > 0 {
+                    self.presenting = Some(mirror.source);
+                }
+                return;
+            }
+        }
+        self.presenting = Some(mirror.source.clone());
+        self.game_mode = None;
+        self.projector = None;
+        self.poll_hyprland();
+        self.toasts.info(match mirror.resolution {
+            Some(res) => tr_fill(
+                "Presenting: {} mirrored at {}; Ctrl+o restores",
+                &[&mirror.source, &res],
+            ),
+            None => tr_fill("Presenting: {} mirrored; Ctrl+o restores", &[&mirror.source]),
+        });
+    }
+
+    /// Force the selected monitor to [`presentation::PROJECTOR_MODE`] at
+    /// 100%, whatever its EDID offers. Only `hyprctl keyword` is used; the
+    /// second press, or unplugging the monitor, reloads monitors.conf.
+    pub fn toggle_projector_mode(&mut self) {
+        if let Some(name) = self.projector.take() {
+            self.game_mode = None;
+            self.presenting = None;
+            let _ = self.reload();
+            self.poll_hyprland();
+            self.toasts.info(tr_fill("{} is back to its monitors.conf mode", &[&name]));
+            return;
+        }
+        let Some(name) = self.current_monitor().map(|m| m.name.clone()) else {
+            return;
+        };
+        let Some(monitor) = self.hypr_monitors.iter().find(|m| m.name == name) else {
+            return;
+        };
+        self.hypr_seen = None;
+        if let Err(e) = set_monitor_rule(&*self.exec, &presentation::projector_rule(monitor)) {
+            self.toasts.error(format!("hyprctl: {e:#}"));
+            return;
+        }
+        self.projector = Some(name.clone());
+        self.poll_hyprland();
+        self.toasts.info(tr_fill(
+            "{} forced to {}; Ctrl+f or unplugging it restores",
+            &[&name, &presentation::PROJECTOR_MODE],
+        ));
+    }
+
+    /// Safe mode (see [`safe`]): every monitor at its preferred mode, 100%,
+    /// side by side, set at once and written to monitors.conf. Unapplied
+    /// edits are dropped; the Live tab shows what Hyprland made of it.
+    pub fn enter_safe_mode(&mut self) {
+        let names = safe::monitor_names(&*self.exec)
+            .unwrap_or_else(|_| self.hypr_monitors.iter().map(|m| m.name.clone()).collect());
+        self.game_mode = None;
+        self.presenting = None;
+        self.projector = None;
+        self.hypr_seen = None;
+        let set = safe::set(&*self.exec, &names);
+        for change in safe::conf_changes(&self.hypr_conf_path, &names) {
+            if let Err(e) = change.make() {
+                self.toasts.error(tr_fill("Safe mode not saved: {}", &[&format!("{e:#}")]));
+                break;
+            }
+        }
+        // Hyprland reloads the rewritten file by itself
+        self.reloaded_at = Some(Instant::now());
+        match set {
+            Ok(()) => self.toasts.warn(tr("Safe mode on; apply a workspace to leave it")),
+            Err(e) => self.toasts.error(format!("hyprctl: {e:#}")),
+        }
+        self.take_running_layout();
+    }
+
+    /// Start the Live tab over from what Hyprland runs now, dropping
+    /// unapplied edits, after hyprmon changed it behind the workspaces.
+    fn take_running_layout(&mut self) {
+        if let Ok(mut monitors) = fetch_monitors(&*self.exec) {
+            self.monitor_db.assign_keys(&mut monitors);
+            self.original_monitors = monitors.clone();
+            self.applied_monitors = monitors.clone();
+            self.hypr_monitors = monitors.clone();
+            self.monitors = monitors;
+            let last = self.monitors.len().saturating_sub(1);
+            self.selected_monitor = self.selected_monitor.min(last);
+        }
+        self.has_changes = false;
+        self.dialog = DialogType::None;
+    }
+
+    /// Open the picker of kept monitors.conf versions (see [`backups`]).
+    pub fn open_rollback(&mut self) {
+        self.backups = backups::list(&self.hypr_conf_path);
+        if self.backups.is_empty() {
+            self.toasts.info(tr("No earlier monitors.conf kept yet"));
+            return;
+        }
+        self.dropdown_selection = 0;
+        self.dialog = DialogType::Rollback;
+    }
+
+    /// Picker rows: when each version was replaced and how it differs.
+    pub fn rollback_options(&self) -> Vec<String> {
+        self.backups
+            .iter()
+            .map(|backup| {
+                let when = clock::format_local(backup.taken);
+                format!("{when}  {}", backups::diff_summary(&self.hypr_conf_path, backup))
+            })
+            .collect()
+    }
+
+    /// What restoring the highlighted version changes, as `+`/`-` lines.
+    pub fn rollback_diff(&self) -> Vec<String> {
+        let Some(backup) = self.backups.get(self.dropdown_selection) else {
+            return Vec::new();
+        };
+        let current = std::fs::read_to_string(&self.hypr_conf_path).unwrap_or_default();
+        let then = std::fs::read_to_string(&backup.path).unwrap_or_default();
+        backups::diff(&current, &then)
+    }
+
+    /// Put kept version `idx` back as monitors.conf and reload Hyprland. The
+    /// replaced file is kept too, so the restore can itself be rolled back.
+    pub fn restore_backup(&mut self, idx: usize) {
+        let Some(backup) = self.backups.get(idx).cloned() else {
+            return;
+        };
+        let made = backups::restore_changes(&backup, &self.hypr_conf_path)
+            .and_then(|changes| changes.iter().try_for_each(config::FileChange::make));
+        if let Err(e) = made {
+            self.toasts.error(tr_fill("Not restored: {}", &[&format!("{e:#}")]));
+            return;
+        }
+        self.game_mode = None;
+        self.presenting = None;
+        self.projector = None;
+        self.hypr_seen = None;
+        self.reloaded_at = Some(Instant::now());
+        let _ = reload_hyprland(&*self.exec);
+        let when = clock::format_local(backup.taken);
+        self.toasts.info(tr_fill("Restored monitors.conf from {}", &[&when]));
+        self.take_running_layout();
+    }
+
+    /// The preset for live monitor `idx`, unless it already runs that way.
+    pub fn suggested_preset(&self, idx: usize) -> Option<&Preset> {
+        let monitor = self.monitors.get(idx)?;
+        self.presets.find(monitor).filter(|preset| !preset.is_followed_by(monitor))
+    }
+
+    /// Stage the selected monitor's preset as unsaved, undoable edits.
+    pub fn use_preset(&mut self) {
+        let idx = self.selected_monitor;
+        let Some(monitor) = self.monitors.get(idx) else {
+            return;
+        };
+        let name = monitor.name.clone();
+        let Some(preset) = self.presets.find(monitor).cloned() else {
+            self.toasts.info(tr_fill("No preset known for {}", &[&monitor.display_name()]));
+            return;
+        };
+        if preset.is_followed_by(monitor) {
+            self.toasts.info(tr_fill("{} already runs its preset", &[&name]));
+            return;
+        }
+        self.checkpoint();
+        preset.apply_to(&mut self.monitors[idx]);
+        self.recalculate_positions();
+        self.has_changes = self.monitors != self.original_monitors;
+        self.toasts
+            .info(tr_fill("{} set to its preset ({}); a applies", &[&name, &preset.summary()]));
+        for note in &preset.notes {
+            self.toasts.info(format!("{name}: {note}"));
+        }
+    }
+
+    /// Give every Live monitor the scale its pixel density suggests (see
+    /// [`presets::density_scale`]) as one undoable edit, to refine from.
+    /// Monitors whose EDID gives no size keep theirs.
+    pub fn scale_by_density(&mut self) {
+        let sizes = match fetch_physical_sizes(&*self.exec) {
+            Ok(sizes) => sizes,
+            Err(e) => {
+                self.toasts.error(format!("hyprctl: {e:#}"));
+                return;
+            }
+        };
+        let mut scales = Vec::new();
+        let mut unknown = Vec::new();
+        for (idx, monitor) in self.monitors.iter().enumerate() {
+            let size = sizes.iter().find(|(name, _)| *name == monitor.name).map(|(_, size)| *size);
+            let scale = size.and_then(|size| {
+                presets::density_scale(&monitor.name, &monitor.resolution, size)
+            });
+            match scale {
+                Some(scale) => scales.push((idx, scale)),
+                None => unknown.push(monitor.name.clone()),
+            }
+        }
+        if !unknown.is_empty() {
+            let names = unknown.join(", ");
+            self.toasts.warn(tr_fill("No physical size for {}; scale left as is", &[&names]));
+        }
+        if scales.iter().all(|&(idx, scale)| (self.monitors[idx].scale - scale).abs() < 1e-9) {
+            if !scales.is_empty() {
+                self.toasts.info(tr("Every monitor already has the scale its density suggests"));
+            }
+            return;
+        }
+        self.checkpoint();
+        for &(idx, scale) in &scales {
+            self.monitors[idx].scale = scale;
+        }
+        self.recalculate_positions();
+        self.has_changes = self.monitors != self.original_monitors;
+        let set: Vec<String> = scales
+            .iter()
+            .map(|&(idx, scale)| format!("{} {:.0}%", self.monitors[idx].name, scale * 100.0))
+            .collect();
+        self.toasts.info(tr_fill("Scaled by pixel density: {}; a applies", &[&set.join(", ")]));
+    }
+
+    /// Live monitors the selected one can swap workspaces with: all others.
+    pub fn swap_targets(&self) -> Vec<&MonitorConfig> {
+        self.monitors
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != self.selected_monitor)
+            .map(|(_, m)| m)
+            .collect()
+    }
+
+    /// Picker entries for the swap dialog: each target with its workspaces.
+    pub fn swap_options(&self) -> Vec<String> {
+        self.swap_targets()
+            .iter()
+            .map(|m| {
+                let workspaces = workspaces_on(&self.hypr_workspaces, &m.name);
+                format!("{}  {}", m.name, workspaces).trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// Ask which monitor the selected one should swap workspaces with.
+    pub fn open_swap_workspaces(&mut self) {
+        if self.current_monitor().is_none() || self.swap_targets().is_empty() {
+            self.toasts.warn(tr("No other monitor to swap workspaces with"));
+            return;
+        }
+        self.dropdown_selection = 0;
+        self.dialog = DialogType::SwapWorkspaces;
+    }
+
+    /// Swap the active workspaces of the selected monitor and swap target
+    /// `target` (an index into [`App::swap_targets`]).
+    pub fn swap_workspaces_with(&mut self, target: usize) {
+        let targets = self.swap_targets();
+        let (Some(from), Some(to)) = (self.current_monitor(), targets.get(target)) else {
+            return;
+        };
+        let (from, to) = (from.name.clone(), to.name.clone());
+        match swap_active_workspaces(&*self.exec, &from, &to) {
+            Ok(()) => {
+                self.refresh_workspaces();
+                self.toasts.info(tr_fill("Swapped workspaces of {} and {}", &[&from, &to]));
+            }
+            Err(e) => self.toasts.error(format!("hyprctl: {e:#}")),
+        }
+    }
+
+    /// Open the details dialog for the selected live monitor. Falls back to
+    /// what the live config knows when hyprctl cannot be asked, and decodes
+    /// the EDID the kernel read where it can.
+    pub fn open_monitor_details(&mut self) {
+        let Some(monitor) = self.current_monitor() else {
+            return;
+        };
+        let mut details = match fetch_monitor_details(&*self.exec, &monitor.name) {
+            Ok(details) => details,
+            Err(e) => {
+                let details = MonitorDetails::from_config(monitor);
+                let e = format!("{e:#}");
+                self.toasts.warn(tr_fill("hyprctl: {} (showing cached details)", &[&e]));
+                details
+            }
+        };
+        details.edid = edid::read(&details.name).and_then(|bytes| Edid::parse(&bytes)).ok();
+        self.monitor_details = Some(details);
+        self.dialog = DialogType::MonitorDetails { scroll: 0 };
+    }
+
+    /// Full refresh on demand: re-read the database and ask Hyprland again.
+    pub fn refresh(&mut self) -> Result<()> {
+        self.monitor_db = MonitorDatabase::load().unwrap_or_default();
+        self.hypr_monitors = fetch_monitors(&*self.exec)?;
+        self.monitor_db.assign_keys(&mut self.hypr_monitors);
+        self.refresh_workspaces();
+        self.focused_monitor = focused_monitor(&*self.exec);
+        self.rebuild_live(false);
+        self.toasts.info(tr("Monitors refreshed."));
+        Ok(())
+    }
+
+    /// Lay the saved settings over the cached Hyprland monitors again,
+    /// dropping unapplied edits. With `rematch`, the active workspace may first
+    /// switch to the best match for the new monitor set.
+    fn rebuild_live(&mut self, rematch: bool) {
+        // A drag holds an index into the row about to be rebuilt
+        self.cancel_drag();
+        self.monitors = self.hypr_monitors.clone();
+        if rematch && self.monitor_db.auto_select(&self.monitors) {
+            self.selected_workspace = self.monitor_db.active_workspace();
+            let _ = self.monitor_db.save();
+            self.refresh_saved_monitors();
+        }
+
+        // Apply saved configs to connected monitors
+        for monitor in &mut self.monitors {
+            self.monitor_db.apply_saved_config(monitor);
+        }
+
+        self.original_monitors = self.monitors.clone();
+        self.applied_monitors = self.monitors.clone();
+        self.selected_monitor = self
+            .selected_monitor
+            .min(self.monitors.len().saturating_sub(1));
+        self.has_changes = false;
+        self.undo_stack.clear();
+    }
+
+    /// `hyprctl reload`, remembering when so its monitor events are not
+    /// mistaken for a hotplug.
+    fn reload(&mut self) -> Result<()> {
+        let reloaded = reload_hyprland(&*self.exec);
+        self.reloaded_at = Some(Instant::now());
+        self.hypr_seen = None;
+        reloaded
+    }
+
+    /// Whether monitor events now are most likely our own reload's echo.
+    fn in_reload_echo(&self) -> bool {
+        self.reloaded_at.is_some_and(|at| at.elapsed() < RELOAD_ECHO)
+    }
+
+    /// Called when a monitor is added via IPC. Hyprland announces each
+    /// hotplug twice (v1 and v2 events), so a monitor already cached is
+    /// ignored; otherwise `hyprctl` is asked once for its modes.
+    pub fn on_monitor_added(&mut self, added: &AddedMonitor) -> Result<()> {
+        if self.hypr_monitors.iter().any(|m| m.name == added.name) {
+            return Ok(());
+        }
+        let monitors = fetch_monitors(&*self.exec)?;
+        let Some(monitor) = monitors.into_iter().find(|m| m.name == added.name) else {
+            return Ok(());
+        };
+        self.monitor_added(monitor, added.description.as_deref())
+    }
+
+    /// Cache `monitor`, rematch the workspace and apply it unless manual-only.
+    /// `description` is what Hyprland announced it as, for the toast.
+    fn monitor_added(
+        &mut self,
+        mut monitor: MonitorConfig,
+        description: Option<&str>,
+    ) -> Result<()> {
+        self.monitor_db.assign_keys(std::slice::from_mut(&mut monitor));
+        let key = MonitorDatabase::get_monitor_key(&monitor);
+        let label = description.unwrap_or(&monitor.name).to_string();
+        let connector = monitor.name.clone();
+        // Checked before the new monitor joins: it has not been set up yet
+        let others_in_sync = !self.out_of_sync();
+        let workspace = self.monitor_db.active_workspace();
+        self.hypr_monitors.push(monitor);
+        // Applying again would reload again, and so on
+        if self.in_reload_echo() {
+            self.rebuild_live(false);
+            return Ok(());
+        }
+        self.rebuild_live(true);
+
+        if self
+            .monitor_db
+            .current_workspace()
+            .is_some_and(|ws| ws.manual_only)
+        {
+            self.toasts.info(tr_fill(
+                "Monitor connected - '{}' is manual-only, press a to apply",
+                &[&self.current_workspace_name()],
+            ));
+            return Ok(());
+        }
+        if !self.auto_apply {
+            self.toasts
+                .info(tr_fill("{} connected - auto-apply paused, press a to apply", &[&label]));
+            return Ok(());
+        }
+
+        // Same workspace, the rest already as saved: give just the new monitor
+        // its line, as a reload would blank every display for a moment
+        let saved = self
+            .monitor_db
+            .current_workspace()
+            .is_some_and(|ws| ws.monitors.contains_key(&key));
+        if saved
+            && others_in_sync
+            && workspace == self.monitor_db.active_workspace()
+            && self.key_collision().is_empty()
+        {
+            let rule = self.monitor_db.monitor_rule(&key, &self.connected_keys());
+            if let Some(Ok(())) = rule.map(|rule| set_monitor_rule(&*self.exec, &rule)) {
+                self.hypr_seen = None;
+                self.hypr_monitors = self.monitors.clone();
+                self.applied_monitors = self.monitors.clone();
+                self.toasts.info(tr_fill("{} connected - applied its saved settings", &[&label]));
+                return Ok(());
+            }
+        }
+
+        // Auto-apply if we have saved config
+        let has_saved = self
+            .monitors
+            .iter()
+            .any(|m| self.monitor_db.get_saved_config(m).is_some());
+        if has_saved {
+            self.toasts.info(tr("Monitor connected - applying saved config..."));
+            // Saved scales went through the fix prompt when they were applied
+            if !self.ask_to_tell_apart(false) {
+                self.save_and_apply_as_is()?;
+            }
+        } else if let Some(preset) = self
+            .monitors
+            .iter()
+            .position(|m| m.name == connector)
+            .and_then(|idx| self.suggested_preset(idx))
+        {
+            self.toasts.info(tr_fill(
+                "New monitor detected! Suggested for {}: {} - select it and press s",
+                &[&connector, &preset.summary()],
+            ));
+        } else {
+            self.toasts.info(tr("New monitor detected!"));
+        }
+        Ok(())
+    }
+
+    /// Called when a monitor is removed via IPC: drop it from the cache,
+    /// without asking `hyprctl`.
+    pub fn on_monitor_removed(&mut self, name: &str) {
+        let before = self.hypr_monitors.len();
+        self.hypr_monitors.retain(|m| m.name != name);
+        if self.hypr_monitors.len() == before {
+            return;
+        }
+        let was_primary = self.monitors.iter().any(|m| m.name == name && m.is_primary);
+        self.rebuild_live(false);
+        // A reload's echo brings the monitor straight back
+        if self.in_reload_echo() {
+            return;
+        }
+        if self.projector.as_deref() == Some(name) {
+            // Otherwise the forced mode would greet the next monitor there
+            self.projector = None;
+            let _ = self.reload();
+            self.toasts.info(tr_fill("{} disconnected; its forced mode is dropped", &[&name]));
+        } else {
+            self.toasts.info(tr("Monitor disconnected."));
+        }
+        if was_primary {
+            self.promote_primary(name);
+        }
+    }
+
+    /// The primary monitor `gone` was unplugged: make the one
+    /// [`MonitorDatabase::primary_successor`] picks primary on screen, unless
+    /// a saved setting already named another. The workspace keeps `gone` as
+    /// its primary, so it takes over again when it is plugged back in.
+    fn promote_primary(&mut self, gone: &str) {
+        if self.monitors.iter().any(|m| m.is_primary) {
+            return;
+        }
+        let Some(idx) = self.monitor_db.primary_successor(&self.monitors) else {
+            return;
+        };
+        // Right after a rebuild all three rows list the same monitors
+        for monitors in [
+            &mut self.monitors,
+            &mut self.original_monitors,
+            &mut self.applied_monitors,
+        ] {
+            for (i, monitor) in monitors.iter_mut().enumerate() {
+                monitor.is_primary = i == idx;
+            }
+        }
+        self.toasts.info(tr_fill(
+            "{} is primary now that {} is unplugged",
+            &[&self.monitors[idx].name, &gone],
+        ));
+    }
+}
+
+/// Numeric order of a dropdown option for stepping: pixel count for
+/// resolutions, the number itself for refresh rates and scales.
+fn option_rank(field: SettingField, option: &str) -> Option<f64> {
+    match field {
+        SettingField::Resolution => {
+            let (w, h) = option.trim().split_once('x')?;
+            Some(w.parse::<f64>().ok()? * h.parse::<f64>().ok()?)
+        }
+        SettingField::RefreshRate => option.trim_end_matches("Hz").parse().ok(),
+        SettingField::Scale => option.trim_end_matches('%').parse().ok(),
+        SettingField::Rotation
+        | SettingField::PositionX
+        | SettingField::PositionY
+        | SettingField::Primary => None,
+    }
+}
+
+#[cfg(test)]
+impl App {
+    /// Build an `App` around `monitors` with a fresh in-memory DB, bypassing the
+    /// `new()` path that shells out to `hyprctl`. Used by UI render tests.
+    pub fn for_test(monitors: Vec<MonitorConfig>) -> Self {
+        Self::from_parts(monitors, MonitorDatabase::default(), Arc::new(exec::Replay::default()))
+    }
+}
+
+/// Logical width a monitor takes up in the Live row.
+fn row_width(monitor: &MonitorConfig) -> i32 {
+    match monitor.resolution.split_once('x').map(|(w, _)| w.parse::<i32>()) {
+        Some(Ok(width)) => (width as f64 / monitor.scale) as i32,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::fs;
+    use std::rc::Rc;
+
+    fn mc(name: &str, make: &str, model: &str, res: &str, x: i32) -> MonitorConfig {
+        MonitorConfig {
+            name: name.into(),
+            description: format!("{make} {model}"),
+            make: make.into(),
+            model: model.into(),
+            resolution: res.into(),
+            refresh_rate: 60.0,
+            position_x: x,
+            position_y: 0,
+            scale: 1.0,
+            rotation: Rotation::Normal,
+            is_primary: false,
+            available_modes: vec![
+                "1920x1080@60.00Hz".into(),
+                "1920x1080@144.00Hz".into(),
+                "2560x1440@60.00Hz".into(),
+            ],
+            serial: String::new(),
+            key_by: KeyBy::Description,
+            mode_preset: None,
+        }
+    }
+
+    fn app_with(monitors: Vec<MonitorConfig>, db: MonitorDatabase) -> App {
+        App::from_parts(monitors, db, Arc::new(exec::Replay::default()))
+    }
+
+    #[test]
+    fn switch_tab_closes_dropdown_and_refreshes_saved() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+        app.dialog = DialogType::EditDropdown;
+        app.switch_tab(MainTab::Saved);
+        assert_eq!(app.main_tab, MainTab::Saved);
+        assert!(matches!(app.dialog, DialogType::None));
+    }
+
+    #[test]
+    fn refresh_saved_monitors_clamps_selection() {
+        let mut app = app_with(vec![], MonitorDatabase::default());
+        app.saved_selected_monitor = 5;
+        app.refresh_saved_monitors();
+        assert_eq!(app.saved_selected_monitor, 0);
+    }
+
+    #[test]
+    fn workspace_navigation_respects_bounds() {
+        let mut db = MonitorDatabase::default();
+        db.add_workspace("Two").unwrap();
+        let mut app = app_with(vec![], db);
+        app.next_workspace();
+        assert_eq!(app.selected_workspace, 1);
+        app.next_workspace(); // already last
+        assert_eq!(app.selected_workspace, 1);
+        app.prev_workspace();
+        assert_eq!(app.selected_workspace, 0);
+        app.prev_workspace(); // already first
+        assert_eq!(app.selected_workspace, 0);
+    }
+
+    #[test]
+    fn workspace_create_rename_delete_persists_to_temp() {
+        let mut p = std::env::temp_dir();
+        p.push(format!("hyprmon_app_ws_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&p);
+        let mut db = MonitorDatabase::default();
+        db.set_config_path(p.clone());
+        let mut app = app_with(vec![], db);
+
+        app.create_workspace("New").unwrap();
+        assert_eq!(app.monitor_db.workspaces.len(), 2);
+        assert!(app.toasts.last_text().contains("New"));
+        assert!(p.exists());
+
+        app.rename_current_workspace("Renamed").unwrap();
+        assert_eq!(app.current_workspace_name(), "Renamed");
+
+        assert!(app.delete_current_workspace());
+        assert!(!app.delete_current_workspace()); // cannot delete the last
+        let _ = std::fs::remove_file(&p);
+    }
+
+    #[test]
+    fn current_workspace_name_out_of_range_is_empty() {
+        let mut app = app_with(vec![], MonitorDatabase::default());
+        app.selected_workspace = 99;
+        assert_eq!(app.current_workspace_name(), "");
+    }
+
+    #[test]
+    fn current_monitor_accessors() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+        assert!(app.current_monitor().is_some());
+        app.current_monitor_mut().unwrap().scale = 2.0;
+        assert_eq!(app.current_monitor().unwrap().scale, 2.0);
+        app.selected_monitor = 99;
+        assert!(app.current_monitor().is_none());
+        assert!(app.current_monitor_mut().is_none());
+    }
+
+    #[test]
+    fn monitor_selection_wraps() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
+            MonitorDatabase::default(),
+        );
+        app.select_next_monitor();
+        assert_eq!(app.selected_monitor, 1);
+        app.select_next_monitor(); // wrap
+        assert_eq!(app.selected_monitor, 0);
+        app.select_prev_monitor(); // wrap back
+        assert_eq!(app.selected_monitor, 1);
+        app.select_prev_monitor();
+        assert_eq!(app.selected_monitor, 0);
+    }
+
+    #[test]
+    fn move_monitor_swaps_and_recalculates() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
+            MonitorDatabase::default(),
+        );
+        app.selected_monitor = 1;
+        app.move_monitor_left();
+        assert_eq!(app.monitors[0].name, "B");
+        assert_eq!(app.selected_monitor, 0);
+        assert!(app.has_changes);
+        app.move_monitor_left(); // already leftmost
+        assert_eq!(app.selected_monitor, 0);
+        app.move_monitor_right();
+        assert_eq!(app.monitors[1].name, "B");
+        assert_eq!(app.selected_monitor, 1);
+        app.move_monitor_right(); // already rightmost
+        assert_eq!(app.selected_monitor, 1);
+    }
+
+    #[test]
+    fn session_restores_by_id_and_skips_what_is_gone() {
+        let mut db = MonitorDatabase::default();
+        db.add_workspace("Desk").unwrap();
+        let monitors =
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)];
+        let mut app = app_with(monitors.clone(), db.clone());
+        app.selected_monitor = 1;
+        app.focus_panel = FocusPanel::Settings;
+        app.selected_workspace = 1;
+        app.main_tab = MainTab::Saved;
+        app.saved_selected_monitor = 5;
+        let session = app.session();
+        assert_eq!(session.workspace, Some(db.workspaces[1].id.clone()));
+
+        // Next run: monitors came back in a different order
+        let mut next = app_with(monitors.into_iter().rev().collect(), db);
+        next.restore_session(&session);
+        assert_eq!(next.current_monitor().unwrap().name, "B");
+        assert_eq!(next.selected_monitor, 0);
+        assert_eq!((next.main_tab, next.focus_panel), (MainTab::Saved, FocusPanel::Settings));
+        assert_eq!(next.selected_workspace, 1);
+
+        let mut gone =
+            app_with(vec![mc("C", "z", "z", "1920x1080", 0)], MonitorDatabase::default());
+        gone.restore_session(&session);
+        assert_eq!((gone.selected_monitor, gone.selected_workspace), (0, 0));
+        assert_eq!(gone.saved_selected_monitor, 0); // clamped to the workspace
+    }
+
+    #[test]
+    fn changed_markers_follow_the_applied_state() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
+            MonitorDatabase::default(),
+        );
+        assert!(!app.monitor_changed(0) && !app.monitor_changed(1));
+        app.monitors[1].scale = 1.5;
+        assert!(app.setting_changed(1, SettingField::Scale));
+        assert!(!app.setting_changed(1, SettingField::Resolution));
+        assert!(app.monitor_changed(1) && !app.monitor_changed(0));
+
+        // Swapping changes positions, not settings; matching is by connector
+        app.selected_monitor = 1;
+        app.move_monitor_left();
+        assert_eq!(app.monitors[0].name, "B");
+        assert!(app.setting_changed(0, SettingField::Scale));
+        assert!(!app.setting_changed(1, SettingField::Scale));
+        assert!(app.monitor_changed(1));
+
+        app.monitors.push(mc("C", "z", "z", "1920x1080", 0));
+        assert!(app.monitor_changed(2));
+        assert!(!app.setting_changed(2, SettingField::Scale));
+    }
+
+    #[test]
+    fn recalculate_positions_lays_edge_to_edge_with_scale() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "2560x1440", 0)],
+            MonitorDatabase::default(),
+        );
+        app.monitors[0].scale = 1.5; // logical width 1280
+        app.recalculate_positions();
+        assert_eq!(app.monitors[0].position_x, 0);
+        assert_eq!(app.monitors[1].position_x, 1280);
+    }
+
+    #[test]
+    fn set_and_toggle_primary() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
+            MonitorDatabase::default(),
+        );
+        app.set_primary(1);
+        assert!(app.monitors[1].is_primary && !app.monitors[0].is_primary);
+
+        app.selected_monitor = 1;
+        app.toggle_primary(); // currently primary -> hand off to the other
+        assert!(app.monitors[0].is_primary);
+        app.toggle_primary(); // selected(1) not primary -> becomes primary
+        assert!(app.monitors[1].is_primary);
+    }
+
+    #[test]
+    fn toggle_primary_single_monitor_stays_primary() {
+        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
+        app.set_primary(0);
+        app.toggle_primary();
+        assert!(app.monitors[0].is_primary);
+    }
+
+    #[test]
+    fn dropdown_options_per_field() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+
+        app.selected_setting = 0; // Resolution -> highest pixel first
+        let res = app.get_dropdown_options();
+        assert_eq!(res.first().map(String::as_str), Some("2560x1440"));
+        assert!(res.contains(&"1920x1080".to_string()));
+
+        app.selected_setting = 1; // RefreshRate for 1920x1080 -> 144,60, then "highrr"
+        let rates = app.get_dropdown_options();
+        assert_eq!(rates, vec!["144Hz", "60Hz", "highrr"]);
+
+        app.selected_setting = 2; // Scale fixed list, then "Custom…"
+        assert_eq!(app.get_dropdown_options().len(), 6);
+
+        app.selected_setting = 3; // Rotation
+        assert_eq!(app.get_dropdown_options().len(), 4);
+
+        app.selected_setting = 4; // Primary has no dropdown
+        assert!(app.get_dropdown_options().is_empty());
+    }
+
+    #[test]
+    fn refresh_rate_options_fall_back_when_no_modes_match() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "3840x2160", 0)], MonitorDatabase::default());
+        app.selected_setting = 1; // no mode matches 3840x2160 -> fallback to current
+        assert_eq!(app.get_dropdown_options(), vec!["60Hz", "highrr", "144Hz @ 1920x1080"]);
+    }
+
+    #[test]
+    fn a_rate_from_another_resolution_switches_to_it() {
+        let monitors = vec![mc("DP-2", "D", "M", "2560x1440", 0)];
+        let mut app = app_with(monitors, MonitorDatabase::default());
+        app.selected_setting = 1;
+        assert_eq!(app.get_dropdown_options(), vec!["60Hz", "highrr", "144Hz @ 1920x1080"]);
+        app.dropdown_selection = 2;
+        app.apply_dropdown_selection();
+        let m = &app.monitors[0];
+        assert_eq!((m.resolution.as_str(), m.refresh_rate), ("1920x1080", 144.0));
+        assert_eq!(app.toasts.last_text(), "Resolution set to 1920x1080 for 144 Hz");
+    }
+
+    #[test]
+    fn apply_dropdown_sets_each_field() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+
+        app.selected_setting = 2; // Scale -> "150%"
+        app.dropdown_selection = 2;
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().scale, 1.5);
+
+        app.selected_setting = 3; // Rotation -> Left
+        app.dropdown_selection = 1;
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().rotation, Rotation::Left);
+
+        app.selected_setting = 1; // RefreshRate -> 144
+        app.dropdown_selection = 0;
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().refresh_rate, 144.0);
+
+        app.selected_setting = 0; // Resolution -> top option
+        let top = app.get_dropdown_options()[0].clone();
+        app.dropdown_selection = 0;
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().resolution, top);
+    }
+
+    #[test]
+    fn mode_presets_show_the_mode_hyprland_picks_and_are_saved_by_name() {
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let mut app = app_with(vec![edp], MonitorDatabase::default());
+        app.selected_setting = 1;
+        app.dropdown_selection = 2; // "highrr"
+        app.apply_dropdown_selection();
+        let m = app.current_monitor().unwrap();
+        assert_eq!((m.resolution.as_str(), m.refresh_rate), ("1920x1080", 144.0));
+        assert_eq!(m.mode_preset, Some(ModePreset::HighRr));
+        assert_eq!(compare::field_value(m, SettingField::RefreshRate), "highrr");
+
+        app.monitor_db.update_monitor(&app.monitors[0].clone());
+        let conf = app.monitor_db.generate_full_config(&HashSet::new());
+        assert!(conf.contains("monitor=eDP-1,highrr,0x0,1\n"), "{conf}");
+
+        app.selected_setting = 0;
+        let options = app.get_dropdown_options();
+        app.dropdown_selection = options.iter().position(|o| o == "highres").unwrap();
+        app.apply_dropdown_selection();
+        let m = app.current_monitor().unwrap();
+        assert_eq!((m.resolution.as_str(), m.refresh_rate), ("2560x1440", 60.0));
+        assert_eq!(m.mode_preset, Some(ModePreset::HighRes));
+
+        app.dropdown_selection = 1; // a fixed resolution drops the preset
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().mode_preset, None);
+    }
+
+    #[test]
+    fn changing_resolution_moves_the_rate_to_one_it_offers() {
+        let mut edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        edp.refresh_rate = 144.0;
+        let mut app = app_with(vec![edp], MonitorDatabase::default());
+        app.selected_setting = 0;
+        app.dropdown_selection = 0; // 2560x1440, only offered at 60 Hz
+        app.apply_dropdown_selection();
+        let m = app.current_monitor().unwrap();
+        assert_eq!((m.resolution.as_str(), m.refresh_rate), ("2560x1440", 60.0));
+        assert!(m.mode_advertised());
+        let last = app.toasts.history().last().unwrap().text.clone();
+        assert_eq!(last, "Refresh rate set to 60 Hz, the nearest eDP-1 offers at 2560x1440");
+
+        app.set_setting_from_text(0, SettingField::Resolution, "2790x1000").unwrap();
+        let m = app.current_monitor().unwrap();
+        assert_eq!(m.refresh_rate, 60.0);
+        assert!(!m.mode_advertised());
+        let last = app.toasts.history().last().unwrap().text.clone();
+        assert!(last.starts_with("2790x1000@60 Hz is not a mode eDP-1 advertises"), "{last}");
+    }
+
+    #[test]
+    fn apply_dropdown_out_of_range_is_noop() {
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+        app.selected_setting = 2;
+        app.dropdown_selection = 99;
+        app.apply_dropdown_selection();
+        assert_eq!(app.current_monitor().unwrap().scale, 1.0); // unchanged
+    }
+
+    #[test]
+    fn generate_config_writes_lines_and_transform() {
+        let mut app = app_with(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0), mc("HDMI-A-1", "MSI", "MP", "2560x1440", 1920)],
+            MonitorDatabase::default(),
+        );
+        app.monitors[1].rotation = Rotation::Left;
+        let cfg = app.generate_config();
+        assert!(cfg.contains("monitor=eDP-1,"));
+        assert!(cfg.contains("desc:MSI MP"));
+        assert!(cfg.contains(",transform,1"));
+    }
+
+    #[test]
+    fn preset_is_suggested_until_used_and_can_be_undone() {
+        let dell = mc("DP-1", "Dell Inc.", "DELL U2720Q", "1920x1080", 0);
+        let mut app = app_with(vec![dell], MonitorDatabase::default());
+        assert!(app.suggested_preset(0).is_some());
+        app.use_preset();
+        // 3840x2160 is not among the modes it lists, so only the scale changes
+        let dp = &app.monitors[0];
+        assert_eq!((dp.resolution.as_str(), dp.scale), ("1920x1080", 1.5));
+        assert!(app.has_changes && app.suggested_preset(0).is_none());
+        app.use_preset();
+        assert_eq!(app.toasts.last_text(), "DP-1 already runs its preset");
+        app.undo();
+        assert_eq!(app.monitors[0].scale, 1.0);
+
+        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
+        app.use_preset();
+        assert!(app.toasts.last_text().starts_with("No preset known"));
+        assert!(!app.has_changes);
+    }
+
+    #[test]
+    fn undo_redo_covers_moves_settings_and_primary() {
+        let mut app = app_with(
+            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
+            MonitorDatabase::default(),
+        );
+        app.move_monitor_right();
+        app.selected_setting = 2; // Scale -> "200%"
+        app.dropdown_selection = 4;
+        app.apply_dropdown_selection();
+        app.toggle_primary();
+        assert!(app.monitors[1].is_primary);
+
+        app.undo();
+        assert!(!app.monitors[1].is_primary);
+        app.undo();
+        assert_eq!(app.monitors[1].scale, 1.0);
+        app.undo();
+        assert_eq!(app.monitors[0].name, "A");
+        assert!(!app.has_changes);
+        app.undo();
+        assert_eq!(app.toasts.last_text(), "Nothing to undo.");
+
+        app.redo();
+        assert_eq!(app.monitors[0].name, "B");
+        assert!(app.has_changes);
+    }
+
+    fn temp_file(tag: &str) -> PathBuf {
+        let mut p = std::env::temp_dir();
+        p.push(format!("hyprmon_app_{tag}_{}", std::process::id()));
+        let _ = fs::remove_file(&p);
+        p
+    }
+
+    /// App whose database, monitors.conf and history log all live in temp files.
+    fn sandboxed(monitors: Vec<MonitorConfig>, mut db: MonitorDatabase, tag: &str) -> App {
+        db.set_config_path(temp_file(&format!("{tag}.json")));
+        let mut app = app_with(monitors, db);
+        app.hypr_conf_path = temp_file(&format!("{tag}.conf"));
+        app.history_log = HistoryLog::at(temp_file(&format!("{tag}.jsonl")));
+        app
+    }
+
+    #[test]
+    fn apply_saved_workspace_writes_profile_and_reverts_db() {
+        let mut db = MonitorDatabase::default();
+        let mut edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        edp.scale = 2.0;
+        db.add_workspace("HiDPI").unwrap();
+        db.set_active(1);
+        db.update_monitor(&edp);
+        db.set_active(0);
+
+        let mut app = sandboxed(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], db, "apply_saved");
+        app.selected_workspace = 1;
+        app.apply_saved_workspace().unwrap();
+
+        assert_eq!(app.monitors[0].scale, 2.0);
+        assert_eq!(app.monitor_db.active_workspace(), 1);
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert!(conf.contains("monitor=eDP-1,1920x1080@60.00,0x0,2"), "{conf}");
+        assert_eq!(app.history_log.load().len(), 1);
+        assert_eq!(app.applied_changes.len(), 1);
+        assert_eq!(app.applied_changes[0].summary(), "eDP-1: scale 100% -> 200%");
+        assert_eq!(app.monitor_db.workspaces[1].apply_count, 1);
+        assert!(app.monitor_db.workspaces[1].last_applied.is_some());
+
+        app.revert_changes();
+        assert_eq!(app.monitor_db.active_workspace(), 0);
+        assert_eq!(app.selected_workspace, 0);
+        assert_eq!(app.monitors[0].scale, 1.0);
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("apply_saved.{ext}")));
+        }
+    }
+
+    #[test]
+    fn scales_hyprland_would_round_are_offered_a_fix_before_apply() {
+        let mut framework = mc("eDP-1", "BOE", "NE135FBM", "2256x1504", 0);
+        framework.scale = 1.5;
+        let mut app = sandboxed(vec![framework], MonitorDatabase::default(), "fix_scale");
+        app.save_and_apply().unwrap();
+        assert_eq!(app.dialog, DialogType::FixScale { focus: DialogButton::Yes });
+        assert!(!app.hypr_conf_path.exists());
+        assert_eq!(app.scale_fix_lines(), ["eDP-1: 1.50 → 1.566667"]);
+
+        app.fix_scales();
+        assert!(app.scale_fixes().is_empty());
+        app.save_and_apply_as_is().unwrap();
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert!(conf.contains("monitor=eDP-1,2256x1504@60.00,0x0,1.566667\n"), "{conf}");
+        app.undo();
+        assert_eq!(app.monitors[0].scale, 1.5);
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("fix_scale.{ext}")));
+        }
+    }
+
+    #[test]
+    fn config_errors_after_apply_point_at_the_generated_line() {
+        let mut app = sandboxed(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0)],
+            MonitorDatabase::default(),
+            "config_errors",
+        );
+        app.save_and_apply().unwrap();
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        let line = conf.lines().position(|l| l.starts_with("monitor=eDP-1")).unwrap() + 1;
+        let path = app.hypr_conf_path.to_string_lossy().to_string();
+        app.show_config_errors(vec![
+            ConfigError {
+                file: Some((path, line)),
+                message: "invalid scale".into(),
+                source: None,
+            },
+            ConfigError { file: None, message: "something else".into(), source: None },
+        ]);
+        assert_eq!(app.dialog, DialogType::ConfigErrors { scroll: 0 });
+        assert!(app.toasts.history().last().unwrap().text.contains("2 config error(s)"));
+        let lines = crate::ui::config_error_lines(&app);
+        let file = app.hypr_conf_path.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(lines[0], format!("{file}:{line}: invalid scale"));
+        assert_eq!(lines[1], format!("  {line:>4} │ monitor=eDP-1,1920x1080@60.00,0x0,1"));
+        assert_eq!(lines[2], "something else");
+
+        app.show_config_errors(Vec::new());
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("config_errors.{ext}")));
+        }
+    }
+
+    #[test]
+    fn identical_monitors_must_be_told_apart_before_apply() {
+        let mut left = mc("DP-1", "Dell", "U2720Q", "2560x1440", 0);
+        let mut right = mc("DP-2", "Dell", "U2720Q", "2560x1440", 2560);
+        (left.serial, right.serial) = ("A1".into(), "B2".into());
+        let mut app = sandboxed(vec![left, right], MonitorDatabase::default(), "tell_apart");
+        assert_eq!(app.key_collision(), [0, 1]);
+        assert!(app.key_collides(1));
+
+        app.save_and_apply().unwrap();
+        assert_eq!(app.dialog, DialogType::TellApart { saved: false });
+        assert!(!app.hypr_conf_path.exists());
+        let labels: Vec<String> = app.key_choices().into_iter().map(|(_, l)| l).collect();
+        assert_eq!(labels, ["By serial (A1, B2)", "By connector (DP-1, DP-2)"]);
+
+        app.tell_apart(KeyBy::Serial).unwrap();
+        assert!(app.key_collision().is_empty());
+        assert_eq!(app.monitor_db.key_by["desc:Dell U2720Q"], KeyBy::Serial);
+        assert!(!app.monitor_changed(0));
+        app.save_and_apply().unwrap();
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert!(conf.contains("monitor=desc:Dell U2720Q A1,2560x1440@60.00,0x0,1"), "{conf}");
+        assert!(conf.contains("monitor=desc:Dell U2720Q B2,2560x1440@60.00,2560x0,1"), "{conf}");
+        assert!(!conf.contains("U2720Q,"), "{conf}");
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("tell_apart.{ext}")));
+        }
+    }
+
+    #[test]
+    fn apply_workspace_at_selects_and_applies_nth() {
+        let mut db = MonitorDatabase::default();
+        let mut edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        db.add_workspace("Docked").unwrap();
+        edp.scale = 1.25;
+        db.update_monitor_in(1, &edp);
+
+        let mut app = sandboxed(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], db, "hotkey");
+        app.apply_workspace_at(5).unwrap();
+        assert_eq!(app.toasts.last_text(), "No workspace #6");
+        assert_eq!(app.selected_workspace, 0);
+
+        app.apply_workspace_at(1).unwrap();
+        assert_eq!(app.selected_workspace, 1);
+        assert_eq!(app.monitors[0].scale, 1.25);
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("hotkey.{ext}")));
+        }
+    }
+
+    #[test]
+    fn apply_saved_workspace_without_matching_monitors_is_refused() {
+        let mut app = sandboxed(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0)],
+            MonitorDatabase::default(),
+            "apply_nomatch",
+        );
+        app.apply_saved_workspace().unwrap();
+        assert!(app.toasts.last_text().contains("No connected monitors"));
+        assert!(!app.hypr_conf_path.exists());
+    }
+
+    #[test]
+    fn load_workspace_into_live_stages_undoable_changes() {
+        let mut db = MonitorDatabase::default();
+        let mut edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        edp.scale = 1.25;
+        db.add_workspace("Other").unwrap();
+        db.set_active(1);
+        db.update_monitor(&edp);
+        db.set_active(0);
+
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], db);
+        app.main_tab = MainTab::Saved;
+        app.selected_workspace = 0;
+        app.load_workspace_into_live();
+        assert!(app.toasts.last_text().contains("No connected monitors"));
+
+        app.selected_workspace = 1;
+        app.load_workspace_into_live();
+        assert_eq!(app.main_tab, MainTab::Live);
+        assert_eq!(app.monitors[0].scale, 1.25);
+        assert!(app.has_changes);
+        app.undo();
+        assert_eq!(app.monitors[0].scale, 1.0);
+        assert!(!app.has_changes);
+    }
+
+    #[test]
+    fn save_live_as_targets_only_the_chosen_workspace() {
+        let mut db = MonitorDatabase::default();
+        db.set_config_path(temp_file("save_as.json"));
+        db.add_workspace("Office").unwrap();
+        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], db);
+        app.monitors[0].scale = 1.5;
+        assert_eq!(app.save_as_options().last().unwrap(), "+ New workspace…");
+
+        app.save_live_as(1);
+        assert_eq!(app.selected_workspace, 1);
+        assert!(app.monitor_db.workspaces[0].monitors.is_empty());
+        assert_eq!(app.monitor_db.workspaces[1].monitors["eDP-1"].scale, 1.5);
+
+        app.save_live_as_new("Experiment").unwrap();
+        assert_eq!(app.current_workspace_name(), "Experiment");
+        assert_eq!(app.monitor_db.workspaces[2].monitors.len(), 1);
+        let _ = fs::remove_file(temp_file("save_as.json"));
+    }
+
+    #[test]
+    fn unmanaged_monitor_is_skipped_on_apply() {
+        let mut app = sandboxed(
+            vec![
+                mc("eDP-1", "N", "M", "1920x1080", 0),
+                mc("DP-2", "Wacom", "Cintiq", "1920x1080", 1920),
+            ],
+            MonitorDatabase::default(),
+            "unmanaged",
+        );
+        app.selected_monitor = 1;
+        app.toggle_selected_monitor_managed();
+        assert!(app.toasts.last_text().contains("unmanaged"));
+        app.save_and_apply().unwrap();
+
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert!(conf.contains("monitor=eDP-1,"), "{conf}");
+        assert!(!conf.contains("Wacom"), "{conf}");
+        assert!(!app.monitor_db.workspaces[0].monitors.contains_key("desc:Wacom Cintiq"));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("unmanaged.{ext}")));
+        }
+    }
+
+    #[test]
+    fn compare_push_and_pull_resolve_one_field_each() {
+        let path = temp_file("compare.json");
+        let mut db = MonitorDatabase::default();
+        db.set_config_path(path.clone());
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        db.update_monitor(&edp);
+        let mut app = app_with(vec![edp], db);
+        app.monitors[0].scale = 1.5;
+        app.monitors[0].rotation = Rotation::Left;
+        assert_eq!(app.compare_rows().len(), 2);
+
+        // Row 0 is Scale: push keeps 1.5 in the workspace.
+        app.compare_push(0);
+        assert_eq!(app.monitor_db.workspaces[0].monitors["eDP-1"].scale, 1.5);
+        assert_eq!(app.monitor_db.workspaces[0].monitors["eDP-1"].rotation, 0);
+        let rows = app.compare_rows();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].field, Some(SettingField::Rotation));
+
+        // Pull restores the saved rotation as an undoable edit.
+        app.compare_pull(0);
+        assert_eq!(app.monitors[0].rotation, Rotation::Normal);
+        assert!(app.compare_rows().is_empty());
+        app.undo();
+        assert_eq!(app.monitors[0].rotation, Rotation::Left);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn out_of_sync_tracks_hyprland_state_not_pending_edits() {
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let mut db = MonitorDatabase::default();
+        db.update_monitor(&edp);
+        let mut app = app_with(vec![edp], db);
+        assert!(!app.out_of_sync());
+
+        app.monitors[0].scale = 2.0; // pending Live edit only
+        assert!(!app.out_of_sync());
+        app.hypr_monitors[0].scale = 1.25; // changed behind hyprmon's back
+        assert!(app.out_of_sync());
+    }
+
+    #[test]
+    fn workspace_naming_prefills_and_cycles_suggestions() {
+        let mut app = app_with(
+            vec![
+                mc("eDP-1", "BOE", "0x0BCA", "1920x1080", 0),
+                mc("DP-1", "Dell Inc.", "U2720Q", "2560x1440", 1920),
+            ],
+            MonitorDatabase::default(),
+        );
+        app.begin_workspace_naming(DialogType::NewWorkspace);
+        assert!(matches!(app.dialog, DialogType::NewWorkspace));
+        assert_eq!(app.text_input.text(), "eDP-1 + DELL U2720Q");
+        app.cycle_name_suggestion();
+        assert_eq!(app.text_input.text(), "Laptop + U2720Q");
+        app.cycle_name_suggestion();
+        app.cycle_name_suggestion();
+        assert_eq!(app.text_input.text(), "eDP-1 + DELL U2720Q");
+    }
+
+    #[test]
+    fn lock_and_manual_only_toggles_persist() {
+        let path = temp_file("lock.json");
+        let mut db = MonitorDatabase::default();
+        db.set_config_path(path.clone());
+        let mut app = app_with(vec![], db);
+
+        app.toggle_lock_active();
+        assert!(app.monitor_db.lock_active);
+        assert!(app.toasts.last_text().starts_with("Locked on 'Default'"));
+        app.toggle_manual_only_current_workspace();
+        let reloaded = MonitorDatabase::load_from(&path).unwrap();
+        assert!(reloaded.lock_active);
+        assert!(reloaded.workspaces[0].manual_only);
+
+        app.toggle_lock_active();
+        assert!(!app.monitor_db.lock_active);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn pending_config_follows_the_tab_without_saving() {
+        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
+        app.monitor_db.update_monitor(&app.monitors[0]);
+        app.monitors[0].scale = 1.5;
+        let live = app.pending_config();
+        assert!(live.starts_with(crate::config::BLOCK_BEGIN), "{live}");
+        assert!(live.contains("1920x1080@60.00,0x0,1.50\n"), "{live}");
+        let saved = &app.monitor_db.current_workspace().unwrap().monitors;
+        assert_eq!(saved.values().next().unwrap().scale, 1.0);
+
+        app.main_tab = MainTab::Saved;
+        assert!(app.pending_config().contains("1920x1080@60.00,0x0,1\n"));
+    }
+
+    #[test]
+    fn config_preview_marks_lines_not_on_disk_and_writes_nothing() {
+        let mut app = sandboxed(
+            vec![mc("A", "x", "x", "1920x1080", 0)],
+            MonitorDatabase::default(),
+            "preview",
+        );
+        app.open_config_preview();
+        assert_eq!(app.dialog, DialogType::ConfigPreview { scroll: 0 });
+        assert!(!app.hypr_conf_path.exists());
+        assert!(app.config_preview.iter().all(|l| l.starts_with('+')));
+
+        fs::write(&app.hypr_conf_path, app.pending_config()).unwrap();
+        app.monitors[0].scale = 2.0;
+        app.open_config_preview();
+        let changed: Vec<&String> =
+            app.config_preview.iter().filter(|l| l.starts_with('+')).collect();
+        assert_eq!(changed, ["+ monitor=desc:x x,1920x1080@60.00,0x0,2"]);
+        let on_disk = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert_eq!(on_disk.matches(",0x0,1\n").count(), 1);
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("preview.{ext}")));
+        }
+    }
+
+    #[test]
+    fn drag_target_previews_what_finish_drag_does() {
+        let mut app = app_with(
+            vec![
+                mc("A", "x", "a", "1920x1080", 0),
+                mc("B", "x", "b", "2560x1440", 1920),
+                mc("C", "x", "c", "1280x1024", 4480),
+            ],
+            MonitorDatabase::default(),
+        );
+        assert_eq!(app.drag_target(), None);
+        let slot = BOX_WIDTH + BOX_GAP;
+        app.drag_state = DragState::Dragging {
+            monitor_idx: 0,
+            start_x: 10,
+            start_y: 0,
+            current_x: 10 + 2 * slot,
+            current_y: 0,
+        };
+        assert_eq!(app.drag_target(), Some((2, 2560 + 1280)));
+        app.finish_drag();
+        let names: Vec<&str> = app.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["B", "C", "A"]);
+        assert_eq!((app.selected_monitor, app.monitors[2].position_x), (2, 3840));
+        assert!(app.has_changes);
+        assert_eq!(app.drag_state, DragState::None);
+
+        app.has_changes = false;
+        app.drag_state = DragState::Dragging {
+            monitor_idx: 2,
+            start_x: 50,
+            start_y: 0,
+            current_x: 50 - slot / 2,
+            current_y: 0,
+        };
+        assert_eq!(app.drag_target(), Some((2, 3840)));
+        app.finish_drag();
+        assert!(!app.has_changes);
+    }
+
     #[test]
-    fn switch_tab_closes_dropdown_and_refreshes_saved() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
-        app.dialog = DialogType::EditDropdown;
-        app.switch_tab(MainTab::Saved);
-        assert_eq!(app.main_tab, MainTab::Saved);
+    fn revert_and_confirm_changes() {
+        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
+        app.monitors[0].scale = 9.0;
+        app.has_changes = true;
+        app.revert_changes();
+        assert_eq!(app.monitors[0].scale, 1.0);
+        assert!(!app.has_changes);
+
+        app.monitors[0].scale = 3.0;
+        app.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
+        app.confirm_changes();
+        assert_eq!(app.original_monitors[0].scale, 3.0);
+        assert!(!app.has_changes);
         assert!(matches!(app.dialog, DialogType::None));
     }
 
     #[test]
-    fn refresh_saved_monitors_clamps_selection() {
-        let mut app = app_with(vec![], MonitorDatabase::default());
-        app.saved_selected_monitor = 5;
-        app.refresh_saved_monitors();
-        assert_eq!(app.saved_selected_monitor, 0);
+    fn hotplug_updates_the_cache_without_asking_hyprctl_again() {
+        let mut db = MonitorDatabase::default();
+        let mut hdmi = mc("HDMI-A-1", "MSI", "MP275Q", "2560x1440", 1920);
+        hdmi.scale = 1.5;
+        db.update_monitor(&hdmi);
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let mut app = sandboxed(vec![edp], db, "hotplug");
+        app.monitors[0].scale = 2.0; // unapplied edit, dropped by the rebuild
+        app.has_changes = true;
+
+        hdmi.scale = 1.0;
+        // Nothing answers the keyword here, so the whole config goes out
+        app.monitor_added(hdmi, None).unwrap();
+        assert_eq!(app.hypr_monitors.len(), 2);
+        assert_eq!((app.monitors[0].scale, app.monitors[1].scale), (1.0, 1.5));
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        app.confirm_changes();
+
+        // The v2 event for the same hotplug is answered from the cache
+        app.on_monitor_added(&AddedMonitor::named("HDMI-A-1")).unwrap();
+        assert_eq!(app.dialog, DialogType::None);
+
+        // Well after that apply's own reload
+        app.reloaded_at = Some(Instant::now() - RELOAD_ECHO);
+        app.on_monitor_removed("HDMI-A-1");
+        assert_eq!(app.monitors.len(), 1);
+        assert_eq!(app.hypr_monitors.len(), 1);
+        assert_eq!(app.toasts.last_text(), "Monitor disconnected.");
+        app.toasts.info("marker");
+        app.on_monitor_removed("DP-9");
+        assert_eq!(app.toasts.last_text(), "marker");
     }
 
     #[test]
-    fn workspace_navigation_respects_bounds() {
+    fn monitor_events_from_our_own_reload_do_not_apply_again() {
         let mut db = MonitorDatabase::default();
-        db.add_workspace("Two");
-        let mut app = app_with(vec![], db);
-        app.next_workspace();
-        assert_eq!(app.selected_workspace, 1);
-        app.next_workspace(); // already last
-        assert_eq!(app.selected_workspace, 1);
-        app.prev_workspace();
-        assert_eq!(app.selected_workspace, 0);
-        app.prev_workspace(); // already first
-        assert_eq!(app.selected_workspace, 0);
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let mut dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        dell.scale = 1.25;
+        db.update_monitor(&edp);
+        db.update_monitor(&dell);
+        let mut app = sandboxed(vec![edp], db, "reload_echo");
+        let hyprctl = Arc::new(exec::Replay::default().answer("hyprctl reload", "ok"));
+        app.exec = replay(&hyprctl);
+        app.save_and_apply_as_is().unwrap();
+        app.confirm_changes();
+
+        // Hyprland re-creates outputs as it reloads
+        app.on_monitor_removed("eDP-1");
+        app.monitor_added(mc("eDP-1", "N", "M", "1920x1080", 0), None).unwrap();
+        dell.scale = 1.0;
+        app.monitor_added(dell, None).unwrap();
+        let calls = ["hyprctl reload", "hyprctl monitors -j", "hyprctl configerrors -j"];
+        assert_eq!(hyprctl.calls(), calls);
+        assert_eq!(app.dialog, DialogType::None);
+        assert_eq!(app.monitors[1].scale, 1.25);
+        assert!(!app.toasts.last_text().starts_with("Monitor"));
     }
 
     #[test]
-    fn workspace_create_rename_delete_persists_to_temp() {
-        let mut p = std::env::temp_dir();
-        p.push(format!("hyprmon_app_ws_{}.json", std::process::id()));
-        let _ = std::fs::remove_file(&p);
+    fn unplugging_the_primary_promotes_the_fallback() {
         let mut db = MonitorDatabase::default();
-        db.set_config_path(p.clone());
-        let mut app = app_with(vec![], db);
-
-        app.create_workspace("New");
-        assert_eq!(app.monitor_db.workspaces.len(), 2);
-        assert!(app.message.contains("New"));
-        assert!(p.exists());
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let mut dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        dell.is_primary = true;
+        db.update_monitor(&edp);
+        db.update_monitor(&dell);
+        let mut app = sandboxed(vec![edp, dell.clone()], db, "primary_gone");
+        assert!(app.monitors[1].is_primary);
 
-        app.rename_current_workspace("Renamed");
-        assert_eq!(app.current_workspace_name(), "Renamed");
+        app.on_monitor_removed("DP-2");
+        assert!(app.monitors[0].is_primary && !app.has_changes);
+        let saved = &app.monitor_db.workspaces[0].monitors;
+        assert!(!saved["eDP-1"].is_primary && saved["desc:Dell U27"].is_primary);
+        assert_eq!(app.toasts.last_text(), "eDP-1 is primary now that DP-2 is unplugged");
 
-        assert!(app.delete_current_workspace());
-        assert!(!app.delete_current_workspace()); // cannot delete the last
-        let _ = std::fs::remove_file(&p);
+        // Docked again, the saved primary is back
+        app.auto_apply = false;
+        app.monitor_added(dell, None).unwrap();
+        let primary: Vec<&str> =
+            app.monitors.iter().filter(|m| m.is_primary).map(|m| m.name.as_str()).collect();
+        assert_eq!(primary, ["DP-2"]);
     }
 
-    #[test]
-    fn current_workspace_name_out_of_range_is_empty() {
-        let mut app = app_with(vec![], MonitorDatabase::default());
-        app.selected_workspace = 99;
-        assert_eq!(app.current_workspace_name(), "");
+    fn replay(replay: &Arc<exec::Replay>) -> Arc<dyn Executor> {
+        replay.clone()
     }
 
+    const HYPR_MONITORS: &str = r#"[
+        {"name":"eDP-1","description":"N M","make":"N","model":"M","width":1920,"height":1080,
+         "refreshRate":60.0,"x":0,"y":0,"scale":1.0,"transform":0,"availableModes":[],
+         "focused":false},
+        {"name":"DP-2","description":"Dell U27","make":"Dell","model":"U27","width":2560,
+         "height":1440,"refreshRate":60.0,"x":1920,"y":0,"scale":1.0,"transform":0,
+         "availableModes":[],"focused":true}
+    ]"#;
+
     #[test]
-    fn current_monitor_accessors() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
-        assert!(app.current_monitor().is_some());
-        app.current_monitor_mut().unwrap().scale = 2.0;
-        assert_eq!(app.current_monitor().unwrap().scale, 2.0);
-        app.selected_monitor = 99;
-        assert!(app.current_monitor().is_none());
-        assert!(app.current_monitor_mut().is_none());
+    fn changes_made_outside_hyprmon_can_be_adopted_or_undone() {
+        let mut db = MonitorDatabase::default();
+        let edp = mc("eDP-1", "N", "M", "1920x1080", 0);
+        let dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        db.update_monitor(&edp);
+        db.update_monitor(&dell);
+        let mut app = sandboxed(vec![edp, dell], db, "external");
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl monitors -j", HYPR_MONITORS)
+                .answer("hyprctl reload", "ok"),
+        );
+        app.exec = replay(&hyprctl);
+        app.poll_hyprland();
+        assert_eq!(app.dialog, DialogType::None);
+
+        // Focus moving is no change
+        let refocused = HYPR_MONITORS.replace("false", "x").replace("true", "false");
+        hyprctl.set("hyprctl monitors -j", refocused.replace("x", "true"));
+        app.poll_hyprland();
+        assert_eq!(app.dialog, DialogType::None);
+
+        let scaled =
+            HYPR_MONITORS.replace(r#"1920,"y":0,"scale":1.0"#, r#"1920,"y":0,"scale":1.25"#);
+        hyprctl.set("hyprctl monitors -j", scaled.as_str());
+        app.poll_hyprland();
+        assert_eq!(app.dialog, DialogType::Reconcile { focus: DialogButton::Yes });
+        assert_eq!(app.external_changes[0].summary(), "desc:Dell U27: scale 100% -> 125%");
+        app.adopt_external_changes().unwrap();
+        assert_eq!((app.monitors[1].scale, app.dialog), (1.25, DialogType::None));
+        assert_eq!(app.monitor_db.workspaces[0].monitors["desc:Dell U27"].scale, 1.25);
+        app.poll_hyprland();
+        app.poll_hyprland();
+        assert_eq!(app.dialog, DialogType::None);
+
+        // Changed again: re-applying reloads monitors.conf over it
+        hyprctl.set("hyprctl monitors -j", HYPR_MONITORS);
+        app.poll_hyprland();
+        assert!(matches!(app.dialog, DialogType::Reconcile { .. }));
+        app.reapply_over_external_changes();
+        assert_eq!(hyprctl.calls().iter().filter(|c| *c == "hyprctl reload").count(), 2);
+        assert_eq!(app.monitors[1].scale, 1.25);
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("external.{ext}")));
+        }
     }
 
     #[test]
-    fn monitor_selection_wraps() {
-        let mut app = app_with(
-            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
-            MonitorDatabase::default(),
+    fn safe_mode_sets_and_writes_preferred_modes_at_once() {
+        let mut dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        dell.scale = 3.0;
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "safe");
+        app.has_changes = true;
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl monitors all -j", HYPR_MONITORS)
+                .answer("hyprctl keyword monitor eDP-1,preferred,auto,1,transform,0", "ok")
+                .answer("hyprctl keyword monitor DP-2,preferred,auto,1,transform,0", "ok")
+                .answer("hyprctl keyword monitor ,preferred,auto,1", "ok")
+                .answer("hyprctl monitors -j", HYPR_MONITORS),
         );
-        app.select_next_monitor();
-        assert_eq!(app.selected_monitor, 1);
-        app.select_next_monitor(); // wrap
-        assert_eq!(app.selected_monitor, 0);
-        app.select_prev_monitor(); // wrap back
-        assert_eq!(app.selected_monitor, 1);
-        app.select_prev_monitor();
-        assert_eq!(app.selected_monitor, 0);
+        app.exec = replay(&hyprctl);
+        app.enter_safe_mode();
+
+        assert_eq!(hyprctl.calls().len(), 5);
+        assert_eq!((app.monitors[1].scale, app.has_changes), (1.0, false));
+        let conf = fs::read_to_string(&app.hypr_conf_path).unwrap();
+        assert!(conf.contains("monitor=DP-2,preferred,auto,1,transform,0"), "{conf}");
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("safe.{ext}")));
+        }
     }
 
     #[test]
-    fn move_monitor_swaps_and_recalculates() {
-        let mut app = app_with(
-            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
-            MonitorDatabase::default(),
+    fn rollback_restores_a_kept_version_and_reloads() {
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0)];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "rollback");
+        app.open_rollback();
+        assert_eq!(app.dialog, DialogType::None);
+
+        fs::write(&app.hypr_conf_path, "monitor=old\n").unwrap();
+        backups::archive(&app.hypr_conf_path).unwrap();
+        fs::write(&app.hypr_conf_path, "monitor=new\n").unwrap();
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl monitors -j", HYPR_MONITORS),
         );
-        app.selected_monitor = 1;
-        app.move_monitor_left();
-        assert_eq!(app.monitors[0].name, "B");
-        assert_eq!(app.selected_monitor, 0);
-        assert!(app.has_changes);
-        app.move_monitor_left(); // already leftmost
-        assert_eq!(app.selected_monitor, 0);
-        app.move_monitor_right();
-        assert_eq!(app.monitors[1].name, "B");
-        assert_eq!(app.selected_monitor, 1);
-        app.move_monitor_right(); // already rightmost
-        assert_eq!(app.selected_monitor, 1);
+        app.exec = replay(&hyprctl);
+        app.has_changes = true;
+        app.open_rollback();
+        assert_eq!(app.dialog, DialogType::Rollback);
+        assert!(app.rollback_options()[0].ends_with("+1 -1"));
+        assert_eq!(app.rollback_diff(), ["+ monitor=old", "- monitor=new"]);
+
+        app.restore_backup(0);
+        assert_eq!(hyprctl.calls(), ["hyprctl reload", "hyprctl monitors -j"]);
+        assert_eq!(fs::read_to_string(&app.hypr_conf_path).unwrap(), "monitor=old\n");
+        assert_eq!((&app.dialog, app.has_changes), (&DialogType::None, false));
+        // The replaced file is kept too, so the restore can be undone
+        let kept = backups::list(&app.hypr_conf_path);
+        assert_eq!(kept.len(), 2);
+        for backup in kept {
+            let _ = fs::remove_file(backup.path);
+        }
+        let _ = fs::remove_file(app.hypr_conf_path.with_extension("conf.bak"));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("rollback.{ext}")));
+        }
     }
 
     #[test]
-    fn recalculate_positions_lays_edge_to_edge_with_scale() {
-        let mut app = app_with(
-            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "2560x1440", 0)],
-            MonitorDatabase::default(),
+    fn apply_sets_monitors_in_the_workspace_order_before_reloading() {
+        let dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "exec_steps");
+        app.monitor_db.workspaces[0].apply_steps.order = vec!["desc:Dell U27".into()];
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl keyword monitor desc:Dell U27,2560x1440@60.00,1920x0,1", "ok")
+                .answer("hyprctl keyword monitor eDP-1,1920x1080@60.00,0x0,1", "ok")
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl configerrors -j", "[]"),
         );
-        app.monitors[0].scale = 1.5; // logical width 1280
-        app.recalculate_positions();
-        assert_eq!(app.monitors[0].position_x, 0);
-        assert_eq!(app.monitors[1].position_x, 1280);
+        app.exec = replay(&hyprctl);
+        app.save_and_apply().unwrap();
+
+        let calls = hyprctl.calls();
+        assert!(calls[0].starts_with("hyprctl keyword monitor desc:Dell U27,"), "{calls:?}");
+        assert!(calls[1].starts_with("hyprctl keyword monitor eDP-1,"), "{calls:?}");
+        assert_eq!(calls[2], "hyprctl reload");
+        assert!(!app.toasts.last_text().contains("refused"));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("exec_steps.{ext}")));
+        }
     }
 
     #[test]
-    fn set_and_toggle_primary() {
-        let mut app = app_with(
-            vec![mc("A", "x", "x", "1920x1080", 0), mc("B", "y", "y", "1920x1080", 1920)],
-            MonitorDatabase::default(),
+    fn apply_status_follows_each_step_and_blames_what_failed() {
+        let dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "exec_status");
+        app.monitor_db.workspaces[0].apply_steps.order = vec!["desc:Dell U27".into()];
+        let drawn = Rc::new(RefCell::new(Vec::new()));
+        let seen = Rc::clone(&drawn);
+        app.progress = Some(Box::new(move |app: &App| {
+            seen.borrow_mut().push(app.apply_status.clone());
+        }));
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl keyword monitor desc:Dell U27,2560x1440@60.00,1920x0,1", "bad")
+                .answer("hyprctl keyword monitor eDP-1,1920x1080@60.00,0x0,1", "ok")
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl configerrors -j", "[]"),
         );
-        app.set_primary(1);
-        assert!(app.monitors[1].is_primary && !app.monitors[0].is_primary);
+        app.exec = replay(&hyprctl);
+        app.save_and_apply().unwrap();
 
-        app.selected_monitor = 1;
-        app.toggle_primary(); // currently primary -> hand off to the other
-        assert!(app.monitors[0].is_primary);
-        app.toggle_primary(); // selected(1) not primary -> becomes primary
-        assert!(app.monitors[1].is_primary);
+        // Drawn queued before anything ran, at each step, then with the
+        // reload's outcome
+        let (queued, setting) = (ApplyStatus::Queued, ApplyStatus::Setting);
+        let failed = ApplyStatus::Failed("bad".to_string());
+        let drawn = drawn.borrow().clone();
+        assert_eq!(drawn[0], [("eDP-1".into(), queued.clone()), ("DP-2".into(), queued.clone())]);
+        assert_eq!(drawn[1], [("eDP-1".into(), queued), ("DP-2".into(), setting.clone())]);
+        assert_eq!(drawn[2], [("eDP-1".into(), setting), ("DP-2".into(), failed.clone())]);
+        let applied = [("eDP-1".into(), ApplyStatus::Applied), ("DP-2".into(), failed.clone())];
+        assert_eq!(drawn[3], applied);
+        assert_eq!(app.apply_status_of("DP-2"), Some(&failed));
+
+        app.monitor_db.workspaces[0].apply_steps = Default::default();
+        app.exec = replay(&Arc::new(exec::Replay::default().answer("hyprctl reload", "no")));
+        app.dialog = DialogType::None;
+        app.save_and_apply().unwrap();
+        let failed = ApplyStatus::Failed("reload: no".to_string());
+        assert_eq!(app.apply_status_of("eDP-1"), Some(&failed));
+
+        // monitors.conf cannot be written where a file stands for its folder
+        app.hypr_conf_path = temp_file("exec_status.json").join("monitors.conf");
+        app.dialog = DialogType::None;
+        assert!(app.save_and_apply().is_err());
+        let Some(ApplyStatus::Failed(why)) = app.apply_status_of("DP-2") else {
+            panic!("{:?}", app.apply_status);
+        };
+        assert!(!why.is_empty());
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("exec_status.{ext}")));
+        }
     }
 
     #[test]
-    fn toggle_primary_single_monitor_stays_primary() {
-        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
-        app.set_primary(0);
-        app.toggle_primary();
-        assert!(app.monitors[0].is_primary);
+    fn apply_turns_off_the_monitors_the_workspace_keeps_dark() {
+        let dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "exec_dpms");
+        app.monitor_db.workspaces[0].dpms_off = vec!["desc:Dell U27".into()];
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl dispatch dpms off DP-2", "ok")
+                .answer("hyprctl configerrors -j", "[]"),
+        );
+        app.exec = replay(&hyprctl);
+        app.save_and_apply().unwrap();
+
+        let calls = hyprctl.calls();
+        assert_eq!(calls[..2], ["hyprctl reload", "hyprctl dispatch dpms off DP-2"]);
+        assert!(!app.toasts.last_text().contains("DPMS"));
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("exec_dpms.{ext}")));
+        }
     }
 
     #[test]
-    fn dropdown_options_per_field() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
-
-        app.selected_setting = 0; // Resolution -> highest pixel first
-        let res = app.get_dropdown_options();
-        assert_eq!(res.first().map(String::as_str), Some("2560x1440"));
-        assert!(res.contains(&"1920x1080".to_string()));
+    fn apply_flags_settings_hyprland_did_not_take() {
+        let mut dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        dell.scale = 1.25;
+        let monitors = vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell];
+        let mut app = sandboxed(monitors, MonitorDatabase::default(), "exec_achieved");
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl monitors -j", HYPR_MONITORS)
+                .answer("hyprctl configerrors -j", "[]"),
+        );
+        app.exec = replay(&hyprctl);
+        app.save_and_apply().unwrap();
 
-        app.selected_setting = 1; // RefreshRate for 1920x1080 -> 144,60
-        let rates = app.get_dropdown_options();
-        assert_eq!(rates, vec!["144Hz", "60Hz"]);
+        assert!(matches!(app.dialog, DialogType::ConfirmApply { .. }));
+        let mismatch = ("DP-2".to_string(), "scale asked 1.25, got 1.00".to_string());
+        assert_eq!(app.apply_mismatches, [mismatch]);
+        assert_eq!(app.apply_status_of("eDP-1"), Some(&ApplyStatus::Verified));
+        let failed = ApplyStatus::Failed("scale asked 1.25, got 1.00".to_string());
+        assert_eq!(app.apply_status_of("DP-2"), Some(&failed));
+        assert!(app.toasts.last_text().contains("DP-2 (scale asked 1.25"));
+        // Editing the layout makes the outcome stale
+        app.checkpoint();
+        assert!(app.apply_status.is_empty());
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("exec_achieved.{ext}")));
+        }
+    }
 
-        app.selected_setting = 2; // Scale fixed list
-        assert_eq!(app.get_dropdown_options().len(), 5);
+    #[test]
+    fn apply_reloads_and_reads_config_errors_through_the_executor() {
+        let mut app = sandboxed(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0)],
+            MonitorDatabase::default(),
+            "exec_apply",
+        );
+        let path = app.hypr_conf_path.to_string_lossy().to_string();
+        let report = format!("Config error in file {path} at line 1: bad line");
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl configerrors -j", serde_json::to_vec(&[report]).unwrap()),
+        );
+        app.exec = replay(&hyprctl);
+        app.save_and_apply().unwrap();
 
-        app.selected_setting = 3; // Rotation
-        assert_eq!(app.get_dropdown_options().len(), 4);
+        let calls = ["hyprctl reload", "hyprctl monitors -j", "hyprctl configerrors -j"];
+        assert_eq!(hyprctl.calls(), calls);
+        assert_eq!(app.dialog, DialogType::ConfigErrors { scroll: 0 });
+        assert_eq!(app.config_errors[0].message, "bad line");
+        // Hyprland could not be asked what it runs, and line 1 is no monitor's
+        assert_eq!(app.apply_status_of("eDP-1"), Some(&ApplyStatus::Applied));
 
-        app.selected_setting = 4; // Primary has no dropdown
-        assert!(app.get_dropdown_options().is_empty());
+        // A rejected monitor= line fails its monitor
+        let written = fs::read_to_string(&path).unwrap();
+        let line = written.lines().position(|l| l.starts_with("monitor=eDP-1")).unwrap();
+        let report = format!("Config error in file {path} at line {}: bad mode", line + 1);
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl configerrors -j", serde_json::to_vec(&[report]).unwrap()),
+        );
+        app.exec = replay(&hyprctl);
+        app.dialog = DialogType::None;
+        app.save_and_apply().unwrap();
+        let failed = ApplyStatus::Failed("bad mode".to_string());
+        assert_eq!(app.apply_status_of("eDP-1"), Some(&failed));
+        assert_eq!(app.toasts.last_text(), "Not applied as asked: eDP-1 (bad mode)");
+        for ext in ["json", "conf", "jsonl"] {
+            let _ = fs::remove_file(temp_file(&format!("exec_apply.{ext}")));
+        }
     }
 
     #[test]
-    fn refresh_rate_options_fall_back_when_no_modes_match() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "3840x2160", 0)], MonitorDatabase::default());
-        app.selected_setting = 1; // no mode matches 3840x2160 -> fallback to current
-        assert_eq!(app.get_dropdown_options(), vec!["60Hz"]);
+    fn refresh_asks_the_executor_for_monitors_workspaces_and_focus() {
+        let mut app = app_with(vec![], MonitorDatabase::default());
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer("hyprctl monitors -j", HYPR_MONITORS)
+                .answer(
+                    "hyprctl workspaces -j",
+                    r#"[{"id":2,"name":"2","monitor":"DP-2","windows":1}]"#,
+                ),
+        );
+        app.exec = replay(&hyprctl);
+        app.refresh().unwrap();
+
+        let names: Vec<&str> = app.hypr_monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["eDP-1", "DP-2"]);
+        assert_eq!(app.monitors.len(), 2);
+        assert_eq!(app.focused_monitor.as_deref(), Some("DP-2"));
+        assert_eq!(workspaces_on(&app.hypr_workspaces, "DP-2"), "2(1)");
+        assert_eq!(
+            hyprctl.calls(),
+            ["hyprctl monitors -j", "hyprctl workspaces -j", "hyprctl monitors -j"]
+        );
+
+        app.exec = Arc::new(exec::Replay::default());
+        assert!(app.refresh().is_err());
+        assert_eq!(app.hypr_monitors.len(), 2);
     }
 
     #[test]
-    fn apply_dropdown_sets_each_field() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
+    fn identify_flashes_each_monitor_through_the_executor() {
+        let mut app = app_with(
+            vec![
+                mc("eDP-1", "N", "M", "1920x1080", 0),
+                mc("DP-2", "Dell", "U27", "2560x1440", 1920),
+            ],
+            MonitorDatabase::default(),
+        );
+        app.identify_prefs.duration = 1;
+        let hyprctl =
+            Arc::new(exec::Replay::default().answer("hyprctl monitors -j", HYPR_MONITORS));
+        app.exec = replay(&hyprctl);
+        app.identify();
 
-        app.selected_setting = 2; // Scale -> "150%"
-        app.dropdown_selection = 2;
-        app.apply_dropdown_selection();
-        assert_eq!(app.current_monitor().unwrap().scale, 1.5);
+        // The flashing runs on a background thread
+        let started = Instant::now();
+        while hyprctl.calls().len() < 3 && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(
+            hyprctl.calls()[..3],
+            [
+                "hyprctl monitors -j",
+                "hyprctl dispatch focusmonitor eDP-1",
+                "hyprctl notify -1 1 rgb(ba68c8) fontsize:40 Monitor 1: Laptop",
+            ]
+        );
 
-        app.selected_setting = 3; // Rotation -> Left
-        app.dropdown_selection = 1;
-        app.apply_dropdown_selection();
-        assert_eq!(app.current_monitor().unwrap().rotation, Rotation::Left);
+        // Colors stay with the monitor, not its place in the row
+        let colors = app.monitor_colors();
+        app.monitors.reverse();
+        assert_eq!(app.monitor_colors(), [colors[1], colors[0]]);
+    }
 
-        app.selected_setting = 1; // RefreshRate -> 144
-        app.dropdown_selection = 0;
-        app.apply_dropdown_selection();
-        assert_eq!(app.current_monitor().unwrap().refresh_rate, 144.0);
+    #[test]
+    fn presentation_mirrors_the_laptop_and_a_reload_restores() {
+        let mut app = app_with(
+            vec![
+                mc("eDP-1", "N", "M", "1920x1080", 0),
+                mc("DP-2", "Dell", "U27", "2560x1440", 1920),
+            ],
+            MonitorDatabase::default(),
+        );
+        let panel = "hyprctl keyword monitor eDP-1,2560x1440@60.00,0x0,1";
+        let mirror = "hyprctl keyword monitor DP-2,2560x1440@60.00,auto,1,mirror,eDP-1";
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer(panel, "ok")
+                .answer(mirror, "ok")
+                .answer("hyprctl reload", "ok"),
+        );
+        app.exec = replay(&hyprctl);
+        app.toggle_presentation();
+        assert_eq!(app.presenting.as_deref(), Some("eDP-1"));
+        assert_eq!(hyprctl.calls()[..2], [panel, mirror]);
+        assert_eq!(
+            app.toasts.last_text(),
+            "Presenting: eDP-1 mirrored at 2560x1440; Ctrl+o restores"
+        );
 
-        app.selected_setting = 0; // Resolution -> top option
-        let top = app.get_dropdown_options()[0].clone();
-        app.dropdown_selection = 0;
-        app.apply_dropdown_selection();
-        assert_eq!(app.current_monitor().unwrap().resolution, top);
+        app.toggle_presentation();
+        assert_eq!(app.presenting, None);
+        assert!(hyprctl.calls().contains(&"hyprctl reload".to_string()));
     }
 
     #[test]
-    fn apply_dropdown_out_of_range_is_noop() {
-        let mut app = app_with(vec![mc("eDP-1", "N", "M", "1920x1080", 0)], MonitorDatabase::default());
-        app.selected_setting = 2;
-        app.dropdown_selection = 99;
-        app.apply_dropdown_selection();
-        assert_eq!(app.current_monitor().unwrap().scale, 1.0); // unchanged
+    fn ctrl_d_scales_each_monitor_by_its_pixel_density() {
+        let mut dell = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        dell.scale = 2.0;
+        let mut app = app_with(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0), dell],
+            MonitorDatabase::default(),
+        );
+        // A 14" panel; the Dell's EDID gives no size
+        let sized = r#""focused":false,"physicalWidth":309,"physicalHeight":174}"#;
+        let json = HYPR_MONITORS.replace(r#""focused":false}"#, sized);
+        app.exec = Arc::new(exec::Replay::default().answer("hyprctl monitors -j", json));
+        app.scale_by_density();
+        assert_eq!((app.monitors[0].scale, app.monitors[1].scale), (1.25, 2.0));
+        assert!(app.has_changes);
+        assert_eq!(app.toasts.last_text(), "Scaled by pixel density: eDP-1 125%; a applies");
+        app.undo();
+        assert_eq!(app.monitors[0].scale, 1.0);
+
+        app.scale_by_density();
+        app.scale_by_density();
+        assert_eq!(
+            app.toasts.last_text(),
+            "Every monitor already has the scale its density suggests"
+        );
     }
 
     #[test]
-    fn generate_config_writes_lines_and_transform() {
+    fn a_forced_projector_mode_ends_when_it_is_unplugged() {
         let mut app = app_with(
-            vec![mc("eDP-1", "N", "M", "1920x1080", 0), mc("HDMI-A-1", "MSI", "MP", "2560x1440", 1920)],
+            vec![
+                mc("eDP-1", "N", "M", "1920x1080", 0),
+                mc("HDMI-A-1", "Epson", "EB", "2560x1440", 1920),
+            ],
             MonitorDatabase::default(),
         );
-        app.monitors[1].rotation = Rotation::Left;
-        let cfg = app.generate_config();
-        assert!(cfg.contains("monitor=eDP-1,"));
-        assert!(cfg.contains("desc:MSI MP"));
-        assert!(cfg.contains(",transform,1"));
+        let forced = "hyprctl keyword monitor HDMI-A-1,1920x1080@60,1920x0,1";
+        let hyprctl = Arc::new(
+            exec::Replay::default().answer(forced, "ok").answer("hyprctl reload", "ok"),
+        );
+        app.exec = replay(&hyprctl);
+        app.selected_monitor = 1;
+        app.toggle_projector_mode();
+        assert_eq!(app.projector.as_deref(), Some("HDMI-A-1"));
+        assert_eq!(hyprctl.calls()[0], forced);
+
+        app.on_monitor_removed("HDMI-A-1");
+        assert_eq!(app.projector, None);
+        assert_eq!(hyprctl.calls().last().unwrap(), "hyprctl reload");
+        assert_eq!(
+            app.toasts.last_text(),
+            "HDMI-A-1 disconnected; its forced mode is dropped"
+        );
     }
 
     #[test]
-    fn revert_and_confirm_changes() {
-        let mut app = app_with(vec![mc("A", "x", "x", "1920x1080", 0)], MonitorDatabase::default());
-        app.monitors[0].scale = 9.0;
-        app.has_changes = true;
-        app.revert_changes();
-        assert_eq!(app.monitors[0].scale, 1.0);
-        assert!(!app.has_changes);
+    fn game_mode_uses_keywords_and_a_reload_restores() {
+        let mut side = mc("DP-2", "Dell", "U27", "2560x1440", 1920);
+        side.rotation = Rotation::Left;
+        let mut app = app_with(
+            vec![mc("eDP-1", "N", "M", "1920x1080", 0), side],
+            MonitorDatabase::default(),
+        );
+        let game = "hyprctl keyword monitor eDP-1,1920x1080@144.00,0x0,1,vrr,1";
+        let other = "hyprctl keyword monitor DP-2,2560x1440@60.00,1920x0,1,transform,1,vrr,0";
+        let hyprctl = Arc::new(
+            exec::Replay::default()
+                .answer(game, "ok")
+                .answer(other, "ok")
+                .answer("hyprctl reload", "ok"),
+        );
+        app.exec = replay(&hyprctl);
+        app.toggle_game_mode();
+        assert_eq!(app.game_mode.as_deref(), Some("eDP-1"));
+        assert_eq!(hyprctl.calls()[..2], [game, other]);
+        assert!(app.toasts.last_text().starts_with("Game mode: eDP-1 at 144 Hz"));
+        assert!(!app.has_changes && app.monitor_db.workspaces[0].monitors.is_empty());
 
-        app.monitors[0].scale = 3.0;
-        app.dialog = DialogType::ConfirmQuit;
-        app.confirm_changes();
-        assert_eq!(app.original_monitors[0].scale, 3.0);
-        assert!(!app.has_changes);
-        assert!(matches!(app.dialog, DialogType::None));
+        app.toggle_game_mode();
+        assert_eq!(app.game_mode, None);
+        assert!(hyprctl.calls().contains(&"hyprctl reload".to_string()));
+
+        // Refused by Hyprland: nothing to restore
+        app.exec = Arc::new(exec::Replay::default());
+        app.toggle_game_mode();
+        assert_eq!(app.game_mode, None);
+        assert!(app.toasts.last_text().starts_with("hyprctl:"));
     }
 }
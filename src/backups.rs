@@ -0,0 +1,168 @@
+//! Every monitors.conf hyprmon replaces is kept, timestamped, in a
+//! `hyprmon-backups` directory beside it, so any of them can be put back
+//! (`hyprmon rollback`, `Ctrl+b`), not just the latest `.conf.bak`.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::clock;
+use crate::config::FileChange;
+
+/// How many copies of one file are kept; older ones are dropped.
+pub const KEEP: usize = 30;
+
+/// One kept copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Unix seconds the file was replaced.
+    pub taken: u64,
+}
+
+/// Where copies of `main` are kept.
+pub fn dir_for(main: &Path) -> PathBuf {
+    main.with_file_name("hyprmon-backups")
+}
+
+/// `monitors` for `monitors.conf`.
+fn stem(main: &Path) -> String {
+    main.file_stem().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// Copies of `main`, newest first: `<stem>-<unix>.conf`, with `-<n>` added
+/// for a second copy within the same second.
+pub fn list(main: &Path) -> Vec<Backup> {
+    let prefix = format!("{}-", stem(main));
+    let Ok(entries) = fs::read_dir(dir_for(main)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(Backup, u32)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(".conf")?;
+            let (taken, n) = stamp.split_once('-').unwrap_or((stamp, "0"));
+            let backup = Backup { path: entry.path(), taken: taken.parse().ok()? };
+            Some((backup, n.parse().ok()?))
+        })
+        .collect();
+    backups.sort_by(|(a, an), (b, bn)| (b.taken, bn).cmp(&(a.taken, an)));
+    backups.into_iter().map(|(backup, _)| backup).collect()
+}
+
+/// Keep a copy of `main` as it is now, unless the newest copy already has
+/// the same content, and drop copies past [`KEEP`].
+pub fn archive(main: &Path) -> Result<()> {
+    let content = fs::read(main)?;
+    let existing = list(main);
+    if existing.first().is_some_and(|newest| fs::read(&newest.path).ok() == Some(content.clone())) {
+        return Ok(());
+    }
+    let dir = dir_for(main);
+    fs::create_dir_all(&dir)?;
+    let now = clock::now();
+    let taken: HashSet<PathBuf> = existing.iter().map(|b| b.path.clone()).collect();
+    let path = (0..)
+        .map(|n| match n {
+            0 => dir.join(format!("{}-{now}.conf", stem(main))),
+            n => dir.join(format!("{}-{now}-{n}.conf", stem(main))),
+        })
+        .find(|path| !taken.contains(path))
+        .expect("some name is free");
+    fs::write(&path, content)?;
+    for old in list(main).iter().skip(KEEP) {
+        let _ = fs::remove_file(&old.path);
+    }
+    Ok(())
+}
+
+/// What restoring `backup` changes, set-wise like the config preview: lines
+/// it would bring back as `+`, lines it would drop as `-`.
+pub fn diff(current: &str, backup: &str) -> Vec<String> {
+    let now: HashSet<&str> = current.lines().collect();
+    let then: HashSet<&str> = backup.lines().collect();
+    let added = backup.lines().filter(|l| !now.contains(l)).map(|l| format!("+ {l}"));
+    let dropped = current.lines().filter(|l| !then.contains(l)).map(|l| format!("- {l}"));
+    added.chain(dropped).collect()
+}
+
+/// `+N -M`: how many lines restoring `backup` over `main` brings back and drops.
+pub fn diff_summary(main: &Path, backup: &Backup) -> String {
+    let lines = diff(&fs::read_to_string(main).unwrap_or_default(), &read(backup));
+    let added = lines.iter().filter(|l| l.starts_with('+')).count();
+    match (added, lines.len() - added) {
+        (0, 0) => "same as now".to_string(),
+        (added, dropped) => format!("+{added} -{dropped}"),
+    }
+}
+
+fn read(backup: &Backup) -> String {
+    fs::read_to_string(&backup.path).unwrap_or_default()
+}
+
+/// The changes restoring `backup` as `main` makes: the current file kept
+/// first, like any other write, so the restore can be undone the same way.
+pub fn restore_changes(backup: &Backup, main: &Path) -> Result<Vec<FileChange>> {
+    let content = fs::read_to_string(&backup.path)
+        .with_context(|| format!("cannot read {}", backup.path.display()))?;
+    let mut changes = FileChange::backups_of(main);
+    changes.push(FileChange::Write { path: main.to_path_buf(), content });
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(tag: &str) -> PathBuf {
+        let name = format!("hyprmon_backups_{tag}_{}", std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("monitors.conf")
+    }
+
+    #[test]
+    fn archives_each_distinct_version_newest_first() {
+        let main = sandbox("archive");
+        for content in ["monitor=a\n", "monitor=a\n", "monitor=b\n", "monitor=c\n"] {
+            fs::write(&main, content).unwrap();
+            archive(&main).unwrap();
+        }
+        let backups = list(&main);
+        // The repeated version is kept once; same-second copies still sort
+        assert_eq!(backups.len(), 3);
+        assert_eq!(read(&backups[0]), "monitor=c\n");
+        assert_eq!(read(&backups[2]), "monitor=a\n");
+        assert_eq!(diff_summary(&main, &backups[0]), "same as now");
+        assert_eq!(diff_summary(&main, &backups[2]), "+1 -1");
+
+        for i in 0..KEEP + 5 {
+            fs::write(&main, format!("monitor=x{i}\n")).unwrap();
+            archive(&main).unwrap();
+        }
+        assert_eq!(list(&main).len(), KEEP);
+        let _ = fs::remove_dir_all(main.parent().unwrap());
+    }
+
+    #[test]
+    fn restoring_keeps_the_current_file_first() {
+        let main = sandbox("restore");
+        fs::write(&main, "# mine\nmonitor=old\n").unwrap();
+        archive(&main).unwrap();
+        fs::write(&main, "# mine\nmonitor=new\n").unwrap();
+        let backup = list(&main).remove(0);
+        assert_eq!(
+            diff(&fs::read_to_string(&main).unwrap(), &read(&backup)),
+            ["+ monitor=old", "- monitor=new"]
+        );
+        for change in restore_changes(&backup, &main).unwrap() {
+            change.make().unwrap();
+        }
+        assert_eq!(fs::read_to_string(&main).unwrap(), "# mine\nmonitor=old\n");
+        assert_eq!(read(&list(&main)[0]), "# mine\nmonitor=new\n");
+        let _ = fs::remove_dir_all(main.parent().unwrap());
+    }
+}
@@ -0,0 +1,1413 @@
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+use crate::backups;
+use crate::clipboard;
+use crate::clock;
+use crate::config::{parse_fallback, Disconnected, MonitorDatabase, DEFAULT_FALLBACK};
+use crate::compare;
+use crate::daemon::{self, DaemonOptions};
+use crate::diagram;
+use crate::dry_run::Writes;
+use crate::edid::{self, Edid};
+use crate::exec::{DryRun, Executor, System};
+use crate::history::HistoryLog;
+use crate::monitor::{fetch_monitors, reload_hyprland, MonitorConfig};
+use crate::pacing::{Pacing, DEFAULT_FPS, DEFAULT_TICK_MS};
+use crate::plain;
+use crate::presets::{self, Presets};
+use crate::safe;
+use crate::schedule::TimeRange;
+use crate::state::SettingField;
+use crate::systemd;
+
+const USAGE: &str = "\
+Usage: hyprmon [--ascii] [--tick-rate MS] [--fps N] [--no-auto-apply] | --plain
+       hyprmon [--dry-run] COMMAND
+
+Without a command, starts the interactive TUI. With --ascii it draws only
+plain ASCII, for TTYs and fonts without box-drawing glyphs. --tick-rate sets
+how often it wakes up without input (default 100 ms) and --fps caps redraws
+(default 60); lower both to save battery. --plain asks through numbered
+line-by-line menus instead, for screen readers. --no-auto-apply starts with
+auto-apply on hotplug paused (Ctrl+p resumes it).
+
+Commands:
+  status            Show the active workspace and whether Hyprland matches it
+  list              List workspaces with their ids, notes and usage
+  history [-n N]    Show the last N applied changes (default 20)
+  duplicate <WORKSPACE> <NEW-NAME>
+                    Copy a saved workspace under a new name
+  delete-monitor <WORKSPACE> <KEY>
+                    Remove a saved monitor entry from a workspace
+  rules <WORKSPACE> [--count N] [--pattern GLOB]... [--require KEY]...
+        [--priority N] [--clear]
+                    Show or set auto-match rules for a workspace
+  steps <WORKSPACE> [--first KEY]... [--delay MS] [--clear]
+                    Show or set how a workspace is applied to flaky docks:
+                    each monitor on its own, the --first ones first (the
+                    rest enabled before disabled), MS apart, then the reload
+  fallback [RULE | --none | --reset]
+                    Show or set the rule for unknown monitors
+  disconnected [keep | comment | disable]
+                    Show or set what monitors.conf says about saved monitors
+                    that are unplugged: their rule, the rule commented out,
+                    or a disable line
+  per-workspace-files [on | off]
+                    Show or set writing each workspace to its own
+                    monitors-<name>.conf, with monitors.conf sourcing the
+                    active one (takes effect on the next apply)
+  primary-fallback [KEY | --auto]
+                    Show or set the monitor that becomes primary when the
+                    primary is unplugged (--auto: laptop panel, else leftmost)
+  nickname [KEY [NAME... | --clear]]
+                    List monitor nicknames, or show, set or remove the one
+                    of the monitor with KEY (as status prints it)
+  lock [WORKSPACE | --off]
+                    Keep the active (or named) workspace on hotplug
+  schedule <WORKSPACE> [HH:MM-HH:MM]... [--clear]
+                    Show or set the times a workspace is preferred
+  apply [--host USER@MACHINE] <WORKSPACE>
+                    Apply a saved workspace now; --host applies one saved on
+                    another machine, through ssh and the hyprmon there
+  config [--copy]   Print the monitors.conf the active workspace generates;
+                    --copy puts it on the clipboard (OSC 52 and wl-copy)
+  export [--svg] [WORKSPACE]
+                    Draw the live (or saved) arrangement as ASCII or SVG
+  keybinds [--mod MODS]
+                    Print Hyprland binds applying each workspace (F1, F2, ...)
+                    and safe mode (Ctrl+Escape)
+  daemon [--json-events] [--metrics-file PATH] [--metrics-addr ADDR]
+                    Apply workspaces on hotplug and on schedule, headless;
+                    --json-events reports one JSON object per line
+                    (hotplug_detected, profile_applied, match_failed,
+                    apply_error) for log collectors and scripts;
+                    --metrics-file keeps Prometheus metrics in PATH and
+                    --metrics-addr serves them at http://ADDR/metrics
+  install-service   Install a systemd user unit running the daemon
+  presets [--fetch [URL]]
+                    Show known-good settings for the connected monitors;
+                    --fetch downloads the current preset list
+  edid [CONNECTOR] [--raw]
+                    Decode what the monitor (or every connected one) claims
+                    in its EDID and mark where hyprctl's modes disagree;
+                    --raw prints it as hex, for edid-decode
+  panic             Safe mode, at once: every monitor at its preferred mode,
+                    100% scale, unrotated, side by side (also written to
+                    monitors.conf; applying a workspace leaves it)
+  rollback [N [--diff]]
+                    List the monitors.conf versions hyprmon kept, newest
+                    first, with how each differs from now; N restores that
+                    one and reloads Hyprland, --diff shows the lines instead
+  help              Show this message
+
+WORKSPACE is a workspace name or the id `list` prints for it.
+
+--dry-run, given with any command that changes something (duplicate,
+delete-monitor, rules, steps, fallback, disconnected, per-workspace-files,
+primary-fallback, nickname, lock, schedule, apply, daemon, install-service,
+presets --fetch, panic, rollback N), prints the files it would write with
+their new contents and the hyprctl commands it would run, and changes
+nothing. It may also come after the command.";
+
+/// What the process was asked to do.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Tui { ascii: bool, pacing: Pacing, auto_apply: bool },
+    /// Line-oriented menus instead of the full-screen TUI.
+    Plain,
+    Status,
+    List,
+    History { limit: usize },
+    Duplicate { source: String, name: String },
+    DeleteMonitor { workspace: String, key: String },
+    Rules { workspace: String, edit: RulesEdit },
+    Steps { workspace: String, edit: StepsEdit },
+    /// `None` shows the current rule; `Some("")` removes it.
+    Fallback { rule: Option<String> },
+    /// `None` shows the current setting.
+    Disconnected { mode: Option<Disconnected> },
+    /// `None` shows the current setting.
+    PerWorkspaceFiles { on: Option<bool> },
+    /// `None` shows the current setting; `Some("")` goes back to automatic.
+    PrimaryFallback { key: Option<String> },
+    /// `key: None` lists every nickname; `name: Some("")` removes one.
+    Nickname { key: Option<String>, name: Option<String> },
+    /// `Some(None)` locks the current workspace, `None` unlocks.
+    Lock { workspace: Option<Option<String>> },
+    /// `ranges: None` shows the schedule; `Some(vec![])` clears it.
+    Schedule {
+        workspace: String,
+        ranges: Option<Vec<TimeRange>>,
+    },
+    Daemon(DaemonOptions),
+    InstallService,
+    /// `fetch: Some(url)` downloads the presets instead of showing them.
+    Presets { fetch: Option<String> },
+    /// `connector: None` shows every connected monitor.
+    Edid { connector: Option<String>, raw: bool },
+    /// `host` applies on another machine over ssh.
+    Apply { workspace: String, host: Option<String> },
+    Keybinds { mods: String },
+    Panic,
+    /// `pick: None` lists the kept versions; 1 is the newest.
+    Rollback { pick: Option<usize>, diff: bool },
+    Config { copy: bool },
+    /// `workspace: None` draws the live layout.
+    Export { svg: bool, workspace: Option<String> },
+    Help,
+    /// `--dry-run`: describe the changes of the command instead of making them.
+    DryRun(Box<Command>),
+}
+
+impl Command {
+    /// Whether the command can write files or reconfigure Hyprland, so that
+    /// `--dry-run` means something for it.
+    fn changes_something(&self) -> bool {
+        matches!(
+            self,
+            Self::Duplicate { .. }
+                | Self::DeleteMonitor { .. }
+                | Self::Rules { .. }
+                | Self::Steps { .. }
+                | Self::Fallback { .. }
+                | Self::Disconnected { .. }
+                | Self::PerWorkspaceFiles { .. }
+                | Self::PrimaryFallback { .. }
+                | Self::Nickname { name: Some(_), .. }
+                | Self::Lock { .. }
+                | Self::Schedule { .. }
+                | Self::Daemon { .. }
+                | Self::InstallService
+                | Self::Presets { fetch: Some(_) }
+                | Self::Apply { .. }
+                | Self::Panic
+                | Self::Rollback { pick: Some(_), diff: false }
+        )
+    }
+}
+
+/// Changes requested by `hyprmon rules`; an empty edit just prints the rules.
+#[derive(Debug, Default, PartialEq)]
+pub struct RulesEdit {
+    pub clear: bool,
+    pub count: Option<usize>,
+    pub patterns: Vec<String>,
+    pub required: Vec<String>,
+    pub priority: Option<i32>,
+}
+
+/// Changes requested by `hyprmon steps`; an empty edit just prints them.
+#[derive(Debug, Default, PartialEq)]
+pub struct StepsEdit {
+    pub clear: bool,
+    pub first: Vec<String>,
+    pub delay_ms: Option<u64>,
+}
+
+/// Parse process arguments (without the program name).
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+    let mut args: Vec<String> = args.into_iter().collect();
+    // Wherever it is, so a preview never turns into a real change; a
+    // workspace cannot be called `--dry-run`
+    let before = args.len();
+    args.retain(|arg| arg != "--dry-run");
+    if args.len() == before {
+        return parse_command(args);
+    }
+    match parse_command(args)? {
+        cmd if cmd.changes_something() => Ok(Command::DryRun(Box::new(cmd))),
+        _ => bail!("--dry-run goes with a command that changes something, e.g. apply"),
+    }
+}
+
+fn parse_command(args: Vec<String>) -> Result<Command> {
+    let mut args = args.into_iter();
+    let Some(cmd) = args.next() else {
+        return Ok(Command::Tui { ascii: false, pacing: Pacing::default(), auto_apply: true });
+    };
+
+    match cmd.as_str() {
+        "status" => match args.next() {
+            None => Ok(Command::Status),
+            Some(other) => bail!("status: unexpected argument '{other}'"),
+        },
+        "list" => match args.next() {
+            None => Ok(Command::List),
+            Some(other) => bail!("list: unexpected argument '{other}'"),
+        },
+        "history" => {
+            let mut limit = 20;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-n" | "--limit" => limit = parse_number(args.next(), &arg)?,
+                    other => bail!("history: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::History { limit })
+        }
+        "duplicate" => {
+            let (Some(source), Some(name), None) = (args.next(), args.next(), args.next()) else {
+                bail!("usage: hyprmon duplicate <WORKSPACE> <NEW-NAME>");
+            };
+            Ok(Command::Duplicate { source, name })
+        }
+        "delete-monitor" => {
+            let (Some(workspace), Some(key), None) = (args.next(), args.next(), args.next())
+            else {
+                bail!("usage: hyprmon delete-monitor <WORKSPACE> <KEY>");
+            };
+            Ok(Command::DeleteMonitor { workspace, key })
+        }
+        "rules" => {
+            let Some(workspace) = args.next() else {
+                bail!("usage: hyprmon rules <WORKSPACE> [OPTIONS]");
+            };
+            let mut edit = RulesEdit::default();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--clear" => edit.clear = true,
+                    "--count" => edit.count = Some(parse_number(args.next(), &arg)?),
+                    "--pattern" => edit.patterns.push(required_value(args.next(), &arg)?),
+                    "--require" => edit.required.push(required_value(args.next(), &arg)?),
+                    "--priority" => match args.next().and_then(|v| v.parse().ok()) {
+                        Some(p) => edit.priority = Some(p),
+                        None => bail!("--priority expects a number"),
+                    },
+                    other => bail!("rules: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::Rules { workspace, edit })
+        }
+        "steps" => {
+            let Some(workspace) = args.next() else {
+                bail!("usage: hyprmon steps <WORKSPACE> [--first KEY]... [--delay MS] [--clear]");
+            };
+            let mut edit = StepsEdit::default();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--clear" => edit.clear = true,
+                    "--first" => edit.first.push(required_value(args.next(), &arg)?),
+                    "--delay" => {
+                        edit.delay_ms = Some(parse_in_range(args.next(), &arg, 0, 10_000)?);
+                    }
+                    other => bail!("steps: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::Steps { workspace, edit })
+        }
+        "fallback" => {
+            let rule = match (args.next(), args.next()) {
+                (None, _) => None,
+                (Some(flag), None) if flag == "--none" => Some(String::new()),
+                (Some(flag), None) if flag == "--reset" => Some(DEFAULT_FALLBACK.to_string()),
+                (Some(rule), None) => Some(rule),
+                _ => bail!("usage: hyprmon fallback [RULE | --none | --reset]"),
+            };
+            Ok(Command::Fallback { rule })
+        }
+        "disconnected" => match (args.next(), args.next()) {
+            (None, _) => Ok(Command::Disconnected { mode: None }),
+            (Some(mode), None) => {
+                Ok(Command::Disconnected { mode: Some(Disconnected::parse(&mode)?) })
+            }
+            _ => bail!("usage: hyprmon disconnected [keep | comment | disable]"),
+        },
+        "per-workspace-files" => match (args.next().as_deref(), args.next()) {
+            (None, _) => Ok(Command::PerWorkspaceFiles { on: None }),
+            (Some("on"), None) => Ok(Command::PerWorkspaceFiles { on: Some(true) }),
+            (Some("off"), None) => Ok(Command::PerWorkspaceFiles { on: Some(false) }),
+            _ => bail!("usage: hyprmon per-workspace-files [on | off]"),
+        },
+        "primary-fallback" => match (args.next(), args.next()) {
+            (None, _) => Ok(Command::PrimaryFallback { key: None }),
+            (Some(flag), None) if flag == "--auto" => {
+                Ok(Command::PrimaryFallback { key: Some(String::new()) })
+            }
+            (Some(key), None) if !key.starts_with("--") => {
+                Ok(Command::PrimaryFallback { key: Some(key) })
+            }
+            _ => bail!("usage: hyprmon primary-fallback [KEY | --auto]"),
+        },
+        "nickname" => {
+            let key = args.next();
+            let words: Vec<String> = args.collect();
+            let name = match words.as_slice() {
+                _ if key.as_deref().is_some_and(|key| key.starts_with("--")) => {
+                    bail!("usage: hyprmon nickname [KEY [NAME... | --clear]]")
+                }
+                [] => None,
+                [clear] if clear == "--clear" => Some(String::new()),
+                words if key.is_some() && !words[0].starts_with("--") => Some(words.join(" ")),
+                _ => bail!("usage: hyprmon nickname [KEY [NAME... | --clear]]"),
+            };
+            Ok(Command::Nickname { key, name })
+        }
+        "lock" => {
+            let workspace = match (args.next(), args.next()) {
+                (None, _) => Some(None),
+                (Some(off), None) if off == "--off" => None,
+                (Some(name), None) => Some(Some(name)),
+                _ => bail!("usage: hyprmon lock [WORKSPACE | --off]"),
+            };
+            Ok(Command::Lock { workspace })
+        }
+        "schedule" => {
+            let Some(workspace) = args.next() else {
+                bail!("usage: hyprmon schedule <WORKSPACE> [HH:MM-HH:MM]... [--clear]");
+            };
+            let mut ranges = None;
+            for arg in args {
+                let list = ranges.get_or_insert_with(Vec::new);
+                match arg.as_str() {
+                    "--clear" => list.clear(),
+                    range => list.push(TimeRange::parse(range)?),
+                }
+            }
+            Ok(Command::Schedule { workspace, ranges })
+        }
+        "apply" => {
+            let (mut workspace, mut host) = (None, None);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--host" => host = Some(required_value(args.next(), &arg)?),
+                    _ if workspace.is_none() => workspace = Some(arg),
+                    other => bail!("apply: unexpected argument '{other}'"),
+                }
+            }
+            let Some(workspace) = workspace else {
+                bail!("usage: hyprmon apply [--host USER@MACHINE] <WORKSPACE>");
+            };
+            if host.as_ref().is_some_and(|host| host.is_empty() || host.starts_with('-')) {
+                bail!("--host expects USER@MACHINE");
+            }
+            Ok(Command::Apply { workspace, host })
+        }
+        "keybinds" => {
+            let mut mods = "SUPER".to_string();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--mod" => mods = required_value(args.next(), &arg)?,
+                    other => bail!("keybinds: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::Keybinds { mods })
+        }
+        "config" => match args.next().as_deref() {
+            None => Ok(Command::Config { copy: false }),
+            Some("--copy") => match args.next() {
+                None => Ok(Command::Config { copy: true }),
+                Some(other) => bail!("config: unexpected argument '{other}'"),
+            },
+            Some(other) => bail!("config: unexpected argument '{other}'"),
+        },
+        "export" => {
+            let (mut svg, mut workspace) = (false, None);
+            for arg in args {
+                match arg.as_str() {
+                    "--svg" => svg = true,
+                    "--ascii" => svg = false,
+                    _ if workspace.is_none() && !arg.starts_with("--") => workspace = Some(arg),
+                    other => bail!("export: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::Export { svg, workspace })
+        }
+        "daemon" => {
+            let mut options = DaemonOptions::default();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--json-events" => options.json_events = true,
+                    "--metrics-file" => {
+                        let path = required_value(args.next(), &arg)?;
+                        options.metrics_file = Some(PathBuf::from(path));
+                    }
+                    "--metrics-addr" => {
+                        options.metrics_addr = Some(required_value(args.next(), &arg)?);
+                    }
+                    other => bail!("daemon: unexpected argument '{other}'"),
+                }
+            }
+            Ok(Command::Daemon(options))
+        }
+        "panic" => match args.next() {
+            None => Ok(Command::Panic),
+            Some(other) => bail!("panic: unexpected argument '{other}'"),
+        },
+        "rollback" => {
+            let (mut pick, mut diff) = (None, false);
+            for arg in args.by_ref() {
+                match arg.as_str() {
+                    "--diff" => diff = true,
+                    _ if pick.is_none() => pick = Some(parse_number(Some(arg), "rollback")?),
+                    _ => bail!("usage: hyprmon rollback [N [--diff]]"),
+                }
+            }
+            if pick == Some(0) || (diff && pick.is_none()) {
+                bail!("usage: hyprmon rollback [N [--diff]]");
+            }
+            Ok(Command::Rollback { pick, diff })
+        }
+        "install-service" => match args.next() {
+            None => Ok(Command::InstallService),
+            Some(other) => bail!("install-service: unexpected argument '{other}'"),
+        },
+        "presets" => match (args.next().as_deref(), args.next(), args.next()) {
+            (None, ..) => Ok(Command::Presets { fetch: None }),
+            (Some("--fetch"), url, None) => {
+                let url = url.unwrap_or_else(|| presets::PRESETS_URL.to_string());
+                Ok(Command::Presets { fetch: Some(url) })
+            }
+            _ => bail!("usage: hyprmon presets [--fetch [URL]]"),
+        },
+        "edid" => {
+            let (mut connector, mut raw) = (None, false);
+            for arg in args.by_ref() {
+                match arg.as_str() {
+                    "--raw" => raw = true,
+                    flag if flag.starts_with('-') => bail!("edid: unknown option '{flag}'"),
+                    _ if connector.is_some() => bail!("usage: hyprmon edid [CONNECTOR] [--raw]"),
+                    _ => connector = Some(arg),
+                }
+            }
+            Ok(Command::Edid { connector, raw })
+        }
+        "--ascii" | "--tick-rate" | "--fps" | "--no-auto-apply" => {
+            let (mut ascii, mut tick_ms, mut fps) = (false, DEFAULT_TICK_MS, DEFAULT_FPS);
+            let mut auto_apply = true;
+            let mut arg = Some(cmd);
+            while let Some(flag) = arg {
+                match flag.as_str() {
+                    "--ascii" => ascii = true,
+                    "--no-auto-apply" => auto_apply = false,
+                    "--tick-rate" => tick_ms = parse_in_range(args.next(), &flag, 10, 1000)?,
+                    "--fps" => fps = parse_in_range(args.next(), &flag, 1, 240)? as u32,
+                    other => bail!("unexpected argument '{other}' after TUI options"),
+                }
+                arg = args.next();
+            }
+            Ok(Command::Tui { ascii, pacing: Pacing::new(tick_ms, fps), auto_apply })
+        }
+        "--plain" => match args.next() {
+            None => Ok(Command::Plain),
+            Some(other) => bail!("--plain: unexpected argument '{other}'"),
+        },
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        other => bail!("unknown command '{other}'\n\n{USAGE}"),
+    }
+}
+
+fn parse_number(value: Option<String>, flag: &str) -> Result<usize> {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(n) => Ok(n),
+        None => bail!("{flag} expects a number"),
+    }
+}
+
+fn parse_in_range(value: Option<String>, flag: &str, min: u64, max: u64) -> Result<u64> {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(n) if (min..=max).contains(&n) => Ok(n),
+        _ => bail!("{flag} expects a number from {min} to {max}"),
+    }
+}
+
+/// Report for `hyprmon status`: active workspace, then sync state with one
+/// line per deviating field.
+fn status_lines(db: &MonitorDatabase, monitors: &[MonitorConfig]) -> Vec<String> {
+    let name = db.current_workspace().map(|ws| ws.name.as_str()).unwrap_or("");
+    let lock = if db.lock_active { " (locked)" } else { "" };
+    let mut out = vec![
+        format!("Workspace: {name}{lock}"),
+        format!("Monitors:  {} connected", monitors.len()),
+    ];
+    let diffs = compare::diff_against_saved(monitors, db);
+    out.push(format!(
+        "State:     {}",
+        if compare::is_out_of_sync(&diffs) { "modified" } else { "in sync" }
+    ));
+    for diff in diffs {
+        if diff.field == Some(SettingField::Primary) {
+            continue; // hyprctl reports focus, not hyprmon's primary flag
+        }
+        let key = match db.nicknames.get(&diff.key) {
+            Some(nickname) => format!("{nickname} ({})", diff.key),
+            None => diff.key.clone(),
+        };
+        out.push(match diff.field {
+            Some(field) => format!(
+                "  {key}: {} {} (saved {})",
+                field.label().trim_end_matches(':').to_lowercase(),
+                diff.live,
+                diff.saved
+            ),
+            None => format!("  {key}: not saved in this workspace"),
+        });
+    }
+    out
+}
+
+/// `bind =` lines for `hyprmon keybinds`: the Nth workspace in tab order gets
+/// F<N>; names are escaped for the shell Hyprland runs `exec` through, and
+/// `#` is doubled so Hyprland does not read it as the start of a comment.
+/// Workspaces past F12 are listed commented out for the user to assign.
+/// Mods+Ctrl+Escape ends the list with safe mode, reachable with the screens dark.
+fn keybind_lines(db: &MonitorDatabase, mods: &str) -> Vec<String> {
+    let mut out = vec!["# hyprmon workspaces (paste into hyprland.conf)".to_string()];
+    for (i, ws) in db.workspaces.iter().enumerate() {
+        let mut quoted = String::new();
+        for c in ws.name.chars() {
+            if matches!(c, '\\' | '"' | '$' | '`') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+            if c == '#' {
+                quoted.push('#');
+            }
+        }
+        let exec = format!("exec, hyprmon apply \"{quoted}\"");
+        out.push(match i {
+            0..12 => format!("bind = {mods}, F{}, {exec}", i + 1),
+            _ => format!("# bind = {mods}, ?, {exec}"),
+        });
+    }
+    out.push(format!("bind = {mods} CTRL, Escape, exec, hyprmon panic"));
+    out
+}
+
+/// `ssh` arguments running `hyprmon apply` on `host`. ssh hands the command
+/// to the remote shell as one line, so the name is quoted for it; a dry run
+/// is passed on for the remote hyprmon to honour.
+fn ssh_apply_args(host: &str, workspace: &str, dry_run: bool) -> Vec<String> {
+    let mut args: Vec<String> =
+        ["--", host, "hyprmon", "apply"].into_iter().map(String::from).collect();
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_-.,:/@%+=".contains(c);
+    args.push(if !workspace.is_empty() && workspace.chars().all(safe) {
+        workspace.to_string()
+    } else {
+        format!("'{}'", workspace.replace('\'', r"'\''"))
+    });
+    if dry_run {
+        args.push("--dry-run".into());
+    }
+    args
+}
+
+fn required_value(value: Option<String>, flag: &str) -> Result<String> {
+    match value {
+        Some(v) => Ok(v),
+        None => bail!("{flag} expects a value"),
+    }
+}
+
+/// Execute a non-TUI command, printing to stdout.
+pub fn run(cmd: Command) -> Result<()> {
+    match cmd {
+        Command::DryRun(cmd) => {
+            execute(*cmd, &DryRun(System), &Writes::new(true))?;
+            println!("Dry run: nothing was changed.");
+            Ok(())
+        }
+        cmd => execute(cmd, &System, &Writes::new(false)),
+    }
+}
+
+fn execute(cmd: Command, exec: &dyn Executor, writes: &Writes) -> Result<()> {
+    match cmd {
+        Command::Tui { .. } | Command::DryRun(_) => unreachable!("handled by main and run"),
+        Command::Help => println!("{USAGE}"),
+        Command::Plain => plain::run()?,
+        Command::Status => {
+            let db = MonitorDatabase::load()?;
+            let mut monitors = fetch_monitors(exec)?;
+            db.assign_keys(&mut monitors);
+            for line in status_lines(&db, &monitors) {
+                println!("{line}");
+            }
+        }
+        Command::List => {
+            let db = MonitorDatabase::load()?;
+            for (i, ws) in db.workspaces.iter().enumerate() {
+                let marker = if i == db.active_workspace() { '*' } else { ' ' };
+                let bundled = if db.is_bundled(i) { "  (bundled)" } else { "" };
+                println!("{marker} {}  {}{bundled}", ws.name, ws.id);
+                println!("    {}", ws.summary());
+            }
+            for error in &db.bundle_errors {
+                eprintln!("hyprmon: skipped {error}");
+            }
+        }
+        Command::History { limit } => {
+            let log = HistoryLog::default();
+            let entries = log.load();
+            if entries.is_empty() {
+                println!("No changes recorded yet ({}).", log.path().display());
+            }
+            let skip = entries.len().saturating_sub(limit);
+            for entry in entries.iter().skip(skip) {
+                for line in entry.lines() {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::Duplicate { source, name } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&source)
+                .with_context(|| format!("no workspace named '{source}'"))?;
+            let copy = db.duplicate_workspace(idx, &name)?;
+            writes.save(&db)?;
+            println!("Duplicated '{source}' as '{}'.", db.workspaces[copy].name);
+        }
+        Command::DeleteMonitor { workspace, key } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&workspace)
+                .with_context(|| format!("no workspace named '{workspace}'"))?;
+            if !db.remove_monitor(idx, &key) {
+                let mut keys: Vec<&String> = db.workspaces[idx].monitors.keys().collect();
+                keys.sort();
+                bail!(
+                    "'{workspace}' has no monitor '{key}' (saved: {})",
+                    keys.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", ")
+                );
+            }
+            writes.save(&db)?;
+            println!("Removed {key} from '{workspace}'.");
+        }
+        Command::Rules { workspace, edit } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&workspace)
+                .with_context(|| format!("no workspace named '{workspace}'"))?;
+            if edit != RulesEdit::default() {
+                let rules = &mut db.workspaces[idx].match_rules;
+                if edit.clear {
+                    *rules = Default::default();
+                }
+                if edit.count.is_some() {
+                    rules.monitor_count = edit.count;
+                }
+                rules.patterns.extend(edit.patterns);
+                rules.required.extend(edit.required);
+                if let Some(p) = edit.priority {
+                    rules.priority = p;
+                }
+                writes.save(&db)?;
+            }
+            println!(
+                "{workspace}: {}",
+                db.workspaces[idx].match_rules.describe()
+            );
+        }
+        Command::Steps { workspace, edit } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&workspace)
+                .with_context(|| format!("no workspace named '{workspace}'"))?;
+            if edit != StepsEdit::default() {
+                let steps = &mut db.workspaces[idx].apply_steps;
+                if edit.clear {
+                    *steps = Default::default();
+                }
+                for key in edit.first {
+                    if !steps.order.contains(&key) {
+                        steps.order.push(key);
+                    }
+                }
+                if let Some(ms) = edit.delay_ms {
+                    steps.delay_ms = ms;
+                }
+                writes.save(&db)?;
+            }
+            println!("{workspace}: {}", db.workspaces[idx].apply_steps.describe());
+        }
+        Command::Fallback { rule } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(rule) = rule {
+                db.fallback = parse_fallback(&rule)?;
+                writes.save(&db)?;
+            }
+            match &db.fallback {
+                Some(rule) => println!("monitor=,{rule}"),
+                None => println!("No fallback rule."),
+            }
+        }
+        Command::Disconnected { mode } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(mode) = mode {
+                db.disconnected = mode;
+                writes.save(&db)?;
+            }
+            println!("{}", db.disconnected.as_str());
+        }
+        Command::PerWorkspaceFiles { on } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(on) = on {
+                db.per_workspace_files = on;
+                writes.save(&db)?;
+            }
+            if db.per_workspace_files {
+                let main = MonitorDatabase::hypr_conf_path();
+                println!("on: monitors.conf sources {}", db.monitor_rules_path(&main).display());
+            } else {
+                println!("off: monitors.conf holds the rules itself");
+            }
+        }
+        Command::PrimaryFallback { key } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(key) = key {
+                db.primary_fallback = Some(key.trim().to_string()).filter(|k| !k.is_empty());
+                writes.save(&db)?;
+            }
+            match &db.primary_fallback {
+                Some(key) => println!("{key}"),
+                None => println!("Automatic: the laptop panel, else the leftmost monitor."),
+            }
+        }
+        Command::Nickname { key: None, .. } => {
+            let db = MonitorDatabase::load()?;
+            if db.nicknames.is_empty() {
+                println!("No nicknames set.");
+            }
+            for (key, nickname) in &db.nicknames {
+                println!("{key}  {nickname}");
+            }
+        }
+        Command::Nickname { key: Some(key), name } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(name) = name {
+                db.set_nickname(&key, &name);
+                writes.save(&db)?;
+            }
+            match db.nicknames.get(&key) {
+                Some(nickname) => println!("{nickname}"),
+                None => println!("{key} has no nickname."),
+            }
+        }
+        Command::Lock { workspace } => {
+            let mut db = MonitorDatabase::load()?;
+            if let Some(Some(name)) = &workspace {
+                let idx = db
+                    .find_workspace(name)
+                    .with_context(|| format!("no workspace named '{name}'"))?;
+                db.set_active(idx);
+            }
+            db.lock_active = workspace.is_some();
+            writes.save(&db)?;
+            match db.current_workspace() {
+                Some(ws) if db.lock_active => println!("Locked on '{}'.", ws.name),
+                _ => println!("Unlocked."),
+            }
+        }
+        Command::Schedule { workspace, ranges } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&workspace)
+                .with_context(|| format!("no workspace named '{workspace}'"))?;
+            if let Some(ranges) = ranges {
+                db.workspaces[idx].schedule = ranges;
+                writes.save(&db)?;
+            }
+            println!("{workspace}: {}", db.workspaces[idx].schedule_label());
+        }
+        Command::Daemon(options) => daemon::run(exec, writes, &options)?,
+        Command::InstallService => {
+            let path = systemd::unit_path();
+            let exe = std::env::current_exe().context("cannot tell where hyprmon is")?;
+            writes.file(&path, &systemd::unit_file(&exe))?;
+            // Without systemd there is nothing to tell; the file is still useful
+            let _ = exec.output("systemctl", &["--user", "daemon-reload"]);
+            println!("Installed {}. Start it with:", path.display());
+            println!("  systemctl --user enable --now hyprmon.service");
+        }
+        Command::Presets { fetch: None } => {
+            let presets = Presets::load()?;
+            for monitor in fetch_monitors(exec)? {
+                let model = format!("{} ({} {})", monitor.name, monitor.make, monitor.model);
+                let Some(preset) = presets.find(&monitor) else {
+                    println!("{model}: no preset known");
+                    continue;
+                };
+                let followed = preset.is_followed_by(&monitor);
+                let state = if followed { "as set now" } else { "suggested" };
+                println!("{model}: {} ({state})", preset.summary());
+                for note in &preset.notes {
+                    println!("    {note}");
+                }
+            }
+        }
+        Command::Presets { fetch: Some(url) } => {
+            let count = presets::fetch(exec, writes, &url)?;
+            if !writes.dry_run() {
+                println!("Fetched {count} presets into {}.", presets::path().display());
+            }
+        }
+        Command::Edid { connector, raw } => {
+            // hyprctl's modes to check against; without Hyprland, none
+            let monitors = fetch_monitors(exec);
+            let connectors = match (connector, &monitors) {
+                (Some(connector), _) => vec![connector],
+                (None, Ok(monitors)) => monitors.iter().map(|m| m.name.clone()).collect(),
+                (None, Err(e)) => bail!("cannot list monitors: {e:#}"),
+            };
+            for (i, connector) in connectors.iter().enumerate() {
+                let bytes = edid::read(connector)?;
+                if i > 0 {
+                    println!();
+                }
+                if raw {
+                    println!("{connector}:");
+                    edid::hex_dump(&bytes).iter().for_each(|row| println!("{row}"));
+                    continue;
+                }
+                let modes = monitors.as_ref().ok().and_then(|all| {
+                    all.iter().find(|m| &m.name == connector).map(|m| m.available_modes.clone())
+                });
+                let decoded = Edid::parse(&bytes).with_context(|| connector.clone())?;
+                println!("{connector}:");
+                for line in decoded.report(modes.as_deref().unwrap_or_default()) {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::Apply { workspace, host: Some(host) } => {
+            let args = ssh_apply_args(&host, &workspace, writes.dry_run());
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            // Past DryRun: the remote hyprmon is the one to only preview it
+            let said = exec
+                .inner()
+                .output("ssh", &args)
+                .with_context(|| format!("cannot apply on {host}"))?;
+            print!("{}", String::from_utf8_lossy(&said));
+        }
+        Command::Apply { workspace, host: None } => {
+            let mut db = MonitorDatabase::load()?;
+            let idx = db
+                .find_workspace(&workspace)
+                .with_context(|| format!("no workspace named '{workspace}'"))?;
+            db.set_active(idx);
+            let name = daemon::apply_active(exec, writes, &mut db, fetch_monitors(exec)?)?;
+            if writes.dry_run() {
+                println!("Would apply '{name}'.");
+            } else {
+                println!("Applied '{name}'.");
+            }
+        }
+        Command::Panic => {
+            // Without names the catch-all rule still lights every monitor up
+            let names = safe::monitor_names(exec).unwrap_or_default();
+            let set = safe::set(exec, &names);
+            writes.changes(safe::conf_changes(&MonitorDatabase::hypr_conf_path(), &names))?;
+            set.context("Hyprland refused part of safe mode")?;
+            if !writes.dry_run() {
+                println!("Safe mode on. Apply a workspace to leave it.");
+            }
+        }
+        Command::Rollback { pick, diff } => {
+            let main = MonitorDatabase::hypr_conf_path();
+            let kept = backups::list(&main);
+            let Some(pick) = pick else {
+                if kept.is_empty() {
+                    println!("No versions kept yet ({}).", backups::dir_for(&main).display());
+                }
+                for (i, backup) in kept.iter().enumerate() {
+                    let when = clock::format_local(backup.taken);
+                    println!("{:>3}  {when}  {}", i + 1, backups::diff_summary(&main, backup));
+                }
+                return Ok(());
+            };
+            let backup = kept.get(pick - 1).with_context(|| {
+                format!("only {} versions kept; `hyprmon rollback` lists them", kept.len())
+            })?;
+            if diff {
+                let current = std::fs::read_to_string(&main).unwrap_or_default();
+                let then = std::fs::read_to_string(&backup.path)?;
+                backups::diff(&current, &then).iter().for_each(|line| println!("{line}"));
+                return Ok(());
+            }
+            writes.changes(backups::restore_changes(backup, &main)?)?;
+            let _ = reload_hyprland(exec);
+            if !writes.dry_run() {
+                let when = clock::format_local(backup.taken);
+                println!("Restored monitors.conf from {when}.");
+            }
+        }
+        Command::Keybinds { mods } => {
+            for line in keybind_lines(&MonitorDatabase::load()?, &mods) {
+                println!("{line}");
+            }
+        }
+        Command::Export { svg, workspace } => {
+            let monitors = match workspace {
+                Some(name) => {
+                    let db = MonitorDatabase::load()?;
+                    let idx = db
+                        .find_workspace(&name)
+                        .with_context(|| format!("no workspace named '{name}'"))?;
+                    db.get_workspace_monitors(idx)
+                }
+                None => fetch_monitors(exec)?,
+            };
+            if svg {
+                print!("{}", diagram::svg(&monitors));
+            } else {
+                print!("{}", diagram::ascii(&monitors, diagram::ASCII_WIDTH));
+            }
+        }
+        Command::Config { copy } => {
+            let db = MonitorDatabase::load()?;
+            // Outside Hyprland every saved monitor counts as connected
+            let connected = fetch_monitors(exec)
+                .map(|mut monitors| {
+                    db.assign_keys(&mut monitors);
+                    monitors.iter().map(MonitorDatabase::get_monitor_key).collect()
+                })
+                .unwrap_or_default();
+            let text = db.hypr_config_text(&connected);
+            if copy {
+                let used = clipboard::copy(exec, &text)?;
+                println!("Copied {} lines via {}.", text.lines().count(), used.join(" and "));
+            } else {
+                print!("{text}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::Replay;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_args_starts_tui() {
+        let pacing = Pacing::default();
+        let tui = |ascii, auto_apply| Command::Tui { ascii, pacing, auto_apply };
+        assert_eq!(parse(args(&[])).unwrap(), tui(false, true));
+        assert_eq!(parse(args(&["--ascii"])).unwrap(), tui(true, true));
+        assert_eq!(parse(args(&["--no-auto-apply", "--ascii"])).unwrap(), tui(true, false));
+        assert!(parse(args(&["--ascii", "status"])).is_err());
+        assert_eq!(parse(args(&["--plain"])).unwrap(), Command::Plain);
+    }
+
+    #[test]
+    fn tui_takes_tick_rate_and_frame_cap() {
+        let pacing = Pacing::new(250, 10);
+        let cmd = parse(args(&["--tick-rate", "250", "--ascii", "--fps", "10"])).unwrap();
+        assert_eq!(cmd, Command::Tui { ascii: true, pacing, auto_apply: true });
+        assert_eq!(pacing.frame_time, std::time::Duration::from_millis(100));
+        let cmd = parse(args(&["--fps", "120"])).unwrap();
+        let pacing = Pacing::new(100, 120);
+        assert_eq!(cmd, Command::Tui { ascii: false, pacing, auto_apply: true });
+        assert!(parse(args(&["--tick-rate"])).is_err());
+        assert!(parse(args(&["--tick-rate", "5"])).is_err());
+        assert!(parse(args(&["--fps", "0"])).is_err());
+        assert!(parse(args(&["--fps", "30", "list"])).is_err());
+    }
+
+    #[test]
+    fn history_parses_limit_and_rejects_garbage() {
+        assert_eq!(
+            parse(args(&["history"])).unwrap(),
+            Command::History { limit: 20 }
+        );
+        assert_eq!(
+            parse(args(&["history", "-n", "5"])).unwrap(),
+            Command::History { limit: 5 }
+        );
+        assert!(parse(args(&["history", "-n", "x"])).is_err());
+        assert!(parse(args(&["history", "--bogus"])).is_err());
+        assert!(parse(args(&["frobnicate"])).is_err());
+        assert_eq!(parse(args(&["list"])).unwrap(), Command::List);
+        assert!(parse(args(&["list", "x"])).is_err());
+        assert_eq!(parse(args(&["--help"])).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn dry_run_wraps_commands_that_change_something() {
+        let apply = Command::Apply { workspace: "Desk".into(), host: None };
+        let dry = Command::DryRun(Box::new(apply));
+        assert_eq!(parse(args(&["apply", "Desk", "--dry-run"])).unwrap(), dry);
+        assert_eq!(parse(args(&["--dry-run", "apply", "Desk"])).unwrap(), dry);
+        let lock = parse(args(&["--dry-run", "lock", "--off"])).unwrap();
+        assert_eq!(lock, Command::DryRun(Box::new(Command::Lock { workspace: None })));
+        assert!(parse(args(&["--dry-run", "daemon"])).is_ok());
+        assert!(parse(args(&["--dry-run", "install-service"])).is_ok());
+        assert!(parse(args(&["--dry-run", "presets", "--fetch"])).is_ok());
+        assert!(parse(args(&["--dry-run", "presets"])).is_err());
+        assert!(parse(args(&["--dry-run", "panic"])).is_ok());
+        assert!(parse(args(&["panic", "now"])).is_err());
+        assert!(parse(args(&["--dry-run", "rollback", "2"])).is_ok());
+        assert!(parse(args(&["--dry-run", "rollback"])).is_err());
+        assert!(parse(args(&["--dry-run", "rollback", "2", "--diff"])).is_err());
+        let daemon = Command::Daemon(DaemonOptions { json_events: true, ..Default::default() });
+        assert_eq!(parse(args(&["daemon", "--json-events"])).unwrap(), daemon);
+        assert!(parse(args(&["daemon", "--json"])).is_err());
+        assert!(parse(args(&["status", "--dry-run"])).is_err());
+        assert!(parse(args(&["apply", "--dry-run"])).is_err());
+        assert!(parse(args(&["--dry-run"])).is_err());
+        assert!(parse(args(&["--dry-run", "--ascii"])).is_err());
+    }
+
+    #[test]
+    fn duplicate_requires_exactly_two_names() {
+        assert_eq!(
+            parse(args(&["duplicate", "Office", "Office 120Hz"])).unwrap(),
+            Command::Duplicate {
+                source: "Office".into(),
+                name: "Office 120Hz".into()
+            }
+        );
+        assert!(parse(args(&["duplicate", "Office"])).is_err());
+        assert!(parse(args(&["duplicate", "a", "b", "c"])).is_err());
+    }
+
+    #[test]
+    fn status_reports_sync_state_and_deviations() {
+        let edp = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let mut db = MonitorDatabase::default();
+        db.update_monitor(&edp);
+        assert_eq!(parse(args(&["status"])).unwrap(), Command::Status);
+
+        let lines = status_lines(&db, std::slice::from_ref(&edp));
+        assert_eq!(lines[0], "Workspace: Default");
+        assert_eq!(lines[2], "State:     in sync");
+
+        let mut drifted = edp.clone();
+        drifted.scale = 1.5;
+        let hdmi = MonitorConfig::for_test("HDMI-A-1", "MSI", "MP", "2560x1440");
+        db.lock_active = true;
+        let lines = status_lines(&db, &[drifted, hdmi]);
+        assert_eq!(lines[0], "Workspace: Default (locked)");
+        assert_eq!(lines[2], "State:     modified");
+        assert_eq!(lines[3], "  eDP-1: scale 1.50 (saved 1.00)");
+        assert_eq!(lines[4], "  desc:MSI MP: not saved in this workspace");
+
+        db.set_nickname("desc:MSI MP", "Left MSI");
+        let hdmi = MonitorConfig::for_test("HDMI-A-1", "MSI", "MP", "2560x1440");
+        let lines = status_lines(&db, &[edp, hdmi]);
+        assert_eq!(lines[3], "  Left MSI (desc:MSI MP): not saved in this workspace");
+    }
+
+    #[test]
+    fn delete_monitor_takes_workspace_and_key() {
+        assert_eq!(
+            parse(args(&["delete-monitor", "Office", "desc:Dell U2720Q"])).unwrap(),
+            Command::DeleteMonitor {
+                workspace: "Office".into(),
+                key: "desc:Dell U2720Q".into()
+            }
+        );
+        assert!(parse(args(&["delete-monitor", "Office"])).is_err());
+    }
+
+    #[test]
+    fn rules_collects_repeatable_options() {
+        let cmd = parse(args(&[
+            "rules", "Projector", "--pattern", "HDMI-*", "--require", "eDP-1", "--priority",
+            "-2", "--count", "2",
+        ]))
+        .unwrap();
+        assert_eq!(
+            cmd,
+            Command::Rules {
+                workspace: "Projector".into(),
+                edit: RulesEdit {
+                    clear: false,
+                    count: Some(2),
+                    patterns: vec!["HDMI-*".into()],
+                    required: vec!["eDP-1".into()],
+                    priority: Some(-2),
+                },
+            }
+        );
+        assert!(parse(args(&["rules"])).is_err());
+        assert!(parse(args(&["rules", "X", "--pattern"])).is_err());
+    }
+
+    #[test]
+    fn steps_takes_an_order_and_a_delay() {
+        let cmd = parse(args(&[
+            "steps", "Docked", "--first", "desc:Dell U27", "--delay", "500", "--first", "eDP-1",
+        ]))
+        .unwrap();
+        let first = vec!["desc:Dell U27".into(), "eDP-1".into()];
+        let edit = StepsEdit { clear: false, first, delay_ms: Some(500) };
+        assert_eq!(cmd, Command::Steps { workspace: "Docked".into(), edit });
+        assert!(parse(args(&["steps"])).is_err());
+        assert!(parse(args(&["steps", "Docked", "--delay", "60000"])).is_err());
+        assert!(parse(args(&["--dry-run", "steps", "Docked", "--clear"])).is_ok());
+    }
+
+    #[test]
+    fn fallback_accepts_rule_none_or_reset() {
+        assert_eq!(
+            parse(args(&["fallback"])).unwrap(),
+            Command::Fallback { rule: None }
+        );
+        assert_eq!(
+            parse(args(&["fallback", "highrr,auto,1.5"])).unwrap(),
+            Command::Fallback {
+                rule: Some("highrr,auto,1.5".into())
+            }
+        );
+        assert_eq!(
+            parse(args(&["fallback", "--none"])).unwrap(),
+            Command::Fallback {
+                rule: Some(String::new())
+            }
+        );
+        assert_eq!(
+            parse(args(&["fallback", "--reset"])).unwrap(),
+            Command::Fallback {
+                rule: Some(DEFAULT_FALLBACK.into())
+            }
+        );
+    }
+
+    #[test]
+    fn disconnected_shows_or_sets_the_mode() {
+        let mode = |list: &[&str]| match parse(args(list)).unwrap() {
+            Command::Disconnected { mode } => mode,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(mode(&["disconnected"]), None);
+        assert_eq!(mode(&["disconnected", "comment"]), Some(Disconnected::Comment));
+        assert!(parse(args(&["disconnected", "forget"])).is_err());
+        assert!(parse(args(&["disconnected", "keep", "now"])).is_err());
+    }
+
+    #[test]
+    fn per_workspace_files_takes_on_or_off() {
+        let on = |list: &[&str]| match parse(args(list)).unwrap() {
+            Command::PerWorkspaceFiles { on } => on,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(on(&["per-workspace-files"]), None);
+        assert_eq!(on(&["per-workspace-files", "on"]), Some(true));
+        assert_eq!(on(&["per-workspace-files", "off"]), Some(false));
+        assert!(parse(args(&["per-workspace-files", "yes"])).is_err());
+    }
+
+    #[test]
+    fn primary_fallback_takes_a_key_or_auto() {
+        let key = |list: &[&str]| match parse(args(list)).unwrap() {
+            Command::PrimaryFallback { key } => key,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(key(&["primary-fallback"]), None);
+        assert_eq!(key(&["primary-fallback", "eDP-1"]).as_deref(), Some("eDP-1"));
+        assert_eq!(key(&["primary-fallback", "--auto"]).as_deref(), Some(""));
+        assert!(parse(args(&["primary-fallback", "--none"])).is_err());
+    }
+
+    #[test]
+    fn nickname_lists_shows_sets_or_clears() {
+        let nickname = |key: Option<&str>, name: Option<&str>| Command::Nickname {
+            key: key.map(String::from),
+            name: name.map(String::from),
+        };
+        assert_eq!(parse(args(&["nickname"])).unwrap(), nickname(None, None));
+        assert_eq!(parse(args(&["nickname", "DP-2"])).unwrap(), nickname(Some("DP-2"), None));
+        assert_eq!(
+            parse(args(&["nickname", "desc:Dell U2720Q", "Left", "Dell"])).unwrap(),
+            nickname(Some("desc:Dell U2720Q"), Some("Left Dell"))
+        );
+        assert_eq!(
+            parse(args(&["nickname", "DP-2", "--clear"])).unwrap(),
+            nickname(Some("DP-2"), Some(""))
+        );
+        assert!(parse(args(&["nickname", "DP-2", "--none"])).is_err());
+        assert!(parse(args(&["nickname", "--clear"])).is_err());
+        assert!(!parse(args(&["nickname", "DP-2"])).unwrap().changes_something());
+    }
+
+    #[test]
+    fn lock_takes_optional_workspace_or_off() {
+        assert_eq!(
+            parse(args(&["lock"])).unwrap(),
+            Command::Lock { workspace: Some(None) }
+        );
+        assert_eq!(
+            parse(args(&["lock", "Present"])).unwrap(),
+            Command::Lock {
+                workspace: Some(Some("Present".into()))
+            }
+        );
+        assert_eq!(
+            parse(args(&["lock", "--off"])).unwrap(),
+            Command::Lock { workspace: None }
+        );
+        assert!(parse(args(&["lock", "a", "b"])).is_err());
+    }
+
+    #[test]
+    fn schedule_parses_ranges_and_clear() {
+        assert_eq!(
+            parse(args(&["schedule", "TV-off"])).unwrap(),
+            Command::Schedule {
+                workspace: "TV-off".into(),
+                ranges: None
+            }
+        );
+        assert_eq!(
+            parse(args(&["schedule", "TV-off", "23:00-07:00", "12:00-13:30"])).unwrap(),
+            Command::Schedule {
+                workspace: "TV-off".into(),
+                ranges: Some(vec![
+                    TimeRange { start: 23 * 60, end: 7 * 60 },
+                    TimeRange { start: 12 * 60, end: 13 * 60 + 30 },
+                ])
+            }
+        );
+        assert_eq!(
+            parse(args(&["schedule", "TV-off", "--clear"])).unwrap(),
+            Command::Schedule {
+                workspace: "TV-off".into(),
+                ranges: Some(vec![])
+            }
+        );
+        assert!(parse(args(&["schedule"])).is_err());
+        assert!(parse(args(&["schedule", "TV-off", "25:00-07:00"])).is_err());
+        let daemon = Command::Daemon(DaemonOptions::default());
+        assert_eq!(parse(args(&["daemon"])).unwrap(), daemon);
+        assert!(parse(args(&["daemon", "x"])).is_err());
+        let metrics = parse(args(&[
+            "daemon",
+            "--metrics-addr",
+            "127.0.0.1:9101",
+            "--metrics-file",
+            "/tmp/hyprmon.prom",
+        ]));
+        let options = DaemonOptions {
+            json_events: false,
+            metrics_file: Some(PathBuf::from("/tmp/hyprmon.prom")),
+            metrics_addr: Some("127.0.0.1:9101".into()),
+        };
+        assert_eq!(metrics.unwrap(), Command::Daemon(options));
+        assert!(parse(args(&["daemon", "--metrics-addr"])).is_err());
+    }
+
+    #[test]
+    fn presets_parse_with_an_optional_fetch_url() {
+        assert_eq!(parse(args(&["presets"])).unwrap(), Command::Presets { fetch: None });
+        let fetch = parse(args(&["presets", "--fetch"])).unwrap();
+        assert_eq!(fetch, Command::Presets { fetch: Some(presets::PRESETS_URL.into()) });
+        let mirror = parse(args(&["presets", "--fetch", "https://example.com/p.json"])).unwrap();
+        assert_eq!(mirror, Command::Presets { fetch: Some("https://example.com/p.json".into()) });
+        assert!(parse(args(&["presets", "--update"])).is_err());
+        assert!(parse(args(&["presets", "--fetch", "a", "b"])).is_err());
+    }
+
+    #[test]
+    fn rollback_takes_a_version_number() {
+        let list = Command::Rollback { pick: None, diff: false };
+        assert_eq!(parse(args(&["rollback"])).unwrap(), list);
+        let diff = parse(args(&["rollback", "--diff", "3"])).unwrap();
+        assert_eq!(diff, Command::Rollback { pick: Some(3), diff: true });
+        assert!(parse(args(&["rollback", "0"])).is_err());
+        assert!(parse(args(&["rollback", "--diff"])).is_err());
+        assert!(parse(args(&["rollback", "1", "2"])).is_err());
+        assert!(parse(args(&["rollback", "latest"])).is_err());
+    }
+
+    #[test]
+    fn edid_parses_an_optional_connector_and_raw() {
+        let all = Command::Edid { connector: None, raw: false };
+        assert_eq!(parse(args(&["edid"])).unwrap(), all);
+        let raw = parse(args(&["edid", "--raw", "DP-1"])).unwrap();
+        assert_eq!(raw, Command::Edid { connector: Some("DP-1".into()), raw: true });
+        assert!(parse(args(&["edid", "DP-1", "DP-2"])).is_err());
+        assert!(parse(args(&["edid", "--hex"])).is_err());
+        assert!(parse(args(&["--dry-run", "edid"])).is_err());
+    }
+
+    #[test]
+    fn apply_and_keybinds_parse() {
+        assert_eq!(
+            parse(args(&["apply", "Docked"])).unwrap(),
+            Command::Apply {
+                workspace: "Docked".into(),
+                host: None
+            }
+        );
+        assert!(parse(args(&["apply"])).is_err());
+        assert_eq!(
+            parse(args(&["apply", "--host", "me@tv", "Couch"])).unwrap(),
+            Command::Apply { workspace: "Couch".into(), host: Some("me@tv".into()) }
+        );
+        assert!(parse(args(&["apply", "Couch", "--host"])).is_err());
+        assert!(parse(args(&["apply", "--host", "-oProxyCommand=x", "Couch"])).is_err());
+        assert!(parse(args(&["apply", "Couch", "Desk"])).is_err());
+        assert_eq!(
+            parse(args(&["keybinds"])).unwrap(),
+            Command::Keybinds { mods: "SUPER".into() }
+        );
+        assert_eq!(
+            parse(args(&["keybinds", "--mod", "SUPER SHIFT"])).unwrap(),
+            Command::Keybinds {
+                mods: "SUPER SHIFT".into()
+            }
+        );
+        assert!(parse(args(&["keybinds", "--mod"])).is_err());
+        assert_eq!(parse(args(&["config"])).unwrap(), Command::Config { copy: false });
+        assert_eq!(parse(args(&["config", "--copy"])).unwrap(), Command::Config { copy: true });
+        assert!(parse(args(&["config", "--paste"])).is_err());
+        assert_eq!(
+            parse(args(&["export", "--svg", "Docked"])).unwrap(),
+            Command::Export {
+                svg: true,
+                workspace: Some("Docked".into())
+            }
+        );
+        assert_eq!(
+            parse(args(&["export"])).unwrap(),
+            Command::Export {
+                svg: false,
+                workspace: None
+            }
+        );
+        assert!(parse(args(&["export", "a", "b"])).is_err());
+    }
+
+    #[test]
+    fn keybind_lines_number_workspaces_and_escape_names() {
+        let mut db = MonitorDatabase::default();
+        db.workspaces[0].name = "Docked".into();
+        db.add_workspace("Say \"hi\"").unwrap();
+        db.add_workspace("Desk #2").unwrap();
+        for i in 0..10 {
+            db.add_workspace(&format!("W{i}")).unwrap();
+        }
+        let lines = keybind_lines(&db, "SUPER");
+        assert_eq!(lines[1], "bind = SUPER, F1, exec, hyprmon apply \"Docked\"");
+        assert_eq!(lines[2], "bind = SUPER, F2, exec, hyprmon apply \"Say \\\"hi\\\"\"");
+        assert_eq!(lines[3], "bind = SUPER, F3, exec, hyprmon apply \"Desk ##2\"");
+        assert_eq!(lines[12], "bind = SUPER, F12, exec, hyprmon apply \"W8\"");
+        assert_eq!(lines[13], "# bind = SUPER, ?, exec, hyprmon apply \"W9\"");
+        assert_eq!(lines[14], "bind = SUPER CTRL, Escape, exec, hyprmon panic");
+    }
+
+    #[test]
+    fn apply_with_a_host_runs_hyprmon_there_over_ssh() {
+        let ssh = "ssh -- me@tv hyprmon apply 'Living room'";
+        let replay = Replay::default().answer(ssh, "Applied 'Living room'.\n");
+        let apply = Command::Apply { workspace: "Living room".into(), host: Some("me@tv".into()) };
+        execute(apply, &replay, &Writes::new(false)).unwrap();
+        assert_eq!(replay.calls(), [ssh]);
+
+        let dry = DryRun(Replay::default().answer(&format!("{ssh} --dry-run"), ""));
+        let apply = Command::Apply { workspace: "Living room".into(), host: Some("me@tv".into()) };
+        execute(apply, &dry, &Writes::new(true)).unwrap();
+        assert_eq!(dry.0.calls(), [format!("{ssh} --dry-run")]);
+
+        let args = ssh_apply_args("tv", "Desk-2", false);
+        assert_eq!(args, ["--", "tv", "hyprmon", "apply", "Desk-2"]);
+        assert_eq!(ssh_apply_args("tv", "it's $HOME", false)[4], r"'it'\''s $HOME'");
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{bail, Result};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::exec::Executor;
+
+/// Put `text` on the clipboard every way that is available: an OSC 52 escape
+/// to the controlling terminal (which forwards it over SSH) and `wl-copy` in
+/// a local Wayland session. Returns the names of the ways that worked.
+pub fn copy(exec: &dyn Executor, text: &str) -> Result<Vec<&'static str>> {
+    let mut used = Vec::new();
+    if write_osc52(text).is_ok() {
+        used.push("OSC 52");
+    }
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    if wayland && exec.feed("wl-copy", &[], text.as_bytes()).is_ok() {
+        used.push("wl-copy");
+    }
+    if used.is_empty() {
+        bail!("no terminal to send OSC 52 to and wl-copy is not available");
+    }
+    Ok(used)
+}
+
+/// Written to the tty rather than stdout so it reaches the terminal even when
+/// output is piped, and does not land in the TUI's screen buffer.
+fn write_osc52(text: &str) -> Result<()> {
+    let mut tty = OpenOptions::new().write(true).open("/dev/tty")?;
+    tty.write_all(osc52(text).as_bytes())?;
+    tty.flush()?;
+    Ok(())
+}
+
+/// The escape that sets the system clipboard (`c`) to `text`.
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len() * 4 / 3 + 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_like_rfc_4648() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(osc52("monitor=,preferred\n"), "\x1b]52;c;bW9uaXRvcj0scHJlZmVycmVkCg==\x07");
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the Unix epoch. Persisted timestamps use this form so the JSON
+/// stays timezone-agnostic; conversion to wall-clock time happens on display.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Break `secs` into local calendar fields via `localtime_r(3)`, which honours
+/// `TZ` and `/etc/localtime` without pulling in a date-time crate.
+fn local_tm(secs: u64) -> Option<libc::tm> {
+    let t = secs as libc::time_t;
+    // SAFETY: `tm` is plain-old-data and fully written by localtime_r on success.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let ok = unsafe { !libc::localtime_r(&t, &mut tm).is_null() };
+    ok.then_some(tm)
+}
+
+/// Format `secs` as local `YYYY-MM-DD HH:MM`.
+pub fn format_local(secs: u64) -> String {
+    match local_tm(secs) {
+        Some(tm) => format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            tm.tm_year + 1900,
+            tm.tm_mon + 1,
+            tm.tm_mday,
+            tm.tm_hour,
+            tm.tm_min
+        ),
+        None => format!("@{secs}"),
+    }
+}
+
+/// Minutes since local midnight (0..1440), for time-of-day schedules.
+pub fn local_minute_of_day(secs: u64) -> u16 {
+    match local_tm(secs) {
+        Some(tm) => (tm.tm_hour * 60 + tm.tm_min) as u16,
+        None => ((secs / 60) % 1440) as u16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_local_has_fixed_shape() {
+        let s = format_local(1_700_000_000);
+        assert_eq!(s.len(), 16, "{s}");
+        assert_eq!(&s[4..5], "-");
+        assert_eq!(&s[13..14], ":");
+        assert!(now() > 1_700_000_000);
+        assert!(local_minute_of_day(now()) < 1440);
+    }
+}
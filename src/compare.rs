@@ -0,0 +1,218 @@
+use crate::config::{MonitorDatabase, SavedMonitor};
+use crate::monitor::{MonitorConfig, Rotation};
+use crate::state::SettingField;
+
+/// One place where a live monitor deviates from the active workspace.
+/// `field` is `None` when the workspace has no entry for the monitor at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Index into the live monitor list.
+    pub monitor: usize,
+    pub key: String,
+    pub field: Option<SettingField>,
+    pub live: String,
+    pub saved: String,
+}
+
+/// Compare every managed live monitor against the active workspace, field by
+/// field. Positions are left out: the generated config may re-pack them.
+pub fn diff_against_saved(monitors: &[MonitorConfig], db: &MonitorDatabase) -> Vec<FieldDiff> {
+    let mut out = Vec::new();
+    for (idx, monitor) in monitors.iter().enumerate() {
+        let key = MonitorDatabase::get_monitor_key(monitor);
+        if !db.is_managed(&key) {
+            continue;
+        }
+        let Some(saved) = db.get_saved_config(monitor) else {
+            out.push(FieldDiff {
+                monitor: idx,
+                key,
+                field: None,
+                live: "connected".to_string(),
+                saved: "not saved".to_string(),
+            });
+            continue;
+        };
+        let saved = saved_as_live(saved, monitor);
+        for field in SettingField::all().into_iter().filter(|f| !f.is_position()) {
+            let (live, stored) = (field_value(monitor, field), field_value(&saved, field));
+            if live != stored {
+                out.push(FieldDiff {
+                    monitor: idx,
+                    key: key.clone(),
+                    field: Some(field),
+                    live,
+                    saved: stored,
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Any saved setting Hyprland itself knows about differs. Monitors the
+/// workspace has never saved do not count (nothing to be out of sync with),
+/// and neither does Primary: Hyprland only reports focus, which moves freely.
+pub fn is_out_of_sync(diffs: &[FieldDiff]) -> bool {
+    diffs
+        .iter()
+        .any(|d| d.field.is_some_and(|f| f != SettingField::Primary))
+}
+
+/// Where Hyprland, asked after an apply, runs a monitor other than it was
+/// told to: another mode (it falls back to 60 Hz on a rate the link cannot
+/// carry) or another scale. One (connector, what differs) pair per monitor.
+/// A mode preset such as `highrr` leaves the mode to Hyprland, so only the
+/// scale is checked then.
+pub fn achieved_mismatches(
+    requested: &[MonitorConfig],
+    achieved: &[MonitorConfig],
+) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for want in requested {
+        let Some(got) = achieved.iter().find(|m| m.name == want.name) else {
+            out.push((want.name.clone(), "not running after the apply".to_string()));
+            continue;
+        };
+        let mode = |m: &MonitorConfig| format!("{}@{:.2}Hz", m.resolution, m.refresh_rate);
+        let mut wrong = Vec::new();
+        let mode_differs = want.resolution != got.resolution
+            || (want.refresh_rate - got.refresh_rate).abs() >= 0.05;
+        if want.mode_preset.is_none() && mode_differs {
+            wrong.push(format!("asked {}, got {}", mode(want), mode(got)));
+        }
+        if (want.scale - got.scale).abs() >= 0.005 {
+            wrong.push(format!("scale asked {:.2}, got {:.2}", want.scale, got.scale));
+        }
+        if !wrong.is_empty() {
+            out.push((want.name.clone(), wrong.join("; ")));
+        }
+    }
+    out
+}
+
+/// Display form of `field` on `monitor`, used both for comparing and showing.
+pub fn field_value(monitor: &MonitorConfig, field: SettingField) -> String {
+    if let (Some(preset), SettingField::Resolution | SettingField::RefreshRate) =
+        (monitor.mode_preset, field)
+    {
+        return preset.keyword().to_string();
+    }
+    match field {
+        SettingField::Resolution => monitor.resolution.clone(),
+        SettingField::RefreshRate => format!("{:.2}Hz", monitor.refresh_rate),
+        SettingField::Scale => format!("{:.2}", monitor.scale),
+        SettingField::Rotation => monitor.rotation.as_str().to_string(),
+        SettingField::PositionX => monitor.position_x.to_string(),
+        SettingField::PositionY => monitor.position_y.to_string(),
+        SettingField::Primary => if monitor.is_primary { "yes" } else { "no" }.to_string(),
+    }
+}
+
+/// Copy one field from `from` onto `to`.
+pub fn copy_field(from: &MonitorConfig, to: &mut MonitorConfig, field: SettingField) {
+    match field {
+        SettingField::Resolution => {
+            to.resolution = from.resolution.clone();
+            to.mode_preset = from.mode_preset;
+        }
+        SettingField::RefreshRate => {
+            to.refresh_rate = from.refresh_rate;
+            to.mode_preset = from.mode_preset;
+        }
+        SettingField::Scale => to.scale = from.scale,
+        SettingField::Rotation => to.rotation = from.rotation,
+        SettingField::PositionX => to.position_x = from.position_x,
+        SettingField::PositionY => to.position_y = from.position_y,
+        SettingField::Primary => to.is_primary = from.is_primary,
+    }
+}
+
+/// `live` with the saved settings laid over it, so fields can be compared and
+/// copied as like-for-like `MonitorConfig`s.
+pub fn saved_as_live(saved: &SavedMonitor, live: &MonitorConfig) -> MonitorConfig {
+    MonitorConfig {
+        resolution: saved.resolution.clone(),
+        refresh_rate: saved.refresh_rate,
+        scale: saved.scale,
+        rotation: Rotation::from_transform(saved.rotation),
+        position_x: saved.position_x,
+        position_y: saved.position_y,
+        is_primary: saved.is_primary,
+        mode_preset: saved.mode_preset,
+        ..live.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_changed_fields_and_unsaved_monitors() {
+        let edp = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let hdmi = MonitorConfig::for_test("HDMI-A-1", "MSI", "MP", "2560x1440");
+        let mut db = MonitorDatabase::default();
+        db.update_monitor(&edp);
+
+        let mut live = edp.clone();
+        live.scale = 1.5;
+        live.position_x = 400; // positions are ignored
+        let diffs = diff_against_saved(&[live, hdmi.clone()], &db);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].field, Some(SettingField::Scale));
+        assert_eq!((diffs[0].live.as_str(), diffs[0].saved.as_str()), ("1.50", "1.00"));
+        assert_eq!(diffs[1].monitor, 1);
+        assert_eq!(diffs[1].field, None);
+        assert!(is_out_of_sync(&diffs));
+        assert!(!is_out_of_sync(&diffs[1..]));
+
+        let focus_only = FieldDiff {
+            field: Some(SettingField::Primary),
+            ..diffs[0].clone()
+        };
+        assert!(!is_out_of_sync(&[focus_only]));
+
+        db.toggle_managed("desc:MSI MP");
+        assert_eq!(diff_against_saved(&[edp, hdmi], &db), vec![]);
+    }
+
+    #[test]
+    fn achieved_mismatches_flag_fallbacks_per_monitor() {
+        let mut want = MonitorConfig::for_test("DP-1", "Dell", "AW", "3440x1440");
+        want.refresh_rate = 174.96;
+        want.scale = 1.33;
+        let edp = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let mut got = want.clone();
+        got.refresh_rate = 174.9601;
+        assert!(achieved_mismatches(&[want.clone()], &[got.clone()]).is_empty());
+
+        got.refresh_rate = 60.0;
+        got.scale = 1.25;
+        assert_eq!(
+            achieved_mismatches(&[want, edp], &[got]),
+            [
+                (
+                    "DP-1".to_string(),
+                    concat!(
+                        "asked 3440x1440@174.96Hz, got 3440x1440@60.00Hz; ",
+                        "scale asked 1.33, got 1.25"
+                    )
+                    .to_string()
+                ),
+                ("eDP-1".to_string(), "not running after the apply".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn copy_field_moves_only_that_field() {
+        let a = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let mut b = a.clone();
+        b.scale = 2.0;
+        b.rotation = Rotation::Left;
+        copy_field(&a, &mut b, SettingField::Scale);
+        assert_eq!(b.scale, 1.0);
+        assert_eq!(b.rotation, Rotation::Left);
+    }
+}
@@ -1,12 +1,16 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::monitor::{MonitorConfig, Rotation};
+use crate::clock;
+use crate::monitor::{KeyBy, ModePreset, MonitorConfig, Rotation};
+use crate::schedule::TimeRange;
+use crate::text;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedMonitor {
     pub resolution: String,
     pub refresh_rate: f64,
@@ -16,23 +20,251 @@ pub struct SavedMonitor {
     pub position_y: i32,
     #[serde(default)]
     pub is_primary: bool,
+    /// `highres` / `highrr` instead of the fixed mode above, which is kept as
+    /// the last one seen so the layout can still be packed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode_preset: Option<ModePreset>,
+}
+
+impl SavedMonitor {
+    /// Snapshot the persisted fields of a live monitor.
+    pub fn from_monitor(monitor: &MonitorConfig) -> Self {
+        Self {
+            resolution: monitor.resolution.clone(),
+            refresh_rate: monitor.refresh_rate,
+            scale: monitor.scale,
+            rotation: monitor.rotation.transform(),
+            position_x: monitor.position_x,
+            position_y: monitor.position_y,
+            is_primary: monitor.is_primary,
+            mode_preset: monitor.mode_preset,
+        }
+    }
+}
+
+/// Extra conditions for auto-selecting a workspace, beyond counting which of
+/// its saved monitors are connected. All fields are optional; an empty rule set
+/// keeps the plain key-count behaviour.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchRules {
+    /// Exact number of connected monitors this workspace is meant for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor_count: Option<usize>,
+    /// Globs (`*`, `?`) matched against a connected monitor's description or
+    /// connector name; each must match at least one monitor.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Monitor keys that must be connected. Saved monitors not listed here are
+    /// optional.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    /// Higher priority wins over a better key match among eligible workspaces.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl MatchRules {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `None` when a hard rule fails; otherwise how many connected monitors a
+    /// pattern matched (counts towards the workspace's score).
+    fn evaluate(&self, connected: &[MonitorConfig]) -> Option<usize> {
+        if self.monitor_count.is_some_and(|n| n != connected.len()) {
+            return None;
+        }
+        let keys: Vec<String> = connected.iter().map(MonitorDatabase::get_monitor_key).collect();
+        if !self.required.iter().all(|r| keys.contains(r)) {
+            return None;
+        }
+        let hits = |m: &MonitorConfig, pat: &str| {
+            glob_match(pat, &m.description) || glob_match(pat, &m.name)
+        };
+        if !self
+            .patterns
+            .iter()
+            .all(|p| connected.iter().any(|m| hits(m, p)))
+        {
+            return None;
+        }
+        Some(
+            connected
+                .iter()
+                .filter(|m| self.patterns.iter().any(|p| hits(m, p)))
+                .count(),
+        )
+    }
+
+    /// One-line summary for the Saved tab, e.g. `2 monitors, HDMI-*, priority 5`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(n) = self.monitor_count {
+            parts.push(format!("{n} monitor(s)"));
+        }
+        parts.extend(self.patterns.iter().cloned());
+        parts.extend(self.required.iter().map(|r| format!("requires {r}")));
+        if self.priority != 0 {
+            parts.push(format!("priority {}", self.priority));
+        }
+        if parts.is_empty() {
+            "any saved monitor".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// How a workspace is put on screen for hardware that one reload is too much
+/// for: each monitor set on its own with `hyprctl keyword`, in `order`, with
+/// a pause between them, before monitors.conf is written and reloaded as usual.
+/// Some docks need the external display up before the internal one goes dark.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApplySteps {
+    /// Monitor keys set first, in this order. The others follow, enabled
+    /// ones before disabled ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    /// Pause between one monitor and the next, in milliseconds.
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+impl ApplySteps {
+    /// Nothing set: the workspace is applied with a single reload.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    pub fn describe(&self) -> String {
+        if self.is_empty() {
+            return "all at once".to_string();
+        }
+        let mut parts = Vec::new();
+        if !self.order.is_empty() {
+            parts.push(format!("{} first", self.order.join(", then ")));
+        }
+        parts.push(format!("{} ms between monitors", self.delay_ms));
+        parts.join("; ")
+    }
 }
 
 /// A workspace represents a saved monitor configuration for a specific location
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workspace {
+    /// Random UUID given at creation. Names can be edited and indices shift
+    /// as workspaces move, so this is what other records point at.
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub monitors: HashMap<String, SavedMonitor>,
+    /// Pinned workspaces are kept at the front of the tab strip.
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "MatchRules::is_empty")]
+    pub match_rules: MatchRules,
+    /// Never chosen by startup/hotplug matching; only applied by hand.
+    #[serde(default)]
+    pub manual_only: bool,
+    /// Free-form note on what the workspace is for.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Unix seconds; `None` for workspaces saved before this was tracked.
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    #[serde(default)]
+    pub last_applied: Option<u64>,
+    /// How many times this workspace has been written to Hyprland.
+    #[serde(default)]
+    pub apply_count: u32,
+    /// Local-time windows in which the daemon prefers this workspace; outside
+    /// them it is never auto-selected. Empty means "any time".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule: Vec<TimeRange>,
+    #[serde(default, skip_serializing_if = "ApplySteps::is_empty")]
+    pub apply_steps: ApplySteps,
+    /// Keys of monitors turned off (DPMS) once the workspace is applied,
+    /// e.g. the TV at the desk. They keep their place in the layout.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dpms_off: Vec<String>,
 }
 
 impl Workspace {
     pub fn new(name: &str) -> Self {
         Self {
+            id: new_workspace_id(),
             name: name.to_string(),
             monitors: HashMap::new(),
+            pinned: false,
+            match_rules: MatchRules::default(),
+            manual_only: false,
+            description: String::new(),
+            created_at: Some(clock::now()),
+            last_applied: None,
+            apply_count: 0,
+            schedule: Vec::new(),
+            apply_steps: ApplySteps::default(),
+            dpms_off: Vec::new(),
         }
     }
 
+    /// Turn monitor `key` off after applying, or stop doing so. Returns
+    /// whether it is turned off now.
+    pub fn toggle_dpms_off(&mut self, key: &str) -> bool {
+        if let Some(pos) = self.dpms_off.iter().position(|k| k == key) {
+            self.dpms_off.remove(pos);
+            false
+        } else {
+            self.dpms_off.push(key.to_string());
+            true
+        }
+    }
+
+    /// Schedule for display, e.g. `23:00-07:00, 12:00-13:00` or `any time`.
+    pub fn schedule_label(&self) -> String {
+        if self.schedule.is_empty() {
+            return "any time".to_string();
+        }
+        let ranges: Vec<String> = self.schedule.iter().map(|r| r.to_string()).collect();
+        ranges.join(", ")
+    }
+
+    /// `None` for unscheduled workspaces, else whether `minute` (since local
+    /// midnight) falls in one of its windows.
+    pub fn scheduled_at(&self, minute: u16) -> Option<bool> {
+        (!self.schedule.is_empty()).then(|| self.schedule.iter().any(|r| r.contains(minute)))
+    }
+
+    /// Record that this workspace was just written to Hyprland.
+    pub fn mark_applied(&mut self, at: u64) {
+        self.last_applied = Some(at);
+        self.apply_count = self.apply_count.saturating_add(1);
+    }
+
+    /// One-line summary of the notes and usage metadata for the Saved tab.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.description.is_empty() {
+            parts.push(self.description.clone());
+        }
+        if let Some(t) = self.created_at {
+            parts.push(format!("created {}", clock::format_local(t)));
+        }
+        match self.last_applied {
+            Some(t) => parts.push(format!(
+                "last applied {} ({}x)",
+                clock::format_local(t),
+                self.apply_count
+            )),
+            None => parts.push("never applied".to_string()),
+        }
+        parts.join(" · ")
+    }
+
     /// Get monitor keys in this workspace
     #[allow(dead_code)]
     pub fn monitor_keys(&self) -> Vec<String> {
@@ -49,27 +281,308 @@ impl Workspace {
             })
             .count()
     }
+
+    /// Auto-selection score for `connected`: `None` if a match rule excludes
+    /// this workspace, else `(priority, saved-key hits + pattern hits)`.
+    pub fn match_score(&self, connected: &[MonitorConfig]) -> Option<(i32, usize)> {
+        let pattern_hits = self.match_rules.evaluate(connected)?;
+        let score = self.matches_monitors(connected) + pattern_hits;
+        (score > 0).then_some((self.match_rules.priority, score))
+    }
+}
+
+/// A random (version 4) UUID for a new workspace. Without `/dev/urandom` the
+/// clock, pid and a counter stand in, which is plenty for a handful of profiles.
+fn new_workspace_id() -> String {
+    use std::io::Read;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let mut bytes = [0u8; 16];
+    let random = fs::File::open("/dev/urandom").and_then(|mut f| f.read_exact(&mut bytes));
+    if random.is_err() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        bytes[..8].copy_from_slice(&nanos.to_le_bytes());
+        bytes[8..12].copy_from_slice(&std::process::id().to_le_bytes());
+        bytes[12..].copy_from_slice(&COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitorDatabase {
     #[serde(default)]
     pub workspaces: Vec<Workspace>,
+    /// Id of the active workspace; see [`Self::active_workspace`].
+    #[serde(default)]
+    active_id: String,
+    /// Index of the active workspace in files written before workspaces had
+    /// ids; only read, to find `active_id` on load.
+    #[serde(default, rename = "active_workspace", skip_serializing)]
+    legacy_active: Option<usize>,
+    /// Keep the active workspace as-is on startup and hotplug instead of
+    /// switching to the best-matching workspace.
+    #[serde(default)]
+    pub lock_active: bool,
+    /// Monitor keys left to hand-written Hyprland config: no line is emitted
+    /// for them and applies never touch their settings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmanaged: Vec<String>,
+    /// Rule for monitors no workspace knows, written as `monitor=,<fallback>`.
+    /// `None` (`null` in JSON) emits no fallback at all.
+    #[serde(default = "default_fallback")]
+    pub fallback: Option<String>,
+    /// What the generated block says about saved monitors not plugged in.
+    #[serde(default, skip_serializing_if = "Disconnected::is_keep")]
+    pub disconnected: Disconnected,
+    /// Key (or connector) of the monitor that becomes primary when the
+    /// primary is unplugged. `None` picks the laptop panel, else the leftmost.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_fallback: Option<String>,
+    /// Write each workspace to its own file beside `monitors.conf` and have
+    /// `monitors.conf` only `source` the active one.
     #[serde(default)]
-    pub active_workspace: usize,
+    pub per_workspace_files: bool,
+    /// How identical monitors are told apart, by the description key they
+    /// share. Descriptions not listed key by description alone.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub key_by: BTreeMap<String, KeyBy>,
+    /// Names given to monitors, like "Left Dell", by monitor key; shown in
+    /// place of the model in the TUI and next to the key in `hyprmon status`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nicknames: BTreeMap<String, String>,
     /// Where `save()` writes. Set by `load_from`; `None` falls back to the real
     /// config path. Skipped during (de)serialization so the JSON format is
     /// unchanged and tests can redirect persistence to a temp file.
     #[serde(skip)]
     config_path: Option<PathBuf>,
+    /// Workspaces as loaded from [`BUNDLED_DIR`], to tell which of
+    /// `workspaces` are still the bundled ones; see [`Self::is_bundled`].
+    #[serde(skip)]
+    bundled: Vec<Workspace>,
+    /// Bundled files that could not be read, with the reason.
+    #[serde(skip)]
+    pub bundle_errors: Vec<String>,
+}
+
+/// Where admins ship workspaces for every user: `*.json` files, each holding
+/// one workspace (as in `monitors.json`) or a list of them.
+pub const BUNDLED_DIR: &str = "/etc/hyprmon/profiles.d";
+
+/// A file in [`BUNDLED_DIR`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Bundle {
+    One(Box<Workspace>),
+    Many(Vec<Workspace>),
+}
+
+pub const DEFAULT_FALLBACK: &str = "preferred,auto,1";
+
+fn default_fallback() -> Option<String> {
+    Some(DEFAULT_FALLBACK.to_string())
+}
+
+/// Normalise a user-entered fallback rule. Accepts `highrr,auto,1.5`,
+/// `,highrr,auto,1.5` or a full `monitor=,highrr,auto,1.5` line; an empty
+/// string means "no fallback".
+pub fn parse_fallback(input: &str) -> Result<Option<String>> {
+    let rule = input.trim();
+    let rule = rule.strip_prefix("monitor=").unwrap_or(rule);
+    let rule = rule.strip_prefix(',').unwrap_or(rule).trim();
+    if rule.is_empty() {
+        return Ok(None);
+    }
+    let fields: Vec<&str> = rule.split(',').map(str::trim).collect();
+    if fields.len() < 3 || fields.iter().any(|f| f.is_empty()) {
+        anyhow::bail!("fallback needs <mode>,<position>,<scale>, e.g. {DEFAULT_FALLBACK}");
+    }
+    Ok(Some(fields.join(",")))
+}
+
+/// How the generated block treats a saved monitor that is not connected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Disconnected {
+    /// Keep its rule, so it comes back as saved when plugged in again.
+    #[default]
+    Keep,
+    /// Write its rule commented out; Hyprland ignores it until the next apply.
+    Comment,
+    /// Write `monitor=<key>,disable`: plugged back in, it stays dark until
+    /// the next apply.
+    Disable,
+}
+
+impl Disconnected {
+    pub const ALL: [Disconnected; 3] = [Self::Keep, Self::Comment, Self::Disable];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Comment => "comment",
+            Self::Disable => "disable",
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.as_str() == text.trim())
+            .ok_or_else(|| anyhow::anyhow!("expected keep, comment or disable, not {text:?}"))
+    }
+
+    fn is_keep(&self) -> bool {
+        *self == Self::Keep
+    }
+}
+
+/// Farthest a typed position may sit from the origin on either axis.
+pub const MAX_POSITION: i32 = 32_767;
+
+/// Parse a typed monitor coordinate: a whole number of logical pixels within
+/// `MAX_POSITION` of the origin.
+pub fn parse_position(input: &str) -> Result<i32> {
+    let value: i32 = input
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{:?} is not a whole number", input.trim()))?;
+    if value.abs() > MAX_POSITION {
+        anyhow::bail!("position must be between -{MAX_POSITION} and {MAX_POSITION}");
+    }
+    Ok(value)
+}
+
+/// Smallest and largest custom scale, in percent.
+pub const SCALE_RANGE: (f64, f64) = (25.0, 400.0);
+
+/// Parse a typed scale in percent (`133`, `133%`, `133.33`) into the factor
+/// Hyprland takes.
+pub fn parse_scale(input: &str) -> Result<f64> {
+    let text = input.trim().trim_end_matches('%').trim();
+    let percent: f64 = text
+        .parse()
+        .ok()
+        .filter(|p: &f64| p.is_finite())
+        .ok_or_else(|| anyhow::anyhow!("{:?} is not a percentage", input.trim()))?;
+    let (min, max) = SCALE_RANGE;
+    if !(min..=max).contains(&percent) {
+        anyhow::bail!("scale must be between {min}% and {max}%");
+    }
+    Ok(percent / 100.0)
+}
+
+/// The scale Hyprland would use instead of `scale` on a `resolution` panel,
+/// or `None` when it takes `scale` as is. Hyprland wants a whole-pixel
+/// logical size, so like it this searches multiples of 1/120 outwards from
+/// the chosen scale for the nearest one that divides both sides evenly.
+pub fn fixed_scale(resolution: &str, scale: f64) -> Option<f64> {
+    let (w, h) = resolution
+        .trim()
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse::<u64>().ok()?, h.trim().parse::<u64>().ok()?)))?;
+    if scale <= 0.0 {
+        return None;
+    }
+    let whole = |v: f64| (v - v.round()).abs() < 1e-6;
+    if whole(w as f64 / scale) && whole(h as f64 / scale) {
+        return None;
+    }
+    let divides = |n: u64| n > 0 && (120 * w) % n == 0 && (120 * h) % n == 0;
+    let start = (scale * 120.0).round() as u64;
+    (0..90)
+        .flat_map(|i| [start + i, start.saturating_sub(i)])
+        .find(|&n| divides(n))
+        .map(|n| n as f64 / 120.0)
+        .filter(|&fixed| (fixed - scale).abs() > 1e-9)
+}
+
+/// A scale as written to `monitors.conf`: `2`, `1.50`, or up to six decimals
+/// when two would not round-trip (`1.566667`).
+pub fn format_scale(scale: f64) -> String {
+    if scale.fract() == 0.0 {
+        return format!("{}", scale as i32);
+    }
+    let short = format!("{:.2}", scale);
+    if short.parse::<f64>().is_ok_and(|s| (s - scale).abs() < 1e-9) {
+        return short;
+    }
+    format!("{:.6}", scale).trim_end_matches('0').to_string()
+}
+
+/// Largest custom resolution on either side, in pixels.
+pub const MAX_RESOLUTION: u32 = 16_384;
+
+/// Parse a typed resolution such as `2560x1080` into Hyprland's `WxH` form.
+pub fn parse_resolution(input: &str) -> Result<String> {
+    let text = input.trim().to_lowercase();
+    let (w, h) = text
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse::<u32>().ok()?, h.trim().parse::<u32>().ok()?)))
+        .ok_or_else(|| anyhow::anyhow!("resolution needs <width>x<height>, e.g. 2560x1080"))?;
+    if w == 0 || h == 0 || w > MAX_RESOLUTION || h > MAX_RESOLUTION {
+        anyhow::bail!("width and height must be between 1 and {MAX_RESOLUTION}");
+    }
+    Ok(format!("{w}x{h}"))
+}
+
+/// Aspect ratios a mode is labelled with, by the width / height range they
+/// cover: panels round (1366x768 is 16:9) and "21:9" ultrawides are really
+/// 64:27 or 43:18.
+const ASPECT_RATIOS: &[(&str, f64, f64)] = &[
+    ("32:9", 3.5, 3.6),
+    ("21:9", 2.33, 2.41),
+    ("16:9", 1.763, 1.792),
+    ("16:10", 1.59, 1.61),
+    ("3:2", 1.49, 1.51),
+    ("4:3", 1.32, 1.34),
+    ("5:4", 1.24, 1.26),
+];
+
+/// The aspect ratio `resolution` (`WxH`) goes by, like `16:9`; `None` for
+/// odd shapes such as 720x400 and for anything that is not a resolution.
+pub fn aspect_ratio(resolution: &str) -> Option<&'static str> {
+    let (w, h) = resolution.trim().split_once('x')?;
+    let ratio = w.parse::<f64>().ok()? / h.parse::<f64>().ok()?;
+    let named = ASPECT_RATIOS.iter().find(|(_, lo, hi)| (*lo..=*hi).contains(&ratio));
+    named.map(|(name, _, _)| *name)
+}
+
+/// Below 1280x720 or of no common aspect ratio: the legacy modes that pad out
+/// an ultrawide's list. Keywords like `highres` never are.
+pub fn is_exotic_resolution(resolution: &str) -> bool {
+    let Some((w, h)) = resolution.trim().split_once('x') else {
+        return false;
+    };
+    let (Ok(w), Ok(h)) = (w.parse::<u64>(), h.parse::<u64>()) else {
+        return false;
+    };
+    w * h < 1280 * 720 || aspect_ratio(resolution).is_none()
 }
 
 impl Default for MonitorDatabase {
     fn default() -> Self {
+        let workspace = Workspace::new("Default");
         Self {
-            workspaces: vec![Workspace::new("Default")],
-            active_workspace: 0,
+            active_id: workspace.id.clone(),
+            legacy_active: None,
+            workspaces: vec![workspace],
+            lock_active: false,
+            unmanaged: Vec::new(),
+            fallback: default_fallback(),
+            disconnected: Disconnected::Keep,
+            primary_fallback: None,
+            per_workspace_files: false,
+            key_by: BTreeMap::new(),
+            nicknames: BTreeMap::new(),
             config_path: None,
+            bundled: Vec::new(),
+            bundle_errors: Vec::new(),
         }
     }
 }
@@ -79,12 +592,24 @@ impl MonitorDatabase {
         dirs::home_dir().unwrap().join(".config/hypr/monitors.json")
     }
 
+    /// Hyprland config file hyprmon writes its managed block into.
+    pub fn hypr_conf_path() -> PathBuf {
+        dirs::home_dir().unwrap().join(".config/hypr/monitors.conf")
+    }
+
+    /// The user's workspaces, followed by those bundled in [`BUNDLED_DIR`].
     pub fn load() -> Result<Self> {
-        Self::load_from(&Self::config_path())
+        Self::load_layered(&Self::config_path(), Some(Path::new(BUNDLED_DIR)))
     }
 
+    /// The database at `path` alone, without bundled workspaces.
+    #[cfg(test)]
     pub fn load_from(path: &Path) -> Result<Self> {
-        if path.exists() {
+        Self::load_layered(path, None)
+    }
+
+    fn load_layered(path: &Path, bundled: Option<&Path>) -> Result<Self> {
+        let mut db = if path.exists() {
             let content = fs::read_to_string(path)?;
             let mut db: MonitorDatabase = serde_json::from_str(&content)?;
             if db.workspaces.is_empty() {
@@ -97,14 +622,78 @@ impl MonitorDatabase {
             for ws in &mut db.workspaces {
                 dedup_prefix_keys(ws);
             }
-            db.config_path = Some(path.to_path_buf());
-            Ok(db)
+            db
         } else {
-            Ok(MonitorDatabase {
-                config_path: Some(path.to_path_buf()),
-                ..Self::default()
-            })
+            Self::default()
+        };
+        if let Some(dir) = bundled {
+            db.merge_bundled(dir);
+        }
+        db.assign_ids();
+        db.config_path = Some(path.to_path_buf());
+        Ok(db)
+    }
+
+    /// Append the workspaces bundled in `dir`, by file name order. One whose
+    /// name or id is already taken is left out: that is how a user overrides
+    /// a bundled workspace, with one of their own. Bundled workspaces without
+    /// an id get one from their file name, so the active one can be tracked.
+    fn merge_bundled(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        for file in files {
+            let read = || -> Result<Bundle> {
+                Ok(serde_json::from_str(&fs::read_to_string(&file)?)?)
+            };
+            let list = match read() {
+                Ok(Bundle::One(ws)) => vec![*ws],
+                Ok(Bundle::Many(list)) => list,
+                Err(e) => {
+                    self.bundle_errors.push(format!("{}: {e}", file.display()));
+                    continue;
+                }
+            };
+            let stem = file.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            let single = list.len() == 1;
+            for (i, mut ws) in list.into_iter().enumerate() {
+                if ws.id.is_empty() {
+                    ws.id = format!("bundled:{stem}");
+                    if !single {
+                        ws.id += &format!(":{i}");
+                    }
+                }
+                if self.workspaces.iter().any(|own| own.name == ws.name || own.id == ws.id) {
+                    continue;
+                }
+                dedup_prefix_keys(&mut ws);
+                self.bundled.push(ws.clone());
+                self.workspaces.push(ws);
+            }
         }
+        // Pinned workspaces lead, bundled ones included
+        self.workspaces.sort_by_key(|ws| !ws.pinned);
+    }
+
+    /// Whether workspace `idx` is bundled and unchanged here, so saving
+    /// leaves it out of `monitors.json`. Once changed it is saved as the
+    /// user's own, overriding the bundled one. Apply counts don't count.
+    pub fn is_bundled(&self, idx: usize) -> bool {
+        let settings = |ws: &Workspace| {
+            serde_json::to_value(Workspace { last_applied: None, apply_count: 0, ..ws.clone() })
+                .ok()
+        };
+        self.workspaces.get(idx).is_some_and(|ws| {
+            self.bundled
+                .iter()
+                .any(|bundled| bundled.id == ws.id && settings(bundled) == settings(ws))
+        })
     }
 
     /// Redirect where `save()` persists. Lets tests target a path other than the
@@ -114,9 +703,126 @@ impl MonitorDatabase {
         self.config_path = Some(path);
     }
 
+    /// Regenerate hyprmon's managed block in the Hyprland config at `path`,
+    /// keeping a `.conf.bak` of the previous file. `connected` are the keys of
+    /// the monitors to pack into the generated row.
+    pub fn write_hypr_config(&self, path: &Path, connected: &HashSet<String>) -> Result<()> {
+        for change in self.hypr_config_changes(path, connected)? {
+            change.make()?;
+        }
+        Ok(())
+    }
+
+    /// What [`write_hypr_config`](Self::write_hypr_config) would do, in order,
+    /// without touching anything. With [`Self::per_workspace_files`] every
+    /// workspace's block goes to its own file beside `path`, the files of
+    /// workspaces since renamed or deleted are dropped, and `path` only
+    /// sources the active one.
+    pub fn hypr_config_changes(
+        &self,
+        path: &Path,
+        connected: &HashSet<String>,
+    ) -> Result<Vec<FileChange>> {
+        let mut changes = Vec::new();
+        let existing = if path.exists() {
+            changes.extend(FileChange::backups_of(path));
+            fs::read_to_string(path).unwrap_or_default()
+        } else {
+            String::new()
+        };
+        let block = if self.per_workspace_files {
+            self.workspace_file_changes(path, connected, &mut changes)?
+        } else {
+            self.generate_full_config(connected)
+        };
+        let content = splice_managed_block(&existing, &block);
+        changes.push(FileChange::Write { path: path.to_path_buf(), content });
+        Ok(changes)
+    }
+
+    /// The per-workspace files for [`Self::hypr_config_changes`], pushed onto
+    /// `changes`. Returns the block `main` gets: a `source` of the active one.
+    fn workspace_file_changes(
+        &self,
+        main: &Path,
+        connected: &HashSet<String>,
+        changes: &mut Vec<FileChange>,
+    ) -> Result<String> {
+        let mut written = HashSet::new();
+        for idx in 0..self.workspaces.len() {
+            let mut db = self.clone();
+            db.set_active(idx);
+            let path = self.workspace_conf_path(main, idx);
+            let content = splice_managed_block("", &db.generate_full_config(connected));
+            written.insert(path.clone());
+            changes.push(FileChange::Write { path, content });
+        }
+
+        // Only files hyprmon wrote start with its marker
+        let prefix = format!("{}-", conf_stem(main));
+        let dir = main.parent().unwrap_or(Path::new("."));
+        let mut stale = Vec::new();
+        for entry in fs::read_dir(dir)?.flatten() {
+            let file = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let ours = name.starts_with(&prefix)
+                && name.ends_with(".conf")
+                && !written.contains(&file)
+                && fs::read_to_string(&file).is_ok_and(|text| text.starts_with(BLOCK_BEGIN));
+            if ours {
+                stale.push(file);
+            }
+        }
+        stale.sort();
+        changes.extend(stale.into_iter().map(FileChange::Remove));
+        let active = self.workspace_conf_path(main, self.active_workspace());
+        Ok(format!("source = {}", active.display()))
+    }
+
+    /// Where workspace `idx` is written with [`Self::per_workspace_files`]:
+    /// `monitors-<name>.conf` beside `main`, the name lowercased and anything
+    /// but letters and digits turned into `-`. Names that come out the same
+    /// get their workspace number added.
+    pub fn workspace_conf_path(&self, main: &Path, idx: usize) -> PathBuf {
+        let slug = |ws: &Workspace| file_slug(&ws.name);
+        let name = self.workspaces.get(idx).map(slug).unwrap_or_default();
+        let shared = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .any(|(i, ws)| i != idx && slug(ws) == name);
+        let name = if shared { format!("{name}-{}", idx + 1) } else { name };
+        main.with_file_name(format!("{}-{name}.conf", conf_stem(main)))
+    }
+
+    /// The file that holds the `monitor=` rules Hyprland reads: `main`, or
+    /// the active workspace's own file with [`Self::per_workspace_files`].
+    pub fn monitor_rules_path(&self, main: &Path) -> PathBuf {
+        if self.per_workspace_files {
+            self.workspace_conf_path(main, self.active_workspace())
+        } else {
+            main.to_path_buf()
+        }
+    }
+
+    /// Where [`save`](Self::save) writes.
+    pub fn path(&self) -> PathBuf {
+        self.config_path.clone().unwrap_or_else(Self::config_path)
+    }
+
+    /// Persist to [`Self::path`], leaving out bundled workspaces no one changed.
     pub fn save(&self) -> Result<()> {
-        let path = self.config_path.clone().unwrap_or_else(Self::config_path);
-        let content = serde_json::to_string_pretty(&self)?;
+        let path = self.path();
+        let content = if self.bundled.is_empty() {
+            serde_json::to_string_pretty(&self)?
+        } else {
+            let mut own = self.clone();
+            own.workspaces = (0..self.workspaces.len())
+                .filter(|&i| !self.is_bundled(i))
+                .map(|i| self.workspaces[i].clone())
+                .collect();
+            serde_json::to_string_pretty(&own)?
+        };
         atomic_write(&path, &content)?;
         Ok(())
     }
@@ -128,47 +834,178 @@ impl MonitorDatabase {
     /// in the description only intermittently, so including it splits one physical
     /// panel across multiple entries. Hyprland matches `desc:` as a prefix, so the
     /// serial-free form still resolves to the connected monitor.
+    ///
+    /// Two identical models share that key, so once told apart (see
+    /// [`Self::split_key`]) they are keyed by serial or by connector instead.
     pub fn get_monitor_key(monitor: &MonitorConfig) -> String {
-        if monitor.name.starts_with("eDP") {
+        if monitor.name.starts_with("eDP") || monitor.key_by == KeyBy::Connector {
             return monitor.name.clone();
         }
         let identity = format!("{} {}", monitor.make.trim(), monitor.model.trim());
         let identity = identity.trim();
-        if identity.is_empty() {
+        let key = if identity.is_empty() {
             format!("desc:{}", monitor.description.trim())
         } else {
             format!("desc:{identity}")
+        };
+        match monitor.key_by {
+            KeyBy::Serial if !monitor.serial.is_empty() => format!("{key} {}", monitor.serial),
+            _ => key,
+        }
+    }
+
+    /// Set each monitor's [`KeyBy`] from the choices made for its description.
+    pub fn assign_keys(&self, monitors: &mut [MonitorConfig]) {
+        for monitor in monitors {
+            monitor.key_by = KeyBy::Description;
+            let shared = Self::get_monitor_key(monitor);
+            monitor.key_by = self.key_by.get(&shared).copied().unwrap_or_default();
+        }
+    }
+
+    /// From now on key the monitors sharing description key `shared` by `by`;
+    /// `keys` are their new keys. Settings saved under the shared key are
+    /// copied to each new key in every workspace, and an unmanaged shared key
+    /// stays unmanaged under the new ones.
+    pub fn split_key(&mut self, shared: &str, by: KeyBy, keys: &[String]) {
+        self.key_by.insert(shared.to_string(), by);
+        for ws in &mut self.workspaces {
+            if let Some(saved) = ws.monitors.remove(shared) {
+                for key in keys {
+                    ws.monitors.entry(key.clone()).or_insert_with(|| saved.clone());
+                }
+            }
+        }
+        if !self.is_managed(shared) {
+            self.unmanaged.retain(|k| k != shared);
+            self.unmanaged.extend(keys.iter().cloned());
+        }
+    }
+
+    /// Give workspaces saved without an id (or with a copied one) a fresh id,
+    /// and point `active_id` at a workspace, taking the pre-id index if any.
+    fn assign_ids(&mut self) {
+        let mut seen = HashSet::new();
+        for ws in &mut self.workspaces {
+            if ws.id.is_empty() || !seen.insert(ws.id.clone()) {
+                ws.id = new_workspace_id();
+                seen.insert(ws.id.clone());
+            }
+        }
+        if !self.workspaces.iter().any(|ws| ws.id == self.active_id) {
+            let idx = self.legacy_active.unwrap_or(0);
+            self.set_active(idx.min(self.workspaces.len() - 1));
+        }
+        self.legacy_active = None;
+    }
+
+    /// Index of the active workspace. It is tracked by id, so this follows
+    /// the profile as workspaces are reordered or deleted; an unknown id
+    /// falls back to the first workspace.
+    pub fn active_workspace(&self) -> usize {
+        self.workspaces
+            .iter()
+            .position(|ws| ws.id == self.active_id)
+            .unwrap_or(0)
+    }
+
+    /// Make workspace `idx` the active one. Out of range is ignored.
+    pub fn set_active(&mut self, idx: usize) {
+        if let Some(ws) = self.workspaces.get(idx) {
+            self.active_id = ws.id.clone();
         }
     }
 
     /// Get current active workspace
     pub fn current_workspace(&self) -> Option<&Workspace> {
-        self.workspaces.get(self.active_workspace)
+        self.workspaces.get(self.active_workspace())
     }
 
     /// Get current active workspace mutably
     pub fn current_workspace_mut(&mut self) -> Option<&mut Workspace> {
-        self.workspaces.get_mut(self.active_workspace)
+        let idx = self.active_workspace();
+        self.workspaces.get_mut(idx)
+    }
+
+    /// Which of `monitors` takes over as primary once the primary is gone:
+    /// the [`Self::primary_fallback`] if it is connected, else the laptop
+    /// panel, else the leftmost monitor.
+    pub fn primary_successor(&self, monitors: &[MonitorConfig]) -> Option<usize> {
+        let configured = self.primary_fallback.as_ref().and_then(|key| {
+            monitors
+                .iter()
+                .position(|m| m.name == *key || Self::get_monitor_key(m) == *key)
+        });
+        configured
+            .or_else(|| monitors.iter().position(|m| m.name.starts_with("eDP")))
+            .or_else(|| {
+                let at = |i: &usize| (monitors[*i].position_x, monitors[*i].position_y);
+                (0..monitors.len()).min_by_key(at)
+            })
     }
 
     /// Update current workspace with monitor config
     pub fn update_monitor(&mut self, monitor: &MonitorConfig) {
+        self.update_monitor_in(self.active_workspace(), monitor);
+    }
+
+    /// Upsert `monitor` into workspace `ws_idx` (which need not be active)
+    pub fn update_monitor_in(&mut self, ws_idx: usize, monitor: &MonitorConfig) {
         let key = Self::get_monitor_key(monitor);
-        let saved = SavedMonitor {
-            resolution: monitor.resolution.clone(),
-            refresh_rate: monitor.refresh_rate,
-            scale: monitor.scale,
-            rotation: monitor.rotation.transform(),
-            position_x: monitor.position_x,
-            position_y: monitor.position_y,
-            is_primary: monitor.is_primary,
-        };
+        if !self.is_managed(&key) {
+            return;
+        }
+        let saved = SavedMonitor::from_monitor(monitor);
 
-        if let Some(ws) = self.current_workspace_mut() {
+        if let Some(ws) = self.workspaces.get_mut(ws_idx) {
             ws.monitors.insert(key, saved);
         }
     }
 
+    pub fn is_managed(&self, key: &str) -> bool {
+        !self.unmanaged.iter().any(|k| k == key)
+    }
+
+    /// Hand monitor `key` over to (or take it back from) manual config.
+    /// Returns whether it is managed afterwards.
+    pub fn toggle_managed(&mut self, key: &str) -> bool {
+        if self.is_managed(key) {
+            self.unmanaged.push(key.to_string());
+            false
+        } else {
+            self.unmanaged.retain(|k| k != key);
+            true
+        }
+    }
+
+    /// `monitor`'s nickname, if it has one. Saved monitors carry their key
+    /// as the name, so that is tried too.
+    pub fn nickname(&self, monitor: &MonitorConfig) -> Option<&str> {
+        let by_key = self.nicknames.get(&Self::get_monitor_key(monitor));
+        by_key.or_else(|| self.nicknames.get(&monitor.name)).map(String::as_str)
+    }
+
+    /// What to call `monitor` on screen: its nickname, else the model.
+    pub fn label(&self, monitor: &MonitorConfig) -> String {
+        match self.nickname(monitor) {
+            Some(nickname) => nickname.to_string(),
+            None => monitor.display_name(),
+        }
+    }
+
+    /// Name monitor `key`; a blank `nickname` removes its name. Returns the
+    /// nickname it has afterwards.
+    pub fn set_nickname(&mut self, key: &str, nickname: &str) -> Option<&str> {
+        let nickname = nickname.trim();
+        if nickname.is_empty() {
+            self.nicknames.remove(key);
+            return None;
+        }
+        let nickname = text::take_graphemes(nickname, MAX_NICKNAME).trim_end().to_string();
+        self.nicknames.insert(key.to_string(), nickname);
+        self.nicknames.get(key).map(String::as_str)
+    }
+
     /// Get saved config for a monitor from current workspace
     pub fn get_saved_config(&self, monitor: &MonitorConfig) -> Option<&SavedMonitor> {
         let key = Self::get_monitor_key(monitor);
@@ -177,7 +1014,21 @@ impl MonitorDatabase {
 
     /// Apply saved config to a monitor
     pub fn apply_saved_config(&self, monitor: &mut MonitorConfig) -> bool {
-        if let Some(saved) = self.get_saved_config(monitor) {
+        self.apply_saved_config_from(self.active_workspace(), monitor)
+    }
+
+    /// Apply the config saved for `monitor` in workspace `ws_idx`, which need not
+    /// be the active one.
+    pub fn apply_saved_config_from(&self, ws_idx: usize, monitor: &mut MonitorConfig) -> bool {
+        let key = Self::get_monitor_key(monitor);
+        if !self.is_managed(&key) {
+            return false;
+        }
+        let saved = self
+            .workspaces
+            .get(ws_idx)
+            .and_then(|ws| ws.monitors.get(&key));
+        if let Some(saved) = saved {
             monitor.resolution = saved.resolution.clone();
             monitor.refresh_rate = saved.refresh_rate;
             monitor.scale = saved.scale;
@@ -185,55 +1036,246 @@ impl MonitorDatabase {
             monitor.position_x = saved.position_x;
             monitor.position_y = saved.position_y;
             monitor.is_primary = saved.is_primary;
+            monitor.mode_preset = saved.mode_preset;
             true
         } else {
             false
         }
     }
 
-    /// Find best matching workspace for connected monitors
-    pub fn find_best_workspace(&self, connected: &[MonitorConfig]) -> Option<usize> {
-        let mut best_idx = None;
-        let mut best_score = 0;
+    /// Find best matching workspace for connected monitors at `minute` past
+    /// local midnight.
+    ///
+    /// Workspaces whose [`MatchRules`] reject the connected set, or whose
+    /// schedule excludes `minute`, are skipped. A workspace inside its schedule
+    /// window outranks unscheduled ones; after that the highest priority wins,
+    /// then the most matches. Ties go to the earliest workspace.
+    pub fn find_best_workspace_at(
+        &self,
+        connected: &[MonitorConfig],
+        minute: u16,
+    ) -> Option<usize> {
+        let mut best: Option<(usize, (bool, i32, usize))> = None;
 
         for (idx, ws) in self.workspaces.iter().enumerate() {
-            let score = ws.matches_monitors(connected);
-            if score > best_score {
-                best_score = score;
-                best_idx = Some(idx);
+            if ws.manual_only {
+                continue;
+            }
+            let scheduled = match ws.scheduled_at(minute) {
+                Some(false) => continue,
+                in_window => in_window.is_some(),
+            };
+            let Some((priority, hits)) = ws.match_score(connected) else {
+                continue;
+            };
+            let score = (scheduled, priority, hits);
+            if best.is_none_or(|(_, b)| score > b) {
+                best = Some((idx, score));
             }
         }
 
-        if best_score > 0 {
-            best_idx
-        } else {
-            None
+        best.map(|(idx, _)| idx)
+    }
+
+    /// Which workspaces' schedule windows are open at `minute`; a change in
+    /// this tells the daemon a schedule boundary was crossed.
+    pub fn schedule_state(&self, minute: u16) -> Vec<Option<bool>> {
+        self.workspaces.iter().map(|ws| ws.scheduled_at(minute)).collect()
+    }
+
+    /// Whether automatic matching must leave the active workspace alone: the
+    /// user locked it, or it is a manual-only workspace they applied on purpose.
+    pub fn holds_active(&self) -> bool {
+        self.lock_active || self.current_workspace().is_some_and(|ws| ws.manual_only)
+    }
+
+    /// Switch `active_workspace` to the best match for `connected` unless it is
+    /// held (see [`holds_active`](Self::holds_active)). Returns whether it changed.
+    pub fn auto_select(&mut self, connected: &[MonitorConfig]) -> bool {
+        self.auto_select_at(connected, clock::local_minute_of_day(clock::now()))
+    }
+
+    /// [`auto_select`](Self::auto_select) for an explicit time of day.
+    pub fn auto_select_at(&mut self, connected: &[MonitorConfig], minute: u16) -> bool {
+        if self.holds_active() {
+            return false;
+        }
+        match self.find_best_workspace_at(connected, minute) {
+            Some(idx) if idx != self.active_workspace() => {
+                self.set_active(idx);
+                true
+            }
+            _ => false,
         }
     }
 
-    /// Add a new workspace
-    pub fn add_workspace(&mut self, name: &str) -> usize {
-        self.workspaces.push(Workspace::new(name));
-        self.workspaces.len() - 1
+    /// `name` trimmed, if it may name workspace `idx` (`None` for a new one):
+    /// not blank and not taken by another workspace, since the CLI picks
+    /// workspaces by name.
+    pub fn check_workspace_name(&self, name: &str, idx: Option<usize>) -> Result<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("name cannot be empty");
+        }
+        let taken = self.workspaces.iter().enumerate().any(|(i, ws)| {
+            Some(i) != idx && ws.name == name
+        });
+        if taken {
+            anyhow::bail!("a workspace named '{name}' already exists");
+        }
+        Ok(name.to_string())
+    }
+
+    /// Add a new workspace under a name no other workspace has.
+    pub fn add_workspace(&mut self, name: &str) -> Result<usize> {
+        let name = self.check_workspace_name(name, None)?;
+        self.workspaces.push(Workspace::new(&name));
+        Ok(self.workspaces.len() - 1)
+    }
+
+    /// Clone workspace `idx` (all monitor entries) under `name`, appending it.
+    /// Returns the new index; fails when `idx` is out of range or `name` is
+    /// blank or taken.
+    pub fn duplicate_workspace(&mut self, idx: usize, name: &str) -> Result<usize> {
+        let name = self.check_workspace_name(name, None)?;
+        let mut copy = self
+            .workspaces
+            .get(idx)
+            .ok_or_else(|| anyhow::anyhow!("no workspace at index {idx}"))?
+            .clone();
+        copy.id = new_workspace_id();
+        copy.name = name;
+        copy.created_at = Some(clock::now());
+        copy.last_applied = None;
+        copy.apply_count = 0;
+        self.workspaces.push(copy);
+        Ok(self.workspaces.len() - 1)
+    }
+
+    /// Index of the workspace with id `name`, else the first one called `name`.
+    pub fn find_workspace(&self, name: &str) -> Option<usize> {
+        let by_id = self.workspaces.iter().position(|ws| ws.id == name);
+        by_id.or_else(|| self.workspaces.iter().position(|ws| ws.name == name))
+    }
+
+    /// Move workspace `from` to position `to`. The active workspace is kept
+    /// by id, so it stays the same profile.
+    fn reorder_workspace(&mut self, from: usize, to: usize) {
+        let ws = self.workspaces.remove(from);
+        self.workspaces.insert(to, ws);
+    }
+
+    /// Number of pinned workspaces; they always occupy the leading slots.
+    fn pinned_count(&self) -> usize {
+        self.workspaces.iter().take_while(|ws| ws.pinned).count()
     }
 
-    /// Delete workspace at index
+    /// Shift workspace `idx` one slot left (`-1`) or right (`+1`) within its
+    /// pinned/unpinned group. Returns its new index.
+    pub fn move_workspace(&mut self, idx: usize, delta: isize) -> usize {
+        self.move_workspace_to(idx, idx.saturating_add_signed(delta))
+    }
+
+    /// Move workspace `idx` to slot `to`, or as near as its pinned/unpinned
+    /// group allows. Returns its new index.
+    pub fn move_workspace_to(&mut self, idx: usize, to: usize) -> usize {
+        let Some(ws) = self.workspaces.get(idx) else {
+            return idx;
+        };
+        let pinned = self.pinned_count();
+        let (lo, hi) = if ws.pinned {
+            (0, pinned)
+        } else {
+            (pinned, self.workspaces.len())
+        };
+        let target = to.clamp(lo, hi.saturating_sub(1).max(lo));
+        self.reorder_workspace(idx, target);
+        target
+    }
+
+    /// Pin (move to the end of the pinned group) or unpin (move to the start
+    /// of the unpinned group) workspace `idx`. Returns its new index.
+    pub fn toggle_pin(&mut self, idx: usize) -> usize {
+        let Some(ws) = self.workspaces.get_mut(idx) else {
+            return idx;
+        };
+        ws.pinned = !ws.pinned;
+        // With `idx` taken out, the other pinned workspaces fill 0..boundary,
+        // so `boundary` is both the end of the pinned group and the start of
+        // the unpinned one.
+        let boundary = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .filter(|(i, w)| *i != idx && w.pinned)
+            .count();
+        self.reorder_workspace(idx, boundary);
+        boundary
+    }
+
+    /// Delete workspace at index. Unchanged bundled workspaces would be back
+    /// on the next load, so they stay.
     pub fn delete_workspace(&mut self, idx: usize) -> bool {
-        if self.workspaces.len() <= 1 || idx >= self.workspaces.len() {
+        let len = self.workspaces.len();
+        if len <= 1 || idx >= len || self.is_bundled(idx) {
             return false;
         }
+        let was_active = idx == self.active_workspace();
         self.workspaces.remove(idx);
-        if self.active_workspace >= self.workspaces.len() {
-            self.active_workspace = self.workspaces.len() - 1;
+        if was_active {
+            self.set_active(idx.min(self.workspaces.len() - 1));
         }
         true
     }
 
-    /// Rename workspace
-    pub fn rename_workspace(&mut self, idx: usize, name: &str) {
+    /// Drop the saved entry `key` from workspace `ws_idx`. Returns whether
+    /// anything was removed.
+    pub fn remove_monitor(&mut self, ws_idx: usize, key: &str) -> bool {
+        self.workspaces
+            .get_mut(ws_idx)
+            .is_some_and(|ws| ws.monitors.remove(key).is_some())
+    }
+
+    /// Copy the saved entry `key` of workspace `from` into workspace `to`,
+    /// replacing whatever `to` had under that key. The copy goes right of
+    /// the other monitors if its saved spot overlaps one of them, and is not
+    /// primary if `to` already has a primary. Returns whether it was copied.
+    pub fn import_monitor(&mut self, from: usize, key: &str, to: usize) -> bool {
+        let source = self.workspaces.get(from).and_then(|ws| ws.monitors.get(key));
+        let (Some(mut saved), Some(target), true) =
+            (source.cloned(), self.workspaces.get(to), from != to)
+        else {
+            return false;
+        };
+        let others: Vec<&SavedMonitor> =
+            target.monitors.iter().filter(|(k, _)| *k != key).map(|(_, m)| m).collect();
+        let rect = |m: &SavedMonitor| {
+            let (w, h) = monitor_logical_size(m);
+            (m.position_x, m.position_y, m.position_x + w, m.position_y + h)
+        };
+        let (x0, y0, x1, y1) = rect(&saved);
+        let overlaps = others.iter().any(|m| {
+            let (ox0, oy0, ox1, oy1) = rect(m);
+            x0 < ox1 && ox0 < x1 && y0 < oy1 && oy0 < y1
+        });
+        if overlaps {
+            saved.position_x = others.iter().map(|m| rect(m).2).max().unwrap_or(0);
+            saved.position_y = 0;
+        }
+        if others.iter().any(|m| m.is_primary) {
+            saved.is_primary = false;
+        }
+        self.workspaces[to].monitors.insert(key.to_string(), saved);
+        true
+    }
+
+    /// Rename workspace, keeping names unique
+    pub fn rename_workspace(&mut self, idx: usize, name: &str) -> Result<()> {
+        let name = self.check_workspace_name(name, Some(idx))?;
         if let Some(ws) = self.workspaces.get_mut(idx) {
-            ws.name = name.to_string();
+            ws.name = name;
         }
+        Ok(())
     }
 
     /// Generate the managed monitor block (monitor= rules + fallback) from ALL
@@ -244,15 +1286,26 @@ impl MonitorDatabase {
     /// `connected` holds the stable keys (see [`Self::get_monitor_key`]) of the
     /// monitors physically present right now. Only these are packed into a
     /// gap-free row; an empty set means "treat every saved monitor as connected"
-    /// (used by tests and as a safe fallback).
+    /// (used by tests and as a safe fallback). The rest are written as
+    /// [`Self::disconnected`] says.
     pub fn generate_full_config(&self, connected: &HashSet<String>) -> String {
         let mut config = String::new();
 
-        // Collect all unique monitors across all workspaces (later workspaces win).
+        // Collect all unique monitors across all workspaces. The active
+        // workspace is merged last so its settings win for any monitor it
+        // shares with other profiles; among the rest, later workspaces win.
         let mut merged: HashMap<String, SavedMonitor> = HashMap::new();
-        for ws in &self.workspaces {
+        let others = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != self.active_workspace())
+            .map(|(_, ws)| ws);
+        for ws in others.chain(self.current_workspace()) {
             for (key, saved) in &ws.monitors {
-                merged.insert(key.clone(), saved.clone());
+                if self.is_managed(key) {
+                    merged.insert(key.clone(), saved.clone());
+                }
             }
         }
 
@@ -285,38 +1338,57 @@ impl MonitorDatabase {
         // entry is retained so a later hotplug still finds its saved res/scale.
         // Logical width is scale- and rotation-aware, so fractional scales pack
         // tightly with no fractional-pixel gap.
+        //
+        // A stored layout that already tiles is kept as is, so positions typed
+        // into the settings panel (a monitor stacked above another, say)
+        // survive; only one with an overlap or a gap is packed.
         let treat_all_connected = connected.is_empty();
-        let mut running_x = 0i32;
-        for (key, saved) in all_monitors.iter_mut() {
-            if treat_all_connected || connected.contains(key) {
-                saved.position_x = running_x;
-                running_x = running_x.saturating_add(monitor_logical_width(saved));
+        let placed: Vec<SavedMonitor> = all_monitors
+            .iter()
+            .filter(|(key, _)| treat_all_connected || connected.contains(key))
+            .map(|(_, saved)| saved.clone())
+            .collect();
+        if !tiles(&placed) {
+            let mut running_x = 0i32;
+            for (key, saved) in all_monitors.iter_mut() {
+                if treat_all_connected || connected.contains(key) {
+                    saved.position_x = running_x;
+                    running_x = running_x.saturating_add(monitor_logical_width(saved));
+                }
             }
         }
 
         for (key, saved) in &all_monitors {
+            if !treat_all_connected && !connected.contains(key) {
+                match self.disconnected {
+                    Disconnected::Keep => {}
+                    Disconnected::Comment => config.push_str("# "),
+                    Disconnected::Disable => {
+                        config.push_str(&format!("monitor={key},disable\n"));
+                        continue;
+                    }
+                }
+            }
             let transform = saved.rotation;
-            let scale = if saved.scale.fract() == 0.0 {
-                format!("{}", saved.scale as i32)
-            } else {
-                format!("{:.2}", saved.scale)
+            let scale = format_scale(saved.scale);
+            let mode = match saved.mode_preset {
+                Some(preset) => preset.keyword().to_string(),
+                None => format!("{}@{:.2}", saved.resolution, saved.refresh_rate),
             };
             if transform == 0 {
                 config.push_str(&format!(
-                    "monitor={},{}@{:.2},{}x{},{}\n",
+                    "monitor={},{},{}x{},{}\n",
                     key,
-                    saved.resolution,
-                    saved.refresh_rate,
+                    mode,
                     saved.position_x,
                     saved.position_y,
                     scale
                 ));
             } else {
                 config.push_str(&format!(
-                    "monitor={},{}@{:.2},{}x{},{},transform,{}\n",
+                    "monitor={},{},{}x{},{},transform,{}\n",
                     key,
-                    saved.resolution,
-                    saved.refresh_rate,
+                    mode,
                     saved.position_x,
                     saved.position_y,
                     scale,
@@ -325,8 +1397,70 @@ impl MonitorDatabase {
             }
         }
 
-        config.push_str("\n# Fallback for unknown monitors\nmonitor=,preferred,auto,1\n");
-        config
+        if let Some(rule) = &self.fallback {
+            config.push_str(&format!("\n# Fallback for unknown monitors\nmonitor=,{}\n", rule));
+        }
+        config
+    }
+
+    /// `monitors.conf` as hyprmon would write it into an empty file: the
+    /// generated block between its markers.
+    pub fn hypr_config_text(&self, connected: &HashSet<String>) -> String {
+        splice_managed_block("", &self.generate_full_config(connected))
+    }
+
+    /// The rule [`Self::generate_full_config`] writes for `key`, without the
+    /// `monitor=` in front, as `hyprctl keyword monitor` takes it.
+    pub fn monitor_rule(&self, key: &str, connected: &HashSet<String>) -> Option<String> {
+        let line = format!("monitor={key},");
+        self.generate_full_config(connected)
+            .lines()
+            .find(|l| l.starts_with(&line))
+            .map(|l| l["monitor=".len()..].to_string())
+    }
+
+    /// The rules of [`Self::generate_full_config`] in the order the active
+    /// workspace's [`ApplySteps`] sets them, without `monitor=`; none when it
+    /// is applied all at once. Of the monitors not connected only `disable`
+    /// rules are kept: Hyprland lists a disabled panel as gone too.
+    pub fn step_rules(&self, connected: &HashSet<String>) -> Vec<String> {
+        let Some(steps) = self.current_workspace().map(|ws| &ws.apply_steps) else {
+            return Vec::new();
+        };
+        if steps.is_empty() {
+            return Vec::new();
+        }
+        let config = self.generate_full_config(connected);
+        let mut rules: Vec<(String, &str)> = config
+            .lines()
+            .filter_map(|line| line.strip_prefix("monitor="))
+            .filter_map(|rule| Some((rule.split_once(',')?.0.to_string(), rule)))
+            .filter(|(key, rule)| {
+                let set = connected.is_empty() || connected.contains(key);
+                !key.is_empty() && (set || rule.ends_with(",disable"))
+            })
+            .collect();
+        let rank = |(key, rule): &(String, &str)| match steps.order.iter().position(|k| k == key) {
+            Some(pos) => (0, pos),
+            None => (1 + usize::from(rule.ends_with(",disable")), 0),
+        };
+        rules.sort_by_key(rank);
+        rules.into_iter().map(|(_, rule)| rule.to_string()).collect()
+    }
+
+    /// DPMS for the connected `monitors` once the active workspace is
+    /// applied, as (connector, on): off for those it keeps dark, on for those
+    /// another workspace does, so switching away wakes them. Monitors no
+    /// workspace turns off are left as they are.
+    pub fn dpms_plan(&self, monitors: &[MonitorConfig]) -> Vec<(String, bool)> {
+        let off = self.current_workspace().map(|ws| ws.dpms_off.as_slice()).unwrap_or_default();
+        let any: HashSet<&String> = self.workspaces.iter().flat_map(|ws| &ws.dpms_off).collect();
+        monitors
+            .iter()
+            .map(|m| (m.name.clone(), Self::get_monitor_key(m)))
+            .filter(|(_, key)| any.contains(key))
+            .map(|(name, key)| (name, !off.contains(&key)))
+            .collect()
     }
 
     /// Get monitors from a specific workspace as MonitorConfig
@@ -335,9 +1469,15 @@ impl MonitorDatabase {
             return vec![];
         };
 
-        ws.monitors
+        let mut monitors: Vec<MonitorConfig> = ws
+            .monitors
             .iter()
             .map(|(key, saved)| {
+                let key_by = if key.starts_with("desc:") {
+                    KeyBy::Description
+                } else {
+                    KeyBy::Connector
+                };
                 let (name, description, make, model) = if key.starts_with("desc:") {
                     let desc = key.strip_prefix("desc:").unwrap_or(key).to_string();
                     let parts: Vec<&str> = desc.rsplitn(2, ' ').collect();
@@ -364,9 +1504,17 @@ impl MonitorDatabase {
                         "{}@{:.0}Hz",
                         saved.resolution, saved.refresh_rate
                     )],
+                    serial: String::new(),
+                    key_by,
+                    mode_preset: saved.mode_preset,
                 }
             })
-            .collect()
+            .collect();
+        // The map has no order; show them left to right like the live layout
+        monitors.sort_by(|a, b| {
+            (a.position_x, a.position_y, &a.name).cmp(&(b.position_x, b.position_y, &b.name))
+        });
+        monitors
     }
 }
 
@@ -374,6 +1522,45 @@ impl MonitorDatabase {
 /// `rename(2)` it over the target. rename is atomic within a filesystem, so a
 /// crash or power loss mid-write can never leave a truncated/corrupt JSON file —
 /// the old file stays intact until the new one is fully written.
+/// One file operation of writing the Hyprland config; see
+/// [`MonitorDatabase::hypr_config_changes`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileChange {
+    /// Copy the previous file aside before it is replaced.
+    Backup { from: PathBuf, to: PathBuf },
+    /// Keep a timestamped copy of the file for `hyprmon rollback`.
+    Archive(PathBuf),
+    Write { path: PathBuf, content: String },
+    /// A per-workspace file no workspace uses any more.
+    Remove(PathBuf),
+}
+
+impl FileChange {
+    /// The copies kept of `path` before it is replaced: the `.conf.bak` and
+    /// the archived one. None when there is no file yet.
+    pub fn backups_of(path: &Path) -> Vec<Self> {
+        if !path.exists() {
+            return Vec::new();
+        }
+        let backup = path.with_extension("conf.bak");
+        vec![
+            Self::Backup { from: path.to_path_buf(), to: backup },
+            Self::Archive(path.to_path_buf()),
+        ]
+    }
+
+    pub fn make(&self) -> Result<()> {
+        match self {
+            Self::Backup { from, to } => drop(fs::copy(from, to)?),
+            Self::Archive(path) => crate::backups::archive(path)?,
+            Self::Write { path, content } => fs::write(path, content)?,
+            // Already gone is just as good
+            Self::Remove(path) => drop(fs::remove_file(path)),
+        }
+        Ok(())
+    }
+}
+
 fn atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
     let tmp = path.with_extension("json.tmp");
     fs::write(&tmp, content)?;
@@ -406,6 +1593,117 @@ pub fn splice_managed_block(existing: &str, block: &str) -> String {
     managed
 }
 
+/// `monitors` for `monitors.conf`.
+fn conf_stem(path: &Path) -> String {
+    path.file_stem().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// `Home office` → `home-office`, for file names.
+fn file_slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    match slug.trim_end_matches('-') {
+        "" => "workspace".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+/// Longest workspace name the TUI input accepts, in graphemes.
+pub const MAX_WORKSPACE_NAME: usize = 20;
+
+/// Longest monitor nickname, in graphemes.
+pub const MAX_NICKNAME: usize = 24;
+
+/// Candidate names for a new workspace built from the connected monitors,
+/// most descriptive first, e.g. `eDP-1 + DELL U2720Q`, `Laptop + U2720Q`,
+/// `2 monitors`. Names already taken get a numeric suffix.
+pub fn suggest_workspace_names(connected: &[MonitorConfig], existing: &[String]) -> Vec<String> {
+    let vendor = |m: &MonitorConfig| {
+        m.make
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase()
+    };
+    let full: Vec<String> = connected
+        .iter()
+        .map(|m| {
+            if m.name.starts_with("eDP") {
+                m.name.clone()
+            } else {
+                format!("{} {}", vendor(m), m.model.trim()).trim().to_string()
+            }
+        })
+        .collect();
+    let short: Vec<String> = connected
+        .iter()
+        .map(|m| {
+            if m.name.starts_with("eDP") {
+                "Laptop".to_string()
+            } else {
+                m.model.trim().to_string()
+            }
+        })
+        .collect();
+
+    let mut candidates = vec![full.join(" + "), short.join(" + ")];
+    match short.as_slice() {
+        [] => candidates.push("Workspace".to_string()),
+        [only] => candidates.push(format!("{only} only")),
+        _ => candidates.push(format!("{} monitors", short.len())),
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    for candidate in candidates {
+        let base = text::take_graphemes(&candidate, MAX_WORKSPACE_NAME).trim().to_string();
+        if base.is_empty() {
+            continue;
+        }
+        let mut name = base.clone();
+        let mut n = 2;
+        while existing.contains(&name) || out.contains(&name) {
+            let suffix = format!(" {n}");
+            let keep = MAX_WORKSPACE_NAME - suffix.len();
+            name = format!("{}{}", text::take_graphemes(&base, keep).trim_end(), suffix);
+            n += 1;
+        }
+        out.push(name);
+    }
+    out
+}
+
+/// Shell-style glob supporting `*` (any run) and `?` (any one char),
+/// case-insensitive since EDID vendor strings vary in case.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            pi = star_p + 1;
+            ti = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
 /// Returns true when `short` is a `desc:` key that is a space-delimited prefix of
 /// `long` — i.e. they identify the same panel, with `long` carrying an extra
 /// serial token Hyprland sometimes appends.
@@ -435,10 +1733,10 @@ fn dedup_prefix_keys(ws: &mut Workspace) {
     }
 }
 
-/// Logical horizontal footprint of a monitor in the Hyprland layout, accounting
-/// for scale and rotation. Portrait orientations (transform 1/3 = 90°/270°) swap
-/// the panel's width and height, so the laid-out width becomes the panel height.
-fn monitor_logical_width(saved: &SavedMonitor) -> i32 {
+/// Logical footprint of a monitor in the Hyprland layout, accounting for scale
+/// and rotation. Portrait orientations (transform 1/3 = 90°/270°) swap the
+/// panel's width and height.
+fn monitor_logical_size(saved: &SavedMonitor) -> (i32, i32) {
     let (w, h) = saved
         .resolution
         .trim()
@@ -446,14 +1744,56 @@ fn monitor_logical_width(saved: &SavedMonitor) -> i32 {
         .and_then(|(w, h)| Some((w.trim().parse::<i32>().ok()?, h.trim().parse::<i32>().ok()?)))
         .unwrap_or((0, 0));
 
-    let footprint = if saved.rotation == 1 || saved.rotation == 3 {
-        h
+    let (w, h) = if saved.rotation == 1 || saved.rotation == 3 {
+        (h, w)
     } else {
-        w
+        (w, h)
     };
 
     let scale = if saved.scale > 0.0 { saved.scale } else { 1.0 };
-    ((footprint as f64) / scale).round() as i32
+    (((w as f64) / scale).round() as i32, ((h as f64) / scale).round() as i32)
+}
+
+/// Logical horizontal footprint of a monitor, what the pack pass advances by.
+fn monitor_logical_width(saved: &SavedMonitor) -> i32 {
+    monitor_logical_size(saved).0
+}
+
+/// Whether `monitors` form one gap-free layout: no two overlap and every one
+/// shares an edge with the rest, so the cursor can cross between all of them.
+pub fn tiles(monitors: &[SavedMonitor]) -> bool {
+    let rects: Vec<(i32, i32, i32, i32)> = monitors
+        .iter()
+        .map(|m| {
+            let (w, h) = monitor_logical_size(m);
+            (m.position_x, m.position_y, w, h)
+        })
+        .collect();
+    let spans_meet = |a: i32, a_len: i32, b: i32, b_len: i32| a < b + b_len && b < a + a_len;
+    let overlap = |a: &(i32, i32, i32, i32), b: &(i32, i32, i32, i32)| {
+        spans_meet(a.0, a.2, b.0, b.2) && spans_meet(a.1, a.3, b.1, b.3)
+    };
+    let touch = |a: &(i32, i32, i32, i32), b: &(i32, i32, i32, i32)| {
+        let side = a.0 + a.2 == b.0 || b.0 + b.2 == a.0;
+        let stacked = a.1 + a.3 == b.1 || b.1 + b.3 == a.1;
+        (side && spans_meet(a.1, a.3, b.1, b.3)) || (stacked && spans_meet(a.0, a.2, b.0, b.2))
+    };
+    for (i, a) in rects.iter().enumerate() {
+        if rects[i + 1..].iter().any(|b| overlap(a, b)) {
+            return false;
+        }
+    }
+    // Flood out from the first monitor across shared edges
+    let mut reached = vec![false; rects.len()];
+    let mut todo = vec![0];
+    while let Some(i) = todo.pop() {
+        if i >= rects.len() || reached[i] {
+            continue;
+        }
+        reached[i] = true;
+        todo.extend((0..rects.len()).filter(|&j| !reached[j] && touch(&rects[i], &rects[j])));
+    }
+    reached.into_iter().all(|r| r)
 }
 
 #[cfg(test)]
@@ -461,6 +1801,8 @@ mod tests {
     use super::*;
     use std::collections::HashSet;
 
+    const NOON: u16 = 12 * 60;
+
     fn saved(res: &str, scale: f64, x: i32) -> SavedMonitor {
         SavedMonitor {
             resolution: res.into(),
@@ -470,6 +1812,7 @@ mod tests {
             position_x: x,
             position_y: 0,
             is_primary: false,
+            mode_preset: None,
         }
     }
 
@@ -479,9 +1822,20 @@ mod tests {
             ws.monitors.insert(key.to_string(), m);
         }
         MonitorDatabase {
+            active_id: ws.id.clone(),
+            legacy_active: None,
             workspaces: vec![ws],
-            active_workspace: 0,
+            lock_active: false,
+            unmanaged: Vec::new(),
+            fallback: default_fallback(),
+            disconnected: Disconnected::Keep,
+            primary_fallback: None,
+            per_workspace_files: false,
+            key_by: BTreeMap::new(),
+            nicknames: BTreeMap::new(),
             config_path: None,
+            bundled: Vec::new(),
+            bundle_errors: Vec::new(),
         }
     }
 
@@ -580,6 +1934,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keeps_a_stacked_layout_that_already_tiles() {
+        // MSI sits above the laptop, centred: touching, no overlap, no gap.
+        let mut top = saved("2560x1440", 1.0, -320);
+        top.position_y = -1440;
+        let db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0)), ("desc:MSI", top)]);
+        let conf = db.generate_full_config(&HashSet::new());
+        assert!(conf.contains("eDP-1,1920x1080@60.00,0x0,1"), "conf:\n{conf}");
+        assert!(conf.contains("desc:MSI,2560x1440@60.00,-320x-1440,1"), "conf:\n{conf}");
+
+        // Only touching at a corner leaves no shared edge -> packed as a row
+        let mut corner = saved("2560x1440", 1.0, 1920);
+        corner.position_y = -1440;
+        assert!(!tiles(&[saved("1920x1080", 1.0, 0), corner]));
+    }
+
+    #[test]
+    fn parses_positions_within_range() {
+        assert_eq!(parse_position(" -1080 ").unwrap(), -1080);
+        assert!(parse_position("12.5").is_err());
+        assert!(parse_position("").is_err());
+        assert!(parse_position("40000").is_err());
+    }
+
+    #[test]
+    fn parses_custom_scales_and_resolutions() {
+        assert_eq!(parse_scale("133%").unwrap(), 1.33);
+        assert_eq!(parse_scale(" 150 ").unwrap(), 1.5);
+        assert!(parse_scale("1.5x").is_err());
+        assert!(parse_scale("10").is_err());
+        assert_eq!(parse_resolution("2560X1080").unwrap(), "2560x1080");
+        assert!(parse_resolution("2560").is_err());
+        assert!(parse_resolution("0x1080").is_err());
+    }
+
+    #[test]
+    fn names_aspect_ratios_and_spots_exotic_modes() {
+        assert_eq!(aspect_ratio("1920x1080"), Some("16:9"));
+        assert_eq!(aspect_ratio("1366x768"), Some("16:9"));
+        assert_eq!(aspect_ratio("2560x1600"), Some("16:10"));
+        assert_eq!(aspect_ratio("2560x1080"), Some("21:9"));
+        assert_eq!(aspect_ratio("3440x1440"), Some("21:9"));
+        assert_eq!(aspect_ratio("5120x1440"), Some("32:9"));
+        assert_eq!(aspect_ratio("720x400"), None);
+        assert_eq!(aspect_ratio("highres"), None);
+        assert!(is_exotic_resolution("800x600") && is_exotic_resolution("1720x1440"));
+        assert!(!is_exotic_resolution("1280x1024") && !is_exotic_resolution("highres"));
+    }
+
+    #[test]
+    fn scales_are_fixed_to_what_hyprland_accepts() {
+        assert_eq!(fixed_scale("1920x1080", 1.5), None);
+        assert_eq!(fixed_scale("2560x1440", 1.25), None);
+        assert_eq!(fixed_scale("2560x1440", 1.5), Some(1.6));
+        // Nearer multiples of 1/120 win, the larger one on a tie
+        let framework = fixed_scale("2256x1504", 1.5).unwrap();
+        assert_eq!(format_scale(framework), "1.566667");
+        assert_eq!(fixed_scale("2880x1800", 1.75), Some(1.8));
+        assert_eq!(fixed_scale("garbage", 1.5), None);
+        assert_eq!(format_scale(2.0), "2");
+        assert_eq!(format_scale(1.25), "1.25");
+        assert_eq!(format_scale(1.5), "1.50");
+    }
+
     fn monitor(name: &str, make: &str, model: &str, desc: &str) -> MonitorConfig {
         MonitorConfig {
             name: name.into(),
@@ -594,6 +2012,9 @@ mod tests {
             rotation: Rotation::Normal,
             is_primary: false,
             available_modes: vec![],
+            serial: String::new(),
+            key_by: KeyBy::Description,
+            mode_preset: None,
         }
     }
 
@@ -679,6 +2100,52 @@ mod tests {
         p
     }
 
+    #[test]
+    fn per_workspace_files_are_sourced_and_pruned() {
+        let main = temp_path("split").with_extension("conf");
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.5, 0))]);
+        db.per_workspace_files = true;
+        db.add_workspace("Home office").unwrap();
+        db.workspaces[1].monitors.insert("eDP-1".into(), saved("1920x1080", 2.0, 0));
+        db.set_active(1);
+        db.write_hypr_config(&main, &HashSet::new()).unwrap();
+
+        let (default, home) = (db.workspace_conf_path(&main, 0), db.workspace_conf_path(&main, 1));
+        assert!(home.to_string_lossy().ends_with("-home-office.conf"), "{}", home.display());
+        let main_text = fs::read_to_string(&main).unwrap();
+        assert!(main_text.contains(&format!("source = {}\n", home.display())), "{main_text}");
+        assert!(!main_text.contains("monitor="));
+        assert!(fs::read_to_string(&default).unwrap().contains("eDP-1,1920x1080@60.00,0x0,1.50"));
+        assert!(fs::read_to_string(&home).unwrap().contains("eDP-1,1920x1080@60.00,0x0,2\n"));
+        assert_eq!(db.monitor_rules_path(&main), home);
+
+        // Renamed: the old file goes, one not written by hyprmon stays
+        let mine = main.with_file_name(format!("{}-mine.conf", conf_stem(&main)));
+        fs::write(&mine, "monitor=,preferred,auto,1\n").unwrap();
+        db.workspaces[1].name = "Desk".into();
+        db.write_hypr_config(&main, &HashSet::new()).unwrap();
+        assert!(!home.exists() && mine.exists());
+        assert!(db.workspace_conf_path(&main, 1).exists());
+
+        for file in [main.clone(), main.with_extension("conf.bak"), mine, default] {
+            let _ = fs::remove_file(file);
+        }
+        let _ = fs::remove_file(db.workspace_conf_path(&main, 1));
+    }
+
+    #[test]
+    fn workspace_files_get_distinct_names() {
+        assert_eq!(file_slug("Home  office!"), "home-office");
+        assert_eq!(file_slug("Đà Nẵng"), "đà-nẵng");
+        assert_eq!(file_slug("***"), "workspace");
+        let mut db = MonitorDatabase::default();
+        db.add_workspace("desk").unwrap();
+        db.workspaces[0].name = "Desk".into();
+        let main = Path::new("/h/monitors.conf");
+        assert_eq!(db.workspace_conf_path(main, 0), Path::new("/h/monitors-desk-1.conf"));
+        assert_eq!(db.workspace_conf_path(main, 1), Path::new("/h/monitors-desk-2.conf"));
+    }
+
     #[test]
     fn save_then_load_round_trips() {
         let p = temp_path("save_rt");
@@ -709,6 +2176,65 @@ mod tests {
         let _ = std::fs::remove_file(&p);
     }
 
+    #[test]
+    fn load_from_gives_ids_and_converts_the_active_index() {
+        let p = temp_path("ids");
+        let json = r#"{"workspaces":[{"name":"A","monitors":{}},{"name":"B","monitors":{}},
+            {"id":"same","name":"C","monitors":{}},{"id":"same","name":"D","monitors":{}}],
+            "active_workspace":1}"#;
+        std::fs::write(&p, json).unwrap();
+        let db = MonitorDatabase::load_from(&p).unwrap();
+        assert_eq!(db.current_workspace().unwrap().name, "B");
+        let ids: HashSet<&str> = db.workspaces.iter().map(|ws| ws.id.as_str()).collect();
+        assert_eq!(ids.len(), 4);
+        assert!(!ids.contains(""));
+
+        db.save().unwrap();
+        let saved = std::fs::read_to_string(&p).unwrap();
+        assert!(!saved.contains("\"active_workspace\""), "{saved}");
+        let again = MonitorDatabase::load_from(&p).unwrap();
+        assert_eq!(again.current_workspace().unwrap().id, db.current_workspace().unwrap().id);
+        let _ = std::fs::remove_file(&p);
+    }
+
+    #[test]
+    fn bundled_workspaces_load_under_the_users_and_stay_out_of_their_file() {
+        let p = temp_path("bundled");
+        let dir = temp_path("bundled.d");
+        std::fs::create_dir_all(&dir).unwrap();
+        let json = r#"{"workspaces":[{"id":"mine","name":"Desk","monitors":{}}]}"#;
+        std::fs::write(&p, json).unwrap();
+        std::fs::write(dir.join("10-dock.json"), r#"{"name":"Dock","monitors":{}}"#).unwrap();
+        let list = r#"[{"name":"Desk","monitors":{}},{"id":"tv","name":"TV","monitors":{}}]"#;
+        std::fs::write(dir.join("20-more.json"), list).unwrap();
+        std::fs::write(dir.join("30-broken.json"), "{").unwrap();
+        std::fs::write(dir.join("README"), "not a profile").unwrap();
+
+        let mut db = MonitorDatabase::load_layered(&p, Some(&dir)).unwrap();
+        let names: Vec<&str> = db.workspaces.iter().map(|ws| ws.name.as_str()).collect();
+        assert_eq!(names, ["Desk", "Dock", "TV"]); // the user's Desk hides the bundled one
+        assert_eq!(db.workspaces[1].id, "bundled:10-dock");
+        assert_eq!(db.bundle_errors.len(), 1);
+        assert!(!db.is_bundled(0) && db.is_bundled(1) && db.is_bundled(2));
+        assert!(!db.delete_workspace(1));
+
+        db.set_active(1);
+        db.workspaces[1].mark_applied(5);
+        db.workspaces[2].manual_only = true;
+        db.save().unwrap();
+        let saved = std::fs::read_to_string(&p).unwrap();
+        assert!(!saved.contains("\"Dock\"") && saved.contains("\"TV\""), "{saved}");
+
+        // TV is the user's now; Dock is still bundled, and still active
+        let db = MonitorDatabase::load_layered(&p, Some(&dir)).unwrap();
+        let names: Vec<&str> = db.workspaces.iter().map(|ws| ws.name.as_str()).collect();
+        assert_eq!(names, ["Desk", "TV", "Dock"]);
+        assert!(db.workspaces[1].manual_only && !db.is_bundled(1) && db.is_bundled(2));
+        assert_eq!(db.current_workspace().unwrap().name, "Dock");
+        let _ = std::fs::remove_file(&p);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn load_from_dedups_serial_duplicates() {
         let p = temp_path("dedup");
@@ -748,9 +2274,11 @@ mod tests {
         assert_eq!(db.current_workspace().unwrap().name, "Default");
         db.current_workspace_mut().unwrap().name = "Renamed".into();
         assert_eq!(db.current_workspace().unwrap().name, "Renamed");
-        db.active_workspace = 99;
-        assert!(db.current_workspace().is_none());
-        assert!(db.current_workspace_mut().is_none());
+        db.set_active(99);
+        assert_eq!(db.current_workspace().unwrap().name, "Renamed");
+        db.active_id = "gone".into();
+        assert_eq!(db.active_workspace(), 0);
+        assert_eq!(db.current_workspace_mut().unwrap().name, "Renamed");
     }
 
     #[test]
@@ -776,33 +2304,371 @@ mod tests {
         assert!(!db.apply_saved_config(&mut other));
     }
 
+    #[test]
+    fn apply_saved_config_from_reads_the_named_workspace() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.5, 0))]);
+        db.add_workspace("Two").unwrap();
+        db.workspaces[1]
+            .monitors
+            .insert("eDP-1".into(), saved("1920x1080", 2.0, 0));
+        let mut m = monitor("eDP-1", "N", "M", "d");
+        assert!(db.apply_saved_config_from(1, &mut m));
+        assert_eq!(m.scale, 2.0);
+        assert!(db.apply_saved_config(&mut m));
+        assert_eq!(m.scale, 1.5);
+        assert!(!db.apply_saved_config_from(9, &mut m));
+    }
+
     #[test]
     fn find_best_workspace_picks_match_else_none() {
         let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0))]);
-        db.add_workspace("Two");
+        db.add_workspace("Two").unwrap();
         assert_eq!(
-            db.find_best_workspace(&[monitor("eDP-1", "N", "M", "d")]),
+            db.find_best_workspace_at(&[monitor("eDP-1", "N", "M", "d")], NOON),
             Some(0)
         );
-        assert_eq!(db.find_best_workspace(&[monitor("DP-9", "Z", "Z", "d")]), None);
+        let unknown = monitor("DP-9", "Z", "Z", "d");
+        assert_eq!(db.find_best_workspace_at(&[unknown], NOON), None);
+    }
+
+    #[test]
+    fn schedule_prefers_open_window_and_excludes_closed_one() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0))]);
+        let idx = db.duplicate_workspace(0, "TV-off").unwrap();
+        db.workspaces[idx].schedule = vec![TimeRange::parse("23:00-07:00").unwrap()];
+        let laptop = [monitor("eDP-1", "N", "M", "d")];
+        assert_eq!(db.find_best_workspace_at(&laptop, NOON), Some(0));
+        assert_eq!(db.find_best_workspace_at(&laptop, 23 * 60), Some(idx));
+        assert_eq!(db.find_best_workspace_at(&laptop, 6 * 60 + 59), Some(idx));
+        assert_eq!(db.schedule_state(NOON), vec![None, Some(false)]);
+
+        db.workspaces[0].schedule = vec![TimeRange::parse("07:00-23:00").unwrap()];
+        assert!(db.auto_select_at(&laptop, 2 * 60));
+        assert_eq!(db.active_workspace(), idx);
+        assert!(!db.auto_select_at(&laptop, 3 * 60));
+    }
+
+    #[test]
+    fn suggested_names_describe_connected_monitors() {
+        let laptop = monitor("eDP-1", "BOE", "0x0BCA", "BOE 0x0BCA");
+        let dell = monitor("DP-1", "Dell Inc.", "U2720Q", "Dell Inc. U2720Q");
+        let names = suggest_workspace_names(&[laptop.clone(), dell], &[]);
+        assert_eq!(names, vec!["eDP-1 + DELL U2720Q", "Laptop + U2720Q", "2 monitors"]);
+
+        let taken = vec!["eDP-1".to_string(), "Laptop only".to_string()];
+        let names = suggest_workspace_names(&[laptop], &taken);
+        assert_eq!(names, vec!["eDP-1 2", "Laptop", "Laptop only 2"]);
+
+        // Long sets are cut to the input limit; empty set still offers a name.
+        let many: Vec<MonitorConfig> = (0..4)
+            .map(|i| monitor(&format!("DP-{i}"), "LG Electronics", "27GN950", "d"))
+            .collect();
+        assert!(suggest_workspace_names(&many, &[])
+            .iter()
+            .all(|n| text::grapheme_count(n) <= MAX_WORKSPACE_NAME));
+        assert_eq!(suggest_workspace_names(&[], &[]), vec!["Workspace"]);
+    }
+
+    #[test]
+    fn glob_match_handles_wildcards_and_case() {
+        assert!(glob_match("HDMI-*", "HDMI-A-1"));
+        assert!(glob_match("*dell*", "Dell Inc. U2720Q"));
+        assert!(glob_match("DP-?", "DP-2"));
+        assert!(!glob_match("DP-?", "DP-12"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("eDP*", "HDMI-A-1"));
+    }
+
+    #[test]
+    fn match_rules_distinguish_laptop_only_from_projector() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0))]);
+        db.workspaces[0].name = "Laptop only".into();
+        db.workspaces[0].match_rules.monitor_count = Some(1);
+        let idx = db.add_workspace("Projector").unwrap();
+        let proj = &mut db.workspaces[idx];
+        proj.monitors.insert("eDP-1".into(), saved("1920x1080", 1.0, 0));
+        proj.match_rules.patterns = vec!["HDMI-*".into()];
+        proj.match_rules.required = vec!["eDP-1".into()];
+
+        let laptop = monitor("eDP-1", "N", "M", "d");
+        let hdmi = monitor("HDMI-A-1", "Epson", "PJ", "Epson PJ");
+        assert_eq!(db.find_best_workspace_at(std::slice::from_ref(&laptop), NOON), Some(0));
+        assert_eq!(db.find_best_workspace_at(&[laptop.clone(), hdmi.clone()], NOON), Some(1));
+        // Required monitor missing -> projector profile ineligible.
+        assert_eq!(db.find_best_workspace_at(&[hdmi], NOON), None);
+
+        // Priority outranks raw match count.
+        db.workspaces[0].match_rules = MatchRules {
+            priority: 5,
+            ..MatchRules::default()
+        };
+        let both = [laptop, monitor("HDMI-A-1", "Epson", "PJ", "Epson PJ")];
+        assert_eq!(db.find_best_workspace_at(&both, NOON), Some(0));
+        assert_eq!(db.workspaces[0].match_rules.describe(), "priority 5");
+        assert_eq!(db.workspaces[1].match_rules.describe(), "HDMI-*, requires eDP-1");
+    }
+
+    #[test]
+    fn workspace_metadata_tracks_usage_and_survives_legacy_json() {
+        let mut ws = Workspace::new("Setup3-final2");
+        assert!(ws.created_at.is_some());
+        assert!(ws.summary().ends_with("never applied"));
+        ws.description = "Projector in room B".into();
+        ws.mark_applied(1_700_000_000);
+        ws.mark_applied(1_700_000_100);
+        assert_eq!(ws.apply_count, 2);
+        assert_eq!(ws.last_applied, Some(1_700_000_100));
+        assert!(ws.summary().starts_with("Projector in room B · created "));
+        assert!(ws.summary().ends_with("(2x)"));
+
+        let legacy: Workspace = serde_json::from_str(r#"{"name":"Old","monitors":{}}"#).unwrap();
+        assert_eq!(legacy.created_at, None);
+        assert_eq!(legacy.summary(), "never applied");
+
+        let mut db = MonitorDatabase::default();
+        db.workspaces[0] = ws;
+        let copy = db.duplicate_workspace(0, "Copy").unwrap();
+        assert_eq!(db.workspaces[copy].apply_count, 0);
+        assert_eq!(db.workspaces[copy].description, "Projector in room B");
+    }
+
+    #[test]
+    fn auto_select_skips_manual_only_and_honours_lock() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0))]);
+        let present = db.add_workspace("Present").unwrap();
+        db.workspaces[present]
+            .monitors
+            .insert("eDP-1".into(), saved("1920x1080", 2.0, 0));
+        db.workspaces[present].match_rules.priority = 9;
+        let laptop = [monitor("eDP-1", "N", "M", "d")];
+
+        // Manual-only never wins matching, even with the higher priority.
+        db.workspaces[present].manual_only = true;
+        assert_eq!(db.find_best_workspace_at(&laptop, NOON), Some(0));
+
+        // Once applied by hand, it is held against auto-switching.
+        db.set_active(present);
+        assert!(db.holds_active());
+        assert!(!db.auto_select(&laptop));
+        assert_eq!(db.active_workspace(), present);
+
+        // A lock holds any workspace; unlocking lets matching move it again.
+        db.workspaces[present].manual_only = false;
+        db.set_active(0);
+        db.lock_active = true;
+        assert!(!db.auto_select(&laptop));
+        db.lock_active = false;
+        assert!(db.auto_select(&laptop));
+        assert_eq!(db.active_workspace(), present);
     }
 
     #[test]
     fn workspace_crud_and_active_clamp() {
         let mut db = db_with(vec![]);
-        assert_eq!(db.add_workspace("Two"), 1);
-        db.rename_workspace(1, "Renamed");
+        assert_eq!(db.add_workspace("Two").unwrap(), 1);
+        db.rename_workspace(1, "Renamed").unwrap();
         assert_eq!(db.workspaces[1].name, "Renamed");
         assert!(db.delete_workspace(1));
         assert!(!db.delete_workspace(0)); // cannot delete the last one
-        db.add_workspace("X");
+        db.add_workspace("X").unwrap();
         assert!(!db.delete_workspace(99)); // out of range
 
         let mut db2 = db_with(vec![]);
-        db2.add_workspace("B");
-        db2.active_workspace = 1;
+        db2.add_workspace("B").unwrap();
+        db2.set_active(1);
         assert!(db2.delete_workspace(1));
-        assert_eq!(db2.active_workspace, 0); // active clamped after delete
+        assert_eq!(db2.active_workspace(), 0); // active clamped after delete
+    }
+
+    #[test]
+    fn active_workspace_follows_its_id_through_deletes() {
+        let mut db = db_with(vec![]);
+        for name in ["A", "B", "C"] {
+            db.add_workspace(name).unwrap();
+        }
+        db.set_active(2); // "B"
+        assert!(db.delete_workspace(1)); // "A", before it
+        assert_eq!(db.current_workspace().unwrap().name, "B");
+        assert!(db.delete_workspace(1)); // "B" itself: the next one takes over
+        assert_eq!(db.current_workspace().unwrap().name, "C");
+
+        let copy = db.duplicate_workspace(1, "D").unwrap();
+        assert_ne!(db.workspaces[copy].id, db.workspaces[1].id);
+        let id = db.workspaces[copy].id.clone();
+        assert_eq!(id.len(), 36);
+        assert_eq!(&id[14..15], "4");
+        assert_eq!(db.find_workspace(&id), Some(copy));
+    }
+
+    #[test]
+    fn import_monitor_copies_one_entry_clear_of_the_others() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.0, 0))]);
+        db.workspaces[0].monitors.get_mut("eDP-1").unwrap().is_primary = true;
+        db.add_workspace("Conference").unwrap();
+        let mut projector = saved("1920x1080", 1.0, 0);
+        projector.is_primary = true;
+        let conference = &mut db.workspaces[1].monitors;
+        conference.insert("desc:Epson EB".into(), projector);
+        conference.insert("desc:Spare".into(), saved("1920x1080", 1.0, 1920));
+
+        assert!(db.import_monitor(1, "desc:Epson EB", 0));
+        let copy = &db.workspaces[0].monitors["desc:Epson EB"];
+        assert_eq!((copy.position_x, copy.is_primary), (1920, false));
+        assert!(!db.workspaces[0].monitors.contains_key("desc:Spare"));
+        assert_eq!(db.workspaces[1].monitors["desc:Epson EB"].position_x, 0);
+
+        // A free spot is kept as saved
+        assert!(db.import_monitor(1, "desc:Spare", 0));
+        assert_eq!(db.workspaces[0].monitors["desc:Spare"].position_x, 3840);
+        db.workspaces[1].monitors.get_mut("desc:Spare").unwrap().position_x = 5760;
+        assert!(db.import_monitor(1, "desc:Spare", 0));
+        assert_eq!(db.workspaces[0].monitors["desc:Spare"].position_x, 5760);
+
+        assert!(!db.import_monitor(1, "desc:Missing", 0));
+        assert!(!db.import_monitor(0, "eDP-1", 0));
+        assert!(!db.import_monitor(1, "desc:Spare", 7));
+    }
+
+    #[test]
+    fn remove_monitor_drops_entry_from_generated_config() {
+        let mut db = db_with(vec![
+            ("eDP-1", saved("1920x1080", 1.0, 0)),
+            ("desc:Loaner X", saved("1920x1080", 1.0, 1920)),
+        ]);
+        assert!(db.remove_monitor(0, "desc:Loaner X"));
+        assert!(!db.remove_monitor(0, "desc:Loaner X"));
+        assert!(!db.remove_monitor(5, "eDP-1"));
+        let all: HashSet<String> = ["eDP-1".to_string(), "desc:Loaner X".to_string()].into();
+        let conf = db.generate_full_config(&all);
+        assert!(!conf.contains("Loaner"), "{conf}");
+        assert!(conf.contains("eDP-1"));
+    }
+
+    #[test]
+    fn unmanaged_monitor_is_neither_emitted_nor_overwritten() {
+        let mut db = db_with(vec![
+            ("eDP-1", saved("1920x1080", 1.0, 0)),
+            ("desc:Wacom Cintiq", saved("1920x1080", 1.0, 1920)),
+        ]);
+        assert!(!db.toggle_managed("desc:Wacom Cintiq"));
+        let conf = db.generate_full_config(&HashSet::new());
+        assert!(!conf.contains("Wacom"), "{conf}");
+        assert!(conf.contains("monitor=eDP-1,1920x1080@60.00,0x0,1"));
+
+        let mut tablet = monitor("DP-2", "Wacom", "Cintiq", "Wacom Cintiq");
+        tablet.scale = 2.0;
+        assert!(!db.apply_saved_config(&mut tablet));
+        assert_eq!(tablet.scale, 2.0);
+        db.update_monitor(&tablet);
+        assert_eq!(db.workspaces[0].monitors["desc:Wacom Cintiq"].scale, 1.0);
+
+        assert!(db.toggle_managed("desc:Wacom Cintiq"));
+        assert!(db.unmanaged.is_empty());
+    }
+
+    #[test]
+    fn nicknames_follow_the_monitor_key_live_and_saved() {
+        let mut db = db_with(vec![("desc:Dell U2720Q", saved("3840x2160", 1.5, 0))]);
+        let dell = monitor("DP-3", "Dell", "U2720Q", "Dell U2720Q");
+        assert_eq!(db.label(&dell), "U2720Q");
+        assert_eq!(db.set_nickname("desc:Dell U2720Q", "  Left Dell "), Some("Left Dell"));
+        assert_eq!(db.label(&dell), "Left Dell");
+        let saved = db.get_workspace_monitors(0);
+        assert_eq!(db.label(&saved[0]), "Left Dell");
+        let long = db.set_nickname("desc:Dell U2720Q", &"x".repeat(40)).unwrap();
+        assert_eq!(long.len(), MAX_NICKNAME);
+        assert_eq!(db.set_nickname("desc:Dell U2720Q", " "), None);
+        assert!(db.nicknames.is_empty());
+    }
+
+    #[test]
+    fn fallback_rule_is_configurable_and_removable() {
+        let mut db = db_with(vec![]);
+        assert!(db
+            .generate_full_config(&HashSet::new())
+            .ends_with("monitor=,preferred,auto,1\n"));
+
+        db.fallback = parse_fallback("monitor=, highrr ,auto,1.5").unwrap();
+        assert_eq!(db.fallback.as_deref(), Some("highrr,auto,1.5"));
+        assert!(db
+            .generate_full_config(&HashSet::new())
+            .ends_with("monitor=,highrr,auto,1.5\n"));
+
+        db.fallback = parse_fallback("  ").unwrap();
+        assert!(!db.generate_full_config(&HashSet::new()).contains("monitor=,"));
+        assert!(parse_fallback("highrr,auto").is_err());
+        assert!(parse_fallback(",preferred,,1").is_err());
+
+        // Missing key keeps the default; explicit null keeps it disabled.
+        let legacy: MonitorDatabase = serde_json::from_str(r#"{"workspaces":[]}"#).unwrap();
+        assert_eq!(legacy.fallback.as_deref(), Some(DEFAULT_FALLBACK));
+        let json = serde_json::to_string(&db).unwrap();
+        let none: MonitorDatabase = serde_json::from_str(&json).unwrap();
+        assert_eq!(none.fallback, None);
+    }
+
+    #[test]
+    fn duplicate_workspace_copies_monitors_under_new_name() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.5, 0))]);
+        assert_eq!(db.duplicate_workspace(0, "Copy").unwrap(), 1);
+        assert_eq!(db.workspaces[1].name, "Copy");
+        assert_eq!(db.workspaces[1].monitors, db.workspaces[0].monitors);
+        assert_eq!(db.find_workspace("Copy"), Some(1));
+        assert_eq!(db.find_workspace("Missing"), None);
+        assert!(db.duplicate_workspace(7, "X").is_err());
+    }
+
+    #[test]
+    fn workspace_names_stay_unique_and_non_blank() {
+        let mut db = db_with(vec![]);
+        let err = |r: Result<usize>| r.unwrap_err().to_string();
+        assert_eq!(err(db.add_workspace("   ")), "name cannot be empty");
+        assert_eq!(
+            err(db.add_workspace(" Default ")),
+            "a workspace named 'Default' already exists"
+        );
+        assert!(db.duplicate_workspace(0, "Default").is_err());
+        assert_eq!(db.add_workspace("  Desk ").unwrap(), 1);
+        assert_eq!(db.workspaces[1].name, "Desk"); // stored trimmed
+
+        assert!(db.rename_workspace(1, "Default").is_err());
+        assert!(db.rename_workspace(1, "\t").is_err());
+        assert_eq!(db.workspaces[1].name, "Desk");
+        db.rename_workspace(1, "Desk").unwrap(); // its own name is fine
+        db.rename_workspace(1, "desk").unwrap(); // names are case-sensitive
+        assert_eq!(db.find_workspace("desk"), Some(1));
+    }
+
+    #[test]
+    fn pinning_moves_to_front_and_moves_stay_within_group() {
+        let mut db = db_with(vec![]);
+        db.add_workspace("B").unwrap();
+        db.add_workspace("C").unwrap();
+        db.set_active(2); // "C"
+        let names = |db: &MonitorDatabase| -> Vec<String> {
+            db.workspaces.iter().map(|w| w.name.clone()).collect()
+        };
+
+        assert_eq!(db.toggle_pin(2), 0);
+        assert_eq!(names(&db), ["C", "Default", "B"]);
+        assert_eq!(db.active_workspace(), 0); // still "C"
+
+        // Unpinned "B" cannot jump ahead of pinned "C".
+        assert_eq!(db.move_workspace(1, -1), 1);
+        assert_eq!(db.move_workspace(2, -1), 1);
+        assert_eq!(names(&db), ["C", "B", "Default"]);
+        assert_eq!(db.move_workspace(2, 1), 2); // already last
+        // Dropped on the pinned one, it lands at the front of its own group
+        assert_eq!(db.move_workspace_to(2, 0), 1);
+        assert_eq!(names(&db), ["C", "Default", "B"]);
+        assert_eq!(db.move_workspace_to(1, 9), 2);
+        assert_eq!(names(&db), ["C", "B", "Default"]);
+
+        assert_eq!(db.toggle_pin(0), 0); // unpin -> first unpinned slot
+        assert!(!db.workspaces[0].pinned);
+        assert_eq!(db.move_workspace(0, 1), 1);
+        assert_eq!(names(&db), ["B", "C", "Default"]);
+        assert_eq!(db.active_workspace(), 1);
     }
 
     #[test]
@@ -814,16 +2680,162 @@ mod tests {
         assert_eq!(MonitorDatabase::get_monitor_key(&m), "desc:MSI MP275Q");
     }
 
+    #[test]
+    fn identical_monitors_split_into_serial_or_connector_keys() {
+        let mut db = MonitorDatabase::default();
+        let mut left = monitor("DP-1", "Dell Inc.", "U2720Q", "");
+        let mut right = monitor("DP-2", "Dell Inc.", "U2720Q", "");
+        (left.serial, right.serial) = ("A1".into(), "B2".into());
+        db.update_monitor(&left);
+        db.unmanaged.push("desc:Dell Inc. U2720Q".into());
+
+        let mut monitors = [left.clone(), right.clone()];
+        for m in &mut monitors {
+            m.key_by = KeyBy::Connector;
+        }
+        let keys: Vec<String> = monitors.iter().map(MonitorDatabase::get_monitor_key).collect();
+        assert_eq!(keys, ["DP-1", "DP-2"]);
+        db.split_key("desc:Dell Inc. U2720Q", KeyBy::Connector, &keys);
+        let saved = &db.workspaces[0].monitors;
+        assert!(saved.contains_key("DP-1") && saved.contains_key("DP-2") && saved.len() == 2);
+        assert_eq!(db.unmanaged, ["DP-1", "DP-2"]);
+
+        // Choices stick to the shared description; serials need a serial
+        db.key_by.insert("desc:Dell Inc. U2720Q".into(), KeyBy::Serial);
+        right.serial.clear();
+        let mut fresh = [left, right];
+        db.assign_keys(&mut fresh);
+        assert_eq!(MonitorDatabase::get_monitor_key(&fresh[0]), "desc:Dell Inc. U2720Q A1");
+        assert_eq!(MonitorDatabase::get_monitor_key(&fresh[1]), "desc:Dell Inc. U2720Q");
+    }
+
     #[test]
     fn get_workspace_monitors_reconstructs_and_out_of_range() {
         let db = db_with(vec![
             ("eDP-1", saved("1920x1080", 1.5, 0)),
             ("desc:ASUSTek COMPUTER INC VY249HF", saved("1920x1080", 1.0, 1920)),
         ]);
-        assert_eq!(db.get_workspace_monitors(0).len(), 2);
+        let monitors = db.get_workspace_monitors(0);
+        assert_eq!(monitors[0].name, "eDP-1");
+        assert_eq!(monitors[1].model, "VY249HF");
         assert!(db.get_workspace_monitors(9).is_empty());
     }
 
+    #[test]
+    fn active_workspace_wins_shared_monitor_settings() {
+        let mut db = db_with(vec![("eDP-1", saved("1920x1080", 1.5, 0))]);
+        db.add_workspace("Later").unwrap();
+        db.workspaces[1]
+            .monitors
+            .insert("eDP-1".into(), saved("1920x1080", 2.0, 0));
+        let conf = db.generate_full_config(&HashSet::new());
+        assert!(conf.contains("eDP-1,1920x1080@60.00,0x0,1.50"), "conf:\n{conf}");
+        db.set_active(1);
+        let conf = db.generate_full_config(&HashSet::new());
+        assert!(conf.contains("eDP-1,1920x1080@60.00,0x0,2\n"), "conf:\n{conf}");
+    }
+
+    #[test]
+    fn step_rules_follow_the_workspace_order_and_disable_last() {
+        let mut db = db_with(vec![
+            ("eDP-1", saved("1920x1080", 1.0, 0)),
+            ("desc:Dell U27", saved("2560x1440", 1.0, 1920)),
+            ("HDMI-A-1", saved("1920x1080", 1.0, 4480)),
+            ("DP-3", saved("1920x1080", 1.0, 6400)),
+        ]);
+        db.disconnected = Disconnected::Disable;
+        let connected: HashSet<String> =
+            ["eDP-1", "desc:Dell U27", "DP-3"].map(String::from).into();
+        assert!(db.step_rules(&connected).is_empty());
+
+        db.workspaces[0].apply_steps.order = vec!["DP-3".into(), "desc:Dell U27".into()];
+        let keys: Vec<String> = db
+            .step_rules(&connected)
+            .iter()
+            .map(|rule| rule.split(',').take(2).collect::<Vec<_>>().join(","))
+            .collect();
+        // The unplugged monitor's disable goes last; the fallback is not set
+        let order = ["DP-3,1920x1080@60.00", "desc:Dell U27,2560x1440@60.00"];
+        assert_eq!(keys, [order[0], order[1], "eDP-1,1920x1080@60.00", "HDMI-A-1,disable"]);
+        let steps = &db.workspaces[0].apply_steps;
+        assert_eq!(steps.describe(), "DP-3, then desc:Dell U27 first; 0 ms between monitors");
+    }
+
+    #[test]
+    fn dpms_plan_darkens_and_wakes_only_the_monitors_workspaces_name() {
+        let tv = MonitorConfig::for_test("HDMI-A-1", "LG", "TV", "3840x2160");
+        let edp = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let tv_key = MonitorDatabase::get_monitor_key(&tv);
+        let mut db = db_with(vec![]);
+        db.workspaces.push(Workspace::new("Movie"));
+        assert!(db.dpms_plan(&[edp.clone(), tv.clone()]).is_empty());
+
+        assert!(db.workspaces[0].toggle_dpms_off(&tv_key));
+        assert_eq!(db.dpms_plan(&[edp.clone(), tv.clone()]), [("HDMI-A-1".to_string(), false)]);
+        db.set_active(1);
+        assert_eq!(db.dpms_plan(&[edp, tv]), [("HDMI-A-1".to_string(), true)]);
+        assert!(!db.workspaces[0].toggle_dpms_off(&tv_key));
+        assert!(db.workspaces[0].dpms_off.is_empty());
+    }
+
+    #[test]
+    fn disconnected_monitors_are_kept_commented_or_disabled() {
+        let mut db = db_with(vec![
+            ("eDP-1", saved("1920x1080", 1.0, 0)),
+            ("HDMI-A-1", saved("1920x1080", 1.0, 1920)),
+        ]);
+        let connected: HashSet<String> = ["eDP-1".to_string()].into();
+        let hdmi = |db: &MonitorDatabase| {
+            let conf = db.generate_full_config(&connected);
+            conf.lines().find(|l| l.contains("HDMI-A-1")).unwrap().to_string()
+        };
+        assert_eq!(hdmi(&db), "monitor=HDMI-A-1,1920x1080@60.00,1920x0,1");
+        db.disconnected = Disconnected::Comment;
+        assert_eq!(hdmi(&db), "# monitor=HDMI-A-1,1920x1080@60.00,1920x0,1");
+        db.disconnected = Disconnected::Disable;
+        assert_eq!(hdmi(&db), "monitor=HDMI-A-1,disable");
+        // Everything counts as connected without a connected set
+        assert!(!db.generate_full_config(&HashSet::new()).contains("disable"));
+
+        let json = serde_json::to_string(&db).unwrap();
+        assert!(json.contains(r#""disconnected":"disable""#));
+        db.disconnected = Disconnected::Keep;
+        assert!(!serde_json::to_string(&db).unwrap().contains("disconnected"));
+    }
+
+    #[test]
+    fn primary_successor_prefers_the_configured_then_the_laptop_then_the_leftmost() {
+        let monitor = |name: &str, x| {
+            let mut m = MonitorConfig::for_test(name, "Dell", name, "1920x1080");
+            m.position_x = x;
+            m
+        };
+        let mut db = MonitorDatabase::default();
+        let monitors = [monitor("DP-2", 1920), monitor("eDP-1", 3840), monitor("DP-1", 0)];
+        assert_eq!(db.primary_successor(&monitors), Some(1));
+        assert_eq!(db.primary_successor(&[monitors[0].clone(), monitors[2].clone()]), Some(1));
+        db.primary_fallback = Some("desc:Dell DP-2".into());
+        assert_eq!(db.primary_successor(&monitors), Some(0));
+        db.primary_fallback = Some("DP-1".into());
+        assert_eq!(db.primary_successor(&monitors), Some(2));
+        assert_eq!(db.primary_successor(&[]), None);
+    }
+
+    #[test]
+    fn monitor_rule_is_the_generated_line_without_its_prefix() {
+        let db = db_with(vec![
+            ("eDP-1", saved("1920x1080", 1.5, 0)),
+            ("desc:ASUSTek COMPUTER INC VY249HF", saved("1920x1080", 1.0, 1280)),
+        ]);
+        let connected: HashSet<String> =
+            ["eDP-1", "desc:ASUSTek COMPUTER INC VY249HF"].map(String::from).into();
+        assert_eq!(
+            db.monitor_rule("desc:ASUSTek COMPUTER INC VY249HF", &connected).as_deref(),
+            Some("desc:ASUSTek COMPUTER INC VY249HF,1920x1080@60.00,1280x0,1")
+        );
+        assert_eq!(db.monitor_rule("eDP", &connected), None);
+    }
+
     #[test]
     fn generate_writes_transform_for_rotated_monitor() {
         let mut m = saved("1920x1080", 1.0, 0);
@@ -848,4 +2860,184 @@ mod tests {
         assert!(!is_desc_prefix("eDP-1", "eDP-1 x")); // not a desc: key
         assert!(!is_desc_prefix("desc:MSI", "desc:MSI")); // identical
     }
+
+    /// xorshift64*: small and deterministic, so a failing case replays from
+    /// the seed in its message.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+            &items[self.below(items.len())]
+        }
+    }
+
+    /// A `monitor=` line of the generated block, read back.
+    #[derive(Debug)]
+    struct Rule {
+        key: String,
+        /// `highres`/`highrr`, or the resolution and refresh rate.
+        mode: Result<ModePreset, (String, f64)>,
+        x: i32,
+        y: i32,
+        scale: f64,
+        transform: u8,
+    }
+
+    /// The inverse of `generate_full_config`: its rules and fallback.
+    fn parse_block(conf: &str) -> (Vec<Rule>, Option<String>) {
+        let mut rules = Vec::new();
+        let mut fallback = None;
+        for line in conf.lines() {
+            let Some(rule) = line.strip_prefix("monitor=") else {
+                assert!(line.is_empty() || line.starts_with('#'), "stray line {line:?}");
+                continue;
+            };
+            if let Some(rest) = rule.strip_prefix(',') {
+                fallback = Some(rest.to_string());
+                continue;
+            }
+            let fields: Vec<&str> = rule.split(',').collect();
+            assert!(matches!(fields.len(), 4 | 6), "{line}");
+            let mode = ModePreset::from_keyword(fields[1]).ok_or_else(|| {
+                let (res, rate) = fields[1].split_once('@').unwrap();
+                (res.to_string(), rate.parse().unwrap())
+            });
+            let (x, y) = fields[2].split_once('x').unwrap();
+            let transform = match fields.get(4) {
+                Some(&"transform") => fields[5].parse().unwrap(),
+                _ => 0,
+            };
+            rules.push(Rule {
+                key: fields[0].to_string(),
+                mode,
+                x: x.parse().unwrap(),
+                y: y.parse().unwrap(),
+                scale: fields[3].parse().unwrap(),
+                transform,
+            });
+        }
+        (rules, fallback)
+    }
+
+    const KEYS: [&str; 6] =
+        ["eDP-1", "DP-1", "HDMI-A-1", "desc:Dell U2720Q", "desc:LG 27GL850", "desc:BenQ PD3220U"];
+
+    fn random_monitor(rng: &mut Rng) -> SavedMonitor {
+        use ModePreset::{HighRes, HighRr};
+        let resolutions = ["1920x1080", "2560x1440", "3840x2160", "1280x800", "3440x1440"];
+        SavedMonitor {
+            resolution: rng.pick(&resolutions).to_string(),
+            refresh_rate: *rng.pick(&[60.0, 59.951, 74.97, 120.0, 143.998, 165.0]),
+            // Hyprland's own steps, including ones that need six decimals
+            scale: (60 + rng.below(241)) as f64 / 120.0,
+            rotation: rng.below(4) as u8,
+            position_x: rng.below(8000) as i32 - 2000,
+            position_y: *rng.pick(&[0, 0, -1080, 1440]),
+            is_primary: false,
+            mode_preset: *rng.pick(&[None, None, Some(HighRes), Some(HighRr)]),
+        }
+    }
+
+    /// A database of 1–3 workspaces sharing some of `KEYS`, with some keys
+    /// unmanaged and any of the fallbacks.
+    fn random_db(rng: &mut Rng) -> MonitorDatabase {
+        let mut db = MonitorDatabase::default();
+        db.workspaces.clear();
+        for w in 0..1 + rng.below(3) {
+            let mut ws = Workspace::new(&format!("ws{w}"));
+            for key in KEYS {
+                if rng.below(2) == 0 {
+                    ws.monitors.insert(key.to_string(), random_monitor(rng));
+                }
+            }
+            db.workspaces.push(ws);
+        }
+        db.set_active(rng.below(db.workspaces.len()));
+        db.unmanaged = KEYS.iter().filter(|_| rng.below(5) == 0).map(|k| k.to_string()).collect();
+        let fallbacks = [None, Some("preferred,auto,1"), Some("highrr,auto,1.5")];
+        db.fallback = rng.pick(&fallbacks).map(String::from);
+        db
+    }
+
+    /// The settings written for `key`: the active workspace's when it has
+    /// them, else those of the last other workspace that does.
+    fn merged<'a>(db: &'a MonitorDatabase, key: &str) -> &'a SavedMonitor {
+        db.current_workspace()
+            .and_then(|ws| ws.monitors.get(key))
+            .or_else(|| db.workspaces.iter().rev().find_map(|ws| ws.monitors.get(key)))
+            .unwrap()
+    }
+
+    #[test]
+    fn generated_rules_read_back_as_the_saved_settings() {
+        for seed in 1..=500u64 {
+            let mut rng = Rng(seed);
+            let db = random_db(&mut rng);
+            let (rules, fallback) = parse_block(&db.generate_full_config(&HashSet::new()));
+            assert_eq!(fallback, db.fallback, "seed {seed}");
+
+            // Every managed key once, with the settings `merged` picks
+            let mut keys: Vec<&str> = rules.iter().map(|r| r.key.as_str()).collect();
+            keys.sort_unstable();
+            let mut expected: Vec<&str> = KEYS
+                .into_iter()
+                .filter(|k| db.is_managed(k))
+                .filter(|k| db.workspaces.iter().any(|ws| ws.monitors.contains_key(*k)))
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(keys, expected, "seed {seed}");
+
+            for rule in &rules {
+                let saved = merged(&db, &rule.key);
+                match (&rule.mode, saved.mode_preset) {
+                    (Ok(preset), Some(saved)) => assert_eq!(*preset, saved, "seed {seed}"),
+                    (Err((res, rate)), None) => {
+                        assert_eq!(*res, saved.resolution, "seed {seed}");
+                        assert!((rate - saved.refresh_rate).abs() <= 0.005, "seed {seed}");
+                    }
+                    (mode, preset) => panic!("seed {seed}: wrote {mode:?} for {preset:?}"),
+                }
+                assert!((rule.scale - saved.scale).abs() < 1e-6, "seed {seed}: {}", rule.scale);
+                assert_eq!(rule.transform, saved.rotation, "seed {seed}");
+                assert_eq!(rule.y, saved.position_y, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn generated_positions_tile_the_connected_monitors() {
+        for seed in 1..=500u64 {
+            let mut rng = Rng(seed);
+            // The pack pass lays out one row, so start from row layouts
+            let mut db = random_db(&mut rng);
+            for saved in db.workspaces.iter_mut().flat_map(|ws| ws.monitors.values_mut()) {
+                saved.position_y = 0;
+            }
+            let connected: HashSet<String> =
+                KEYS.iter().filter(|_| rng.below(3) > 0).map(|k| k.to_string()).collect();
+            let (rules, _) = parse_block(&db.generate_full_config(&connected));
+
+            // Whatever was saved, the connected monitors come out in a layout
+            // Hyprland can use: no overlap and no gap
+            let placed: Vec<SavedMonitor> = rules
+                .iter()
+                .filter(|r| connected.contains(&r.key))
+                .map(|r| {
+                    SavedMonitor { position_x: r.x, position_y: r.y, ..merged(&db, &r.key).clone() }
+                })
+                .collect();
+            assert!(tiles(&placed), "seed {seed}: {rules:?}");
+        }
+    }
 }
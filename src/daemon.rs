@@ -0,0 +1,404 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::clock;
+use crate::dry_run::Writes;
+use crate::exec::Executor;
+use crate::config::MonitorDatabase;
+use crate::history::{HistoryEntry, HistoryLog};
+use crate::hypr_ipc::{self, HyprEvent};
+use crate::metrics::{self, Metrics};
+use crate::monitor::{
+    config_errors, fetch_monitors, reload_hyprland, set_dpms, set_monitor_rules, MonitorConfig,
+    RELOAD_ECHO,
+};
+use crate::systemd;
+
+/// How often the daemon re-checks workspace schedules between hotplug events.
+const TICK: Duration = Duration::from_secs(30);
+
+/// How `hyprmon daemon` reports what it does.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DaemonOptions {
+    /// [`DaemonEvent`]s as JSON lines instead of text.
+    pub json_events: bool,
+    /// Keep [`Metrics`] in this file for node_exporter's textfile collector.
+    pub metrics_file: Option<PathBuf>,
+    /// Serve [`Metrics`] at `http://ADDR/metrics`.
+    pub metrics_addr: Option<String>,
+}
+
+/// What wakes the daemon up.
+enum Wake {
+    Hypr(HyprEvent),
+    /// SIGHUP, e.g. from `systemctl --user reload hyprmon`.
+    Reload,
+}
+
+impl From<HyprEvent> for Wake {
+    fn from(event: HyprEvent) -> Self {
+        Self::Hypr(event)
+    }
+}
+
+/// Run headless: apply the best workspace on hotplug and whenever a schedule
+/// window opens or closes, reporting as `options` say. Under systemd it
+/// reports readiness, pings the watchdog, and on SIGHUP starts over as if
+/// just launched. Never returns unless setup fails.
+pub fn run(exec: &dyn Executor, writes: &Writes, options: &DaemonOptions) -> Result<()> {
+    let (tx, rx) = mpsc::channel::<Wake>();
+    let hup = tx.clone();
+    if let Err(e) = systemd::on_sighup(move || drop(hup.send(Wake::Reload))) {
+        eprintln!("hyprmon: {e}, SIGHUP will stop the daemon");
+    }
+    if let Err(e) = hypr_ipc::start_listener(tx) {
+        eprintln!("hyprmon: IPC unavailable ({e}), only following schedules");
+    }
+
+    let mut state = DaemonState {
+        json_events: options.json_events,
+        metrics_file: options.metrics_file.clone(),
+        ..Default::default()
+    };
+    if let Some(addr) = &options.metrics_addr {
+        let bound = metrics::serve(addr, state.metrics.clone())
+            .with_context(|| format!("cannot serve metrics on {addr}"))?;
+        eprintln!("hyprmon: metrics at http://{bound}/metrics");
+    }
+    state.write_metrics();
+    let watchdog = systemd::watchdog_interval();
+    let mut ping_at = Instant::now();
+    let mut hotplug = true;
+    loop {
+        if let Err(e) = state.step(exec, writes, hotplug) {
+            state.report(&DaemonEvent::ApplyError { message: format!("{e:#}") });
+        }
+        // Sent again after each reload, which systemd waits for
+        systemd::notify("READY=1");
+        // Window and workspace events don't matter here, but must not keep
+        // pushing the next schedule check back either
+        let deadline = Instant::now() + TICK;
+        hotplug = loop {
+            if let Some(every) = watchdog.filter(|_| Instant::now() >= ping_at) {
+                systemd::notify("WATCHDOG=1");
+                ping_at = Instant::now() + every;
+            }
+            let wake_at = if watchdog.is_some() { deadline.min(ping_at) } else { deadline };
+            match rx.recv_timeout(wake_at.saturating_duration_since(Instant::now())) {
+                Ok(Wake::Reload) => {
+                    systemd::notify("RELOADING=1");
+                    state.restart();
+                    break true;
+                }
+                Ok(Wake::Hypr(HyprEvent::WorkspacesChanged | HyprEvent::MonitorFocused(_))) => {}
+                // Set off by our own apply; answering it would apply again
+                Ok(_) if state.in_reload_echo(Instant::now()) => {}
+                Ok(_) => {
+                    // A dock often reports several monitors at once; settle first.
+                    while rx.recv_timeout(Duration::from_millis(500)).is_ok() {}
+                    break true;
+                }
+                Err(RecvTimeoutError::Timeout) if Instant::now() < deadline => {}
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(TICK);
+                    break false;
+                }
+            }
+        };
+    }
+}
+
+/// Something the daemon did or ran into. Reported as a `hyprmon: …` line,
+/// or with `--json-events` as one JSON object per line on stdout, e.g.
+/// `{"event":"profile_applied","workspace":"Desk","id":"…","dry_run":false,"seconds":…,"time":…}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DaemonEvent {
+    /// Monitors were plugged or unplugged; `monitors` are the connectors now.
+    HotplugDetected { monitors: Vec<String> },
+    /// `seconds` is how long writing the config and reloading took.
+    ProfileApplied { workspace: String, id: String, dry_run: bool, seconds: f64 },
+    /// No saved workspace fits the connected monitors.
+    MatchFailed { monitors: Vec<String> },
+    /// Applying failed, or Hyprland reported an error in the written config.
+    ApplyError { message: String },
+}
+
+impl DaemonEvent {
+    /// The line for people; `None` for events only worth a JSON line.
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::HotplugDetected { .. } => None,
+            Self::ProfileApplied { workspace, dry_run: false, .. } => {
+                Some(format!("hyprmon: applied '{workspace}'"))
+            }
+            Self::ProfileApplied { workspace, dry_run: true, .. } => {
+                Some(format!("hyprmon: would apply '{workspace}'"))
+            }
+            Self::MatchFailed { monitors } => {
+                Some(format!("hyprmon: no workspace matches {}", monitors.join(", ")))
+            }
+            Self::ApplyError { message } => Some(format!("hyprmon: {message}")),
+        }
+    }
+
+    /// The event as a JSON line, stamped with unix seconds `time`.
+    fn json(&self, time: u64) -> String {
+        #[derive(Serialize)]
+        struct Stamped<'a> {
+            #[serde(flatten)]
+            event: &'a DaemonEvent,
+            time: u64,
+        }
+        serde_json::to_string(&Stamped { event: self, time }).unwrap_or_default()
+    }
+}
+
+/// What the daemon remembers between ticks.
+#[derive(Debug, Default)]
+pub struct DaemonState {
+    /// Schedule windows seen on the last tick; `None` before the first one.
+    windows: Option<Vec<Option<bool>>>,
+    /// When the last apply reloaded Hyprland.
+    reloaded_at: Option<Instant>,
+    /// Report [`DaemonEvent`]s as JSON lines instead of text.
+    json_events: bool,
+    /// Counted from every reported event; shared with the metrics endpoint.
+    metrics: Arc<Mutex<Metrics>>,
+    metrics_file: Option<PathBuf>,
+}
+
+impl DaemonState {
+    fn step(&mut self, exec: &dyn Executor, writes: &Writes, hotplug: bool) -> Result<()> {
+        let minute = clock::local_minute_of_day(clock::now());
+        let mut db = MonitorDatabase::load()?;
+        // The first look happens at startup, not because of a hotplug
+        let startup = self.windows.is_none();
+        if !self.due(&db, minute, hotplug) {
+            return Ok(());
+        }
+        let mut monitors = fetch_monitors(exec)?;
+        db.assign_keys(&mut monitors);
+        let names: Vec<String> = monitors.iter().map(|m| m.name.clone()).collect();
+        if hotplug && !startup {
+            self.report(&DaemonEvent::HotplugDetected { monitors: names.clone() });
+        }
+        if db.find_best_workspace_at(&monitors, minute).is_none() {
+            self.report(&DaemonEvent::MatchFailed { monitors: names });
+        }
+        if !select(&mut db, &monitors, minute, hotplug) {
+            return Ok(());
+        }
+
+        let started = Instant::now();
+        let workspace = apply_active(exec, writes, &mut db, monitors)?;
+        let seconds = started.elapsed().as_secs_f64();
+        let id = db.current_workspace().map(|ws| ws.id.clone()).unwrap_or_default();
+        let dry_run = writes.dry_run();
+        self.report(&DaemonEvent::ProfileApplied { workspace, id, dry_run, seconds });
+        if dry_run {
+            return Ok(());
+        }
+        self.reloaded_at = Some(Instant::now());
+        for error in config_errors(exec) {
+            let message = format!("Hyprland config error: {}", error.message);
+            self.report(&DaemonEvent::ApplyError { message });
+        }
+        Ok(())
+    }
+
+    /// Print `event`: as JSON on stdout, or as text with errors on stderr.
+    /// It is counted in the metrics either way.
+    fn report(&self, event: &DaemonEvent) {
+        let now = clock::now();
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.record(event, now);
+        }
+        self.write_metrics();
+        if self.json_events {
+            println!("{}", event.json(now));
+        } else if let Some(line) = event.text() {
+            match event {
+                DaemonEvent::ApplyError { .. } => eprintln!("{line}"),
+                _ => println!("{line}"),
+            }
+        }
+    }
+
+    /// Refresh the metrics textfile, if there is one.
+    fn write_metrics(&self) {
+        let (Some(path), Ok(metrics)) = (&self.metrics_file, self.metrics.lock()) else {
+            return;
+        };
+        if let Err(e) = metrics::write_textfile(path, &metrics) {
+            eprintln!("hyprmon: cannot write {}: {e:#}", path.display());
+        }
+    }
+
+    /// Forget what was seen so far, so the next step looks afresh as at
+    /// startup: the database is read anew on every step anyway.
+    fn restart(&mut self) {
+        self.windows = None;
+    }
+
+    /// Whether monitor events at `now` are most likely the echo of our own
+    /// reload rather than a hotplug.
+    fn in_reload_echo(&self, now: Instant) -> bool {
+        self.reloaded_at.is_some_and(|at| now.saturating_duration_since(at) < RELOAD_ECHO)
+    }
+
+    /// Only hotplug and crossing a schedule boundary warrant a fresh look;
+    /// the first tick always does.
+    fn due(&mut self, db: &MonitorDatabase, minute: u16, hotplug: bool) -> bool {
+        let windows = db.schedule_state(minute);
+        let changed = self.windows.as_ref() != Some(&windows);
+        self.windows = Some(windows);
+        hotplug || changed
+    }
+}
+
+/// Write the active workspace's settings for `monitors` to Hyprland, reload it,
+/// and record the apply in the history log and the workspace's usage stats.
+/// Returns the workspace name.
+pub fn apply_active(
+    exec: &dyn Executor,
+    writes: &Writes,
+    db: &mut MonitorDatabase,
+    mut monitors: Vec<MonitorConfig>,
+) -> Result<String> {
+    db.assign_keys(&mut monitors);
+    let before = monitors.clone();
+    for monitor in &mut monitors {
+        db.apply_saved_config(monitor);
+    }
+    let connected: HashSet<String> =
+        monitors.iter().map(MonitorDatabase::get_monitor_key).collect();
+    let steps = db.step_rules(&connected);
+    if !steps.is_empty() {
+        let pause = match db.current_workspace() {
+            Some(ws) if !writes.dry_run() => ws.apply_steps.delay(),
+            _ => Duration::ZERO,
+        };
+        // The reload below still sets whatever a refused step left out
+        let _ = set_monitor_rules(exec, &steps, pause);
+    }
+    writes.hypr_config(db, &MonitorDatabase::hypr_conf_path(), &connected)?;
+    let _ = reload_hyprland(exec);
+    for (name, on) in db.dpms_plan(&monitors) {
+        let _ = set_dpms(exec, &name, on);
+    }
+
+    let name = db.current_workspace().map(|ws| ws.name.clone()).unwrap_or_default();
+    let entry = HistoryEntry::between(&name, &before, &monitors);
+    let _ = writes.history(&HistoryLog::default(), &entry);
+    if let Some(ws) = db.current_workspace_mut() {
+        ws.mark_applied(clock::now());
+    }
+    writes.save(db)?;
+    Ok(name)
+}
+
+/// Re-pick the active workspace for `monitors` at `minute` and report whether
+/// it should be applied: when the pick changed, or on hotplug if any connected
+/// monitor has saved settings. Manual-only workspaces are left alone.
+fn select(
+    db: &mut MonitorDatabase,
+    monitors: &[MonitorConfig],
+    minute: u16,
+    hotplug: bool,
+) -> bool {
+    let switched = db.auto_select_at(monitors, minute);
+    if db.current_workspace().is_none_or(|ws| ws.manual_only) {
+        return false;
+    }
+    switched || (hotplug && monitors.iter().any(|m| db.get_saved_config(m).is_some()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Workspace;
+    use crate::schedule::TimeRange;
+
+    fn db() -> (MonitorDatabase, Vec<MonitorConfig>) {
+        let edp = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let mut db = MonitorDatabase::default();
+        db.update_monitor(&edp);
+        let mut night = Workspace::new("TV-off");
+        night.monitors = db.workspaces[0].monitors.clone();
+        night.schedule = vec![TimeRange::parse("23:00-07:00").unwrap()];
+        db.workspaces.push(night);
+        (db, vec![edp])
+    }
+
+    #[test]
+    fn due_only_on_hotplug_or_schedule_boundary() {
+        let (db, _) = db();
+        let mut state = DaemonState::default();
+        assert!(state.due(&db, 12 * 60, false));
+        assert!(!state.due(&db, 13 * 60, false));
+        assert!(state.due(&db, 13 * 60, true));
+        assert!(state.due(&db, 23 * 60, false));
+        assert!(!state.due(&db, 2 * 60, false));
+        assert!(state.due(&db, 7 * 60, false));
+    }
+
+    #[test]
+    fn monitor_events_right_after_an_apply_are_its_echo() {
+        let mut state = DaemonState::default();
+        let applied = Instant::now();
+        assert!(!state.in_reload_echo(applied));
+        state.reloaded_at = Some(applied);
+        assert!(state.in_reload_echo(applied + Duration::from_millis(300)));
+        assert!(!state.in_reload_echo(applied + RELOAD_ECHO));
+    }
+
+    #[test]
+    fn events_read_as_text_or_json_lines() {
+        let applied = DaemonEvent::ProfileApplied {
+            workspace: "Desk".into(),
+            id: "1234".into(),
+            dry_run: true,
+            seconds: 0.5,
+        };
+        assert_eq!(applied.text().unwrap(), "hyprmon: would apply 'Desk'");
+        assert_eq!(
+            applied.json(7),
+            concat!(
+                r#"{"event":"profile_applied","workspace":"Desk","id":"1234","dry_run":true,"#,
+                r#""seconds":0.5,"time":7}"#
+            )
+        );
+        let monitors = vec!["eDP-1".into(), "DP-1".into()];
+        let plugged = DaemonEvent::HotplugDetected { monitors };
+        assert_eq!(plugged.text(), None);
+        assert_eq!(
+            plugged.json(5),
+            r#"{"event":"hotplug_detected","monitors":["eDP-1","DP-1"],"time":5}"#
+        );
+        let failed = DaemonEvent::MatchFailed { monitors: vec!["HDMI-A-1".into()] };
+        assert_eq!(failed.text().unwrap(), "hyprmon: no workspace matches HDMI-A-1");
+        let error = DaemonEvent::ApplyError { message: "hyprctl failed".into() };
+        assert_eq!(error.json(5), r#"{"event":"apply_error","message":"hyprctl failed","time":5}"#);
+    }
+
+    #[test]
+    fn select_follows_schedule_and_respects_manual_only() {
+        let (mut db, monitors) = db();
+        assert!(!select(&mut db, &monitors, 12 * 60, false));
+        assert!(select(&mut db, &monitors, 12 * 60, true));
+        assert!(select(&mut db, &monitors, 23 * 60 + 30, false));
+        assert_eq!(db.active_workspace(), 1);
+        assert!(select(&mut db, &monitors, 8 * 60, false));
+        assert_eq!(db.active_workspace(), 0);
+
+        db.workspaces[0].manual_only = true;
+        assert!(!select(&mut db, &monitors, 8 * 60, true));
+    }
+}
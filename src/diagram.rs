@@ -0,0 +1,208 @@
+use crate::monitor::{identify_color, MonitorConfig, Rotation};
+
+/// Widest ASCII diagram `hyprmon export` draws, in columns.
+pub const ASCII_WIDTH: usize = 72;
+
+/// A monitor's footprint in layout coordinates: position and the logical size
+/// Hyprland gives it (resolution divided by scale, swapped when portrait).
+fn logical_rect(m: &MonitorConfig) -> (i32, i32, i32, i32) {
+    let (w, h) = m
+        .resolution
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse::<f64>().ok()?, h.trim().parse::<f64>().ok()?)))
+        .unwrap_or((0.0, 0.0));
+    let scale = if m.scale > 0.0 { m.scale } else { 1.0 };
+    let (w, h) = ((w / scale).round() as i32, (h / scale).round() as i32);
+    match m.rotation {
+        Rotation::Left | Rotation::Right => (m.position_x, m.position_y, h, w),
+        Rotation::Normal | Rotation::Inverted => (m.position_x, m.position_y, w, h),
+    }
+}
+
+/// Smallest rectangle holding every monitor: (x, y, width, height).
+fn bounds(monitors: &[MonitorConfig]) -> (i32, i32, i32, i32) {
+    let rects: Vec<_> = monitors.iter().map(logical_rect).collect();
+    let min_x = rects.iter().map(|r| r.0).min().unwrap_or(0);
+    let min_y = rects.iter().map(|r| r.1).min().unwrap_or(0);
+    let max_x = rects.iter().map(|r| r.0 + r.2).max().unwrap_or(0);
+    let max_y = rects.iter().map(|r| r.1 + r.3).max().unwrap_or(0);
+    (min_x, min_y, (max_x - min_x).max(1), (max_y - min_y).max(1))
+}
+
+/// One legend line: number, connector, mode, scale, position, rotation.
+fn describe(i: usize, m: &MonitorConfig) -> String {
+    let mut line = format!(
+        "{}. {} ({}): {}@{:.0}Hz, scale {}, at {},{}",
+        i + 1,
+        m.name,
+        m.display_name(),
+        m.resolution,
+        m.refresh_rate,
+        m.scale,
+        m.position_x,
+        m.position_y
+    );
+    if m.rotation != Rotation::Normal {
+        line.push_str(&format!(", {}", m.rotation.as_str()));
+    }
+    if m.is_primary {
+        line.push_str(", primary");
+    }
+    line
+}
+
+/// The arrangement drawn to scale in plain ASCII, at most `max_cols` wide,
+/// with a legend below. Boxes are numbered like the TUI; `*` marks primary.
+pub fn ascii(monitors: &[MonitorConfig], max_cols: usize) -> String {
+    if monitors.is_empty() {
+        return "(no monitors)\n".to_string();
+    }
+    let (min_x, min_y, width, _) = bounds(monitors);
+    // Terminal cells are about twice as tall as wide
+    let cols_per_px = (max_cols.max(8) - 1) as f64 / width as f64;
+    let rows_per_px = cols_per_px / 2.0;
+    let boxes: Vec<(usize, usize, usize, usize)> = monitors
+        .iter()
+        .map(|m| {
+            let (x, y, w, h) = logical_rect(m);
+            (
+                ((x - min_x) as f64 * cols_per_px).round() as usize,
+                ((y - min_y) as f64 * rows_per_px).round() as usize,
+                ((w as f64 * cols_per_px).round() as usize).max(6),
+                ((h as f64 * rows_per_px).round() as usize).max(4),
+            )
+        })
+        .collect();
+    let cols = boxes.iter().map(|b| b.0 + b.2 + 1).max().unwrap_or(0);
+    let rows = boxes.iter().map(|b| b.1 + b.3 + 1).max().unwrap_or(0);
+    let mut grid = vec![vec![' '; cols]; rows];
+
+    // Every edge first, so corners on a shared edge are not drawn over
+    for &(x, y, w, h) in &boxes {
+        for row in [y, y + h] {
+            grid[row][x..=x + w].fill('-');
+        }
+        for row in &mut grid[y..=y + h] {
+            row[x] = '|';
+            row[x + w] = '|';
+        }
+    }
+    for (i, (m, &(x, y, w, h))) in monitors.iter().zip(&boxes).enumerate() {
+        for (row, col) in [(y, x), (y, x + w), (y + h, x), (y + h, x + w)] {
+            grid[row][col] = '+';
+        }
+        let primary = if m.is_primary { "*" } else { "" };
+        let labels = [format!("{primary}{} {}", i + 1, m.name), m.resolution.clone()];
+        for (line, label) in labels.iter().enumerate().take(h - 1) {
+            for (k, c) in label.chars().take(w - 1).enumerate() {
+                grid[y + 1 + line][x + 1 + k] = c;
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for row in grid {
+        out.push_str(row.iter().collect::<String>().trim_end());
+        out.push('\n');
+    }
+    out.push('\n');
+    for (i, m) in monitors.iter().enumerate() {
+        out.push_str(&describe(i, m));
+        out.push('\n');
+    }
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The arrangement as an SVG in layout coordinates: one rectangle per
+/// monitor in its identify color, labelled with number, connector,
+/// resolution and position.
+pub fn svg(monitors: &[MonitorConfig]) -> String {
+    let (min_x, min_y, width, height) = bounds(monitors);
+    let pad = width.max(height) / 40;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" \
+         height=\"{}\" font-family=\"sans-serif\">\n",
+        min_x - pad,
+        min_y - pad,
+        width + 2 * pad,
+        height + 2 * pad,
+        (width + 2 * pad) / 4,
+        (height + 2 * pad) / 4
+    );
+    for (i, m) in monitors.iter().enumerate() {
+        let (x, y, w, h) = logical_rect(m);
+        let (r, g, b) = identify_color(i);
+        let font = (w.min(h) / 10).max(1);
+        out.push_str(&format!(
+            "  <rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" \
+             fill=\"#{r:02x}{g:02x}{b:02x}\" fill-opacity=\"0.25\" \
+             stroke=\"#{r:02x}{g:02x}{b:02x}\" stroke-width=\"{}\"/>\n",
+            (font / 8).max(1)
+        ));
+        let primary = if m.is_primary { " (primary)" } else { "" };
+        let lines = [
+            format!("{} {}{primary}", i + 1, m.name),
+            format!("{} @ {}", m.resolution, m.scale),
+            format!("{},{}", m.position_x, m.position_y),
+        ];
+        for (k, line) in lines.iter().enumerate() {
+            out.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" font-size=\"{font}\" text-anchor=\"middle\">{}</text>\n",
+                x + w / 2,
+                y + h / 2 + (k as i32 - 1) * font * 6 / 5 + font / 3,
+                xml_escape(line)
+            ));
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(name: &str, resolution: &str, scale: f64, x: i32) -> MonitorConfig {
+        let mut m = MonitorConfig::for_test(name, "Dell", "U2720Q", resolution);
+        m.scale = scale;
+        m.position_x = x;
+        m
+    }
+
+    #[test]
+    fn ascii_draws_boxes_to_scale_with_a_legend() {
+        let mut laptop = at("eDP-1", "1920x1080", 1.0, 0);
+        laptop.is_primary = true;
+        let mut side = at("DP-2", "2560x1440", 1.0, 1920);
+        side.rotation = Rotation::Left;
+        let out = ascii(&[laptop, side], 40);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "+---------------------+----------------+");
+        assert_eq!(lines[1], "|*1 eDP-1             |2 DP-2          |");
+        assert_eq!(lines[2], "|1920x1080            |2560x1440       |");
+        assert_eq!(lines[6], "+---------------------+                |");
+        assert_eq!(lines[15], "                      +----------------+");
+        assert!(out.contains("1. eDP-1 (Laptop): 1920x1080@60Hz, scale 1, at 0,0, primary"));
+        let legend = "2. DP-2 (U2720Q): 2560x1440@60Hz, scale 1, at 1920,0, Portrait Left";
+        assert!(out.contains(legend));
+        assert!(lines.iter().all(|l| l.is_ascii() && l.len() <= 80));
+    }
+
+    #[test]
+    fn svg_uses_logical_sizes_and_escapes_names() {
+        let out = svg(&[at("eDP-1", "2880x1800", 2.0, 0), at("<DP>", "1920x1080", 1.0, 1440)]);
+        assert!(out.contains("viewBox=\"-84 -84 3528 1248\""), "{out}");
+        assert!(out.contains("<rect x=\"0\" y=\"0\" width=\"1440\" height=\"900\""), "{out}");
+        assert!(out.contains("<rect x=\"1440\" y=\"0\" width=\"1920\" height=\"1080\""));
+        assert!(out.contains(">2 &lt;DP&gt;</text>"));
+        assert!(out.contains(">1440,0</text>"));
+        assert!(out.ends_with("</svg>\n"));
+    }
+}
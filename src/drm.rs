@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::Path;
+
+use crate::i18n::tr_fill;
+
+/// A GPU as the kernel's DRM subsystem lists it under `/sys/class/drm`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrmCard {
+    /// `card0`, `card1`, …
+    pub name: String,
+    /// Connector names as Hyprland spells them (`DP-1`, `HDMI-A-1`, `eDP-1`).
+    pub connectors: Vec<String>,
+    /// Display pipes (CRTCs) the card has, which caps how many outputs it
+    /// drives at once. Only debugfs tells, so `None` when it is not readable.
+    pub crtcs: Option<usize>,
+}
+
+/// The GPUs of this machine and their connectors.
+pub fn read_topology() -> Vec<DrmCard> {
+    parse_topology(Path::new("/sys/class/drm"), Path::new("/sys/kernel/debug/dri"))
+}
+
+/// Walk a `/sys/class/drm`-shaped directory: `cardN` is a GPU and
+/// `cardN-CONNECTOR` one of its connectors. CRTCs are counted in
+/// `<debugfs>/N/state`. Split from [`read_topology`] so tests can use a fake tree.
+pub fn parse_topology(sysfs: &Path, debugfs: &Path) -> Vec<DrmCard> {
+    let Ok(entries) = fs::read_dir(sysfs) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> =
+        entries.flatten().filter_map(|e| e.file_name().into_string().ok()).collect();
+    names.sort();
+
+    let mut cards: Vec<DrmCard> = names
+        .iter()
+        .filter_map(|name| {
+            let minor = name.strip_prefix("card")?;
+            minor.parse::<u32>().ok()?;
+            let state = fs::read_to_string(debugfs.join(minor).join("state")).ok();
+            Some(DrmCard {
+                name: name.clone(),
+                connectors: Vec::new(),
+                crtcs: state.map(|s| s.lines().filter(|l| l.starts_with("crtc[")).count()),
+            })
+        })
+        .collect();
+    for name in &names {
+        let Some((card, connector)) = name.split_once('-') else {
+            continue;
+        };
+        if let Some(card) = cards.iter_mut().find(|c| c.name == card) {
+            card.connectors.push(connector.to_string());
+        }
+    }
+    cards
+}
+
+/// One line per GPU asked to drive more outputs than it has CRTCs for,
+/// given the connectors of the monitors about to be enabled. Hyprland
+/// leaves the extra ones black without saying so.
+pub fn overcommitted(cards: &[DrmCard], enabled: &[&str]) -> Vec<String> {
+    cards
+        .iter()
+        .filter_map(|card| {
+            let crtcs = card.crtcs?;
+            let on: Vec<&str> = enabled
+                .iter()
+                .copied()
+                .filter(|name| card.connectors.iter().any(|c| c == name))
+                .collect();
+            (on.len() > crtcs).then(|| {
+                tr_fill(
+                    "{} drives at most {} displays but {} are on ({}); one may stay black",
+                    &[&card.name, &crtcs, &on.len(), &on.join(", ")],
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cards_connectors_and_crtcs_from_a_drm_tree() {
+        let root = std::env::temp_dir().join(format!("hyprmon_drm_{}", std::process::id()));
+        let (sysfs, debugfs) = (root.join("class"), root.join("dri"));
+        let dirs = ["card0", "card0-eDP-1", "card0-DP-1", "card0-HDMI-A-1", "card1", "card1-DP-2"];
+        for dir in dirs.iter().chain(&["renderD128"]) {
+            fs::create_dir_all(sysfs.join(dir)).unwrap();
+        }
+        fs::write(sysfs.join("version"), "drm 1.1.0").unwrap();
+        fs::create_dir_all(debugfs.join("0")).unwrap();
+        fs::write(
+            debugfs.join("0/state"),
+            "plane[31]: plane-0\ncrtc[51]: crtc-0\n\tenable=1\ncrtc[72]: crtc-1\n",
+        )
+        .unwrap();
+
+        let cards = parse_topology(&sysfs, &debugfs);
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].connectors, ["DP-1", "HDMI-A-1", "eDP-1"]);
+        assert_eq!(cards[0].crtcs, Some(2));
+        assert_eq!((cards[1].name.as_str(), cards[1].crtcs), ("card1", None));
+
+        assert!(overcommitted(&cards, &["eDP-1", "DP-1", "DP-2"]).is_empty());
+        let warnings = overcommitted(&cards, &["eDP-1", "DP-1", "HDMI-A-1", "DP-2"]);
+        assert_eq!(
+            warnings,
+            ["card0 drives at most 2 displays but 3 are on (eDP-1, DP-1, HDMI-A-1); \
+              one may stay black"]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! `--dry-run` for CLI commands: they read for real but send every file
+//! change through [`Writes`], which either makes it or describes it on
+//! stdout. Commands run through [`DryRun`](crate::exec::DryRun) alongside.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::config::{FileChange, MonitorDatabase};
+use crate::history::{HistoryEntry, HistoryLog};
+
+/// Where a CLI command's file changes go.
+#[derive(Debug, Clone, Copy)]
+pub struct Writes {
+    dry_run: bool,
+}
+
+impl Writes {
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Persist the database (`monitors.json`).
+    pub fn save(&self, db: &MonitorDatabase) -> Result<()> {
+        if self.dry_run {
+            println!("would update {}", db.path().display());
+            return Ok(());
+        }
+        db.save()
+    }
+
+    /// Regenerate the Hyprland config at `path`; see
+    /// [`MonitorDatabase::write_hypr_config`].
+    pub fn hypr_config(
+        &self,
+        db: &MonitorDatabase,
+        path: &Path,
+        connected: &HashSet<String>,
+    ) -> Result<()> {
+        if !self.dry_run {
+            return db.write_hypr_config(path, connected);
+        }
+        for change in db.hypr_config_changes(path, connected)? {
+            for line in describe(&change) {
+                println!("{line}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Make `changes` in order.
+    pub fn changes(&self, changes: Vec<FileChange>) -> Result<()> {
+        for change in changes {
+            if self.dry_run {
+                describe(&change).iter().for_each(|line| println!("{line}"));
+            } else {
+                change.make()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write `content` to `path`, creating its directory.
+    pub fn file(&self, path: &Path, content: &str) -> Result<()> {
+        let change = FileChange::Write { path: path.to_path_buf(), content: content.to_string() };
+        if self.dry_run {
+            for line in describe(&change) {
+                println!("{line}");
+            }
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        change.make()
+    }
+
+    /// Record an apply in the history log.
+    pub fn history(&self, log: &HistoryLog, entry: &HistoryEntry) -> Result<()> {
+        if self.dry_run {
+            println!("would log the change to {}", log.path().display());
+            return Ok(());
+        }
+        log.append(entry)
+    }
+}
+
+/// `change` for the dry-run report: what happens to which file, then any
+/// new content indented below it.
+pub fn describe(change: &FileChange) -> Vec<String> {
+    match change {
+        FileChange::Backup { from, to } => {
+            vec![format!("would back up {} to {}", from.display(), to.display())]
+        }
+        FileChange::Archive(path) => {
+            let dir = crate::backups::dir_for(path);
+            vec![format!("would keep a copy of {} in {}", path.display(), dir.display())]
+        }
+        FileChange::Write { path, content } => {
+            let mut out = vec![format!("would write {}:", path.display())];
+            out.extend(content.lines().map(|line| format!("    {line}")));
+            out
+        }
+        FileChange::Remove(path) => vec![format!("would remove {}", path.display())],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn describes_each_file_change() {
+        let conf = PathBuf::from("/tmp/monitors.conf");
+        let backup = FileChange::Backup { from: conf.clone(), to: conf.with_extension("conf.bak") };
+        assert_eq!(
+            describe(&backup),
+            ["would back up /tmp/monitors.conf to /tmp/monitors.conf.bak"]
+        );
+        assert_eq!(
+            describe(&FileChange::Archive(conf.clone())),
+            ["would keep a copy of /tmp/monitors.conf in /tmp/hyprmon-backups"]
+        );
+        let write = FileChange::Write { path: conf.clone(), content: "a\nmonitor=b\n".into() };
+        assert_eq!(
+            describe(&write),
+            ["would write /tmp/monitors.conf:", "    a", "    monitor=b"]
+        );
+        assert_eq!(describe(&FileChange::Remove(conf)), ["would remove /tmp/monitors.conf"]);
+    }
+
+    #[test]
+    fn dry_run_leaves_the_files_alone() {
+        let dir = std::env::temp_dir().join(format!("hyprmon_dry_run_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut db = MonitorDatabase::default();
+        db.set_config_path(dir.join("monitors.json"));
+        let conf = dir.join("monitors.conf");
+        let log = HistoryLog::at(dir.join("history.jsonl"));
+
+        let dry = Writes::new(true);
+        dry.save(&db).unwrap();
+        dry.hypr_config(&db, &conf, &HashSet::new()).unwrap();
+        dry.history(&log, &HistoryEntry::between("Default", &[], &[])).unwrap();
+        dry.file(&dir.join("user/hyprmon.service"), "[Unit]\n").unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        let real = Writes::new(false);
+        real.save(&db).unwrap();
+        real.hypr_config(&db, &conf, &HashSet::new()).unwrap();
+        assert!(db.path().exists() && conf.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
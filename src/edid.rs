@@ -0,0 +1,636 @@
+//! The monitor's own account of itself: the EDID the kernel read from it,
+//! decoded. Set beside hyprctl's mode list it shows which side a missing or
+//! extra mode comes from.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// One mode the EDID claims.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub width: u32,
+    pub height: u32,
+    pub refresh: f64,
+    pub interlaced: bool,
+    /// Where it was listed: `preferred`, `detailed`, `standard`, `CTA VIC 97`, …
+    pub source: String,
+}
+
+impl Timing {
+    fn new(width: u32, height: u32, refresh: f64, source: impl Into<String>) -> Self {
+        Self { width, height, refresh, interlaced: false, source: source.into() }
+    }
+
+    /// Whether hyprctl lists this mode, within half a hertz: the EDID gives
+    /// nominal rates (60) where the mode runs at 59.94.
+    pub fn listed_in(&self, modes: &[String]) -> bool {
+        modes.iter().filter_map(|mode| parse_mode(mode)).any(|(w, h, rate)| {
+            w == self.width && h == self.height && (rate - self.refresh).abs() < 0.5
+        })
+    }
+
+    fn same_mode(&self, other: &Timing) -> bool {
+        (self.width, self.height, self.interlaced) == (other.width, other.height, other.interlaced)
+            && (self.refresh - other.refresh).abs() < 0.5
+    }
+}
+
+/// "2560x1440@59.95Hz" → (2560, 1440, 59.95).
+fn parse_mode(mode: &str) -> Option<(u32, u32, f64)> {
+    let (res, rate) = mode.split_once('@')?;
+    let (w, h) = res.trim().split_once('x')?;
+    let rate = rate.trim().trim_end_matches("Hz").parse().ok()?;
+    Some((w.parse().ok()?, h.parse().ok()?, rate))
+}
+
+/// HDR static metadata from the CTA-861 extension.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hdr {
+    /// Transfer functions: `SDR`, `HDR`, `PQ (ST 2084)`, `HLG`.
+    pub eotfs: Vec<&'static str>,
+    /// Desired content luminance, cd/m².
+    pub max_luminance: Option<f64>,
+    pub max_frame_average: Option<f64>,
+    pub min_luminance: Option<f64>,
+}
+
+/// The rates a monitor says it takes, from its range limits descriptor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeLimits {
+    pub vertical_hz: (u16, u16),
+    pub horizontal_khz: (u16, u16),
+    pub max_pixel_clock_mhz: Option<u16>,
+}
+
+/// What the EDID says, as far as hyprmon decodes it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Edid {
+    /// PNP id, e.g. `DEL`.
+    pub manufacturer: String,
+    pub product: u16,
+    /// The numeric serial; 0 when unused.
+    pub serial_number: u32,
+    /// The serial string descriptor, which is what Hyprland shows.
+    pub serial: Option<String>,
+    pub name: Option<String>,
+    /// Week (0 when not given) and year of manufacture.
+    pub made: (u8, u16),
+    pub version: (u8, u8),
+    /// `digital, DisplayPort, 10 bits per color` or `analog`.
+    pub input: String,
+    /// Centimetres.
+    pub size_cm: Option<(u8, u8)>,
+    pub range: Option<RangeLimits>,
+    pub timings: Vec<Timing>,
+    pub hdr: Option<Hdr>,
+    pub colorimetry: Vec<&'static str>,
+    /// CTA blocks that say which link it is: `HDMI`, `HDMI Forum`.
+    pub interfaces: Vec<&'static str>,
+    /// Extension blocks hyprmon does not decode, by tag.
+    pub other_extensions: Vec<u8>,
+    /// Blocks whose checksum is wrong.
+    pub bad_checksums: Vec<usize>,
+}
+
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// Established timings, bit by bit from byte 35 on (most significant first).
+const ESTABLISHED: &[(u32, u32, f64)] = &[
+    (720, 400, 70.0),
+    (720, 400, 88.0),
+    (640, 480, 60.0),
+    (640, 480, 67.0),
+    (640, 480, 72.0),
+    (640, 480, 75.0),
+    (800, 600, 56.0),
+    (800, 600, 60.0),
+    (800, 600, 72.0),
+    (800, 600, 75.0),
+    (832, 624, 75.0),
+    (1024, 768, 87.0),
+    (1024, 768, 60.0),
+    (1024, 768, 70.0),
+    (1024, 768, 75.0),
+    (1280, 1024, 75.0),
+    (1152, 870, 75.0),
+];
+
+/// CTA-861 video codes for the common progressive modes.
+const VICS: &[(u8, u32, u32, f64)] = &[
+    (1, 640, 480, 60.0),
+    (2, 720, 480, 60.0),
+    (3, 720, 480, 60.0),
+    (4, 1280, 720, 60.0),
+    (16, 1920, 1080, 60.0),
+    (17, 720, 576, 50.0),
+    (18, 720, 576, 50.0),
+    (19, 1280, 720, 50.0),
+    (31, 1920, 1080, 50.0),
+    (32, 1920, 1080, 24.0),
+    (33, 1920, 1080, 25.0),
+    (34, 1920, 1080, 30.0),
+    (63, 1920, 1080, 120.0),
+    (64, 1920, 1080, 100.0),
+    (93, 3840, 2160, 24.0),
+    (94, 3840, 2160, 25.0),
+    (95, 3840, 2160, 30.0),
+    (96, 3840, 2160, 50.0),
+    (97, 3840, 2160, 60.0),
+    (98, 4096, 2160, 24.0),
+    (99, 4096, 2160, 25.0),
+    (100, 4096, 2160, 30.0),
+    (101, 4096, 2160, 50.0),
+    (102, 4096, 2160, 60.0),
+    (117, 3840, 2160, 100.0),
+    (118, 3840, 2160, 120.0),
+];
+
+/// The EDID of `connector` (`DP-1`), as the kernel exposes it.
+pub fn read(connector: &str) -> Result<Vec<u8>> {
+    read_from(Path::new("/sys/class/drm"), connector)
+}
+
+/// [`read`] under a `/sys/class/drm`-shaped directory: `cardN-CONNECTOR/edid`.
+pub fn read_from(sysfs: &Path, connector: &str) -> Result<Vec<u8>> {
+    let suffix = format!("-{connector}");
+    let dir = fs::read_dir(sysfs)
+        .with_context(|| format!("cannot list {}", sysfs.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with("card") && name.ends_with(&suffix)
+        })
+        .with_context(|| format!("no DRM connector {connector}"))?;
+    let edid = fs::read(dir.join("edid"))?;
+    if edid.is_empty() {
+        bail!("{connector} has no EDID (nothing connected?)");
+    }
+    Ok(edid)
+}
+
+/// `bytes` as a hex dump, 16 per line, as `edid-decode` reads it.
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .map(|row| row.iter().map(|b| format!("{b:02x}")).collect::<Vec<_>>().join(" "))
+        .collect()
+}
+
+impl Edid {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 128 || bytes[..8] != HEADER {
+            bail!("not an EDID ({} bytes, no header)", bytes.len());
+        }
+        let b = bytes;
+        let id = u16::from_be_bytes([b[8], b[9]]);
+        let letter = |shift: u16| (b'A' - 1 + ((id >> shift) & 0x1F) as u8) as char;
+        let mut edid = Edid {
+            manufacturer: [letter(10), letter(5), letter(0)].iter().collect(),
+            product: u16::from_le_bytes([b[10], b[11]]),
+            serial_number: u32::from_le_bytes([b[12], b[13], b[14], b[15]]),
+            made: (if b[16] == 0xFF { 0 } else { b[16] }, 1990 + b[17] as u16),
+            version: (b[18], b[19]),
+            input: input(b[20], b[19]),
+            size_cm: (b[21] > 0 && b[22] > 0).then_some((b[21], b[22])),
+            ..Default::default()
+        };
+
+        let mut detailed = Vec::new();
+        for (i, desc) in b[54..126].chunks(18).enumerate() {
+            if desc[0] != 0 || desc[1] != 0 {
+                let source = if i == 0 { "preferred" } else { "detailed" };
+                detailed.extend(detailed_timing(desc, source));
+                continue;
+            }
+            let text = || Some(descriptor_text(&desc[5..]));
+            match desc[3] {
+                0xFF => edid.serial = text(),
+                0xFC => edid.name = text(),
+                0xFD => edid.range = Some(range_limits(desc)),
+                _ => {}
+            }
+        }
+        let mut standard = Vec::new();
+        for pair in b[38..54].chunks(2) {
+            if pair == [0x01, 0x01] || pair[0] == 0 {
+                continue;
+            }
+            let width = (pair[0] as u32 + 31) * 8;
+            let height = match pair[1] >> 6 {
+                0 if (b[18], b[19]) < (1, 3) => width,
+                0 => width * 10 / 16,
+                1 => width * 3 / 4,
+                2 => width * 4 / 5,
+                _ => width * 9 / 16,
+            };
+            let refresh = (pair[1] & 0x3F) as f64 + 60.0;
+            standard.push(Timing::new(width, height, refresh, "standard"));
+        }
+        let established_bits = u32::from_be_bytes([0, b[35], b[36], b[37]]);
+        let established: Vec<Timing> = ESTABLISHED
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| established_bits & (1 << (23 - i)) != 0)
+            .map(|(_, &(w, h, hz))| {
+                let mut timing = Timing::new(w, h, hz, "established");
+                // 1024x768@87 is the interlaced one
+                timing.interlaced = hz == 87.0 && w == 1024;
+                timing
+            })
+            .collect();
+
+        let mut cta = Vec::new();
+        edid.bad_checksums = bytes
+            .chunks(128)
+            .enumerate()
+            .filter(|(_, block)| block.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0)
+            .map(|(i, _)| i)
+            .collect();
+        for block in bytes[128..].chunks_exact(128) {
+            match block[0] {
+                0x02 => edid.cta_block(block, &mut detailed, &mut cta),
+                tag => edid.other_extensions.push(tag),
+            }
+        }
+
+        // Preferred first, then the biggest, fastest modes; each mode once,
+        // credited to the most specific place that lists it
+        let mut timings: Vec<Timing> = Vec::new();
+        for timing in detailed.into_iter().chain(standard).chain(cta).chain(established) {
+            if !timings.iter().any(|known| known.same_mode(&timing)) {
+                timings.push(timing);
+            }
+        }
+        timings.sort_by(|a, b| {
+            (b.source == "preferred")
+                .cmp(&(a.source == "preferred"))
+                .then((b.width * b.height).cmp(&(a.width * a.height)))
+                .then(b.refresh.total_cmp(&a.refresh))
+        });
+        edid.timings = timings;
+        Ok(edid)
+    }
+
+    /// A CTA-861 extension: its data blocks, then its detailed timings.
+    fn cta_block(&mut self, block: &[u8], detailed: &mut Vec<Timing>, cta: &mut Vec<Timing>) {
+        let dtd_start = (block[2] as usize).clamp(4, 127);
+        let mut at = 4;
+        while at < dtd_start {
+            let (tag, len) = (block[at] >> 5, (block[at] & 0x1F) as usize);
+            let Some(payload) = block.get(at + 1..(at + 1 + len).min(dtd_start)) else {
+                break;
+            };
+            match (tag, payload) {
+                (2, vics) => {
+                    for &byte in vics {
+                        // Codes 1-64 carry a "native" flag in the top bit
+                        let vic = if (129..=192).contains(&byte) { byte & 0x7F } else { byte };
+                        if let Some(&(_, w, h, hz)) = VICS.iter().find(|(code, ..)| *code == vic) {
+                            cta.push(Timing::new(w, h, hz, format!("CTA VIC {vic}")));
+                        }
+                    }
+                }
+                (3, [0x03, 0x0C, 0x00, ..]) => self.interfaces.push("HDMI"),
+                (3, [0xD8, 0x5D, 0xC4, ..]) => self.interfaces.push("HDMI Forum"),
+                (7, [0x05, bits, rest @ ..]) => self.colorimetry = colorimetry(*bits, rest),
+                (7, [0x06, eotfs, _, luminance @ ..]) => self.hdr = Some(hdr(*eotfs, luminance)),
+                _ => {}
+            }
+            at += 1 + len;
+        }
+        for desc in block[dtd_start..127].chunks_exact(18) {
+            if desc[0] == 0 && desc[1] == 0 {
+                break;
+            }
+            detailed.extend(detailed_timing(desc, "detailed"));
+        }
+    }
+
+    /// The decoded fields, then every timing marked where hyprctl does not
+    /// list it, then the modes hyprctl lists that the EDID does not.
+    /// Headings are flush left, entries indented.
+    pub fn report(&self, modes: &[String]) -> Vec<String> {
+        let field = |label: &str, value: String| format!("  {label:<14}{value}");
+        let mut lines = vec![field(
+            "Manufacturer",
+            format!("{} (product {:#06x})", self.manufacturer, self.product),
+        )];
+        if let Some(name) = &self.name {
+            lines.push(field("Name", name.clone()));
+        }
+        let serial = match (&self.serial, self.serial_number) {
+            (Some(text), 0) => text.clone(),
+            (Some(text), n) => format!("{text} (number {n})"),
+            (None, 0) => "none".to_string(),
+            (None, n) => n.to_string(),
+        };
+        lines.push(field("Serial", serial));
+        let made = match self.made {
+            (0, year) => year.to_string(),
+            (week, year) => format!("week {week} of {year}"),
+        };
+        lines.push(field("Made", made));
+        lines.push(field(
+            "Version",
+            format!("EDID {}.{}, {}", self.version.0, self.version.1, self.input),
+        ));
+        if let Some((w, h)) = self.size_cm {
+            lines.push(field("Screen size", format!("{w} × {h} cm")));
+        }
+        if let Some(limits) = &self.range {
+            let ((v_min, v_max), (h_min, h_max)) = (limits.vertical_hz, limits.horizontal_khz);
+            let mut range = format!("{v_min}-{v_max} Hz vertical, {h_min}-{h_max} kHz horizontal");
+            if let Some(clock) = limits.max_pixel_clock_mhz {
+                range += &format!(", pixel clock up to {clock} MHz");
+            }
+            lines.push(field("Range", range));
+        }
+        if !self.interfaces.is_empty() {
+            lines.push(field("Blocks", self.interfaces.join(", ")));
+        }
+        let hdr = match &self.hdr {
+            None => "not claimed".to_string(),
+            Some(hdr) => {
+                let mut text = hdr.eotfs.join(", ");
+                let nits = |label: &str, value: Option<f64>, digits: usize| {
+                    value.map(|v| format!("{label} {v:.digits$} cd/m²"))
+                };
+                let levels: Vec<String> = [
+                    nits("max", hdr.max_luminance, 0),
+                    nits("frame average", hdr.max_frame_average, 0),
+                    nits("min", hdr.min_luminance, 2),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !levels.is_empty() {
+                    text += &format!("; {}", levels.join(", "));
+                }
+                text
+            }
+        };
+        lines.push(field("HDR", hdr));
+        if !self.colorimetry.is_empty() {
+            lines.push(field("Colorimetry", self.colorimetry.join(", ")));
+        }
+        for tag in &self.other_extensions {
+            let name = if *tag == 0x70 { "DisplayID" } else { "unknown" };
+            lines.push(field("Extension", format!("{name} ({tag:#04x}), not decoded")));
+        }
+        if !self.bad_checksums.is_empty() {
+            let blocks: Vec<String> = self.bad_checksums.iter().map(|b| b.to_string()).collect();
+            lines.push(field("Checksum", format!("wrong in block {}", blocks.join(", "))));
+        }
+
+        lines.push(format!("EDID timings ({})", self.timings.len()));
+        for timing in &self.timings {
+            let mode = format!(
+                "{}x{}{}@{:.2}Hz",
+                timing.width,
+                timing.height,
+                if timing.interlaced { "i" } else { "" },
+                timing.refresh
+            );
+            let note = if modes.is_empty() || timing.interlaced || timing.listed_in(modes) {
+                ""
+            } else {
+                "  not in hyprctl's list"
+            };
+            lines.push(format!("  {mode:<22}{}{note}", timing.source));
+        }
+        let extra: Vec<&String> = modes
+            .iter()
+            .filter(|mode| {
+                parse_mode(mode).is_some_and(|(width, height, refresh)| {
+                    let timing = Timing::new(width, height, refresh, "");
+                    !self.timings.iter().any(|claimed| claimed.same_mode(&timing))
+                })
+            })
+            .collect();
+        if !extra.is_empty() {
+            lines.push(format!("Listed by hyprctl only ({})", extra.len()));
+            lines.extend(extra.iter().map(|mode| format!("  {mode}")));
+        }
+        lines
+    }
+}
+
+/// Byte 20: the video input.
+fn input(byte: u8, revision: u8) -> String {
+    if byte & 0x80 == 0 {
+        return "analog".to_string();
+    }
+    if revision < 4 {
+        return "digital".to_string();
+    }
+    let mut parts = vec!["digital"];
+    let interface = match byte & 0x0F {
+        1 => "DVI",
+        2 | 3 => "HDMI",
+        4 => "MDDI",
+        5 => "DisplayPort",
+        _ => "",
+    };
+    if !interface.is_empty() {
+        parts.push(interface);
+    }
+    let depth = match (byte >> 4) & 0x07 {
+        0 | 7 => String::new(),
+        n => format!("{} bits per color", 4 + 2 * n),
+    };
+    if !depth.is_empty() {
+        parts.push(&depth);
+    }
+    parts.join(", ")
+}
+
+/// An 18-byte detailed timing descriptor.
+fn detailed_timing(desc: &[u8], source: &str) -> Option<Timing> {
+    let clock = u16::from_le_bytes([desc[0], desc[1]]) as f64 * 10_000.0;
+    let h_active = desc[2] as u32 | ((desc[4] as u32 & 0xF0) << 4);
+    let h_blank = desc[3] as u32 | ((desc[4] as u32 & 0x0F) << 8);
+    let v_active = desc[5] as u32 | ((desc[7] as u32 & 0xF0) << 4);
+    let v_blank = desc[6] as u32 | ((desc[7] as u32 & 0x0F) << 8);
+    let total = ((h_active + h_blank) * (v_active + v_blank)) as f64;
+    if total == 0.0 {
+        return None;
+    }
+    let interlaced = desc[17] & 0x80 != 0;
+    let mut timing = Timing::new(h_active, v_active, clock / total, source);
+    if interlaced {
+        // Each field is half the frame; the rate is per field
+        timing.height *= 2;
+        timing.interlaced = true;
+    }
+    Some(timing)
+}
+
+/// Text in a display descriptor: up to 13 bytes, ended by a newline.
+fn descriptor_text(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0x0A).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+/// A range limits descriptor (`0xFD`). EDID 1.4 adds 255 to a limit whose
+/// offset flag is set.
+fn range_limits(desc: &[u8]) -> RangeLimits {
+    let flags = desc[4];
+    let limit = |value: u8, offset: bool| value as u16 + if offset { 255 } else { 0 };
+    RangeLimits {
+        vertical_hz: (limit(desc[5], flags & 0x03 == 0x03), limit(desc[6], flags & 0x02 != 0)),
+        horizontal_khz: (limit(desc[7], flags & 0x0C == 0x0C), limit(desc[8], flags & 0x08 != 0)),
+        max_pixel_clock_mhz: (desc[9] > 0).then_some(desc[9] as u16 * 10),
+    }
+}
+
+/// The HDR static metadata data block, from its EOTF byte on.
+fn hdr(eotfs: u8, luminance: &[u8]) -> Hdr {
+    let names = ["SDR", "HDR", "PQ (ST 2084)", "HLG"];
+    let eotfs = names.iter().enumerate().filter(|(i, _)| eotfs & (1 << i) != 0);
+    let code = |i: usize| luminance.get(i).copied().filter(|&cv| cv > 0);
+    let max = |cv: u8| 50.0 * 2f64.powf(cv as f64 / 32.0);
+    let max_luminance = code(0).map(max);
+    Hdr {
+        eotfs: eotfs.map(|(_, name)| *name).collect(),
+        max_luminance,
+        max_frame_average: code(1).map(max),
+        min_luminance: code(2)
+            .zip(max_luminance)
+            .map(|(cv, top)| top * (cv as f64 / 255.0).powi(2) / 100.0),
+    }
+}
+
+/// The colorimetry data block: which wide gamuts it takes.
+fn colorimetry(bits: u8, rest: &[u8]) -> Vec<&'static str> {
+    let names = [
+        (0x80, "BT.2020 RGB"),
+        (0x40, "BT.2020 YCC"),
+        (0x20, "BT.2020 cYCC"),
+        (0x10, "opRGB"),
+        (0x08, "opYCC 601"),
+        (0x04, "sYCC 601"),
+        (0x02, "xvYCC 709"),
+        (0x01, "xvYCC 601"),
+    ];
+    let mut out: Vec<&str> =
+        names.iter().filter(|(bit, _)| bits & bit != 0).map(|(_, name)| *name).collect();
+    if rest.first().is_some_and(|b| b & 0x80 != 0) {
+        out.push("DCI-P3");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4K Dell over DisplayPort with HDR, and a CTA extension.
+    fn sample() -> Vec<u8> {
+        let mut b = vec![0u8; 256];
+        b[..8].copy_from_slice(&HEADER);
+        b[8..20].copy_from_slice(&[0x10, 0xAC, 0xC6, 0xA0, 0x78, 0x56, 0x34, 0x12, 23, 31, 1, 4]);
+        b[20..23].copy_from_slice(&[0xB5, 60, 34]);
+        b[35..38].copy_from_slice(&[0x21, 0x08, 0x00]);
+        // Standard timings: 1920x1080@60, the rest unused
+        b[38..54].fill(0x01);
+        b[38..40].copy_from_slice(&[0xD1, 0xC0]);
+        // 3840x2160 at 533.25 MHz, 597 x 336 mm
+        b[54..72].copy_from_slice(&[
+            0x4D, 0xD0, 0x00, 0xA0, 0xF0, 0x70, 0x3E, 0x80, 0x30, 0x20, 0x35, 0x00, 0x55, 0x50,
+            0x21, 0x00, 0x00, 0x1A,
+        ]);
+        b[72..90].copy_from_slice(b"\0\0\0\xFC\0DELL U2720Q\n ");
+        b[90..108].copy_from_slice(b"\0\0\0\xFD\0\x30\x4B\x1E\x8C\x3C\0\x0A      ");
+        b[108..126].copy_from_slice(b"\0\0\0\xFF\0ABC123\n      ");
+        b[126] = 1;
+        let cta: &[u8] = &[
+            0x02, 0x03, 19, 0x70, // CTA-861 rev 3, detailed timings from byte 19
+            0x43, 0x90, 97, 4, // VICs 16 (native), 97, 4
+            0xE6, 0x06, 0x0D, 0x01, 115, 96, 23, // HDR: SDR, PQ, HLG
+            0xE3, 0x05, 0xC0, 0x80, // BT.2020 RGB and YCC, DCI-P3
+            // 1920x1080 at 148.5 MHz
+            0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40, 0x58, 0x2C, 0x45, 0x00, 0x55, 0x50,
+            0x21, 0x00, 0x00, 0x1E,
+        ];
+        b[128..128 + cta.len()].copy_from_slice(cta);
+        for block in b.chunks_mut(128) {
+            let sum = block[..127].iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+            block[127] = 0u8.wrapping_sub(sum);
+        }
+        b
+    }
+
+    #[test]
+    fn decodes_identity_limits_and_hdr() {
+        let edid = Edid::parse(&sample()).unwrap();
+        assert_eq!((edid.manufacturer.as_str(), edid.product), ("DEL", 0xA0C6));
+        assert_eq!(edid.name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(edid.serial.as_deref(), Some("ABC123"));
+        assert_eq!((edid.serial_number, edid.made), (0x12345678, (23, 2021)));
+        assert_eq!(edid.input, "digital, DisplayPort, 10 bits per color");
+        let range = edid.range.unwrap();
+        assert_eq!((range.vertical_hz, range.horizontal_khz), ((48, 75), (30, 140)));
+        assert_eq!(range.max_pixel_clock_mhz, Some(600));
+        assert_eq!(edid.colorimetry, ["BT.2020 RGB", "BT.2020 YCC", "DCI-P3"]);
+        assert!(edid.bad_checksums.is_empty());
+        let hdr = edid.hdr.unwrap();
+        assert_eq!(hdr.eotfs, ["SDR", "PQ (ST 2084)", "HLG"]);
+        assert_eq!(hdr.max_frame_average, Some(400.0));
+        assert!((hdr.min_luminance.unwrap() - 0.049).abs() < 0.001);
+    }
+
+    #[test]
+    fn timings_are_listed_once_and_checked_against_hyprctl() {
+        let edid = Edid::parse(&sample()).unwrap();
+        let modes: Vec<String> = edid
+            .timings
+            .iter()
+            .map(|t| format!("{}x{}@{:.0} {}", t.width, t.height, t.refresh, t.source))
+            .collect();
+        assert_eq!(
+            modes,
+            [
+                "3840x2160@60 preferred",
+                "1920x1080@60 detailed",
+                "1280x720@60 CTA VIC 4",
+                "1024x768@60 established",
+                "800x600@60 established",
+                "640x480@60 established",
+            ]
+        );
+
+        let hyprctl: Vec<String> =
+            ["3840x2160@60.00Hz", "1920x1080@60.00Hz", "640x480@59.94Hz", "2560x1440@59.95Hz"]
+                .iter()
+                .map(|m| m.to_string())
+                .collect();
+        let report = edid.report(&hyprctl);
+        let line = |start: &str| report.iter().find(|l| l.trim_start().starts_with(start));
+        assert!(!line("3840x2160@60.00Hz").unwrap().contains("not in"));
+        assert!(line("1280x720@60.00Hz").unwrap().ends_with("not in hyprctl's list"));
+        assert!(report.contains(&"Listed by hyprctl only (1)".to_string()), "{report:#?}");
+        assert!(line("HDR").unwrap().contains("max 604 cd/m², frame average 400 cd/m²"));
+    }
+
+    #[test]
+    fn reads_from_sysfs_and_rejects_garbage() {
+        let root = std::env::temp_dir().join(format!("hyprmon_edid_{}", std::process::id()));
+        fs::create_dir_all(root.join("card1-DP-2")).unwrap();
+        fs::create_dir_all(root.join("card1-HDMI-A-1")).unwrap();
+        fs::write(root.join("card1-DP-2/edid"), sample()).unwrap();
+        fs::write(root.join("card1-HDMI-A-1/edid"), b"").unwrap();
+        assert_eq!(read_from(&root, "DP-2").unwrap(), sample());
+        assert!(read_from(&root, "HDMI-A-1").is_err());
+        assert!(read_from(&root, "DP-9").is_err());
+        let _ = fs::remove_dir_all(&root);
+
+        let mut bad = sample();
+        bad[60] ^= 1;
+        assert_eq!(Edid::parse(&bad).unwrap().bad_checksums, [0]);
+        assert!(Edid::parse(&[0; 128]).is_err());
+        let dump = hex_dump(&sample()[..18]);
+        assert_eq!(dump, ["00 ff ff ff ff ff ff 00 10 ac c6 a0 78 56 34 12", "17 1f"]);
+    }
+}
@@ -0,0 +1,200 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+/// Runs the external programs hyprmon drives (`hyprctl`, `wl-copy`). Everything
+/// that shells out goes through one of these, so tests can swap in [`Replay`]
+/// and answer with recorded output instead of a live Hyprland.
+pub trait Executor: Send + Sync {
+    /// Run `program` and return what it printed. A program that cannot be
+    /// started or exits unsuccessfully is an error.
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Vec<u8>>;
+
+    /// Run `program` with `input` on its stdin and wait for it to exit, without
+    /// reading its output: `wl-copy` forks to serve the clipboard and keeps
+    /// any captured pipe open for as long as it does.
+    fn feed(&self, program: &str, args: &[&str], input: &[u8]) -> io::Result<()>;
+
+    /// The executor that really runs commands: itself, or the wrapped one for
+    /// [`DryRun`], for a command that must run even in a dry run.
+    fn inner(&self) -> &dyn Executor;
+}
+
+/// The real thing: spawns processes.
+pub struct System;
+
+impl Executor for System {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+        let output = Command::new(program).args(args).stdin(Stdio::null()).output()?;
+        if !output.status.success() {
+            return Err(failed(program, output.status, &output.stdout));
+        }
+        Ok(output.stdout)
+    }
+
+    fn feed(&self, program: &str, args: &[&str], input: &[u8]) -> io::Result<()> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input)?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(failed(program, status, b""));
+        }
+        Ok(())
+    }
+
+    fn inner(&self) -> &dyn Executor {
+        self
+    }
+}
+
+/// hyprctl explains itself on stdout (e.g. "HYPRLAND_INSTANCE_SIGNATURE not
+/// set!"), so that is what the error carries.
+fn failed(program: &str, status: std::process::ExitStatus, stdout: &[u8]) -> io::Error {
+    let said = String::from_utf8_lossy(stdout);
+    match said.trim() {
+        "" => io::Error::other(format!("{program} failed ({status})")),
+        said => io::Error::other(format!("{program} failed: {said}")),
+    }
+}
+
+/// `--dry-run` executor: queries (`hyprctl … -j`) still go to the wrapped
+/// executor so commands see the real state; anything else is printed instead
+/// of run, and answered the way hyprctl confirms a command.
+pub struct DryRun<E>(pub E);
+
+impl<E: Executor> Executor for DryRun<E> {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+        if args.last() == Some(&"-j") {
+            return self.0.output(program, args);
+        }
+        println!("would run: {}", command_line(program, args));
+        Ok(b"ok".to_vec())
+    }
+
+    fn feed(&self, program: &str, args: &[&str], _input: &[u8]) -> io::Result<()> {
+        println!("would run: {}", command_line(program, args));
+        Ok(())
+    }
+
+    fn inner(&self) -> &dyn Executor {
+        &self.0
+    }
+}
+
+/// `program` and `args` as they would be typed, arguments with spaces quoted.
+fn command_line(program: &str, args: &[&str]) -> String {
+    let mut line = program.to_string();
+    for arg in args {
+        line.push(' ');
+        if arg.is_empty() || arg.contains(char::is_whitespace) {
+            line.push_str(&format!("'{}'", arg.replace('\'', r"'\''")));
+        } else {
+            line.push_str(arg);
+        }
+    }
+    line
+}
+
+/// The executor `App::new` and the CLI start with.
+pub fn system() -> Arc<dyn Executor> {
+    Arc::new(System)
+}
+
+/// Test executor: answers each command line with recorded output and keeps
+/// a log of what was run. Unknown commands fail as if not installed.
+#[cfg(test)]
+#[derive(Default)]
+pub struct Replay {
+    answers: std::sync::Mutex<Vec<(String, Vec<u8>)>>,
+    calls: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl Replay {
+    /// Answer `command` (program and args joined by spaces) with `stdout`.
+    pub fn answer(self, command: &str, stdout: impl Into<Vec<u8>>) -> Self {
+        self.set(command, stdout);
+        self
+    }
+
+    /// Answer `command` with `stdout` from now on, e.g. after a hotplug.
+    pub fn set(&self, command: &str, stdout: impl Into<Vec<u8>>) {
+        let mut answers = self.answers.lock().unwrap();
+        answers.retain(|(known, _)| known != command);
+        answers.push((command.to_string(), stdout.into()));
+    }
+
+    /// Command lines run so far, oldest first.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, program: &str, args: &[&str]) -> String {
+        let line = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>();
+        let line = line.join(" ");
+        self.calls.lock().unwrap().push(line.clone());
+        line
+    }
+}
+
+#[cfg(test)]
+impl Executor for Replay {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+        let line = self.record(program, args);
+        let answers = self.answers.lock().unwrap();
+        match answers.iter().find(|(command, _)| *command == line) {
+            Some((_, stdout)) => Ok(stdout.clone()),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, format!("no answer for {line}"))),
+        }
+    }
+
+    fn feed(&self, program: &str, args: &[&str], _input: &[u8]) -> io::Result<()> {
+        self.output(program, args).map(|_| ())
+    }
+
+    fn inner(&self) -> &dyn Executor {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_reports_failures_and_replay_records_calls() {
+        assert_eq!(System.output("sh", &["-c", "echo hi"]).unwrap(), b"hi\n");
+        let err = System.output("sh", &["-c", "echo not running; exit 1"]).unwrap_err();
+        assert_eq!(err.to_string(), "sh failed: not running");
+        assert!(System.output("hyprmon-no-such-program", &[]).is_err());
+        System.feed("sh", &["-c", "cat >/dev/null"], b"text").unwrap();
+
+        let replay = Replay::default().answer("hyprctl reload", "ok");
+        assert_eq!(replay.output("hyprctl", &["reload"]).unwrap(), b"ok");
+        assert!(replay.output("hyprctl", &["monitors", "-j"]).is_err());
+        assert_eq!(replay.calls(), ["hyprctl reload", "hyprctl monitors -j"]);
+    }
+
+    #[test]
+    fn dry_run_queries_but_only_prints_commands() {
+        let dry = DryRun(Replay::default().answer("hyprctl monitors -j", "[]"));
+        assert_eq!(dry.output("hyprctl", &["monitors", "-j"]).unwrap(), b"[]");
+        assert_eq!(dry.output("hyprctl", &["keyword", "monitor", "DP-1,disable"]).unwrap(), b"ok");
+        assert_eq!(dry.output("ssh", &["tv", "hyprmon", "apply", "--dry-run"]).unwrap(), b"ok");
+        dry.feed("wl-copy", &[], b"text").unwrap();
+        assert_eq!(dry.0.calls(), ["hyprctl monitors -j"]);
+        assert!(dry.inner().output("hyprctl", &["reload"]).is_err());
+        assert_eq!(dry.0.calls(), ["hyprctl monitors -j", "hyprctl reload"]);
+        assert_eq!(
+            command_line("hyprctl", &["notify", "-1", "it's on", ""]),
+            r"hyprctl notify -1 'it'\''s on' ''"
+        );
+    }
+}
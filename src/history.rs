@@ -0,0 +1,251 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::clock;
+use crate::config::{MonitorDatabase, SavedMonitor};
+use crate::monitor::{MonitorConfig, Rotation};
+
+/// One monitor's settings before and after an apply. `None` on either side
+/// means the monitor was absent (newly connected / no longer present).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorChange {
+    pub monitor: String,
+    pub before: Option<SavedMonitor>,
+    pub after: Option<SavedMonitor>,
+}
+
+impl MonitorChange {
+    /// Human-readable field diffs, e.g. `["scale 100% -> 150%"]`.
+    pub fn describe(&self) -> Vec<String> {
+        let (before, after) = match (&self.before, &self.after) {
+            (Some(b), Some(a)) => (b, a),
+            (None, Some(_)) => return vec!["added".to_string()],
+            (Some(_), None) => return vec!["removed".to_string()],
+            (None, None) => return vec![],
+        };
+
+        let mut out = Vec::new();
+        if before.resolution != after.resolution {
+            out.push(format!("resolution {} -> {}", before.resolution, after.resolution));
+        }
+        if before.refresh_rate != after.refresh_rate {
+            out.push(format!(
+                "refresh {:.0}Hz -> {:.0}Hz",
+                before.refresh_rate, after.refresh_rate
+            ));
+        }
+        if before.scale != after.scale {
+            out.push(format!(
+                "scale {:.0}% -> {:.0}%",
+                before.scale * 100.0,
+                after.scale * 100.0
+            ));
+        }
+        if before.rotation != after.rotation {
+            out.push(format!(
+                "rotation {} -> {}",
+                Rotation::from_transform(before.rotation).as_str(),
+                Rotation::from_transform(after.rotation).as_str()
+            ));
+        }
+        if (before.position_x, before.position_y) != (after.position_x, after.position_y) {
+            out.push(format!(
+                "position {}x{} -> {}x{}",
+                before.position_x, before.position_y, after.position_x, after.position_y
+            ));
+        }
+        if before.is_primary != after.is_primary {
+            out.push(format!("primary {} -> {}", before.is_primary, after.is_primary));
+        }
+        out
+    }
+
+    /// `monitor: diffs` on one line, as the log and the confirm dialog show it.
+    pub fn summary(&self) -> String {
+        format!("{}: {}", self.monitor, self.describe().join(", "))
+    }
+}
+
+/// A single applied change, as stored in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Unix seconds (see [`clock::now`]).
+    pub timestamp: u64,
+    /// Workspace the change was applied to.
+    pub profile: String,
+    pub changes: Vec<MonitorChange>,
+}
+
+impl HistoryEntry {
+    /// Diff two live layouts by stable monitor key. Unchanged monitors are
+    /// omitted so the log only carries what actually moved.
+    pub fn between(profile: &str, before: &[MonitorConfig], after: &[MonitorConfig]) -> Self {
+        let snapshot = |list: &[MonitorConfig]| -> Vec<(String, SavedMonitor)> {
+            list.iter()
+                .map(|m| (MonitorDatabase::get_monitor_key(m), SavedMonitor::from_monitor(m)))
+                .collect()
+        };
+        let before = snapshot(before);
+        let after = snapshot(after);
+
+        let mut changes: Vec<MonitorChange> = after
+            .iter()
+            .filter_map(|(key, now)| {
+                let prev = before.iter().find(|(k, _)| k == key).map(|(_, s)| s.clone());
+                (prev.as_ref() != Some(now)).then(|| MonitorChange {
+                    monitor: key.clone(),
+                    before: prev,
+                    after: Some(now.clone()),
+                })
+            })
+            .collect();
+        changes.extend(
+            before
+                .iter()
+                .filter(|(key, _)| !after.iter().any(|(k, _)| k == key))
+                .map(|(key, prev)| MonitorChange {
+                    monitor: key.clone(),
+                    before: Some(prev.clone()),
+                    after: None,
+                }),
+        );
+
+        Self {
+            timestamp: clock::now(),
+            profile: profile.to_string(),
+            changes,
+        }
+    }
+
+    /// Multi-line summary used by both `hyprmon history` and the TUI view.
+    pub fn lines(&self) -> Vec<String> {
+        let mut out = vec![format!(
+            "{}  {}",
+            clock::format_local(self.timestamp),
+            self.profile
+        )];
+        if self.changes.is_empty() {
+            out.push("    (re-applied, no setting changes)".to_string());
+        }
+        for change in &self.changes {
+            out.push(format!("    {}", change.summary()));
+        }
+        out
+    }
+}
+
+/// Append-only JSON-lines log of applied changes under the XDG state dir.
+#[derive(Debug, Clone)]
+pub struct HistoryLog {
+    path: PathBuf,
+}
+
+impl Default for HistoryLog {
+    fn default() -> Self {
+        Self::at(Self::default_path())
+    }
+}
+
+impl HistoryLog {
+    /// `$XDG_STATE_HOME/hyprmon/history.jsonl` (`~/.local/state/...` by default).
+    pub fn default_path() -> PathBuf {
+        dirs::state_dir()
+            .unwrap_or_else(|| dirs::home_dir().unwrap().join(".local/state"))
+            .join("hyprmon/history.jsonl")
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// All readable entries, oldest first. Malformed lines (e.g. a write cut
+    /// short by a crash) are skipped rather than poisoning the whole log.
+    pub fn load(&self) -> Vec<HistoryEntry> {
+        fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(tag: &str) -> HistoryLog {
+        let mut p = std::env::temp_dir();
+        p.push(format!("hyprmon_history_{tag}_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&p);
+        HistoryLog::at(p)
+    }
+
+    #[test]
+    fn between_records_only_changed_added_and_removed_monitors() {
+        let a = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        let b = MonitorConfig::for_test("HDMI-A-1", "MSI", "MP", "2560x1440");
+        let c = MonitorConfig::for_test("DP-1", "Dell", "U", "1920x1080");
+        let mut a2 = a.clone();
+        a2.scale = 1.5;
+
+        let entry = HistoryEntry::between("Office", &[a, b.clone(), c], &[a2, b]);
+        assert_eq!(entry.profile, "Office");
+        assert_eq!(entry.changes.len(), 2);
+        assert_eq!(entry.changes[0].monitor, "eDP-1");
+        assert_eq!(entry.changes[0].describe(), vec!["scale 100% -> 150%"]);
+        assert_eq!(entry.changes[1].monitor, "desc:Dell U");
+        assert_eq!(entry.changes[1].describe(), vec!["removed"]);
+    }
+
+    #[test]
+    fn append_then_load_skips_malformed_lines() {
+        let log = temp_log("rt");
+        let entry = HistoryEntry {
+            timestamp: 42,
+            profile: "Desk".into(),
+            changes: vec![],
+        };
+        log.append(&entry).unwrap();
+        fs::write(
+            log.path(),
+            format!("{}not json\n", fs::read_to_string(log.path()).unwrap()),
+        )
+        .unwrap();
+        log.append(&entry).unwrap();
+
+        let loaded = log.load();
+        assert_eq!(loaded, vec![entry.clone(), entry]);
+        let _ = fs::remove_file(log.path());
+    }
+
+    #[test]
+    fn lines_mention_profile_and_field_diffs() {
+        let entry = HistoryEntry {
+            timestamp: 0,
+            profile: "Desk".into(),
+            changes: vec![],
+        };
+        let lines = entry.lines();
+        assert!(lines[0].ends_with("Desk"));
+        assert!(lines[1].contains("no setting changes"));
+    }
+}
@@ -7,8 +7,33 @@ use std::sync::mpsc::Sender;
 
 #[derive(Debug, Clone)]
 pub enum HyprEvent {
-    MonitorAdded(String),
+    /// Monitor hotplug.
+    MonitorAdded(AddedMonitor),
+    /// Monitor unplugged, by connector name.
     MonitorRemoved(String),
+    /// A workspace or window came, went or moved, so which workspaces are on
+    /// which monitor (and their window counts) may have changed.
+    WorkspacesChanged,
+    /// Hyprland moved focus to this connector.
+    MonitorFocused(String),
+}
+
+/// A monitor Hyprland just brought up. `monitoraddedv2` says which one in
+/// full; the v1 event that precedes it only gives the connector name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddedMonitor {
+    /// Hyprland's monitor id.
+    pub id: Option<u32>,
+    pub name: String,
+    /// EDID make, model and serial, when the event carried them.
+    pub description: Option<String>,
+}
+
+impl AddedMonitor {
+    /// A v1 event: the connector name and nothing else.
+    pub fn named(name: &str) -> Self {
+        Self { id: None, name: name.to_string(), description: None }
+    }
 }
 
 fn get_socket_path() -> Result<PathBuf> {
@@ -33,7 +58,7 @@ fn get_socket_path() -> Result<PathBuf> {
     Ok(old_path)
 }
 
-pub fn start_listener(tx: Sender<HyprEvent>) -> Result<()> {
+pub fn start_listener<T: From<HyprEvent> + Send + 'static>(tx: Sender<T>) -> Result<()> {
     let socket_path = get_socket_path()?;
     let stream = UnixStream::connect(&socket_path)?;
     let reader = BufReader::new(stream);
@@ -41,7 +66,7 @@ pub fn start_listener(tx: Sender<HyprEvent>) -> Result<()> {
     std::thread::spawn(move || {
         for line in reader.lines().map_while(Result::ok) {
             if let Some(event) = parse_event(&line) {
-                let _ = tx.send(event);
+                let _ = tx.send(event.into());
             }
         }
     });
@@ -59,8 +84,28 @@ fn parse_event(line: &str) -> Option<HyprEvent> {
     let data = parts[1];
 
     match event_type {
-        "monitoradded" | "monitoraddedv2" => Some(HyprEvent::MonitorAdded(data.to_string())),
+        "monitoradded" => Some(HyprEvent::MonitorAdded(AddedMonitor::named(data))),
+        // ID,NAME,DESCRIPTION; the description may hold commas of its own
+        "monitoraddedv2" => {
+            let mut fields = data.splitn(3, ',');
+            let id = fields.next()?.parse().ok();
+            let name = fields.next().filter(|n| !n.is_empty())?.to_string();
+            let description = fields
+                .next()
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string);
+            Some(HyprEvent::MonitorAdded(AddedMonitor { id, name, description }))
+        }
         "monitorremoved" => Some(HyprEvent::MonitorRemoved(data.to_string())),
+        // NAME,WORKSPACE (v2: NAME,WORKSPACE_ID)
+        "focusedmon" | "focusedmonv2" => {
+            let name = data.split(',').next().filter(|n| !n.is_empty())?;
+            Some(HyprEvent::MonitorFocused(name.to_string()))
+        }
+        "createworkspace" | "createworkspacev2" | "destroyworkspace" | "destroyworkspacev2"
+        | "moveworkspace" | "moveworkspacev2" | "renameworkspace" | "openwindow"
+        | "closewindow" | "movewindow" | "movewindowv2" => Some(HyprEvent::WorkspacesChanged),
         _ => None,
     }
 }
@@ -73,22 +118,46 @@ mod tests {
     fn parse_event_recognizes_monitor_add_remove() {
         assert!(matches!(
             parse_event("monitoradded>>HDMI-A-1"),
-            Some(HyprEvent::MonitorAdded(s)) if s == "HDMI-A-1"
-        ));
-        assert!(matches!(
-            parse_event("monitoraddedv2>>1,HDMI-A-1,Desc"),
-            Some(HyprEvent::MonitorAdded(s)) if s == "1,HDMI-A-1,Desc"
+            Some(HyprEvent::MonitorAdded(m)) if m == AddedMonitor::named("HDMI-A-1")
         ));
+        let Some(HyprEvent::MonitorAdded(added)) =
+            parse_event("monitoraddedv2>>1,HDMI-A-1,Dell Inc. U2720Q, rev 2")
+        else {
+            panic!("monitoraddedv2 not recognized");
+        };
+        assert_eq!(added.id, Some(1));
+        assert_eq!(added.name, "HDMI-A-1");
+        assert_eq!(added.description.as_deref(), Some("Dell Inc. U2720Q, rev 2"));
         assert!(matches!(
             parse_event("monitorremoved>>eDP-1"),
             Some(HyprEvent::MonitorRemoved(s)) if s == "eDP-1"
         ));
     }
 
+    #[test]
+    fn parse_event_notices_workspace_and_window_moves() {
+        for line in ["createworkspacev2>>3,3", "moveworkspace>>3,DP-1", "openwindow>>a,1,kitty,~"] {
+            assert!(matches!(parse_event(line), Some(HyprEvent::WorkspacesChanged)), "{line}");
+        }
+    }
+
+    #[test]
+    fn parse_event_follows_monitor_focus() {
+        for line in ["focusedmon>>DP-1,3", "focusedmonv2>>DP-1,3"] {
+            assert!(matches!(
+                parse_event(line),
+                Some(HyprEvent::MonitorFocused(s)) if s == "DP-1"
+            ));
+        }
+        assert!(parse_event("focusedmon>>").is_none());
+    }
+
     #[test]
     fn parse_event_ignores_unrelated_and_malformed() {
-        assert!(parse_event("workspace>>1").is_none()); // unrelated event
+        assert!(parse_event("workspace>>1").is_none()); // focus only
         assert!(parse_event("noseparatorhere").is_none()); // missing ">>"
         assert!(parse_event("").is_none());
+        assert!(parse_event("monitoraddedv2>>1").is_none());
+        assert!(parse_event("monitoraddedv2>>1,,Dell").is_none());
     }
 }
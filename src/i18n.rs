@@ -0,0 +1,706 @@
+use anyhow::{bail, Result};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::prefs::Prefs;
+
+/// Languages the interface text is available in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    En,
+    Vi,
+}
+
+/// Codes accepted by `language = "…"` in config.toml.
+pub const LANGUAGES: &[&str] = &["en", "vi"];
+
+impl Lang {
+    /// `vi`, `vi_VN.UTF-8`, `vi-VN`, … → Vietnamese; `None` for languages
+    /// without a translation.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let base = code.split(['_', '-', '.', '@']).next().unwrap_or("");
+        match base.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Lang::En),
+            "vi" => Some(Lang::Vi),
+            _ => None,
+        }
+    }
+
+    /// `language` from config.toml, else the locale. An unknown code is an
+    /// error so a typo is reported instead of silently showing English.
+    pub fn from_prefs(prefs: &Prefs) -> Result<Self> {
+        match &prefs.language {
+            Some(code) => match Self::from_code(code) {
+                Some(lang) => Ok(lang),
+                None => bail!("unknown language '{code}' (available: {})", LANGUAGES.join(", ")),
+            },
+            None => Ok(Self::from_env(|name| std::env::var(name).ok())),
+        }
+    }
+
+    /// The language of the first locale variable that is set, as gettext
+    /// reads them: `LC_ALL`, then `LC_MESSAGES`, then `LANG`.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| var(name).filter(|v| !v.is_empty()))
+            .map(|value| Self::from_code(&value).unwrap_or(Lang::En))
+            .next()
+            .unwrap_or(Lang::En)
+    }
+}
+
+/// English text → Vietnamese. The English string is the key, gettext style,
+/// so call sites stay readable and a missing entry falls back to English.
+const VI: &[(&str, &str)] = &[
+    // Help bar
+    ("Help", "Trợ giúp"),
+    ("Tab", "Thẻ"),
+    ("Select", "Chọn"),
+    ("Move", "Di chuyển"),
+    ("Primary", "Chính"),
+    ("Manage", "Quản lý"),
+    ("Identify", "Nhận diện"),
+    ("Refresh", "Làm mới"),
+    ("Apply", "Áp dụng"),
+    ("Save as", "Lưu thành"),
+    ("Fallback", "Dự phòng"),
+    ("History", "Lịch sử"),
+    ("Compare", "So sánh"),
+    ("Undo/Redo", "Hoàn tác/Làm lại"),
+    ("Quit", "Thoát"),
+    ("Workspace", "Không gian"),
+    ("Reorder", "Sắp xếp"),
+    ("Pin", "Ghim"),
+    ("New", "Mới"),
+    ("Rename", "Đổi tên"),
+    ("Copy", "Sao chép"),
+    ("Notes", "Ghi chú"),
+    ("Delete", "Xóa"),
+    ("Remove monitor", "Gỡ màn hình"),
+    ("Apply Nth", "Áp dụng thứ N"),
+    ("Edit in Live", "Sửa ở Live"),
+    ("Manual-only", "Chỉ thủ công"),
+    ("Lock", "Khóa"),
+    ("Focus:", "Đang chọn:"),
+    ("Arrangement", "Bố trí"),
+    ("Settings", "Cài đặt"),
+    ("Details", "Chi tiết"),
+    ("Change", "Đổi"),
+    // Status line
+    ("applied", "đã áp dụng"),
+    ("never applied", "chưa áp dụng"),
+    ("game mode", "chế độ game"),
+    ("presenting", "đang trình chiếu"),
+    ("forced 1080p60", "ép 1080p60"),
+    ("auto-apply paused", "tạm dừng tự áp dụng"),
+    ("monitor", "màn hình"),
+    ("monitors", "màn hình"),
+    ("● unsaved", "● chưa lưu"),
+    ("no changes", "không thay đổi"),
+    // Help overlay
+    (" Keybindings ", " Phím tắt "),
+    (" ↑↓ Scroll | Esc Close ", " ↑↓ Cuộn | Esc Đóng "),
+    ("Everywhere", "Mọi nơi"),
+    ("Live tab", "Thẻ Live"),
+    ("Saved tab", "Thẻ Saved"),
+    ("Dialogs", "Hộp thoại"),
+    ("Show this help", "Hiện trợ giúp này"),
+    (
+        "Command palette (fuzzy search over all actions)",
+        "Bảng lệnh (tìm gần đúng mọi thao tác)",
+    ),
+    ("Switch to the Live / Saved tab", "Chuyển sang thẻ Live / Saved"),
+    (
+        "Switch between Arrangement and Settings panels",
+        "Chuyển giữa bảng Bố trí và Cài đặt",
+    ),
+    ("Apply the Nth saved workspace", "Áp dụng không gian đã lưu thứ N"),
+    (
+        "Apply (with a 15s confirm / revert countdown)",
+        "Áp dụng (đếm ngược 15 giây để giữ / hoàn nguyên)",
+    ),
+    ("Lock / unlock the active workspace", "Khóa / mở khóa không gian đang dùng"),
+    (
+        "Pause / resume auto-apply on hotplug (this session)",
+        "Tạm dừng / tiếp tục tự áp dụng khi cắm màn hình (phiên này)",
+    ),
+    ("History of applied changes", "Lịch sử các thay đổi đã áp dụng"),
+    (
+        "Message log (every status message this session)",
+        "Nhật ký thông báo (mọi thông báo trong phiên này)",
+    ),
+    (
+        "Identify monitors (number on each screen)",
+        "Nhận diện màn hình (hiện số trên từng màn hình)",
+    ),
+    (
+        "Copy the monitors.conf that a would write",
+        "Sao chép monitors.conf mà a sẽ ghi",
+    ),
+    (
+        "View the monitors.conf that a would write (+ marks changes)",
+        "Xem monitors.conf mà a sẽ ghi (+ đánh dấu thay đổi)",
+    ),
+    ("Refresh monitor list", "Làm mới danh sách màn hình"),
+    (
+        "Focus the monitor in Hyprland (◉ marks the focused one)",
+        "Chuyển tiêu điểm Hyprland sang màn hình (◉ đánh dấu màn hình đang có tiêu điểm)",
+    ),
+    (
+        "Swap its workspaces with another monitor's",
+        "Hoán đổi không gian làm việc với màn hình khác",
+    ),
+    (
+        "Game mode: top refresh rate + VRR here, VRR off elsewhere; b restores",
+        "Chế độ game: tần số quét cao nhất + VRR ở đây, tắt VRR ở màn khác; b để khôi phục",
+    ),
+    (
+        "Use the known-good preset for the monitor's model (✦ shows one)",
+        "Dùng thiết lập đề xuất cho mẫu màn hình này (✦ cho biết có sẵn)",
+    ),
+    ("Select monitor (from either panel)", "Chọn màn hình (từ bảng nào cũng được)"),
+    (
+        "Monitor details: serial, size, modes, DPMS/VRR (Arrangement)",
+        "Chi tiết màn hình: số sê-ri, kích thước, chế độ, DPMS/VRR (Bố trí)",
+    ),
+    ("Move monitor left / right", "Di chuyển màn hình sang trái / phải"),
+    (
+        "Cancel a mouse drag (so does leaving the panel)",
+        "Hủy kéo chuột (rời khỏi bảng cũng vậy)",
+    ),
+    ("Select setting (from either panel)", "Chọn mục cài đặt (từ bảng nào cũng được)"),
+    (
+        "Open the dropdown for a setting, or type a position (Settings)",
+        "Mở danh sách chọn của mục cài đặt, hoặc nhập vị trí (Cài đặt)",
+    ),
+    (
+        "Step the selected setting (or mouse wheel over it)",
+        "Tăng / giảm mục đang chọn (hoặc cuộn chuột trên nó)",
+    ),
+    (
+        "Fold / unfold the selected setting's group (or click its heading)",
+        "Thu gọn / mở nhóm của mục đang chọn (hoặc nhấp tiêu đề nhóm)",
+    ),
+    ("Make the selected monitor primary", "Đặt màn hình đang chọn làm màn hình chính"),
+    (
+        "Toggle whether hyprmon manages the monitor",
+        "Bật / tắt việc hyprmon quản lý màn hình",
+    ),
+    ("Save the live layout as…", "Lưu bố cục hiện tại thành…"),
+    ("Undo / redo layout edits", "Hoàn tác / làm lại chỉnh sửa bố cục"),
+    ("Compare Live with the active workspace", "So sánh Live với không gian đang dùng"),
+    (
+        "Edit the fallback rule for unknown monitors",
+        "Sửa quy tắc dự phòng cho màn hình lạ",
+    ),
+    ("Previous / next workspace", "Không gian trước / sau"),
+    (
+        "Move workspace left / right (or drag its tab)",
+        "Di chuyển không gian sang trái / phải (hoặc kéo thẻ của nó)",
+    ),
+    ("Pin / unpin workspace", "Ghim / bỏ ghim không gian"),
+    ("New workspace", "Không gian mới"),
+    ("Rename workspace", "Đổi tên không gian"),
+    ("Duplicate workspace", "Nhân bản không gian"),
+    ("Delete workspace", "Xóa không gian"),
+    ("Remove the selected monitor", "Gỡ màn hình đang chọn"),
+    (
+        "Load workspace into Live as unsaved edits",
+        "Nạp không gian vào Live thành chỉnh sửa chưa lưu",
+    ),
+    ("Edit workspace notes", "Sửa ghi chú không gian"),
+    (
+        "Import monitors from another workspace (Space picks several)",
+        "Nhập màn hình từ không gian khác (Space để chọn nhiều)",
+    ),
+    ("Toggle manual-only", "Bật / tắt chỉ thủ công"),
+    (
+        "Turn the selected monitor off / on when applying (DPMS)",
+        "Tắt / bật màn hình đang chọn khi áp dụng (DPMS)",
+    ),
+    ("Confirm / cancel input and pickers", "Xác nhận / hủy nhập liệu và danh sách chọn"),
+    (
+        "Answer confirmations (Keep changes? Delete? Quit?)",
+        "Trả lời xác nhận (Giữ thay đổi? Xóa? Thoát?)",
+    ),
+    (
+        "Nickname the monitor, e.g. Left Dell (shown instead of its model)",
+        "Đặt biệt danh cho màn hình, vd. Dell trái (hiện thay cho tên mẫu)",
+    ),
+    (
+        "Scale every monitor by its pixel density, to refine from",
+        "Đặt tỉ lệ mọi màn hình theo mật độ điểm ảnh, để chỉnh tiếp",
+    ),
+    (
+        "In the Resolution dropdown: hide / show modes under 720p or odd shapes",
+        "Trong danh sách Độ phân giải: ẩn / hiện chế độ dưới 720p hoặc tỉ lệ lạ",
+    ),
+    (
+        "Projector: force 1920x1080@60 here; Ctrl+f or unplugging restores",
+        "Máy chiếu: ép 1920x1080@60 tại đây; Ctrl+f hoặc rút cáp để khôi phục",
+    ),
+    (
+        "Presentation: mirror the laptop on every screen / restore",
+        "Trình chiếu: nhân bản màn hình laptop lên mọi màn hình / khôi phục",
+    ),
+    (
+        "Safe mode: every monitor at its preferred mode, 100%, side by side",
+        "Chế độ an toàn: mọi màn hình ở chế độ ưu tiên, 100%, xếp cạnh nhau",
+    ),
+    (
+        "Restore an earlier monitors.conf (each kept with its diff)",
+        "Khôi phục monitors.conf trước đó (mỗi bản kèm khác biệt)",
+    ),
+    ("Earlier monitors.conf", "monitors.conf trước đó"),
+    (" ↑↓ Pick | Enter Restore | Esc Close ", " ↑↓ Chọn | Enter Khôi phục | Esc Đóng "),
+    ("Same as the current file.", "Giống tệp hiện tại."),
+    (
+        "Changed outside hyprmon: adopt it / re-apply the workspace",
+        "Đổi bên ngoài hyprmon: giữ thay đổi / áp dụng lại không gian",
+    ),
+    (
+        "Confirmations: highlight Yes or No (Enter presses it)",
+        "Xác nhận: chọn Có hoặc Không (Enter để bấm)",
+    ),
+    (
+        "Next suggested name (New workspace, Save as new)",
+        "Tên gợi ý tiếp theo (Không gian mới, Lưu thành mới)",
+    ),
+    (
+        "Move in lists, scroll history and help",
+        "Di chuyển trong danh sách, cuộn lịch sử và trợ giúp",
+    ),
+    (
+        "Compare: save live value / pull saved value",
+        "So sánh: lưu giá trị live / lấy giá trị đã lưu",
+    ),
+    (
+        "Dropdown: page through long lists (Home / End jump)",
+        "Danh sách chọn: lật trang (Home / End để nhảy)",
+    ),
+    (
+        "Text fields: move (Shift selects, Ctrl+A all, Ctrl+U clears)",
+        "Ô nhập: di chuyển (Shift để chọn, Ctrl+A chọn hết, Ctrl+U xóa)",
+    ),
+    (
+        "Dropdown: filter options (Backspace edits, Esc clears)",
+        "Danh sách chọn: lọc (Backspace để sửa, Esc để xóa)",
+    ),
+    // Tabs and panels
+    ("[modified] ", "[đã sửa] "),
+    ("Locked:", "Đã khóa:"),
+    (" Workspaces: ", " Không gian: "),
+    (" Arrangement ", " Bố trí "),
+    (" Settings ", " Cài đặt "),
+    ("Terminal too small", "Cửa sổ terminal quá nhỏ"),
+    ("need at least", "cần tối thiểu"),
+    ("q quits", "q để thoát"),
+    (" Monitor Arrangement ", " Bố trí màn hình "),
+    ("No monitors detected.", "Không phát hiện màn hình nào."),
+    ("Drop at slot", "Thả vào ô"),
+    ("off", "tắt"),
+    ("mirrored", "nhân bản"),
+    ("not connected", "chưa kết nối"),
+    ("unmanaged", "không quản lý"),
+    ("queued", "đang chờ"),
+    ("setting…", "đang đặt…"),
+    ("verified", "đã xác minh"),
+    ("failed", "thất bại"),
+    ("Release to drop, Esc to cancel", "Thả ra để đặt, Esc để hủy"),
+    (
+        "Drag to move | ←→/hl Select | Shift+←→/HL Reorder | ⏎ Details | P Primary | I Identify",
+        "Kéo để di chuyển | ←→/hl Chọn | Shift+←→/HL Sắp xếp | ⏎ Chi tiết | P Chính | I Nhận diện",
+    ),
+    (
+        "⚠ Identical monitors share saved settings | A Choose how to tell them apart",
+        "⚠ Các màn hình giống hệt nhau dùng chung cài đặt | A Chọn cách phân biệt",
+    ),
+    ("Settings for Monitor", "Cài đặt cho màn hình"),
+    ("Mode", "Chế độ"),
+    ("Geometry", "Hình học"),
+    ("Color", "Màu sắc"),
+    ("Advanced", "Nâng cao"),
+    ("Resolution:", "Độ phân giải:"),
+    ("Refresh Rate:", "Tần số quét:"),
+    ("Scale:", "Tỉ lệ:"),
+    ("Rotation:", "Xoay:"),
+    ("X Position:", "Vị trí X:"),
+    ("Y Position:", "Vị trí Y:"),
+    ("[Change]", "[Đổi]"),
+    ("Set as primary monitor", "Đặt làm màn hình chính"),
+    ("changed since last apply", "đã đổi từ lần áp dụng trước"),
+    ("Suggested:", "Đề xuất:"),
+    ("s uses it", "s để dùng"),
+    (" Saved Settings ", " Cài đặt đã lưu "),
+    (
+        " Note: Saved configs are read-only. Edit in Live panel.",
+        " Lưu ý: cấu hình đã lưu chỉ đọc. Hãy sửa ở thẻ Live.",
+    ),
+    // Dialogs
+    (" Confirm ", " Xác nhận "),
+    ("Do you want to keep these changes?", "Bạn có muốn giữ các thay đổi này?"),
+    ("Auto-revert in {} seconds", "Tự hoàn nguyên sau {} giây"),
+    ("Re-applied; no settings changed.", "Đã áp dụng lại; không có thiết lập nào đổi."),
+    ("Hyprland runs these otherwise:", "Hyprland đang chạy khác với yêu cầu:"),
+    (" Changed Outside hyprmon ", " Đã đổi bên ngoài hyprmon "),
+    (
+        "Hyprland's monitors changed since hyprmon last looked:",
+        "Màn hình của Hyprland đã đổi kể từ lần hyprmon xem gần nhất:",
+    ),
+    (
+        "Adopt it into {}, or re-apply {} over it?",
+        "Đưa thay đổi vào {}, hay áp dụng lại {} đè lên?",
+    ),
+    ("… and {} more", "… và {} mục khác"),
+    (" Scale Not Accepted ", " Tỉ lệ không hợp lệ "),
+    (
+        "Hyprland needs a whole-pixel logical size and would round:",
+        "Hyprland cần kích thước logic nguyên điểm ảnh và sẽ làm tròn:",
+    ),
+    (
+        "Use the fixed scales? No applies them as typed.",
+        "Dùng tỉ lệ đã sửa? Không thì áp dụng như đã nhập.",
+    ),
+    (" Warning ", " Cảnh báo "),
+    (
+        "You have unsaved changes.\nAre you sure you want to quit?",
+        "Bạn có thay đổi chưa lưu.\nBạn có chắc muốn thoát?",
+    ),
+    (" Enter Confirm | Esc Cancel ", " Enter Xác nhận | Esc Hủy "),
+    (" Enter Select | Esc Cancel ", " Enter Chọn | Esc Hủy "),
+    (
+        " Space Pick | Enter Import | Esc Cancel ",
+        " Space Đánh dấu | Enter Nhập | Esc Hủy ",
+    ),
+    (" Select ", " Chọn "),
+    (" Tab: all ", " Tab: tất cả "),
+    (" Tab: fewer ", " Tab: bớt "),
+    (" No match", " Không khớp"),
+    (" Change History ", " Lịch sử thay đổi "),
+    ("No changes recorded yet.", "Chưa ghi nhận thay đổi nào."),
+    (" Hyprland Config Errors ", " Lỗi cấu hình Hyprland "),
+    ("Generated monitors.conf", "monitors.conf sẽ tạo"),
+    ("{} lines differ from disk", "{} dòng khác với tệp trên đĩa"),
+    (" ↑↓ Scroll | Esc Keep or revert ", " ↑↓ Cuộn | Esc Giữ hoặc hoàn nguyên "),
+    (" Monitor Details ", " Chi tiết màn hình "),
+    (" Command Palette ", " Bảng lệnh "),
+    (" ↑↓ Select | Enter Run | Esc Close ", " ↑↓ Chọn | Enter Chạy | Esc Đóng "),
+    (" No matching command", " Không có lệnh phù hợp"),
+    (" Messages ", " Thông báo "),
+    ("No messages yet.", "Chưa có thông báo nào."),
+    // Monitor details and compare dialogs
+    ("Connector", "Cổng"),
+    ("Make", "Hãng"),
+    ("Model", "Mẫu"),
+    ("Serial", "Số sê-ri"),
+    ("Physical size", "Kích thước"),
+    ("Current mode", "Chế độ hiện tại"),
+    ("Workspaces", "Không gian"),
+    ("Preset", "Cài đặt mẫu"),
+    ("unknown", "không rõ"),
+    ("none", "không có"),
+    ("none known", "chưa biết"),
+    ("on", "bật"),
+    ("(windows in parentheses)", "(số cửa sổ trong ngoặc)"),
+    ("(s uses it)", "(s để dùng)"),
+    ("Modes ({})", "Chế độ ({})"),
+    ("not readable from /sys/class/drm", "không đọc được từ /sys/class/drm"),
+    ("Live matches the saved workspace.", "Hiện tại khớp với không gian đã lưu."),
+    // Buttons
+    ("[Y] Yes", "[Y] Có"),
+    ("[N] No", "[N] Không"),
+    ("[Y] Adopt", "[Y] Giữ thay đổi"),
+    ("[N] Re-apply", "[N] Áp dụng lại"),
+    ("[Cancel]", "[Hủy]"),
+    // Toasts
+    (
+        "{} drives at most {} displays but {} are on ({}); one may stay black",
+        "{} chỉ xuất tối đa {} màn hình nhưng đang bật {} ({}); có thể một màn hình bị tối",
+    ),
+    ("Config: {} (using defaults)", "Cấu hình: {} (dùng mặc định)"),
+    ("Config: {} (using default keys)", "Cấu hình: {} (dùng phím mặc định)"),
+    ("Config: {} (using default theme)", "Cấu hình: {} (dùng giao diện mặc định)"),
+    ("Config: {} (using English)", "Cấu hình: {} (dùng tiếng Anh)"),
+    ("Presets: {} (using the bundled ones)", "Cài đặt mẫu: {} (dùng bộ có sẵn)"),
+    ("IPC unavailable: {}", "Không dùng được IPC: {}"),
+    ("IPC: lost connection to Hyprland", "IPC: mất kết nối với Hyprland"),
+    ("Error: {}", "Lỗi: {}"),
+    ("Created workspace: {}", "Đã tạo không gian: {}"),
+    ("Duplicated '{}' as '{}'", "Đã nhân bản '{}' thành '{}'"),
+    ("Renamed to: {}", "Đã đổi tên thành: {}"),
+    ("Pinned '{}'", "Đã ghim '{}'"),
+    ("Unpinned '{}'", "Đã bỏ ghim '{}'"),
+    ("Pinned workspaces stay ahead of the others", "Không gian đã ghim luôn đứng trước"),
+    ("Workspace deleted", "Đã xóa không gian"),
+    ("Bundled workspaces cannot be deleted", "Không thể xóa không gian có sẵn"),
+    ("Cannot delete last workspace", "Không thể xóa không gian cuối cùng"),
+    ("Updated notes for '{}'", "Đã cập nhật ghi chú của '{}'"),
+    ("'{}' will only be applied manually", "'{}' chỉ được áp dụng thủ công"),
+    ("'{}' can be auto-applied again", "'{}' lại có thể tự động áp dụng"),
+    (
+        "Locked on '{}' - monitor changes won't switch workspace",
+        "Đã khóa ở '{}' - thay đổi màn hình sẽ không đổi không gian",
+    ),
+    (
+        "Unlocked - workspace follows connected monitors",
+        "Đã mở khóa - không gian theo các màn hình đang kết nối",
+    ),
+    (
+        "Auto-apply on - plugged-in monitors get their saved settings",
+        "Bật tự động áp dụng - màn hình cắm vào nhận cài đặt đã lưu",
+    ),
+    (
+        "Auto-apply paused - plugged-in monitors are left as they come up",
+        "Tạm dừng tự động áp dụng - màn hình cắm vào được giữ nguyên",
+    ),
+    ("Saved live layout to '{}'", "Đã lưu bố cục hiện tại vào '{}'"),
+    ("Adopted the outside change into {}", "Đã đưa thay đổi bên ngoài vào {}"),
+    ("Re-applied {}", "Đã áp dụng lại {}"),
+    ("Saved live {} to workspace", "Đã lưu {} hiện tại vào không gian"),
+    ("{} has nothing saved to pull", "{} không có gì đã lưu để lấy"),
+    (
+        "Pulled saved {} into Live (apply to write)",
+        "Đã lấy {} đã lưu vào Hiện tại (áp dụng để ghi)",
+    ),
+    (
+        "Fallback set to monitor=,{} (apply to write)",
+        "Đã đặt dự phòng monitor=,{} (áp dụng để ghi)",
+    ),
+    ("Fallback removed (apply to write)", "Đã bỏ dự phòng (áp dụng để ghi)"),
+    ("{} is managed by hyprmon again", "{} lại do hyprmon quản lý"),
+    (
+        "{} is now unmanaged - configure it in hyprland.conf",
+        "{} không còn được quản lý - hãy cấu hình trong hyprland.conf",
+    ),
+    ("{} is now called '{}'", "{} giờ có tên '{}'"),
+    ("{} has no nickname now", "{} giờ không có biệt danh"),
+    ("{} turns off when '{}' is applied", "{} tắt khi áp dụng '{}'"),
+    ("{} stays on when '{}' is applied", "{} vẫn bật khi áp dụng '{}'"),
+    ("Removed {} from '{}'", "Đã gỡ {} khỏi '{}'"),
+    (
+        "No other workspace has monitors to import",
+        "Không có không gian nào khác có màn hình để nhập",
+    ),
+    ("Imported {} from '{}' into '{}'", "Đã nhập {} từ '{}' vào '{}'"),
+    ("Imported {} monitors into '{}'", "Đã nhập {} màn hình vào '{}'"),
+    ("Monitors now keyed as {}", "Màn hình giờ được nhận theo {}"),
+    ("Undone.", "Đã hoàn tác."),
+    ("Nothing to undo.", "Không có gì để hoàn tác."),
+    ("Redone.", "Đã làm lại."),
+    ("Nothing to redo.", "Không có gì để làm lại."),
+    (
+        "{} now overlaps or is cut off from the others; apply will lay them out in a row",
+        "{} giờ chồng lên hoặc tách khỏi các màn hình khác; áp dụng sẽ xếp chúng thành hàng",
+    ),
+    ("Resolution set to {} for {} Hz", "Đã đặt độ phân giải {} cho {} Hz"),
+    (
+        "Refresh rate set to {} Hz, the nearest {} offers at {}",
+        "Đã đặt tần số quét {} Hz, gần nhất mà {} hỗ trợ ở {}",
+    ),
+    (
+        "{}@{} Hz is not a mode {} advertises; Hyprland may pick another",
+        "{}@{} Hz không phải chế độ {} hỗ trợ; Hyprland có thể chọn chế độ khác",
+    ),
+    ("Applied! Check your monitors.", "Đã áp dụng! Hãy kiểm tra màn hình."),
+    (
+        "Applied workspace '{}'. Check your monitors.",
+        "Đã áp dụng không gian '{}'. Hãy kiểm tra màn hình.",
+    ),
+    ("No connected monitors are saved in '{}'", "'{}' không lưu màn hình nào đang kết nối"),
+    ("No workspace #{}", "Không có không gian số {}"),
+    (
+        "Loaded {} monitor(s) from '{}'. Review, then A to apply.",
+        "Đã tải {} màn hình từ '{}'. Xem lại, rồi nhấn A để áp dụng.",
+    ),
+    ("Drag cancelled.", "Đã hủy kéo."),
+    (" and ", " và "),
+    ("Copied monitors.conf ({} lines) via {}", "Đã sao chép monitors.conf ({} dòng) qua {}"),
+    ("Copied {} diagram of {} monitor(s) via {}", "Đã sao chép sơ đồ {} của {} màn hình qua {}"),
+    ("Copy failed: {}", "Sao chép thất bại: {}"),
+    ("DPMS of {}: {}", "DPMS của {}: {}"),
+    ("Not applied as asked: {}", "Chưa áp dụng như yêu cầu: {}"),
+    ("A step was refused: {}", "Một bước bị từ chối: {}"),
+    (
+        "Hyprland reported {} config error(s); see the error list",
+        "Hyprland báo {} lỗi cấu hình; xem danh sách lỗi",
+    ),
+    ("Changes reverted.", "Đã hoàn nguyên thay đổi."),
+    ("Configuration saved!", "Đã lưu cấu hình!"),
+    (
+        "Identifying monitors... Check your displays!",
+        "Đang nhận diện màn hình... Hãy nhìn màn hình!",
+    ),
+    ("Game mode off, monitors.conf restored", "Tắt chế độ chơi game, đã khôi phục monitors.conf"),
+    (
+        "Game mode: {} at {} Hz with VRR, VRR off elsewhere; b restores",
+        "Chế độ chơi game: {} ở {} Hz có VRR, tắt VRR ở nơi khác; b để khôi phục",
+    ),
+    ("Presentation over, monitors.conf restored", "Hết trình chiếu, đã khôi phục monitors.conf"),
+    (
+        "Nothing to present on: only one monitor is connected",
+        "Không có gì để trình chiếu: chỉ một màn hình được kết nối",
+    ),
+    (
+        "Presenting: {} mirrored at {}; Ctrl+o restores",
+        "Đang trình chiếu: {} phản chiếu ở {}; Ctrl+o để khôi phục",
+    ),
+    (
+        "Presenting: {} mirrored; Ctrl+o restores",
+        "Đang trình chiếu: {} phản chiếu; Ctrl+o để khôi phục",
+    ),
+    ("{} is back to its monitors.conf mode", "{} đã về chế độ trong monitors.conf"),
+    (
+        "{} forced to {}; Ctrl+f or unplugging it restores",
+        "{} bị ép về {}; Ctrl+f hoặc rút ra để khôi phục",
+    ),
+    ("Safe mode not saved: {}", "Chưa lưu chế độ an toàn: {}"),
+    (
+        "Safe mode on; apply a workspace to leave it",
+        "Bật chế độ an toàn; áp dụng một không gian để thoát",
+    ),
+    ("No earlier monitors.conf kept yet", "Chưa giữ bản monitors.conf nào trước đó"),
+    ("Not restored: {}", "Chưa khôi phục: {}"),
+    ("Restored monitors.conf from {}", "Đã khôi phục monitors.conf từ {}"),
+    ("No preset known for {}", "Không có cài đặt mẫu cho {}"),
+    ("{} already runs its preset", "{} đã chạy cài đặt mẫu của nó"),
+    ("{} set to its preset ({}); a applies", "{} đã đặt theo mẫu ({}); a để áp dụng"),
+    (
+        "No physical size for {}; scale left as is",
+        "Không rõ kích thước thật của {}; giữ nguyên tỉ lệ",
+    ),
+    (
+        "Every monitor already has the scale its density suggests",
+        "Mọi màn hình đã có tỉ lệ theo mật độ điểm ảnh",
+    ),
+    ("Scaled by pixel density: {}; a applies", "Tỉ lệ theo mật độ điểm ảnh: {}; a để áp dụng"),
+    ("No other monitor to swap workspaces with", "Không có màn hình khác để đổi không gian"),
+    ("Swapped workspaces of {} and {}", "Đã đổi không gian của {} và {}"),
+    ("hyprctl: {} (showing cached details)", "hyprctl: {} (hiện thông tin đã lưu)"),
+    ("Monitors refreshed.", "Đã làm mới màn hình."),
+    (
+        "Monitor connected - '{}' is manual-only, press a to apply",
+        "Đã kết nối màn hình - '{}' chỉ áp dụng thủ công, nhấn a để áp dụng",
+    ),
+    (
+        "{} connected - auto-apply paused, press a to apply",
+        "Đã kết nối {} - tự động áp dụng đang tạm dừng, nhấn a để áp dụng",
+    ),
+    ("{} connected - applied its saved settings", "Đã kết nối {} - đã áp dụng cài đặt đã lưu"),
+    (
+        "Monitor connected - applying saved config...",
+        "Đã kết nối màn hình - đang áp dụng cấu hình đã lưu...",
+    ),
+    (
+        "New monitor detected! Suggested for {}: {} - select it and press s",
+        "Phát hiện màn hình mới! Gợi ý cho {}: {} - chọn nó rồi nhấn s",
+    ),
+    ("New monitor detected!", "Phát hiện màn hình mới!"),
+    ("{} disconnected; its forced mode is dropped", "Đã ngắt {}; bỏ chế độ bị ép của nó"),
+    ("Monitor disconnected.", "Đã ngắt kết nối màn hình."),
+    ("{} is primary now that {} is unplugged", "{} giờ là màn hình chính vì {} đã bị rút"),
+];
+
+/// `text` in `lang`, or `text` itself when there is no translation.
+pub fn tr_in(lang: Lang, text: &'static str) -> &'static str {
+    let table = match lang {
+        Lang::En => return text,
+        Lang::Vi => VI,
+    };
+    table
+        .iter()
+        .find(|(en, _)| *en == text)
+        .map_or(text, |(_, translated)| translated)
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Pick the interface language for the rest of the process; only the first
+/// call counts.
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// `text` in the interface language (English until [`set_lang`] is called).
+pub fn tr(text: &'static str) -> &'static str {
+    tr_in(*LANG.get_or_init(|| Lang::En), text)
+}
+
+/// [`tr`] with each `{}` in the text filled in turn from `args`.
+pub fn tr_fill(text: &'static str, args: &[&dyn Display]) -> String {
+    fill(tr(text), args)
+}
+
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(args) {
+        out.push_str(&arg.to_string());
+        out.push_str(part);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Files whose strings go through `tr`; every table key must still be
+    /// used in one of them, or it was reworded without updating the table.
+    const SOURCES: &[&str] = &[
+        include_str!("app.rs"),
+        include_str!("drm.rs"),
+        include_str!("input.rs"),
+        include_str!("main.rs"),
+        include_str!("palette.rs"),
+        include_str!("plain.rs"),
+        include_str!("ui/arrangement.rs"),
+        include_str!("ui/dialogs.rs"),
+        include_str!("ui/help.rs"),
+        include_str!("ui/saved.rs"),
+        include_str!("ui/settings.rs"),
+        include_str!("ui/tabs.rs"),
+        include_str!("ui/toasts.rs"),
+        include_str!("state.rs"),
+    ];
+
+    #[test]
+    fn detects_locale_like_gettext() {
+        let env = |pairs: &'static [(&str, &str)]| {
+            move |name: &str| {
+                pairs.iter().find(|(n, _)| *n == name).map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(Lang::from_env(env(&[("LANG", "vi_VN.UTF-8")])), Lang::Vi);
+        assert_eq!(Lang::from_env(env(&[("LANG", "vi_VN.UTF-8"), ("LC_ALL", "C")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[("LC_MESSAGES", "vi"), ("LANG", "de_DE")])), Lang::Vi);
+        assert_eq!(Lang::from_env(env(&[("LC_ALL", ""), ("LANG", "de_DE")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[])), Lang::En);
+        assert_eq!(Lang::from_code("vi-VN"), Some(Lang::Vi));
+        assert_eq!(Lang::from_code("klingon"), None);
+
+        let prefs = |toml: &str| toml::from_str::<Prefs>(toml).unwrap();
+        assert_eq!(Lang::from_prefs(&prefs("language = \"vi\"\n")).unwrap(), Lang::Vi);
+        assert!(Lang::from_prefs(&prefs("language = \"xx\"\n")).is_err());
+    }
+
+    #[test]
+    fn translations_fall_back_to_english() {
+        assert_eq!(tr_in(Lang::Vi, "Quit"), "Thoát");
+        assert_eq!(tr_in(Lang::En, "Quit"), "Quit");
+        assert_eq!(tr_in(Lang::Vi, "Not in the table"), "Not in the table");
+        let removed = fill(tr_in(Lang::Vi, "Removed {} from '{}'"), &[&"DP-1", &"Desk"]);
+        assert_eq!(removed, "Đã gỡ DP-1 khỏi 'Desk'");
+        assert_eq!(tr_fill("No workspace #{}", &[&3]), "No workspace #3");
+    }
+
+    #[test]
+    fn every_translation_is_used_and_unique() {
+        for (i, (en, vi)) in VI.iter().enumerate() {
+            assert!(!VI[..i].iter().any(|(other, _)| other == en), "duplicate key {en:?}");
+            assert_ne!(en, vi);
+            let quoted = format!("\"{}\"", en.replace('\n', "\\n"));
+            assert!(SOURCES.iter().any(|src| src.contains(&quoted)), "unused key {en:?}");
+        }
+    }
+}
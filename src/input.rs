@@ -1,9 +1,14 @@
 use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
-use crate::app::App;
-use crate::state::{DialogType, DragState, FocusPanel, MainTab, SettingField};
-use crate::ui::{settings::row_to_setting, BOX_GAP, BOX_WIDTH};
+use crate::app::{workspace_name_input, App};
+use crate::config::parse_fallback;
+use crate::i18n::{tr, tr_fill};
+use crate::palette::{palette_matches, run_palette_command};
+use crate::state::{DialogButton, DialogType, DragState, FocusPanel, MainTab, SettingField};
+use crate::ui::{
+    config_error_lines, help_overlay_lines, history_lines, message_lines, monitor_details_lines,
+    Hit, TextInput, DROPDOWN_ROWS,
+};
 
 pub enum InputResult {
     Continue,
@@ -11,6 +16,20 @@ pub enum InputResult {
 }
 
 pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> InputResult {
+    // Yes/no dialogs: Tab and ←/→ move the highlight, Enter presses it
+    if let Some(focus) = app.dialog.focused_button() {
+        match code {
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                app.dialog.toggle_button();
+                return InputResult::Continue;
+            }
+            KeyCode::Enter => {
+                let key = if focus == DialogButton::Yes { 'y' } else { 'n' };
+                return handle_key(app, KeyCode::Char(key), modifiers);
+            }
+            _ => {}
+        }
+    }
     match app.dialog {
         DialogType::ConfirmApply { .. } => match code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -18,12 +37,12 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Inpu
             }
             KeyCode::Char('n') | KeyCode::Char('N') => {
                 app.revert_changes();
-                let _ = app.save_and_apply();
+                let _ = app.save_and_apply_as_is();
                 app.dialog = DialogType::None;
             }
             _ => {}
         },
-        DialogType::ConfirmQuit => match code {
+        DialogType::ConfirmQuit { .. } => match code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
                 return InputResult::Quit;
             }
@@ -33,73 +52,112 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Inpu
             _ => {}
         },
         DialogType::EditDropdown => match code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if app.dropdown_selection > 0 {
-                    app.dropdown_selection -= 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                let max = app.get_dropdown_options().len().saturating_sub(1);
-                if app.dropdown_selection < max {
-                    app.dropdown_selection += 1;
-                }
-            }
+            KeyCode::Up | KeyCode::Char('k') => app.move_dropdown_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_dropdown_selection(1),
+            KeyCode::PageUp => app.move_dropdown_selection(-(DROPDOWN_ROWS as isize)),
+            KeyCode::PageDown => app.move_dropdown_selection(DROPDOWN_ROWS as isize),
+            KeyCode::Home => app.move_dropdown_selection(isize::MIN),
+            KeyCode::End => app.move_dropdown_selection(isize::MAX),
+            KeyCode::Tab => app.toggle_exotic_modes(),
             KeyCode::Enter => {
-                app.apply_dropdown_selection();
+                let picked = app.dropdown_matches().contains(&app.dropdown_selection);
+                app.input_buffer.clear();
                 app.dialog = DialogType::None;
+                // Closed first: "Custom…" opens the typed entry in its place
+                if picked {
+                    app.apply_dropdown_selection();
+                }
+            }
+            KeyCode::Esc if !app.input_buffer.is_empty() => {
+                app.set_dropdown_filter(String::new());
             }
             KeyCode::Esc => {
                 app.dialog = DialogType::None;
             }
+            KeyCode::Backspace => {
+                let mut filter = app.input_buffer.clone();
+                filter.pop();
+                app.set_dropdown_filter(filter);
+            }
+            // Anything else typed narrows the list (j/k above still navigate)
+            KeyCode::Char(c) => app.set_dropdown_filter(format!("{}{c}", app.input_buffer)),
             _ => {}
         },
-        DialogType::NewWorkspace => match code {
+        DialogType::NewWorkspace
+        | DialogType::RenameWorkspace
+        | DialogType::DuplicateWorkspace
+        | DialogType::SaveAsNew
+        | DialogType::EditNotes
+        | DialogType::EditFallback
+        | DialogType::EditNickname => match code {
+            // The validator set up with the input says whether it may be empty;
+            // names taken by another workspace are refused here, inline too
             KeyCode::Enter => {
-                if !app.input_buffer.is_empty() {
-                    let name = app.input_buffer.clone();
-                    app.create_workspace(&name);
-                    app.input_buffer.clear();
-                    app.dialog = DialogType::None;
+                if let Some(text) = app.text_input.submit() {
+                    let done = match app.dialog {
+                        DialogType::EditFallback => {
+                            app.set_fallback(&text);
+                            Ok(())
+                        }
+                        DialogType::EditNotes => {
+                            app.set_current_workspace_description(&text);
+                            Ok(())
+                        }
+                        DialogType::EditNickname => {
+                            app.set_selected_nickname(&text);
+                            Ok(())
+                        }
+                        DialogType::NewWorkspace => app.create_workspace(&text),
+                        DialogType::RenameWorkspace => app.rename_current_workspace(&text),
+                        DialogType::SaveAsNew => app.save_live_as_new(&text),
+                        _ => app.duplicate_current_workspace(&text),
+                    };
+                    match done {
+                        Ok(()) => {
+                            app.text_input.clear();
+                            app.dialog = DialogType::None;
+                        }
+                        Err(e) => app.text_input.set_error(e),
+                    }
                 }
             }
             KeyCode::Esc => {
-                app.input_buffer.clear();
+                app.text_input.clear();
                 app.dialog = DialogType::None;
             }
-            KeyCode::Backspace => {
-                app.input_buffer.pop();
+            KeyCode::Tab
+                if matches!(app.dialog, DialogType::NewWorkspace | DialogType::SaveAsNew) =>
+            {
+                app.cycle_name_suggestion();
             }
-            KeyCode::Char(c) => {
-                if app.input_buffer.len() < 20 {
-                    app.input_buffer.push(c);
-                }
+            _ => {
+                app.text_input.handle_key(code, modifiers);
             }
-            _ => {}
         },
-        DialogType::RenameWorkspace => match code {
+        DialogType::EditSetting => match code {
             KeyCode::Enter => {
-                if !app.input_buffer.is_empty() {
-                    let name = app.input_buffer.clone();
-                    app.rename_current_workspace(&name);
-                    app.input_buffer.clear();
+                if app.submit_setting_input() {
                     app.dialog = DialogType::None;
                 }
             }
             KeyCode::Esc => {
-                app.input_buffer.clear();
                 app.dialog = DialogType::None;
             }
-            KeyCode::Backspace => {
-                app.input_buffer.pop();
+            _ => {
+                app.text_input.handle_key(code, modifiers);
             }
-            KeyCode::Char(c) => {
-                if app.input_buffer.len() < 20 {
-                    app.input_buffer.push(c);
-                }
+        },
+        DialogType::DeleteMonitor { .. } => match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                app.delete_selected_saved_monitor();
+                app.dialog = DialogType::None;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.dialog = DialogType::None;
             }
             _ => {}
         },
-        DialogType::DeleteWorkspace => match code {
+        DialogType::DeleteWorkspace { .. } => match code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
                 app.delete_current_workspace();
                 app.dialog = DialogType::None;
@@ -109,220 +167,640 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Inpu
             }
             _ => {}
         },
-        DialogType::None => {
-            match code {
-                KeyCode::Char('q') | KeyCode::Char('Q') => {
-                    if app.has_changes {
-                        app.dialog = DialogType::ConfirmQuit;
-                    } else {
-                        return InputResult::Quit;
-                    }
-                }
-                // Main tab switching
-                KeyCode::Char('1') => {
-                    app.switch_tab(MainTab::Live);
-                }
-                KeyCode::Char('2') => {
-                    app.switch_tab(MainTab::Saved);
-                }
-                // Workspace navigation (in Saved panel)
-                KeyCode::Char('[') => {
-                    if app.main_tab == MainTab::Saved {
-                        app.prev_workspace();
-                    }
-                }
-                KeyCode::Char(']') => {
-                    if app.main_tab == MainTab::Saved {
-                        app.next_workspace();
-                    }
+        DialogType::Compare => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.compare_rows().len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Char('>') | KeyCode::Char('s') => {
+                app.compare_push(app.dropdown_selection);
+                let max = app.compare_rows().len().saturating_sub(1);
+                app.dropdown_selection = app.dropdown_selection.min(max);
+            }
+            KeyCode::Char('<') | KeyCode::Char('l') => {
+                app.compare_pull(app.dropdown_selection);
+                let max = app.compare_rows().len().saturating_sub(1);
+                app.dropdown_selection = app.dropdown_selection.min(max);
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::SaveAs => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.save_as_options().len() - 1;
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Enter => {
+                if app.dropdown_selection < app.monitor_db.workspaces.len() {
+                    app.save_live_as(app.dropdown_selection);
+                    app.dialog = DialogType::None;
+                } else {
+                    app.begin_workspace_naming(DialogType::SaveAsNew);
                 }
-                KeyCode::Char('n') | KeyCode::Char('N') => {
-                    if app.main_tab == MainTab::Saved {
-                        app.input_buffer.clear();
-                        app.dialog = DialogType::NewWorkspace;
-                    }
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::ImportMonitors => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.import_candidates().len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Char(' ') => app.toggle_import_pick(app.dropdown_selection),
+            KeyCode::Enter => {
+                app.dialog = DialogType::None;
+                app.import_picked_monitors();
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::FixScale { .. } => match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('n') | KeyCode::Char('N') => {
+                app.dialog = DialogType::None;
+                if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    app.fix_scales();
                 }
-                KeyCode::Char('d') | KeyCode::Char('D') => {
-                    if app.main_tab == MainTab::Saved {
-                        app.dialog = DialogType::DeleteWorkspace;
-                    }
+                if let Err(e) = app.save_and_apply_as_is() {
+                    app.toasts.error(tr_fill("Error: {}", &[&e]));
                 }
-                KeyCode::Tab => {
-                    if modifiers.contains(KeyModifiers::SHIFT) {
-                        app.select_next_monitor();
-                    } else {
-                        app.focus_panel = match app.focus_panel {
-                            FocusPanel::Arrangement => FocusPanel::Settings,
-                            FocusPanel::Settings => FocusPanel::Arrangement,
-                        };
-                    }
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::Reconcile { .. } => match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('a') => {
+                if let Err(e) = app.adopt_external_changes() {
+                    app.toasts.error(tr_fill("Error: {}", &[&e]));
                 }
-                KeyCode::BackTab => {
-                    app.select_next_monitor();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('r') => {
+                app.reapply_over_external_changes();
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::TellApart { saved } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.key_choices().len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Enter => {
+                app.dialog = DialogType::None;
+                let choice = app.key_choices().get(app.dropdown_selection).map(|&(by, _)| by);
+                let result = match choice {
+                    Some(by) => app.tell_apart(by).and_then(|()| {
+                        if saved {
+                            app.apply_saved_workspace()
+                        } else {
+                            app.save_and_apply()
+                        }
+                    }),
+                    None => Ok(()),
+                };
+                if let Err(e) = result {
+                    app.toasts.error(tr_fill("Error: {}", &[&e]));
                 }
-                KeyCode::Char('p') | KeyCode::Char('P') => {
-                    if app.main_tab == MainTab::Live {
-                        app.toggle_primary();
-                    }
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::SwapWorkspaces => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.swap_targets().len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Enter => {
+                app.swap_workspaces_with(app.dropdown_selection);
+                app.dialog = DialogType::None;
+            }
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::Rollback => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.backups.len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Enter => app.restore_backup(app.dropdown_selection),
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::ConfigErrors { scroll } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dialog = DialogType::ConfigErrors {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = config_error_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::ConfigErrors {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.start_apply_countdown(),
+            _ => {}
+        },
+        DialogType::ConfigPreview { scroll } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dialog = DialogType::ConfigPreview {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = app.config_preview.len().saturating_sub(1);
+                app.dialog = DialogType::ConfigPreview {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.dialog = DialogType::None;
+            }
+            KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::History { scroll } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dialog = DialogType::History {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = history_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::History {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::Messages { scroll } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dialog = DialogType::Messages {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = message_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::Messages {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('g') => {
+                app.dialog = DialogType::None;
+            }
+            _ => {}
+        },
+        DialogType::MonitorDetails { scroll } => match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dialog = DialogType::MonitorDetails {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max = monitor_details_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::MonitorDetails {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                app.dialog = DialogType::None;
+                app.monitor_details = None;
+            }
+            _ => {}
+        },
+        DialogType::Help { scroll } => {
+            let max = help_overlay_lines().len().saturating_sub(1);
+            let scroll = match code {
+                KeyCode::Up | KeyCode::Char('k') => scroll.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => (scroll + 1).min(max),
+                KeyCode::PageUp => scroll.saturating_sub(10),
+                KeyCode::PageDown => (scroll + 10).min(max),
+                KeyCode::Home => 0,
+                KeyCode::End => max,
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                    app.dialog = DialogType::None;
+                    return InputResult::Continue;
                 }
-                KeyCode::Char('a') | KeyCode::Char('A') => {
-                    if app.main_tab == MainTab::Live {
-                        if let Err(e) = app.save_and_apply() {
-                            app.message = format!("Error: {}", e);
-                        }
-                    }
+                _ => scroll,
+            };
+            app.dialog = DialogType::Help { scroll };
+        }
+        DialogType::CommandPalette => match code {
+            KeyCode::Esc => {
+                app.dialog = DialogType::None;
+                app.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let picked = palette_matches(app, &app.input_buffer)
+                    .into_iter()
+                    .nth(app.dropdown_selection);
+                app.dialog = DialogType::None;
+                app.input_buffer.clear();
+                if let Some(entry) = picked {
+                    return run_palette_command(app, entry.command);
                 }
-                KeyCode::Char('i') | KeyCode::Char('I') => {
-                    if app.main_tab == MainTab::Live {
-                        app.identify();
-                        app.message = "Identifying monitors... Check your displays!".to_string();
-                    }
+            }
+            KeyCode::Up => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.dropdown_selection = app.dropdown_selection.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                let max = palette_matches(app, &app.input_buffer).len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Char('n') if modifiers.contains(KeyModifiers::CONTROL) => {
+                let max = palette_matches(app, &app.input_buffer).len().saturating_sub(1);
+                app.dropdown_selection = (app.dropdown_selection + 1).min(max);
+            }
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+                app.dropdown_selection = 0;
+            }
+            KeyCode::Char(c) => {
+                app.input_buffer.push(c);
+                app.dropdown_selection = 0;
+            }
+            _ => {}
+        },
+        DialogType::None => {
+            if code == KeyCode::Esc && app.cancel_drag() {
+                return InputResult::Continue;
+            }
+            if let Some((code, modifiers)) = app.keymap.translate(code, modifiers) {
+                let result = handle_main_key(app, code, modifiers);
+                // Switching tab or panel mid-drag drops the drag
+                if app.main_tab != MainTab::Live || app.focus_panel != FocusPanel::Arrangement {
+                    app.cancel_drag();
                 }
-                KeyCode::Char('r') | KeyCode::Char('R') => {
-                    if app.main_tab == MainTab::Live {
-                        if let Err(e) = app.refresh() {
-                            app.message = format!("Error: {}", e);
-                        }
-                    } else if app.main_tab == MainTab::Saved {
-                        // R for Rename in Saved panel
-                        app.input_buffer = app.current_workspace_name();
-                        app.dialog = DialogType::RenameWorkspace;
-                    }
+                return result;
+            }
+        }
+    }
+    InputResult::Continue
+}
+
+/// Keys of the main view (no dialog open), after user rebinding.
+pub fn handle_main_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> InputResult {
+    match code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            if app.has_changes {
+                app.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
+            } else {
+                return InputResult::Quit;
+            }
+        }
+        // Main tab switching
+        KeyCode::Char(c @ '1'..='9') if modifiers.contains(KeyModifiers::ALT) => {
+            let idx = c as usize - '1' as usize;
+            if let Err(e) = app.apply_workspace_at(idx) {
+                app.toasts.error(tr_fill("Error: {}", &[&e]));
+            }
+        }
+        KeyCode::Char('1') => {
+            app.switch_tab(MainTab::Live);
+        }
+        KeyCode::Char('2') => {
+            app.switch_tab(MainTab::Saved);
+        }
+        // Workspace navigation (in Saved panel)
+        KeyCode::Char('[') if app.main_tab == MainTab::Saved => {
+            app.prev_workspace();
+        }
+        KeyCode::Char(']') if app.main_tab == MainTab::Saved => {
+            app.next_workspace();
+        }
+        KeyCode::Char('{') if app.main_tab == MainTab::Saved => {
+            app.move_current_workspace(-1);
+        }
+        KeyCode::Char('}') if app.main_tab == MainTab::Saved => {
+            app.move_current_workspace(1);
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => match app.main_tab {
+            MainTab::Live => app.open_nickname(),
+            MainTab::Saved => app.begin_workspace_naming(DialogType::NewWorkspace),
+        },
+        KeyCode::Char('d')
+            if modifiers.contains(KeyModifiers::CONTROL) && app.main_tab == MainTab::Live =>
+        {
+            app.scale_by_density();
+        }
+        // Elsewhere Ctrl+d does nothing rather than reach plain d
+        KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {}
+        KeyCode::Char('d') | KeyCode::Char('D') if app.main_tab == MainTab::Saved => {
+            app.dialog = DialogType::DeleteWorkspace { focus: DialogButton::No };
+        }
+        KeyCode::Char('x') | KeyCode::Delete
+            if app.main_tab == MainTab::Saved && !app.saved_monitors.is_empty() =>
+        {
+            app.dialog = DialogType::DeleteMonitor { focus: DialogButton::No };
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') if app.main_tab == MainTab::Saved => {
+            let copy = format!("{} copy", app.current_workspace_name());
+            app.text_input = workspace_name_input(&copy);
+            app.dialog = DialogType::DuplicateWorkspace;
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') if app.main_tab == MainTab::Saved => {
+            app.load_workspace_into_live();
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') => {
+            app.dropdown_selection = 0;
+            app.dialog = DialogType::Compare;
+        }
+        KeyCode::Char('f')
+            if modifiers.contains(KeyModifiers::CONTROL)
+                && (app.main_tab == MainTab::Live || app.projector.is_some()) =>
+        {
+            app.toggle_projector_mode();
+        }
+        // Elsewhere Ctrl+f does nothing rather than reach plain f
+        KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {}
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            let rule = app.monitor_db.fallback.clone().unwrap_or_default();
+            app.text_input = TextInput::new(&rule, 40).validated(|s| parse_fallback(s).map(drop));
+            app.dialog = DialogType::EditFallback;
+        }
+        KeyCode::Char('o') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_presentation();
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') if app.main_tab == MainTab::Saved => {
+            let notes = app
+                .monitor_db
+                .workspaces
+                .get(app.selected_workspace)
+                .map(|ws| ws.description.clone())
+                .unwrap_or_default();
+            app.text_input = TextInput::new(&notes, 60);
+            app.dialog = DialogType::EditNotes;
+        }
+        KeyCode::Tab => {
+            if modifiers.contains(KeyModifiers::SHIFT) {
+                app.select_next_monitor();
+            } else {
+                app.focus_panel = match app.focus_panel {
+                    FocusPanel::Arrangement => FocusPanel::Settings,
+                    FocusPanel::Settings => FocusPanel::Arrangement,
+                };
+            }
+        }
+        KeyCode::BackTab => {
+            app.select_next_monitor();
+        }
+        KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_auto_apply();
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => match app.main_tab {
+            MainTab::Live => app.toggle_primary(),
+            MainTab::Saved => app.toggle_pin_current_workspace(),
+        },
+        KeyCode::Char('m') | KeyCode::Char('M') => match app.main_tab {
+            MainTab::Live => app.toggle_selected_monitor_managed(),
+            MainTab::Saved => app.toggle_manual_only_current_workspace(),
+        },
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            let result = match app.main_tab {
+                MainTab::Live => app.save_and_apply(),
+                MainTab::Saved => app.apply_saved_workspace(),
+            };
+            if let Err(e) = result {
+                app.toasts.error(tr_fill("Error: {}", &[&e]));
+            }
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => match app.main_tab {
+            MainTab::Live => {
+                app.identify();
+                app.toasts.info(tr("Identifying monitors... Check your displays!"));
+            }
+            MainTab::Saved => app.open_import_monitors(),
+        },
+        KeyCode::Char('w') | KeyCode::Char('W') => match app.main_tab {
+            MainTab::Live => app.focus_selected_monitor(),
+            MainTab::Saved => app.toggle_saved_monitor_dpms(),
+        },
+        KeyCode::Char('X') if app.main_tab == MainTab::Live => {
+            app.open_swap_workspaces();
+        }
+        KeyCode::Char('b') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.open_rollback();
+        }
+        // Restoring works from either tab
+        KeyCode::Char('b') | KeyCode::Char('B')
+            if app.main_tab == MainTab::Live || app.game_mode.is_some() =>
+        {
+            app.toggle_game_mode();
+        }
+        KeyCode::Char('s') if app.main_tab == MainTab::Live => {
+            app.use_preset();
+        }
+        KeyCode::Char('!') => app.enter_safe_mode(),
+        KeyCode::Char('S') if app.main_tab == MainTab::Live => {
+            app.dropdown_selection = app.selected_workspace;
+            app.dialog = DialogType::SaveAs;
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            app.open_history();
+        }
+        KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.open_config_preview();
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            app.dialog = DialogType::Messages { scroll: 0 };
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.copy_config(),
+        KeyCode::Char('?') => {
+            app.dialog = DialogType::Help { scroll: 0 };
+        }
+        KeyCode::Char(':') => {
+            app.input_buffer.clear();
+            app.dropdown_selection = 0;
+            app.dialog = DialogType::CommandPalette;
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') if app.main_tab == MainTab::Live => {
+            app.undo();
+        }
+        KeyCode::Char('r')
+            if modifiers.contains(KeyModifiers::CONTROL) && app.main_tab == MainTab::Live =>
+        {
+            app.redo();
+        }
+        // Elsewhere Ctrl+r does nothing rather than reach plain r
+        KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {}
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if app.main_tab == MainTab::Live {
+                if let Err(e) = app.refresh() {
+                    app.toasts.error(tr_fill("Error: {}", &[&e]));
                 }
-                KeyCode::Left | KeyCode::Char('h') => {
-                    if app.focus_panel == FocusPanel::Arrangement {
-                        match app.main_tab {
-                            MainTab::Live => {
-                                if modifiers.contains(KeyModifiers::SHIFT) {
-                                    app.move_monitor_left();
-                                } else {
-                                    app.select_prev_monitor();
-                                }
-                            }
-                            MainTab::Saved => {
-                                if app.saved_selected_monitor > 0 {
-                                    app.saved_selected_monitor -= 1;
-                                }
-                            }
-                        }
+            } else if app.main_tab == MainTab::Saved {
+                // R for Rename in Saved panel
+                app.text_input = workspace_name_input(&app.current_workspace_name());
+                app.dialog = DialogType::RenameWorkspace;
+            }
+        }
+        // Only the arrangement moves sideways and only the settings up and
+        // down, so arrows work from either panel and focus follows them
+        KeyCode::Left | KeyCode::Char('h') => {
+            app.focus_panel = FocusPanel::Arrangement;
+            match app.main_tab {
+                MainTab::Live => {
+                    if modifiers.contains(KeyModifiers::SHIFT) {
+                        app.move_monitor_left();
+                    } else {
+                        app.select_prev_monitor();
                     }
                 }
-                KeyCode::Right | KeyCode::Char('l') => {
-                    if app.focus_panel == FocusPanel::Arrangement {
-                        match app.main_tab {
-                            MainTab::Live => {
-                                if modifiers.contains(KeyModifiers::SHIFT) {
-                                    app.move_monitor_right();
-                                } else {
-                                    app.select_next_monitor();
-                                }
-                            }
-                            MainTab::Saved => {
-                                if app.saved_selected_monitor
-                                    < app.saved_monitors.len().saturating_sub(1)
-                                {
-                                    app.saved_selected_monitor += 1;
-                                }
-                            }
-                        }
+                MainTab::Saved => {
+                    if app.saved_selected_monitor > 0 {
+                        app.saved_selected_monitor -= 1;
                     }
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if app.focus_panel == FocusPanel::Settings {
-                        match app.main_tab {
-                            MainTab::Live => {
-                                if app.selected_setting > 0 {
-                                    app.selected_setting -= 1;
-                                }
-                            }
-                            MainTab::Saved => {
-                                if app.saved_selected_setting > 0 {
-                                    app.saved_selected_setting -= 1;
-                                }
-                            }
-                        }
+            }
+        }
+        KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_lock_active();
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            app.focus_panel = FocusPanel::Arrangement;
+            match app.main_tab {
+                MainTab::Live => {
+                    if modifiers.contains(KeyModifiers::SHIFT) {
+                        app.move_monitor_right();
+                    } else {
+                        app.select_next_monitor();
                     }
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if app.focus_panel == FocusPanel::Settings {
-                        let max = SettingField::all().len() - 1;
-                        match app.main_tab {
-                            MainTab::Live => {
-                                if app.selected_setting < max {
-                                    app.selected_setting += 1;
-                                }
-                            }
-                            MainTab::Saved => {
-                                if app.saved_selected_setting < max {
-                                    app.saved_selected_setting += 1;
-                                }
-                            }
-                        }
+                MainTab::Saved => {
+                    if app.saved_selected_monitor < app.saved_monitors.len().saturating_sub(1) {
+                        app.saved_selected_monitor += 1;
                     }
                 }
-                KeyCode::Char('H') => {
-                    if app.focus_panel == FocusPanel::Arrangement && app.main_tab == MainTab::Live {
-                        app.move_monitor_left();
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.focus_panel = FocusPanel::Settings;
+            match app.main_tab {
+                MainTab::Live => app.step_setting(-1),
+                MainTab::Saved => {
+                    if app.saved_selected_setting > 0 {
+                        app.saved_selected_setting -= 1;
                     }
                 }
-                KeyCode::Char('L') => {
-                    if app.focus_panel == FocusPanel::Arrangement && app.main_tab == MainTab::Live {
-                        app.move_monitor_right();
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.focus_panel = FocusPanel::Settings;
+            let max = SettingField::all().len() - 1;
+            match app.main_tab {
+                MainTab::Live => app.step_setting(1),
+                MainTab::Saved => {
+                    if app.saved_selected_setting < max {
+                        app.saved_selected_setting += 1;
                     }
                 }
-                KeyCode::Enter | KeyCode::Char(' ') => {
-                    if app.focus_panel == FocusPanel::Settings && app.main_tab == MainTab::Live {
-                        let field = SettingField::all()[app.selected_setting];
-                        if field == SettingField::Primary {
-                            app.toggle_primary();
-                        } else {
-                            app.dropdown_selection = 0;
-                            app.dialog = DialogType::EditDropdown;
-                        }
-                    }
+            }
+        }
+        KeyCode::Char('H') | KeyCode::Char('L') if app.main_tab == MainTab::Live => {
+            app.focus_panel = FocusPanel::Arrangement;
+            if code == KeyCode::Char('H') {
+                app.move_monitor_left();
+            } else {
+                app.move_monitor_right();
+            }
+        }
+        KeyCode::Char('z') | KeyCode::Char('Z') if app.main_tab == MainTab::Live => {
+            app.focus_panel = FocusPanel::Settings;
+            app.toggle_setting_group(SettingField::all()[app.selected_setting].group());
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-')
+            if app.main_tab == MainTab::Live =>
+        {
+            app.focus_panel = FocusPanel::Settings;
+            app.cycle_selected_setting(code != KeyCode::Char('-'));
+        }
+        KeyCode::Enter | KeyCode::Char(' ') if app.main_tab == MainTab::Live => {
+            if app.focus_panel == FocusPanel::Arrangement {
+                app.open_monitor_details();
+            } else if app.selected_setting_folded() {
+                app.toggle_setting_group(SettingField::all()[app.selected_setting].group());
+            } else {
+                let field = SettingField::all()[app.selected_setting];
+                if field == SettingField::Primary {
+                    app.toggle_primary();
+                } else if field.is_position() {
+                    app.open_setting_input();
+                } else {
+                    app.open_dropdown();
                 }
-                _ => {}
             }
         }
+        _ => {}
     }
     InputResult::Continue
 }
 
-pub fn handle_mouse(
-    app: &mut App,
-    kind: MouseEventKind,
-    col: u16,
-    row: u16,
-    terminal_width: u16,
-    terminal_height: u16,
-) -> InputResult {
-    let col = col as usize;
-    let row = row as usize;
-    let height = terminal_height as usize;
-    let width = terminal_width as usize;
-
-    // Use ratatui's Layout to compute exact same areas as main.rs render
-    let rect = Rect::new(0, 0, terminal_width, terminal_height);
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),      // Main tabs (3 rows for box)
-            Constraint::Percentage(38), // Arrangement
-            Constraint::Percentage(43), // Settings
-            Constraint::Length(3),      // Help bar
-        ])
-        .split(rect);
-
-    let tabs_area = chunks[0];
-    let arrangement_area = chunks[1];
-    let settings_area = chunks[2];
+/// Pasted text goes into the open input dialog, or the dropdown filter or
+/// palette query, with line breaks dropped. Elsewhere it is ignored.
+pub fn handle_paste(app: &mut App, text: &str) {
+    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+    match app.dialog {
+        DialogType::NewWorkspace
+        | DialogType::RenameWorkspace
+        | DialogType::DuplicateWorkspace
+        | DialogType::SaveAsNew
+        | DialogType::EditNotes
+        | DialogType::EditFallback
+        | DialogType::EditNickname
+        | DialogType::EditSetting => app.text_input.insert(&text),
+        DialogType::EditDropdown => app.set_dropdown_filter(format!("{}{text}", app.input_buffer)),
+        DialogType::CommandPalette => {
+            app.input_buffer.push_str(&text);
+            app.dropdown_selection = 0;
+        }
+        _ => {}
+    }
+}
 
-    let tabs_start = tabs_area.y as usize;
-    let tabs_end = (tabs_area.y + tabs_area.height) as usize;
-    let arrangement_start = arrangement_area.y as usize;
-    let arrangement_end = (arrangement_area.y + arrangement_area.height) as usize;
-    let settings_start = settings_area.y as usize;
-    let settings_end = (settings_area.y + settings_area.height) as usize;
+pub fn handle_mouse(app: &mut App, kind: MouseEventKind, col: u16, row: u16) -> InputResult {
+    // Everything below asks what the last frame drew under the pointer
+    let hit = app.hits.at(col, row);
 
     match app.dialog {
         DialogType::EditDropdown => {
@@ -331,176 +809,226 @@ pub fn handle_mouse(
                 return InputResult::Continue;
             }
             match kind {
-                MouseEventKind::Down(MouseButton::Left) => {
-                    let options = app.get_dropdown_options();
-                    if options.is_empty() {
+                MouseEventKind::Down(MouseButton::Left) => match hit {
+                    Some(Hit::DropdownOption(idx)) => {
+                        app.dropdown_selection = idx;
+                        app.input_buffer.clear();
                         app.dialog = DialogType::None;
-                        return InputResult::Continue;
+                        app.apply_dropdown_selection();
                     }
-
-                    // Calculate dropdown bounds - MUST match dialogs.rs render_dropdown exactly
-                    let area_x = settings_area.x as usize;
-                    let area_y = settings_area.y as usize;
-                    let area_width = settings_area.width as usize;
-                    let area_height = settings_area.height as usize;
-
-                    let dropdown_height = (options.len() + 2).min(10);
-                    let dropdown_width = options.iter().map(|s| s.len()).max().unwrap_or(10) + 6;
-                    let dropdown_width = dropdown_width.max(20); // Match dialogs.rs minimum
-
-                    // Position: BELOW the setting row, aligned with value column
-                    let raw_x = area_x + 18;
-                    let raw_y = area_y + 3 + app.selected_setting; // One row below
-
-                    // Clamp to area bounds (matching dialogs.rs clamping)
-                    let dropdown_x = raw_x.min(area_x + area_width - dropdown_width);
-                    let dropdown_y = raw_y.min(area_y + area_height - dropdown_height);
-
-                    // Check if click is inside dropdown area (including border)
-                    if col >= dropdown_x
-                        && col < dropdown_x + dropdown_width
-                        && row >= dropdown_y
-                        && row < dropdown_y + dropdown_height
-                    {
-                        // Inside dropdown - check if on an option (skip border rows)
-                        if row > dropdown_y && row < dropdown_y + dropdown_height - 1 {
-                            let clicked_idx = row - dropdown_y - 1;
-                            if clicked_idx < options.len() {
-                                app.dropdown_selection = clicked_idx;
-                                app.apply_dropdown_selection();
-                                app.dialog = DialogType::None;
-                            }
-                        }
-                        // Click on border does nothing, stays open
-                    } else {
+                    // Click on border does nothing, stays open
+                    Some(Hit::Dropdown) => {}
+                    _ => {
                         // Click outside dropdown closes it
+                        app.input_buffer.clear();
                         app.dialog = DialogType::None;
                     }
-                }
-                MouseEventKind::ScrollUp => {
-                    if app.dropdown_selection > 0 {
-                        app.dropdown_selection -= 1;
-                    }
-                }
-                MouseEventKind::ScrollDown => {
-                    let max = app.get_dropdown_options().len().saturating_sub(1);
-                    if app.dropdown_selection < max {
-                        app.dropdown_selection += 1;
-                    }
-                }
+                },
+                MouseEventKind::ScrollUp => app.move_dropdown_selection(-1),
+                MouseEventKind::ScrollDown => app.move_dropdown_selection(1),
                 _ => {}
             }
         }
-        DialogType::ConfirmApply { .. } | DialogType::ConfirmQuit => {
-            if let MouseEventKind::Down(MouseButton::Left) = kind {
-                let center_y = height / 2;
-                let center_x = width / 2;
-                if row >= center_y && row <= center_y + 2 {
-                    if col >= center_x.saturating_sub(12) && col <= center_x.saturating_sub(6) {
-                        // [Y] Yes
-                        if matches!(app.dialog, DialogType::ConfirmQuit) {
-                            return InputResult::Quit;
-                        } else {
-                            app.confirm_changes();
-                        }
-                    } else if col >= center_x.saturating_sub(2) && col <= center_x + 4 {
-                        // [N] No
-                        if matches!(app.dialog, DialogType::ConfirmApply { .. }) {
-                            app.revert_changes();
-                            let _ = app.save_and_apply();
-                        }
-                        app.dialog = DialogType::None;
-                    }
-                }
+        DialogType::ConfirmApply { .. }
+        | DialogType::ConfirmQuit { .. }
+        | DialogType::NewWorkspace
+        | DialogType::RenameWorkspace
+        | DialogType::DuplicateWorkspace
+        | DialogType::DeleteWorkspace { .. }
+        | DialogType::DeleteMonitor { .. }
+        | DialogType::FixScale { .. }
+        | DialogType::Reconcile { .. }
+        | DialogType::SaveAsNew
+        | DialogType::EditNotes
+        | DialogType::EditSetting
+        | DialogType::EditFallback
+        | DialogType::EditNickname => {
+            // A button click acts exactly like its key
+            if let (MouseEventKind::Down(MouseButton::Left), Some(Hit::Button(button))) =
+                (kind, hit)
+            {
+                let code = match button {
+                    DialogButton::Yes => KeyCode::Char('y'),
+                    DialogButton::No => KeyCode::Char('n'),
+                    DialogButton::Ok => KeyCode::Enter,
+                    DialogButton::Cancel => KeyCode::Esc,
+                };
+                return handle_key(app, code, KeyModifiers::NONE);
             }
         }
-        DialogType::NewWorkspace | DialogType::RenameWorkspace | DialogType::DeleteWorkspace => {
-            // Input dialogs - ignore mouse, use keyboard
+        DialogType::SaveAs
+        | DialogType::ImportMonitors
+        | DialogType::SwapWorkspaces
+        | DialogType::Rollback
+        | DialogType::TellApart { .. }
+        | DialogType::Compare
+        | DialogType::CommandPalette => {
+            // Picker dialogs - ignore mouse, use keyboard
         }
+        DialogType::ConfigErrors { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::ConfigErrors {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = config_error_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::ConfigErrors {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
+        DialogType::ConfigPreview { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::ConfigPreview {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = app.config_preview.len().saturating_sub(1);
+                app.dialog = DialogType::ConfigPreview {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
+        DialogType::History { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::History {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = history_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::History {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
+        DialogType::Messages { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::Messages {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = message_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::Messages {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
+        DialogType::MonitorDetails { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::MonitorDetails {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = monitor_details_lines(app).len().saturating_sub(1);
+                app.dialog = DialogType::MonitorDetails {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
+        DialogType::Help { scroll } => match kind {
+            MouseEventKind::ScrollUp => {
+                app.dialog = DialogType::Help {
+                    scroll: scroll.saturating_sub(1),
+                };
+            }
+            MouseEventKind::ScrollDown => {
+                let max = help_overlay_lines().len().saturating_sub(1);
+                app.dialog = DialogType::Help {
+                    scroll: (scroll + 1).min(max),
+                };
+            }
+            _ => {}
+        },
         DialogType::None => {
+            let in_arrangement = app.hits.within(col, row, Hit::Arrangement);
+            let in_settings = app.hits.within(col, row, Hit::Settings);
+            // The wheel over the selected Live setting steps its value in place
+            let on_selected_setting = app.main_tab == MainTab::Live
+                && matches!(hit, Some(Hit::Setting(i) | Hit::SettingValue(i))
+                    if i == app.selected_setting)
+                && SettingField::all()[app.selected_setting] != SettingField::Primary;
             match kind {
-                MouseEventKind::Down(MouseButton::Left) => {
-                    // Check if click is on main tabs area
-                    if row >= tabs_start && row < tabs_end {
-                        // Tabs are boxed and centered
-                        let center = width / 2;
-                        if col < center {
-                            app.switch_tab(MainTab::Live);
+                MouseEventKind::Down(MouseButton::Left) => match hit {
+                    Some(Hit::MainTab(tab)) => app.switch_tab(tab),
+                    // Compact mode: pick the panel shown
+                    Some(Hit::Panel(panel)) => app.focus_panel = panel,
+                    Some(Hit::Monitor(i)) => {
+                        app.focus_panel = FocusPanel::Arrangement;
+                        if app.main_tab == MainTab::Live {
+                            app.selected_monitor = i;
+                            // Start dragging only in Live
+                            app.drag_state = DragState::Dragging {
+                                monitor_idx: i,
+                                start_x: col,
+                                start_y: row,
+                                current_x: col,
+                                current_y: row,
+                            };
                         } else {
-                            app.switch_tab(MainTab::Saved);
+                            app.saved_selected_monitor = i;
+                            app.drag_state = DragState::None;
                         }
-                    } else if row >= arrangement_start && row < arrangement_end {
-                        // Click in arrangement panel
+                    }
+                    // Pick the workspace, and start dragging its tab
+                    Some(Hit::WorkspaceTab(i)) => {
                         app.focus_panel = FocusPanel::Arrangement;
-
-                        // Calculate which monitor was clicked
-                        let num_monitors = if app.main_tab == MainTab::Live {
-                            app.monitors.len()
+                        app.select_workspace(i);
+                        app.drag_state = DragState::Workspace { idx: i, over: i };
+                    }
+                    Some(Hit::Arrangement | Hit::WorkspaceTabs) => {
+                        app.focus_panel = FocusPanel::Arrangement
+                    }
+                    // Only Live settings have clickable values
+                    Some(Hit::SettingValue(idx)) => {
+                        app.focus_panel = FocusPanel::Settings;
+                        app.selected_setting = idx;
+                        let field = SettingField::all()[idx];
+                        if field == SettingField::Primary {
+                            app.toggle_primary();
+                        } else if field.is_position() {
+                            app.open_setting_input();
                         } else {
-                            app.saved_monitors.len()
-                        };
-
-                        if num_monitors > 0 {
-                            let box_width = BOX_WIDTH as usize;
-                            let gap = BOX_GAP as usize;
-                            let total_width =
-                                (box_width * num_monitors) + (gap * (num_monitors - 1));
-                            let start_x = width.saturating_sub(total_width) / 2;
-
-                            for i in 0..num_monitors {
-                                let box_start = start_x + i * (box_width + gap);
-                                let box_end = box_start + box_width;
-                                if col >= box_start && col < box_end {
-                                    if app.main_tab == MainTab::Live {
-                                        app.selected_monitor = i;
-                                        // Start dragging only in Live
-                                        app.drag_state = DragState::Dragging {
-                                            monitor_idx: i,
-                                            start_x: col as u16,
-                                            start_y: row as u16,
-                                            current_x: col as u16,
-                                            current_y: row as u16,
-                                        };
-                                    } else {
-                                        app.saved_selected_monitor = i;
-                                        app.drag_state = DragState::None;
-                                    }
-                                    break;
-                                }
-                            }
+                            app.open_dropdown();
                         }
-                    } else if row >= settings_start && row < settings_end {
-                        // Click in settings panel
+                    }
+                    Some(Hit::Setting(idx)) => {
                         app.focus_panel = FocusPanel::Settings;
-
-                        if let Some(idx) = row_to_setting(row, settings_start) {
-                            if app.main_tab == MainTab::Live {
-                                app.selected_setting = idx;
-                                let field = SettingField::all()[idx];
-
-                                // Convert to panel-local x for robust hit testing.
-                                let rel_col = col.saturating_sub(settings_area.x as usize);
-                                if field == SettingField::Primary {
-                                    // Checkbox is around column 4-7
-                                    if (3..=8).contains(&rel_col) {
-                                        app.toggle_primary();
-                                    }
-                                } else {
-                                    // Value area is around column 18-35, [Change] is after
-                                    if rel_col >= 17 {
-                                        app.dropdown_selection = 0;
-                                        app.dialog = DialogType::EditDropdown;
-                                    }
-                                }
-                            } else {
-                                // Saved panel is read-only; only update highlight.
-                                app.saved_selected_setting = idx;
-                            }
+                        if app.main_tab == MainTab::Live {
+                            app.selected_setting = idx;
+                        } else {
+                            // Saved panel is read-only; only update highlight.
+                            app.saved_selected_setting = idx;
                         }
                     }
-                }
+                    Some(Hit::SettingGroup(group)) => {
+                        app.focus_panel = FocusPanel::Settings;
+                        app.toggle_setting_group(group);
+                    }
+                    Some(Hit::Settings) => app.focus_panel = FocusPanel::Settings,
+                    _ => {}
+                },
                 MouseEventKind::Drag(MouseButton::Left) => {
-                    if let DragState::Dragging {
+                    if let DragState::Workspace { idx, .. } = app.drag_state {
+                        // Off the tab row the tab goes back where it was
+                        if !app.hits.within(col, row, Hit::WorkspaceTabs) {
+                            app.cancel_drag();
+                        } else if let Some(Hit::WorkspaceTab(over)) = hit {
+                            app.drag_state = DragState::Workspace { idx, over };
+                        }
+                    } else if !in_arrangement {
+                        // Dragged out of the panel: give up, the box snaps back
+                        app.cancel_drag();
+                    } else if let DragState::Dragging {
                         monitor_idx,
                         start_x,
                         start_y,
@@ -511,52 +1039,14 @@ pub fn handle_mouse(
                             monitor_idx,
                             start_x,
                             start_y,
-                            current_x: col as u16,
-                            current_y: row as u16,
+                            current_x: col,
+                            current_y: row,
                         };
                     }
                 }
-                MouseEventKind::Up(MouseButton::Left) => {
-                    if let DragState::Dragging {
-                        start_x, current_x, ..
-                    } = app.drag_state
-                    {
-                        let drag_distance = current_x as i16 - start_x as i16;
-                        let box_width = BOX_WIDTH as i16;
-                        let gap = BOX_GAP as i16;
-                        let threshold = (box_width + gap) / 2;
-
-                        if drag_distance.abs() > threshold {
-                            let positions_moved =
-                                (drag_distance.abs() + threshold) / (box_width + gap);
-
-                            if drag_distance > 0 {
-                                for _ in 0..positions_moved {
-                                    if app.selected_monitor < app.monitors.len() - 1 {
-                                        app.monitors
-                                            .swap(app.selected_monitor, app.selected_monitor + 1);
-                                        app.selected_monitor += 1;
-                                    }
-                                }
-                            } else {
-                                for _ in 0..positions_moved {
-                                    if app.selected_monitor > 0 {
-                                        app.monitors
-                                            .swap(app.selected_monitor, app.selected_monitor - 1);
-                                        app.selected_monitor -= 1;
-                                    }
-                                }
-                            }
-
-                            app.recalculate_positions();
-                            app.has_changes = true;
-                        }
-
-                        app.drag_state = DragState::None;
-                    }
-                }
+                MouseEventKind::Up(MouseButton::Left) => app.finish_drag(),
                 MouseEventKind::ScrollUp => {
-                    if row >= arrangement_start && row < arrangement_end {
+                    if in_arrangement {
                         match app.main_tab {
                             MainTab::Live => app.select_prev_monitor(),
                             MainTab::Saved => {
@@ -565,13 +1055,11 @@ pub fn handle_mouse(
                                 }
                             }
                         }
-                    } else if row >= settings_start && row < settings_end {
+                    } else if on_selected_setting {
+                        app.cycle_selected_setting(true);
+                    } else if in_settings {
                         match app.main_tab {
-                            MainTab::Live => {
-                                if app.selected_setting > 0 {
-                                    app.selected_setting -= 1;
-                                }
-                            }
+                            MainTab::Live => app.step_setting(-1),
                             MainTab::Saved => {
                                 if app.saved_selected_setting > 0 {
                                     app.saved_selected_setting -= 1;
@@ -581,7 +1069,7 @@ pub fn handle_mouse(
                     }
                 }
                 MouseEventKind::ScrollDown => {
-                    if row >= arrangement_start && row < arrangement_end {
+                    if in_arrangement {
                         match app.main_tab {
                             MainTab::Live => app.select_next_monitor(),
                             MainTab::Saved => {
@@ -592,14 +1080,12 @@ pub fn handle_mouse(
                                 }
                             }
                         }
-                    } else if row >= settings_start && row < settings_end {
+                    } else if on_selected_setting {
+                        app.cycle_selected_setting(false);
+                    } else if in_settings {
                         let max = SettingField::all().len().saturating_sub(1);
                         match app.main_tab {
-                            MainTab::Live => {
-                                if app.selected_setting < max {
-                                    app.selected_setting += 1;
-                                }
-                            }
+                            MainTab::Live => app.step_setting(1),
                             MainTab::Saved => {
                                 if app.saved_selected_setting < max {
                                     app.saved_selected_setting += 1;
@@ -618,7 +1104,10 @@ pub fn handle_mouse(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::monitor::MonitorConfig;
+    use crate::monitor::{MonitorConfig, Rotation};
+    use crate::state::SettingGroup;
+    use crate::ui::{HitMap, MainLayout};
+    use ratatui::{backend::TestBackend, layout::Rect, Terminal};
     use std::path::PathBuf;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::time::Instant;
@@ -653,29 +1142,60 @@ mod tests {
         handle_key(app, code, KeyModifiers::NONE)
     }
 
+    /// Draw `a` on a `w`×`h` screen the way main.rs does, recording its hits.
+    fn draw(a: &mut App, w: u16, h: u16) {
+        let mut t = Terminal::new(TestBackend::new(w, h)).unwrap();
+        let mut hits = HitMap::default();
+        t.draw(|f| hits = crate::ui::render(f, a)).unwrap();
+        a.hits = hits;
+    }
+
+    /// A mouse event at (`col`, `row`) on what `a` looks like on a `w`×`h` screen.
+    fn mouse(a: &mut App, kind: MouseEventKind, col: u16, row: u16, w: u16, h: u16)
+        -> InputResult {
+        draw(a, w, h);
+        handle_mouse(a, kind, col, row)
+    }
+
     fn regions() -> Vec<Rect> {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Percentage(38),
-                Constraint::Percentage(43),
-                Constraint::Length(3),
-            ])
-            .split(Rect::new(0, 0, W, H))
-            .to_vec()
+        let l = MainLayout::new(Rect::new(0, 0, W, H), FocusPanel::Arrangement).unwrap();
+        vec![l.tabs, l.arrangement.unwrap(), l.settings.unwrap(), l.help]
     }
 
     #[test]
     fn confirm_quit_dialog_keys() {
         let mut a = two_mon();
-        a.dialog = DialogType::ConfirmQuit;
+        a.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
         assert!(matches!(k(&mut a, KeyCode::Char('n')), InputResult::Continue));
         assert!(matches!(a.dialog, DialogType::None));
-        a.dialog = DialogType::ConfirmQuit;
+        a.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
         assert!(matches!(k(&mut a, KeyCode::Char('y')), InputResult::Quit));
     }
 
+    #[test]
+    fn yes_no_dialogs_move_and_press_the_highlighted_button() {
+        let mut a = temp_app();
+        a.main_tab = MainTab::Saved;
+        a.monitor_db.add_workspace("Two").unwrap();
+        k(&mut a, KeyCode::Char('D'));
+        assert_eq!(a.dialog, DialogType::DeleteWorkspace { focus: DialogButton::No });
+        k(&mut a, KeyCode::Enter); // the default keeps the workspace
+        assert_eq!((a.dialog, a.monitor_db.workspaces.len()), (DialogType::None, 2));
+
+        k(&mut a, KeyCode::Char('D'));
+        k(&mut a, KeyCode::Left);
+        assert_eq!(a.dialog.focused_button(), Some(DialogButton::Yes));
+        k(&mut a, KeyCode::Tab);
+        k(&mut a, KeyCode::Right);
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitor_db.workspaces.len(), 1);
+
+        a.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
+        k(&mut a, KeyCode::BackTab);
+        assert!(matches!(k(&mut a, KeyCode::Enter), InputResult::Quit));
+        assert_eq!(DialogType::EditNotes.focused_button(), None);
+    }
+
     #[test]
     fn confirm_apply_yes_confirms_changes() {
         let mut a = two_mon();
@@ -683,6 +1203,7 @@ mod tests {
         a.dialog = DialogType::ConfirmApply {
             countdown: 5,
             started: Instant::now(),
+            focus: DialogButton::Yes,
         };
         k(&mut a, KeyCode::Char('y'));
         assert!(!a.has_changes);
@@ -706,9 +1227,9 @@ mod tests {
         assert_eq!(a.dropdown_selection, 0);
         k(&mut a, KeyCode::Up); // bounded at 0
         assert_eq!(a.dropdown_selection, 0);
-        a.dropdown_selection = 4;
-        k(&mut a, KeyCode::Down); // bounded at max (4)
-        assert_eq!(a.dropdown_selection, 4);
+        a.dropdown_selection = 5;
+        k(&mut a, KeyCode::Down); // bounded at max (5, "Custom…")
+        assert_eq!(a.dropdown_selection, 5);
         a.dropdown_selection = 2; // "150%"
         k(&mut a, KeyCode::Enter);
         assert!(matches!(a.dialog, DialogType::None));
@@ -718,6 +1239,165 @@ mod tests {
         assert!(matches!(a.dialog, DialogType::None));
     }
 
+    /// One monitor offering 30 resolutions, with the Resolution dropdown open.
+    fn many_modes() -> App {
+        let mut m = MonitorConfig::for_test("DP-1", "D", "M", "3000x1000");
+        m.available_modes = (1..=30).map(|i| format!("{}x1000@60.00Hz", 3000 - i * 10)).collect();
+        m.available_modes.push("3000x1000@60.00Hz".into());
+        let mut a = App::for_test(vec![m]);
+        a.focus_panel = FocusPanel::Settings;
+        a.selected_setting = 0;
+        k(&mut a, KeyCode::Enter);
+        a
+    }
+
+    #[test]
+    fn dropdown_pages_and_filters_long_lists() {
+        let mut a = many_modes();
+        assert_eq!(a.dialog, DialogType::EditDropdown);
+        k(&mut a, KeyCode::PageDown);
+        assert_eq!(a.dropdown_selection, DROPDOWN_ROWS);
+        k(&mut a, KeyCode::End);
+        assert_eq!(a.dropdown_selection, 32); // "Custom…", after "highres"
+        k(&mut a, KeyCode::Home);
+        assert_eq!(a.dropdown_selection, 0);
+
+        // "29" matches 2990x1000 down to 2900x1000
+        for c in "29".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        let options = a.get_dropdown_options();
+        let shown: Vec<&str> = a.dropdown_matches().iter().map(|&i| options[i].as_str()).collect();
+        assert_eq!(shown.len(), 10);
+        assert_eq!(shown[9], "2900x1000");
+        assert_eq!(options[a.dropdown_selection], "2990x1000");
+        k(&mut a, KeyCode::Char('j'));
+        assert_eq!(options[a.dropdown_selection], "2980x1000");
+        k(&mut a, KeyCode::Char('z'));
+        assert!(a.dropdown_matches().is_empty());
+        k(&mut a, KeyCode::Backspace);
+        k(&mut a, KeyCode::Esc); // first Esc clears the filter
+        assert_eq!(a.dialog, DialogType::EditDropdown);
+        assert_eq!(a.dropdown_matches().len(), 33);
+        k(&mut a, KeyCode::Char('2'));
+        k(&mut a, KeyCode::Char('7'));
+        k(&mut a, KeyCode::Char('9'));
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::None);
+        assert_eq!(a.monitors[0].resolution, "2790x1000");
+        assert!(a.input_buffer.is_empty());
+    }
+
+    #[test]
+    fn resolution_dropdown_names_aspect_ratios_and_hides_exotic_modes() {
+        let mut m = MonitorConfig::for_test("DP-1", "D", "M", "800x600");
+        m.available_modes = ["3440x1440", "2560x1080", "1920x1080", "800x600", "720x400"]
+            .iter()
+            .map(|res| format!("{res}@60.00Hz"))
+            .collect();
+        let mut a = App::for_test(vec![m]);
+        a.focus_panel = FocusPanel::Settings;
+        a.selected_setting = 0;
+        k(&mut a, KeyCode::Enter);
+        let options = a.get_dropdown_options();
+        assert_eq!(a.dropdown_label(&options[0]), "3440x1440   21:9");
+        assert_eq!(a.dropdown_label(&options[4]), "720x400");
+        for c in "21:9".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        assert_eq!(a.dropdown_matches(), [0, 1]);
+        k(&mut a, KeyCode::Esc);
+
+        // The current 800x600 stays, so does everything past the modes
+        k(&mut a, KeyCode::Tab);
+        let shown: Vec<&str> = a.dropdown_matches().iter().map(|&i| options[i].as_str()).collect();
+        assert_eq!(shown, ["3440x1440", "2560x1080", "1920x1080", "800x600", "highres", "Custom…"]);
+        k(&mut a, KeyCode::Tab);
+        assert_eq!(a.dropdown_matches().len(), options.len());
+    }
+
+    #[test]
+    fn mouse_picks_from_the_scrolled_dropdown() {
+        let mut a = many_modes();
+        for _ in 0..20 {
+            mouse(&mut a, MouseEventKind::ScrollDown, 0, 0, W, H);
+        }
+        assert_eq!(a.dropdown_selection, 20);
+        draw(&mut a, W, H);
+        let dd = a.hits.rect(Hit::Dropdown).unwrap();
+        assert_eq!(dd.height as usize, DROPDOWN_ROWS + 2);
+        // Centered highlight: rows show matches 16..24, so the first row is 16
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), dd.x + 2, dd.y + 1, W, H);
+        assert_eq!(a.monitors[0].resolution, a.get_dropdown_options()[16]);
+    }
+
+    #[test]
+    fn plus_minus_step_the_selected_setting() {
+        let mut a = two_mon();
+        a.focus_panel = FocusPanel::Settings;
+        a.selected_setting = 2; // Scale
+        k(&mut a, KeyCode::Char('+'));
+        assert_eq!(a.monitors[0].scale, 1.25);
+        assert!(a.has_changes);
+        for _ in 0..6 {
+            k(&mut a, KeyCode::Char('='));
+        }
+        assert_eq!(a.monitors[0].scale, 2.0); // stops at the top
+        a.monitors[0].scale = 1.33;
+        k(&mut a, KeyCode::Char('-'));
+        assert_eq!(a.monitors[0].scale, 1.25);
+        assert_eq!(a.dialog, DialogType::None);
+
+        a.selected_setting = 3; // Rotation wraps
+        k(&mut a, KeyCode::Char('-'));
+        assert_eq!(a.monitors[0].rotation, Rotation::Inverted);
+        k(&mut a, KeyCode::Char('+'));
+        assert_eq!(a.monitors[0].rotation, Rotation::Normal);
+
+        a.selected_setting = 0; // Resolution: up means more pixels
+        a.monitors[0].available_modes =
+            vec!["1280x720@60Hz".into(), "1920x1080@60Hz".into(), "2560x1440@60Hz".into()];
+        k(&mut a, KeyCode::Char('+'));
+        assert_eq!(a.monitors[0].resolution, "2560x1440");
+        k(&mut a, KeyCode::Char('-'));
+        k(&mut a, KeyCode::Char('-'));
+        assert_eq!(a.monitors[0].resolution, "1280x720");
+
+        a.focus_panel = FocusPanel::Arrangement; // works from there too, focus follows
+        k(&mut a, KeyCode::Char('+'));
+        assert_eq!(a.monitors[0].resolution, "1920x1080");
+        assert_eq!(a.focus_panel, FocusPanel::Settings);
+    }
+
+    #[test]
+    fn arrows_work_from_either_panel_and_move_focus() {
+        let mut a = two_mon();
+        a.focus_panel = FocusPanel::Settings;
+        k(&mut a, KeyCode::Right);
+        assert_eq!((a.selected_monitor, a.focus_panel), (1, FocusPanel::Arrangement));
+        k(&mut a, KeyCode::Char('j'));
+        assert_eq!((a.selected_setting, a.focus_panel), (1, FocusPanel::Settings));
+        k(&mut a, KeyCode::Char('H'));
+        assert_eq!(a.monitors[0].name, "HDMI-A-1");
+        assert_eq!(a.focus_panel, FocusPanel::Arrangement);
+    }
+
+    #[test]
+    fn wheel_over_the_selected_setting_steps_it() {
+        let mut a = two_mon();
+        a.selected_setting = 2; // Scale
+        let set = regions()[2];
+        let scale_row = set.y + 5;
+        mouse(&mut a, MouseEventKind::ScrollUp, 20, scale_row, W, H);
+        assert_eq!(a.monitors[0].scale, 1.25);
+        mouse(&mut a, MouseEventKind::ScrollDown, 20, scale_row, W, H);
+        assert_eq!(a.monitors[0].scale, 1.0);
+        // Elsewhere in the panel the wheel still moves the selection
+        mouse(&mut a, MouseEventKind::ScrollDown, 20, set.y + 2, W, H);
+        assert_eq!(a.selected_setting, 3);
+        assert_eq!(a.monitors[0].scale, 1.0);
+    }
+
     #[test]
     fn workspace_input_dialogs_create_rename_delete() {
         let mut a = temp_app();
@@ -726,41 +1406,69 @@ mod tests {
         a.dialog = DialogType::NewWorkspace;
         k(&mut a, KeyCode::Char('W'));
         k(&mut a, KeyCode::Char('s'));
-        assert_eq!(a.input_buffer, "Ws");
+        assert_eq!(a.text_input.text(), "Ws");
         k(&mut a, KeyCode::Backspace);
-        assert_eq!(a.input_buffer, "W");
+        assert_eq!(a.text_input.text(), "W");
         k(&mut a, KeyCode::Enter);
         assert!(matches!(a.dialog, DialogType::None));
         assert_eq!(a.monitor_db.workspaces.len(), 2);
 
         a.dialog = DialogType::RenameWorkspace;
-        a.input_buffer.clear();
+        a.text_input.clear();
         k(&mut a, KeyCode::Char('Z'));
         k(&mut a, KeyCode::Enter);
         assert_eq!(a.current_workspace_name(), "Z");
 
         a.dialog = DialogType::NewWorkspace;
-        a.input_buffer = "abc".into();
+        a.text_input.set_text("abc");
         k(&mut a, KeyCode::Esc);
-        assert!(a.input_buffer.is_empty());
+        assert!(a.text_input.text().is_empty());
         assert!(matches!(a.dialog, DialogType::None));
 
-        a.dialog = DialogType::DeleteWorkspace;
+        a.dialog = DialogType::DeleteWorkspace { focus: DialogButton::No };
         k(&mut a, KeyCode::Char('n'));
         assert!(matches!(a.dialog, DialogType::None));
         let before = a.monitor_db.workspaces.len();
-        a.dialog = DialogType::DeleteWorkspace;
+        a.dialog = DialogType::DeleteWorkspace { focus: DialogButton::No };
         k(&mut a, KeyCode::Char('y'));
         assert_eq!(a.monitor_db.workspaces.len(), before - 1);
     }
 
+    #[test]
+    fn x_removes_saved_monitor_after_confirm() {
+        let path = temp_path();
+        let mut a = two_mon();
+        a.monitor_db.set_config_path(path.clone());
+        a.monitor_db.update_monitor(&a.monitors[0].clone());
+        a.monitor_db.update_monitor(&a.monitors[1].clone());
+        a.switch_tab(MainTab::Saved);
+        assert_eq!(a.saved_monitors.len(), 2);
+
+        k(&mut a, KeyCode::Char('x'));
+        assert!(matches!(a.dialog, DialogType::DeleteMonitor { .. }));
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.saved_monitors.len(), 2);
+
+        let key = a.selected_saved_monitor_key().unwrap();
+        k(&mut a, KeyCode::Delete);
+        k(&mut a, KeyCode::Char('y'));
+        assert_eq!(a.saved_monitors.len(), 1);
+        assert!(!a.monitor_db.workspaces[0].monitors.contains_key(&key));
+
+        // Live tab: no-op
+        a.main_tab = MainTab::Live;
+        k(&mut a, KeyCode::Char('x'));
+        assert!(matches!(a.dialog, DialogType::None));
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn main_quit_and_tab_switch() {
         let mut a = two_mon();
         assert!(matches!(k(&mut a, KeyCode::Char('q')), InputResult::Quit));
         a.has_changes = true;
         k(&mut a, KeyCode::Char('q'));
-        assert!(matches!(a.dialog, DialogType::ConfirmQuit));
+        assert!(matches!(a.dialog, DialogType::ConfirmQuit { .. }));
         a.dialog = DialogType::None;
         k(&mut a, KeyCode::Char('2'));
         assert_eq!(a.main_tab, MainTab::Saved);
@@ -768,6 +1476,16 @@ mod tests {
         assert_eq!(a.main_tab, MainTab::Live);
     }
 
+    #[test]
+    fn alt_digit_applies_nth_workspace_without_switching_tab() {
+        let mut a = App::for_test(vec![]);
+        handle_key(&mut a, KeyCode::Char('3'), KeyModifiers::ALT);
+        assert_eq!(a.toasts.last_text(), "No workspace #3");
+        assert_eq!(a.main_tab, MainTab::Live);
+        handle_key(&mut a, KeyCode::Char('2'), KeyModifiers::ALT);
+        assert_eq!(a.main_tab, MainTab::Live); // not the plain "2 = Saved tab"
+    }
+
     #[test]
     fn main_focus_and_monitor_navigation() {
         let mut a = two_mon();
@@ -804,11 +1522,269 @@ mod tests {
         k(&mut a, KeyCode::Char(' '));
     }
 
+    #[test]
+    fn undo_and_ctrl_r_redo_in_live_tab() {
+        let mut a = two_mon();
+        handle_key(&mut a, KeyCode::Right, KeyModifiers::SHIFT);
+        assert_eq!(a.monitors[1].name, "eDP-1");
+        k(&mut a, KeyCode::Char('u'));
+        assert_eq!(a.monitors[0].name, "eDP-1");
+        assert!(!a.has_changes);
+        handle_key(&mut a, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(a.monitors[1].name, "eDP-1");
+        assert!(a.has_changes);
+    }
+
+    #[test]
+    fn ctrl_keys_off_their_tab_do_not_fall_through_to_the_plain_key() {
+        let mut a = temp_app();
+        a.main_tab = MainTab::Saved;
+        for c in ['d', 'f', 'r'] {
+            handle_key(&mut a, KeyCode::Char(c), KeyModifiers::CONTROL);
+            assert_eq!(a.dialog, DialogType::None, "Ctrl+{c}");
+        }
+    }
+
+    #[test]
+    fn o_edits_workspace_notes_and_empty_enter_clears() {
+        let path = temp_path();
+        let mut a = App::for_test(vec![]);
+        a.monitor_db.set_config_path(path.clone());
+        a.main_tab = MainTab::Saved;
+        a.monitor_db.workspaces[0].description = "old".into();
+
+        k(&mut a, KeyCode::Char('o'));
+        assert!(matches!(a.dialog, DialogType::EditNotes));
+        assert_eq!(a.text_input.text(), "old");
+        k(&mut a, KeyCode::Char('!'));
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitor_db.workspaces[0].description, "old!");
+
+        k(&mut a, KeyCode::Char('o'));
+        for _ in 0..4 {
+            k(&mut a, KeyCode::Backspace);
+        }
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::None);
+        assert!(a.monitor_db.workspaces[0].description.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn w_on_the_saved_tab_toggles_turning_the_monitor_off() {
+        let mut a = two_mon();
+        let path = temp_path();
+        a.monitor_db.set_config_path(path.clone());
+        a.main_tab = MainTab::Saved;
+        a.saved_monitors = a.monitors.clone();
+        a.saved_selected_monitor = 1;
+        k(&mut a, KeyCode::Char('w'));
+        assert_eq!(a.monitor_db.workspaces[0].dpms_off, ["HDMI-A-1"]);
+        assert_eq!(a.toasts.last_text(), "HDMI-A-1 turns off when 'Default' is applied");
+        k(&mut a, KeyCode::Char('W'));
+        assert!(a.monitor_db.workspaces[0].dpms_off.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn n_on_the_live_tab_nicknames_the_selected_monitor() {
+        let mut a = two_mon();
+        let path = temp_path();
+        a.monitor_db.set_config_path(path.clone());
+        a.selected_monitor = 1;
+        k(&mut a, KeyCode::Char('n'));
+        assert_eq!(a.dialog, DialogType::EditNickname);
+        handle_paste(&mut a, "Left MSI");
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::None);
+        assert_eq!(a.monitor_db.nicknames["desc:MSI MP"], "Left MSI");
+        assert_eq!(a.toasts.last_text(), "HDMI-A-1 is now called 'Left MSI'");
+        assert_eq!(a.monitor_db.label(&a.monitors[1]), "Left MSI");
+
+        // Opens on the current one; emptied, it goes
+        k(&mut a, KeyCode::Char('N'));
+        assert_eq!(a.text_input.text(), "Left MSI");
+        a.text_input.set_text("");
+        k(&mut a, KeyCode::Enter);
+        assert!(a.monitor_db.nicknames.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn f_edits_fallback_and_rejects_malformed_rules() {
+        let path = temp_path();
+        let mut a = App::for_test(vec![]);
+        a.monitor_db.set_config_path(path.clone());
+
+        k(&mut a, KeyCode::Char('f'));
+        assert!(matches!(a.dialog, DialogType::EditFallback));
+        assert_eq!(a.text_input.text(), "preferred,auto,1");
+        a.text_input.set_text("highrr,auto");
+        k(&mut a, KeyCode::Enter);
+        // Shown in the dialog, which stays open for a fix
+        assert!(a.text_input.error().unwrap().starts_with("fallback needs"));
+        assert!(matches!(a.dialog, DialogType::EditFallback));
+        assert_eq!(a.monitor_db.fallback.as_deref(), Some("preferred,auto,1"));
+
+        a.text_input.clear();
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitor_db.fallback, None);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn compare_view_pushes_and_pulls_rows() {
+        let path = temp_path();
+        let mut a = two_mon();
+        a.monitor_db.set_config_path(path.clone());
+        a.monitor_db.update_monitor(&a.monitors[0].clone());
+        a.monitor_db.update_monitor(&a.monitors[1].clone());
+        a.monitors[0].scale = 2.0;
+        a.monitors[1].scale = 2.0;
+
+        k(&mut a, KeyCode::Char('v'));
+        assert!(matches!(a.dialog, DialogType::Compare));
+        k(&mut a, KeyCode::Down);
+        assert_eq!(a.dropdown_selection, 1);
+        k(&mut a, KeyCode::Char('>')); // push row 1 -> one row left, clamp
+        assert_eq!(a.compare_rows().len(), 1);
+        assert_eq!(a.dropdown_selection, 0);
+        k(&mut a, KeyCode::Char('<'));
+        assert!(a.compare_rows().is_empty());
+        assert_eq!(a.monitors[0].scale, 1.0);
+        k(&mut a, KeyCode::Esc);
+        assert!(matches!(a.dialog, DialogType::None));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn ctrl_l_locks_and_m_marks_manual_only() {
+        let path = temp_path();
+        let mut a = App::for_test(vec![]);
+        a.monitor_db.set_config_path(path.clone());
+        handle_key(&mut a, KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert!(a.monitor_db.lock_active);
+        k(&mut a, KeyCode::Char('m')); // Live tab, no monitors: ignored
+        assert!(!a.monitor_db.workspaces[0].manual_only);
+        assert!(a.monitor_db.unmanaged.is_empty());
+        a.main_tab = MainTab::Saved;
+        k(&mut a, KeyCode::Char('m'));
+        assert!(a.monitor_db.workspaces[0].manual_only);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn swap_picker_offers_the_other_monitors() {
+        let mut a = two_mon();
+        a.selected_monitor = 1;
+        a.hypr_workspaces = crate::monitor::parse_workspaces(
+            br#"[{"id":2,"name":"2","monitor":"eDP-1","windows":1}]"#,
+        )
+        .unwrap();
+        k(&mut a, KeyCode::Char('X'));
+        assert_eq!(a.dialog, DialogType::SwapWorkspaces);
+        assert_eq!(a.swap_options(), ["eDP-1  2(1)"]);
+        k(&mut a, KeyCode::Down); // bounded at the only target
+        assert_eq!(a.dropdown_selection, 0);
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.dialog, DialogType::None);
+
+        let mut a = App::for_test(vec![MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080")]);
+        k(&mut a, KeyCode::Char('X'));
+        assert_eq!(a.dialog, DialogType::None);
+        assert_eq!(a.toasts.last_text(), "No other monitor to swap workspaces with");
+    }
+
+    #[test]
+    fn history_viewer_opens_scrolls_and_closes() {
+        let mut a = two_mon();
+        let mut p = temp_path();
+        p.set_extension("jsonl");
+        a.history_log = crate::history::HistoryLog::at(p.clone());
+        let entry = crate::history::HistoryEntry::between("Desk", &[], &a.monitors);
+        a.history_log.append(&entry).unwrap();
+
+        k(&mut a, KeyCode::Char('t'));
+        assert!(matches!(a.dialog, DialogType::History { scroll: 0 }));
+        assert_eq!(a.history_entries.len(), 1);
+        k(&mut a, KeyCode::Down);
+        assert!(matches!(a.dialog, DialogType::History { scroll: 1 }));
+        k(&mut a, KeyCode::Up);
+        k(&mut a, KeyCode::Up); // bounded at 0
+        assert!(matches!(a.dialog, DialogType::History { scroll: 0 }));
+        k(&mut a, KeyCode::Esc);
+        assert!(matches!(a.dialog, DialogType::None));
+        let _ = std::fs::remove_file(&p);
+    }
+
+    #[test]
+    fn save_as_picker_captures_into_existing_or_new_workspace() {
+        let mut a = temp_app();
+        a.monitors = vec![MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080")];
+        a.monitor_db.add_workspace("Office").unwrap();
+
+        k(&mut a, KeyCode::Char('S'));
+        assert!(matches!(a.dialog, DialogType::SaveAs));
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::None));
+        assert_eq!(a.current_workspace_name(), "Office");
+        assert!(a.monitor_db.workspaces[0].monitors.is_empty());
+
+        k(&mut a, KeyCode::Char('S'));
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Down); // bounded at "+ New workspace…"
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::SaveAsNew));
+        assert_eq!(a.text_input.text(), "eDP-1"); // suggested from the monitor set
+        k(&mut a, KeyCode::Char('X'));
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.current_workspace_name(), "X");
+        assert_eq!(a.monitor_db.workspaces[2].monitors.len(), 1);
+    }
+
+    #[test]
+    fn import_picker_copies_ticked_or_highlighted_monitors() {
+        let mut a = temp_app();
+        a.main_tab = MainTab::Saved;
+        k(&mut a, KeyCode::Char('i'));
+        assert!(matches!(a.dialog, DialogType::None)); // nothing to import yet
+
+        let conference = a.monitor_db.add_workspace("Conference").unwrap();
+        let mut projector = MonitorConfig::for_test("HDMI-A-1", "Epson", "EB", "1920x1080");
+        projector.position_x = 1920;
+        let laptop = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        for monitor in [&laptop, &projector] {
+            a.monitor_db.update_monitor_in(conference, monitor);
+        }
+
+        k(&mut a, KeyCode::Char('i'));
+        assert!(matches!(a.dialog, DialogType::ImportMonitors));
+        assert!(a.import_options()[1].starts_with("[ ] Conference: desc:Epson EB"));
+        k(&mut a, KeyCode::Char(' '));
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Char(' '));
+        k(&mut a, KeyCode::Up);
+        k(&mut a, KeyCode::Char(' ')); // untick the laptop again
+        assert!(a.import_options()[1].starts_with("[x]"));
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::None));
+        let keys: Vec<&String> = a.monitor_db.workspaces[0].monitors.keys().collect();
+        assert_eq!(keys, ["desc:Epson EB"]);
+        assert_eq!(a.saved_monitors.len(), 1);
+
+        // Nothing ticked: Enter takes the highlighted row
+        k(&mut a, KeyCode::Char('i'));
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitor_db.workspaces[0].monitors.len(), 2);
+        assert_eq!(a.monitor_db.workspaces[conference].monitors.len(), 2);
+    }
+
     #[test]
     fn saved_tab_keys() {
         let mut a = two_mon();
         a.monitor_db.set_config_path(temp_path());
-        a.monitor_db.add_workspace("Two");
+        a.monitor_db.add_workspace("Two").unwrap();
         a.main_tab = MainTab::Saved;
         a.saved_monitors = a.monitors.clone();
 
@@ -820,11 +1796,28 @@ mod tests {
         assert!(matches!(a.dialog, DialogType::NewWorkspace));
         a.dialog = DialogType::None;
         k(&mut a, KeyCode::Char('d'));
-        assert!(matches!(a.dialog, DialogType::DeleteWorkspace));
+        assert!(matches!(a.dialog, DialogType::DeleteWorkspace { .. }));
         a.dialog = DialogType::None;
         k(&mut a, KeyCode::Char('r'));
         assert!(matches!(a.dialog, DialogType::RenameWorkspace));
         a.dialog = DialogType::None;
+        k(&mut a, KeyCode::Char('c'));
+        assert!(matches!(a.dialog, DialogType::DuplicateWorkspace));
+        assert_eq!(a.text_input.text(), "Default copy");
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitor_db.workspaces.len(), 3);
+        assert_eq!(a.current_workspace_name(), "Default copy");
+        k(&mut a, KeyCode::Char('p')); // pin the copy -> front
+        assert_eq!(a.selected_workspace, 0);
+        assert!(a.monitor_db.workspaces[0].pinned);
+        k(&mut a, KeyCode::Char('}')); // pinned cannot leave its group
+        assert_eq!(a.selected_workspace, 0);
+        k(&mut a, KeyCode::Char('p'));
+        k(&mut a, KeyCode::Char('}'));
+        assert_eq!(a.selected_workspace, 1);
+        assert_eq!(a.current_workspace_name(), "Default copy");
+        k(&mut a, KeyCode::Char('{'));
+        assert_eq!(a.selected_workspace, 0);
 
         // Workspace navigation above refreshed saved_monitors to the (empty) saved
         // workspace; repopulate to exercise the saved-panel cursor movement.
@@ -842,35 +1835,217 @@ mod tests {
         assert_eq!(a.saved_selected_setting, 0);
     }
 
+    #[test]
+    fn esc_or_leaving_the_panel_cancels_a_drag() {
+        let mut a = two_mon();
+        let arr_row = regions()[1].y + 1;
+        let down = MouseEventKind::Down(MouseButton::Left);
+        let drag = MouseEventKind::Drag(MouseButton::Left);
+        let up = MouseEventKind::Up(MouseButton::Left);
+        mouse(&mut a, down, 55, arr_row, W, H);
+        mouse(&mut a, drag, 90, arr_row, W, H);
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.drag_state, DragState::None);
+        mouse(&mut a, up, 90, arr_row, W, H);
+        assert_eq!(a.monitors[0].name, "eDP-1");
+        assert!(!a.has_changes);
+        assert_eq!(a.toasts.last_text(), "Drag cancelled.");
+
+        mouse(&mut a, down, 55, arr_row, W, H);
+        mouse(&mut a, drag, 90, regions()[2].y + 2, W, H);
+        mouse(&mut a, drag, 90, arr_row, W, H);
+        mouse(&mut a, up, 90, arr_row, W, H);
+        assert_eq!(a.drag_state, DragState::None);
+        assert!(!a.has_changes);
+
+        mouse(&mut a, down, 55, arr_row, W, H);
+        k(&mut a, KeyCode::Tab);
+        assert_eq!(a.drag_state, DragState::None);
+    }
+
     #[test]
     fn mouse_clicks_tabs_arrangement_settings() {
         let mut a = two_mon();
         let r = regions();
         let tabs_row = r[0].y;
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 120, tabs_row, W, H);
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 120, tabs_row, W, H);
         assert_eq!(a.main_tab, MainTab::Saved);
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 5, tabs_row, W, H);
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 5, tabs_row, W, H);
         assert_eq!(a.main_tab, MainTab::Live);
 
         let arr_row = r[1].y + 1;
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 55, arr_row, W, H);
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 55, arr_row, W, H);
         assert_eq!(a.focus_panel, FocusPanel::Arrangement);
         assert_eq!(a.selected_monitor, 0);
         assert!(matches!(a.drag_state, DragState::Dragging { .. }));
 
-        handle_mouse(&mut a, MouseEventKind::Drag(MouseButton::Left), 90, arr_row, W, H);
-        handle_mouse(&mut a, MouseEventKind::Up(MouseButton::Left), 90, arr_row, W, H);
+        mouse(&mut a, MouseEventKind::Drag(MouseButton::Left), 90, arr_row, W, H);
+        mouse(&mut a, MouseEventKind::Up(MouseButton::Left), 90, arr_row, W, H);
         assert!(matches!(a.drag_state, DragState::None));
         assert!(a.has_changes);
 
         let set = r[2];
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 20, set.y + 2, W, H);
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 20, set.y + 2, W, H);
         assert_eq!(a.focus_panel, FocusPanel::Settings);
         assert_eq!(a.selected_setting, 0);
         assert!(matches!(a.dialog, DialogType::EditDropdown));
         a.dialog = DialogType::None;
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 5, set.y + 7, W, H);
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 20, set.y + 7, W, H);
         assert_eq!(a.selected_setting, 4);
+        assert!(matches!(a.dialog, DialogType::EditSetting));
+        assert_eq!(a.text_input.text(), "2560"); // X of eDP-1, now second in the row
+        a.dialog = DialogType::None;
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 5, set.y + 10, W, H);
+        assert_eq!(a.selected_setting, 6);
+
+        // Headings fold on a click; z folds the selected setting's group and
+        // Enter on a folded one opens it again
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 5, set.y + 4, W, H);
+        assert_eq!(a.collapsed_groups, [SettingGroup::Geometry]);
+        k(&mut a, KeyCode::Char('z'));
+        assert_eq!(a.collapsed_groups, [SettingGroup::Geometry, SettingGroup::Advanced]);
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.collapsed_groups, [SettingGroup::Geometry]);
+    }
+
+    #[test]
+    fn taken_workspace_names_are_refused_inline() {
+        let mut a = temp_app();
+        a.main_tab = MainTab::Saved;
+        a.monitor_db.add_workspace("Desk").unwrap();
+        for dialog in [DialogType::NewWorkspace, DialogType::DuplicateWorkspace] {
+            a.dialog = dialog;
+            a.text_input = workspace_name_input("Desk ");
+            k(&mut a, KeyCode::Enter);
+            assert_eq!(a.dialog, dialog);
+            assert_eq!(a.text_input.error(), Some("a workspace named 'Desk' already exists"));
+            assert_eq!(a.monitor_db.workspaces.len(), 2);
+        }
+
+        a.dialog = DialogType::RenameWorkspace;
+        a.text_input = workspace_name_input(&a.current_workspace_name());
+        k(&mut a, KeyCode::Enter); // keeping its own name is fine
+        assert_eq!(a.dialog, DialogType::None);
+
+        a.dialog = DialogType::RenameWorkspace;
+        a.text_input = workspace_name_input("Desk");
+        k(&mut a, KeyCode::Enter);
+        assert!(a.text_input.error().is_some());
+        k(&mut a, KeyCode::Char('2')); // editing clears the error
+        assert_eq!(a.text_input.error(), None);
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.current_workspace_name(), "Desk2");
+    }
+
+    #[test]
+    fn paste_goes_into_the_open_text_field() {
+        let mut a = two_mon();
+        handle_paste(&mut a, "ignored");
+        assert_eq!(a.dialog, DialogType::None);
+
+        a.text_input = workspace_name_input("Desk");
+        a.dialog = DialogType::RenameWorkspace;
+        handle_key(&mut a, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        handle_paste(&mut a, "Home\noffice");
+        assert_eq!(a.text_input.text(), "Homeoffice");
+
+        a.dialog = DialogType::CommandPalette;
+        handle_paste(&mut a, "undo");
+        assert_eq!(a.input_buffer, "undo");
+    }
+
+    #[test]
+    fn typed_settings_filter_keys_and_show_errors_inline() {
+        let mut a = two_mon();
+        a.focus_panel = FocusPanel::Settings;
+        a.selected_setting = 5; // Y Position
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::EditSetting));
+        assert_eq!(a.text_input.text(), "0");
+        k(&mut a, KeyCode::Backspace);
+        for c in "-1x080".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        assert_eq!(a.text_input.text(), "-1080");
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::None));
+        assert_eq!(a.monitors[0].position_y, -1080);
+        assert!(a.has_changes);
+        // Stacked on top of HDMI-A-1 the layout tiles; moved off it, it warns
+        assert!(!a.toasts.last_text().contains("lay them out in a row"));
+        a.selected_setting = 4;
+        k(&mut a, KeyCode::Enter);
+        for c in "3000".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.monitors[0].position_x, 3000);
+        assert!(a.toasts.last_text().contains("lay them out in a row"));
+
+        k(&mut a, KeyCode::Enter);
+        for c in "99".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::EditSetting));
+        assert_eq!(a.text_input.error(), Some("position must be between -32767 and 32767"));
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.monitors[0].position_x, 3000);
+
+        // "Custom…" closes the scale dropdown and opens the typed entry
+        a.selected_setting = 2;
+        k(&mut a, KeyCode::Enter);
+        k(&mut a, KeyCode::End);
+        k(&mut a, KeyCode::Enter);
+        assert!(matches!(a.dialog, DialogType::EditSetting));
+        assert_eq!(a.text_input.text(), "100");
+        handle_key(&mut a, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        for c in "1x33%".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::None);
+        assert_eq!(a.monitors[0].scale, 1.33);
+    }
+
+    #[test]
+    fn mouse_hits_scrolled_monitor_boxes() {
+        let monitors = (1..=7)
+            .map(|i| MonitorConfig::for_test(&format!("DP-{i}"), "D", "M", "1920x1080"))
+            .collect();
+        let mut a = App::for_test(monitors);
+        a.selected_monitor = 6;
+        draw(&mut a, W, H);
+        assert_eq!(a.hits.rect(Hit::Monitor(0)), None); // scrolled out of view
+        let b = a.hits.rect(Hit::Monitor(1)).unwrap();
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), b.x + 1, b.y + 1, W, H);
+        assert_eq!(a.selected_monitor, 1);
+    }
+
+    #[test]
+    fn compact_mouse_follows_the_visible_panel() {
+        let (w, h) = (60, 20);
+        let down = MouseEventKind::Down(MouseButton::Left);
+        let mut a = two_mon();
+        // Row 3 is the panel switcher; the right half shows Settings
+        mouse(&mut a, down, 45, 3, w, h);
+        assert_eq!(a.focus_panel, FocusPanel::Settings);
+        let set = MainLayout::new(Rect::new(0, 0, w, h), a.focus_panel).unwrap().settings.unwrap();
+        assert_eq!(set.y, 4);
+        mouse(&mut a, down, 20, set.y + 3, w, h);
+        assert_eq!(a.selected_setting, 1);
+        assert_eq!(a.dialog, DialogType::EditDropdown);
+        draw(&mut a, w, h);
+        let dd = a.hits.rect(Hit::Dropdown).unwrap();
+        assert!(dd.right() <= w && dd.bottom() <= set.bottom());
+        mouse(&mut a, down, dd.x + 2, dd.y + 1, w, h);
+        assert_eq!(a.dialog, DialogType::None);
+
+        mouse(&mut a, down, 5, 3, w, h);
+        assert_eq!(a.focus_panel, FocusPanel::Arrangement);
+        mouse(&mut a, down, 5, 3, 30, 10); // too small: ignored
+        mouse(&mut a, down, 45, 3, 30, 10);
+        assert_eq!(a.focus_panel, FocusPanel::Arrangement);
     }
 
     #[test]
@@ -880,39 +2055,169 @@ mod tests {
         a.selected_setting = 2;
         a.dialog = DialogType::EditDropdown;
         let set = regions()[2];
-        let ddy = set.y + 5;
-        handle_mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 25, ddy + 1, W, H);
+        let ddy = set.y + 6;
+        mouse(&mut a, MouseEventKind::Down(MouseButton::Left), 25, ddy + 1, W, H);
         assert!(matches!(a.dialog, DialogType::None));
         assert_eq!(a.current_monitor().unwrap().scale, 1.0);
 
         a.dialog = DialogType::EditDropdown;
         a.dropdown_selection = 0;
-        handle_mouse(&mut a, MouseEventKind::ScrollDown, 0, 0, W, H);
+        mouse(&mut a, MouseEventKind::ScrollDown, 0, 0, W, H);
         assert_eq!(a.dropdown_selection, 1);
-        handle_mouse(&mut a, MouseEventKind::ScrollUp, 0, 0, W, H);
+        mouse(&mut a, MouseEventKind::ScrollUp, 0, 0, W, H);
         assert_eq!(a.dropdown_selection, 0);
 
-        a.dialog = DialogType::ConfirmQuit;
-        let (cx, cy) = (W as usize / 2, H as usize / 2);
-        let res = handle_mouse(
-            &mut a,
-            MouseEventKind::Down(MouseButton::Left),
-            (cx - 9) as u16,
-            cy as u16,
-            W,
-            H,
-        );
-        assert!(matches!(res, InputResult::Quit));
-        a.dialog = DialogType::ConfirmQuit;
-        handle_mouse(
-            &mut a,
-            MouseEventKind::Down(MouseButton::Left),
-            (cx + 1) as u16,
-            cy as u16,
-            W,
-            H,
-        );
-        assert!(matches!(a.dialog, DialogType::None));
+    }
+
+    #[test]
+    fn user_keymap_rebinds_main_view_keys() {
+        let prefs = toml::from_str("[keys]\nquit = \"x\"\n").unwrap();
+        let mut a = two_mon();
+        a.keymap = crate::keymap::KeyMap::from_prefs(&prefs).unwrap();
+        assert!(matches!(k(&mut a, KeyCode::Char('q')), InputResult::Continue));
+        assert!(matches!(k(&mut a, KeyCode::Char('x')), InputResult::Quit));
+    }
+
+    #[test]
+    fn question_mark_opens_scrollable_help() {
+        let mut a = two_mon();
+        k(&mut a, KeyCode::Char('?'));
+        assert_eq!(a.dialog, DialogType::Help { scroll: 0 });
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Char('j'));
+        assert_eq!(a.dialog, DialogType::Help { scroll: 2 });
+        k(&mut a, KeyCode::Char('a')); // other keys do nothing while open
+        assert_eq!(a.dialog, DialogType::Help { scroll: 2 });
+        k(&mut a, KeyCode::End);
+        let last = help_overlay_lines().len() - 1;
+        assert_eq!(a.dialog, DialogType::Help { scroll: last });
+        mouse(&mut a, MouseEventKind::ScrollDown, 0, 0, W, H);
+        assert_eq!(a.dialog, DialogType::Help { scroll: last });
+        mouse(&mut a, MouseEventKind::ScrollUp, 0, 0, W, H);
+        assert_eq!(a.dialog, DialogType::Help { scroll: last - 1 });
+        k(&mut a, KeyCode::Char('?'));
+        assert_eq!(a.dialog, DialogType::None);
+    }
+
+    #[test]
+    fn g_opens_the_message_log() {
+        let mut a = two_mon();
+        k(&mut a, KeyCode::Char('u'));
+        handle_key(&mut a, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        k(&mut a, KeyCode::Char('g'));
+        assert_eq!(a.dialog, DialogType::Messages { scroll: 0 });
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Down);
+        let max = message_lines(&a).len() - 1;
+        assert_eq!(a.dialog, DialogType::Messages { scroll: max });
+        mouse(&mut a, MouseEventKind::ScrollUp, 0, 0, W, H);
+        assert_eq!(a.dialog, DialogType::Messages { scroll: max - 1 });
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.dialog, DialogType::None);
+
+        // With Ctrl it shows the config an apply would write instead
+        handle_key(&mut a, KeyCode::Char('g'), KeyModifiers::CONTROL);
+        assert_eq!(a.dialog, DialogType::ConfigPreview { scroll: 0 });
+        assert!(!a.config_preview.is_empty());
+        mouse(&mut a, MouseEventKind::ScrollDown, 0, 0, W, H);
+        assert_eq!(a.dialog, DialogType::ConfigPreview { scroll: 1 });
+        handle_key(&mut a, KeyCode::Char('g'), KeyModifiers::CONTROL);
+        assert_eq!(a.dialog, DialogType::None);
+    }
+
+    #[test]
+    fn enter_on_a_monitor_box_opens_its_details() {
+        let mut a = two_mon();
+        a.selected_monitor = 1;
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::MonitorDetails { scroll: 0 });
+        let name = a.monitor_details.as_ref().unwrap().name.clone();
+        assert_eq!(name, a.monitors[1].name);
+        k(&mut a, KeyCode::Char('j'));
+        assert_eq!(a.dialog, DialogType::MonitorDetails { scroll: 1 });
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.dialog, DialogType::None);
+        assert!(a.monitor_details.is_none());
+
+        a.main_tab = MainTab::Saved; // Saved boxes are not live outputs
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.dialog, DialogType::None);
+    }
+
+    #[test]
+    fn colon_opens_palette_and_enter_runs_the_top_match() {
+        let mut a = two_mon();
+        k(&mut a, KeyCode::Char(':'));
+        assert_eq!(a.dialog, DialogType::CommandPalette);
+        for c in "quit".chars() {
+            k(&mut a, KeyCode::Char(c)); // typed, not handled as main-view keys
+        }
+        assert_eq!(a.input_buffer, "quit");
+        k(&mut a, KeyCode::Backspace);
+        k(&mut a, KeyCode::Char('t'));
+        assert!(matches!(k(&mut a, KeyCode::Enter), InputResult::Quit));
+        assert_eq!(a.dialog, DialogType::None);
+        assert!(a.input_buffer.is_empty());
+
+        k(&mut a, KeyCode::Char(':'));
+        for c in "unmanage".chars() {
+            k(&mut a, KeyCode::Char(c));
+        }
+        k(&mut a, KeyCode::Down);
+        k(&mut a, KeyCode::Enter);
+        assert_eq!(a.selected_monitor, 1);
+        let key = crate::config::MonitorDatabase::get_monitor_key(&a.monitors[1]);
+        assert!(!a.monitor_db.is_managed(&key));
+
+        k(&mut a, KeyCode::Char(':'));
+        k(&mut a, KeyCode::Char('z'));
+        k(&mut a, KeyCode::Char('z'));
+        k(&mut a, KeyCode::Enter); // nothing matches: just closes
+        k(&mut a, KeyCode::Char(':'));
+        k(&mut a, KeyCode::Esc);
+        assert_eq!(a.dialog, DialogType::None);
+    }
+
+    /// Where the open dialog draws `button` on a W×H screen: its centre.
+    fn button_at(a: &mut App, button: DialogButton) -> (u16, u16) {
+        draw(a, W, H);
+        let rect = a.hits.rect(Hit::Button(button)).unwrap();
+        (rect.x + rect.width / 2, rect.y)
+    }
+
+    #[test]
+    fn mouse_clicks_dialog_buttons() {
+        let click = |a: &mut App, (x, y): (u16, u16)| {
+            mouse(a, MouseEventKind::Down(MouseButton::Left), x, y, W, H)
+        };
+        let mut a = temp_app();
+        a.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
+        let yes = button_at(&mut a, DialogButton::Yes);
+        assert!(matches!(click(&mut a, yes), InputResult::Quit));
+        let no = button_at(&mut a, DialogButton::No);
+        click(&mut a, no);
+        assert_eq!(a.dialog, DialogType::None);
+
+        k(&mut a, KeyCode::Char('2'));
+        a.dialog = DialogType::NewWorkspace;
+        a.text_input = workspace_name_input("Docked");
+        let cancel = button_at(&mut a, DialogButton::Cancel);
+        click(&mut a, (cancel.0, cancel.1 - 1)); // the row above is not a button
+        assert_eq!(a.dialog, DialogType::NewWorkspace);
+        let ok = button_at(&mut a, DialogButton::Ok);
+        click(&mut a, ok);
+        assert_eq!(a.dialog, DialogType::None);
+        assert_eq!(a.current_workspace_name(), "Docked");
+
+        a.dialog = DialogType::DeleteWorkspace { focus: DialogButton::No };
+        let yes = button_at(&mut a, DialogButton::Yes);
+        click(&mut a, yes);
+        assert!(a.monitor_db.find_workspace("Docked").is_none());
+
+        a.dialog = DialogType::RenameWorkspace;
+        let cancel = button_at(&mut a, DialogButton::Cancel);
+        click(&mut a, cancel);
+        assert_eq!(a.dialog, DialogType::None);
     }
 
     #[test]
@@ -920,14 +2225,14 @@ mod tests {
         let mut a = two_mon();
         let r = regions();
         let (arr_row, set_row) = (r[1].y + 1, r[2].y + 1);
-        handle_mouse(&mut a, MouseEventKind::ScrollDown, 0, arr_row, W, H);
+        mouse(&mut a, MouseEventKind::ScrollDown, 0, arr_row, W, H);
         assert_eq!(a.selected_monitor, 1);
-        handle_mouse(&mut a, MouseEventKind::ScrollUp, 0, arr_row, W, H);
+        mouse(&mut a, MouseEventKind::ScrollUp, 0, arr_row, W, H);
         assert_eq!(a.selected_monitor, 0);
         a.focus_panel = FocusPanel::Settings;
-        handle_mouse(&mut a, MouseEventKind::ScrollDown, 0, set_row, W, H);
+        mouse(&mut a, MouseEventKind::ScrollDown, 0, set_row, W, H);
         assert_eq!(a.selected_setting, 1);
-        handle_mouse(&mut a, MouseEventKind::ScrollUp, 0, set_row, W, H);
+        mouse(&mut a, MouseEventKind::ScrollUp, 0, set_row, W, H);
         assert_eq!(a.selected_setting, 0);
     }
 }
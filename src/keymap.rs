@@ -0,0 +1,209 @@
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::{HashMap, HashSet};
+
+use crate::prefs::Prefs;
+
+/// A key press as looked up in the map. Shift is folded into the character for
+/// `Char` keys, since terminals disagree on whether they report it.
+pub type KeyPress = (KeyCode, KeyModifiers);
+
+/// Rebindable main-view actions: config name, then default keys. The first
+/// default is the key `input.rs` handles the action under.
+const ACTIONS: &[(&str, &[&str])] = &[
+    ("quit", &["q", "Q"]),
+    ("live_tab", &["1"]),
+    ("saved_tab", &["2"]),
+    ("prev_workspace", &["["]),
+    ("next_workspace", &["]"]),
+    ("move_workspace_left", &["{"]),
+    ("move_workspace_right", &["}"]),
+    ("new_workspace", &["n", "N"]),
+    ("delete_workspace", &["d", "D"]),
+    ("remove_monitor", &["x", "Delete"]),
+    ("duplicate_workspace", &["c", "C"]),
+    ("edit_in_live", &["e", "E"]),
+    ("compare", &["v", "V"]),
+    ("fallback", &["f", "F"]),
+    ("notes", &["o", "O"]),
+    ("switch_panel", &["Tab"]),
+    ("next_monitor", &["BackTab", "Shift+Tab"]),
+    ("primary", &["p", "P"]),
+    ("manage", &["m", "M"]),
+    ("apply", &["a", "A"]),
+    ("identify", &["i", "I"]),
+    ("focus", &["w", "W"]),
+    ("swap_workspaces", &["X"]),
+    ("game_mode", &["b", "B"]),
+    ("safe_mode", &["!"]),
+    ("presentation", &["Ctrl+o"]),
+    ("projector", &["Ctrl+f"]),
+    ("preset", &["s"]),
+    ("density_scale", &["Ctrl+d"]),
+    ("copy_config", &["y", "Y"]),
+    ("view_config", &["Ctrl+g"]),
+    ("rollback", &["Ctrl+b"]),
+    ("save_as", &["S"]),
+    ("history", &["t", "T"]),
+    ("messages", &["g", "G"]),
+    ("help", &["?"]),
+    ("palette", &[":"]),
+    ("undo", &["u", "U"]),
+    ("redo", &["Ctrl+r"]),
+    ("refresh", &["r", "R"]),
+    ("lock", &["Ctrl+l"]),
+    ("auto_apply", &["Ctrl+p"]),
+    ("left", &["Left", "h"]),
+    ("right", &["Right", "l"]),
+    ("up", &["Up", "k"]),
+    ("down", &["Down", "j"]),
+    ("move_left", &["Shift+Left", "H"]),
+    ("move_right", &["Shift+Right", "L"]),
+    ("select", &["Enter", "Space"]),
+    ("increase", &["+", "="]),
+    ("decrease", &["-"]),
+    ("fold", &["z", "Z"]),
+];
+
+/// Translates user keys to the keys `handle_key` understands. Keys the user
+/// config does not touch pass through unchanged; default keys of a rebound
+/// action are dropped so the old binding stops working.
+#[derive(Debug, Default)]
+pub struct KeyMap {
+    remap: HashMap<KeyPress, KeyPress>,
+    disabled: HashSet<KeyPress>,
+}
+
+impl KeyMap {
+    /// Build from the `[keys]` table of the user's config file.
+    pub fn from_prefs(prefs: &Prefs) -> Result<Self> {
+        let mut map = Self::default();
+        for (name, keys) in &prefs.keys {
+            let Some((_, defaults)) = ACTIONS.iter().find(|(n, _)| n == name) else {
+                let names: Vec<&str> = ACTIONS.iter().map(|(n, _)| *n).collect();
+                bail!("unknown action '{name}' in [keys] (known: {})", names.join(", "));
+            };
+            let target = parse_key(defaults[0])?;
+            for key in defaults.iter() {
+                map.disabled.insert(parse_key(key)?);
+            }
+            for key in keys.keys() {
+                map.remap.insert(parse_key(key)?, target);
+            }
+        }
+        Ok(map)
+    }
+
+    /// The key to handle in place of `code`+`modifiers`, or `None` when the
+    /// press was unbound by the user's config.
+    pub fn translate(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyPress> {
+        let press = normalize(code, modifiers);
+        if let Some(target) = self.remap.get(&press) {
+            return Some(*target);
+        }
+        (!self.disabled.contains(&press)).then_some((code, modifiers))
+    }
+}
+
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> KeyPress {
+    match code {
+        KeyCode::Char(_) => (code, modifiers - KeyModifiers::SHIFT),
+        KeyCode::BackTab => (KeyCode::Tab, modifiers | KeyModifiers::SHIFT),
+        _ => (code, modifiers),
+    }
+}
+
+/// Parse `h`, `Ctrl+r`, `Shift+Left`, `F5`, `Space`, … into a key press.
+pub fn parse_key(text: &str) -> Result<KeyPress> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    while let Some((prefix, tail)) = rest.split_once('+').filter(|(_, t)| !t.is_empty()) {
+        modifiers |= match prefix.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => bail!("unknown modifier '{prefix}' in key '{text}'"),
+        };
+        rest = tail;
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "delete" | "del" => KeyCode::Delete,
+            "backspace" => KeyCode::Backspace,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            other => match other.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => bail!("unknown key '{text}'"),
+            },
+        },
+    };
+    Ok(normalize(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(toml: &str) -> Prefs {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn parse_key_handles_chars_names_and_modifiers() {
+        assert_eq!(parse_key("h").unwrap(), (KeyCode::Char('h'), KeyModifiers::NONE));
+        assert_eq!(parse_key("Ctrl+r").unwrap(), (KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key("Shift+Left").unwrap(), (KeyCode::Left, KeyModifiers::SHIFT));
+        assert_eq!(parse_key("+").unwrap(), (KeyCode::Char('+'), KeyModifiers::NONE));
+        assert_eq!(parse_key("Alt++").unwrap(), (KeyCode::Char('+'), KeyModifiers::ALT));
+        assert_eq!(parse_key("f5").unwrap().0, KeyCode::F(5));
+        assert_eq!(parse_key("BackTab").unwrap(), parse_key("Shift+Tab").unwrap());
+        assert!(parse_key("Hyper+x").is_err());
+        assert!(parse_key("Nope").is_err());
+        assert!(parse_key("F13").is_err());
+    }
+
+    #[test]
+    fn default_map_passes_everything_through() {
+        let map = KeyMap::default();
+        let shift_s = (KeyCode::Char('S'), KeyModifiers::SHIFT);
+        assert_eq!(map.translate(shift_s.0, shift_s.1), Some(shift_s));
+        for (_, keys) in ACTIONS {
+            for key in *keys {
+                parse_key(key).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn rebinding_replaces_the_default_keys() {
+        let map = KeyMap::from_prefs(&prefs("[keys]\nleft = [\"Left\", \"n\"]\nredo = \"R\"\n"))
+            .unwrap();
+        let none = KeyModifiers::NONE;
+        assert_eq!(map.translate(KeyCode::Char('n'), none), Some((KeyCode::Left, none)));
+        assert_eq!(map.translate(KeyCode::Left, none), Some((KeyCode::Left, none)));
+        assert_eq!(map.translate(KeyCode::Char('h'), none), None);
+        assert_eq!(
+            map.translate(KeyCode::Char('R'), KeyModifiers::SHIFT),
+            Some((KeyCode::Char('r'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(map.translate(KeyCode::Char('r'), KeyModifiers::CONTROL), None);
+        assert_eq!(map.translate(KeyCode::Char('j'), none), Some((KeyCode::Char('j'), none)));
+
+        assert!(KeyMap::from_prefs(&prefs("[keys]\nfly = \"z\"\n")).is_err());
+        assert!(KeyMap::from_prefs(&prefs("[keys]\nup = \"Ctrl+Nope\"\n")).is_err());
+    }
+}
@@ -1,41 +1,81 @@
 mod app;
+mod backups;
+mod cli;
+mod clipboard;
+mod clock;
+mod compare;
 mod config;
+mod daemon;
+mod diagram;
+mod drm;
+mod dry_run;
+mod edid;
+mod exec;
+mod history;
 mod hypr_ipc;
+mod i18n;
 mod input;
+mod keymap;
+mod metrics;
 mod monitor;
+mod pacing;
+mod palette;
+mod plain;
+mod prefs;
+mod presentation;
+mod presets;
+mod safe;
+#[cfg(test)]
+mod scenario;
+mod schedule;
+mod session;
 mod state;
+mod systemd;
+mod text;
+mod toast;
 mod ui;
+mod undo;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+        EnableMouseCapture, Event, KeyEventKind,
+    },
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::prelude::*;
-use std::{io::stdout, sync::mpsc, time::Duration};
+use std::{
+    cell::RefCell,
+    io::stdout,
+    rc::Rc,
+    sync::mpsc::{self, TryRecvError},
+    time::{Duration, Instant},
+};
 
 use app::App;
-use hypr_ipc::HyprEvent;
-use input::{handle_key, handle_mouse, InputResult};
-use state::DialogType;
-use state::MainTab;
-use ui::{
-    render_arrangement_panel, render_confirm_apply_dialog, render_confirm_quit_dialog,
-    render_dropdown, render_help_bar, render_input_dialog, render_main_tabs,
-    render_saved_arrangement_panel, render_saved_settings_panel, render_settings_panel,
-    render_workspace_tabs,
-};
+use hypr_ipc::{AddedMonitor, HyprEvent};
+use i18n::{tr, tr_fill};
+use input::{handle_key, handle_mouse, handle_paste, InputResult};
+use pacing::{FrameLimiter, Pacing};
 
 fn main() -> Result<()> {
+    let command = cli::parse(std::env::args().skip(1))?;
+    let cli::Command::Tui { ascii, pacing, auto_apply } = command else {
+        return cli::run(command);
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
     stdout().execute(EnableMouseCapture)?;
+    stdout().execute(EnableBracketedPaste)?;
 
-    let result = run_app();
+    let result = run_app(ascii, pacing, auto_apply);
 
     // Cleanup terminal
+    stdout().execute(DisableBracketedPaste)?;
     stdout().execute(DisableMouseCapture)?;
     disable_raw_mode()?;
     stdout().execute(LeaveAlternateScreen)?;
@@ -43,148 +83,116 @@ fn main() -> Result<()> {
     result
 }
 
-fn run_app() -> Result<()> {
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+fn run_app(ascii: bool, pacing: Pacing, auto_apply: bool) -> Result<()> {
+    let terminal = Rc::new(RefCell::new(Terminal::new(CrosstermBackend::new(stdout()))?));
     let mut app = App::new()?;
+    // An apply blocks the loop below, so it draws its own progress
+    let shared = Rc::clone(&terminal);
+    app.progress = Some(Box::new(move |app: &App| {
+        let _ = shared.borrow_mut().draw(|frame| {
+            ui::render(frame, app);
+            if ascii {
+                ui::ascii::asciify(frame.buffer_mut());
+            }
+        });
+    }));
+    app.auto_apply = auto_apply;
+    app.restore_session(&session::Session::load());
 
     // Start Hyprland IPC listener for monitor events
     let (ipc_tx, ipc_rx) = mpsc::channel::<HyprEvent>();
-    if let Err(e) = hypr_ipc::start_listener(ipc_tx) {
-        app.message = format!("IPC: {}", e);
+    match hypr_ipc::start_listener(ipc_tx) {
+        Ok(()) => app.ipc_connected = true,
+        Err(e) => app.toasts.warn(tr_fill("IPC unavailable: {}", &[&e])),
     }
 
+    // Hyprland emits no event for `hyprctl keyword monitor`, so poll for it
+    let mut last_poll = Instant::now();
+    let mut frames = FrameLimiter::new(pacing, Instant::now());
+
     loop {
-        // Handle IPC events (non-blocking)
-        while let Ok(event) = ipc_rx.try_recv() {
-            match event {
-                HyprEvent::MonitorAdded(name) => {
-                    let _ = app.on_monitor_added(&name);
-                }
-                HyprEvent::MonitorRemoved(name) => {
-                    let _ = app.on_monitor_removed(&name);
-                }
-            }
-        }
-        // Handle countdown timer for confirm dialog
-        if let DialogType::ConfirmApply { countdown, started } = app.dialog {
-            let elapsed = started.elapsed().as_secs() as u8;
-            if elapsed >= countdown {
-                app.revert_changes();
-                let _ = app.save_and_apply();
-                app.dialog = DialogType::None;
-            }
+        if last_poll.elapsed() >= Duration::from_secs(5) {
+            app.poll_hyprland();
+            last_poll = Instant::now();
         }
 
-        // Render UI
-        terminal.draw(|frame| {
-            let area = frame.area();
-
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3), // Main tabs (3 rows for box)
-                    Constraint::Percentage(38),
-                    Constraint::Percentage(43),
-                    Constraint::Length(3),
-                ])
-                .split(area);
-
-            render_main_tabs(frame, chunks[0], &app);
-
-            match app.main_tab {
-                MainTab::Live => {
-                    render_arrangement_panel(frame, chunks[1], &app);
-                    render_settings_panel(frame, chunks[2], &app);
-                }
-                MainTab::Saved => {
-                    // Split arrangement area for workspace tabs
-                    let saved_chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([
-                            Constraint::Length(2), // Workspace tabs
-                            Constraint::Min(0),    // Arrangement
-                        ])
-                        .split(chunks[1]);
-
-                    render_workspace_tabs(frame, saved_chunks[0], &app);
-                    render_saved_arrangement_panel(frame, saved_chunks[1], &app);
-                    render_saved_settings_panel(frame, chunks[2], &app);
-                }
-            }
-
-            render_help_bar(frame, chunks[3], &app);
-
-            // Render dialogs on top
-            match app.dialog {
-                DialogType::EditDropdown => {
-                    if app.main_tab == MainTab::Live {
-                        render_dropdown(frame, chunks[2], &app);
+        // Handle IPC events (non-blocking)
+        let mut workspaces_changed = false;
+        let mut added: Vec<AddedMonitor> = Vec::new();
+        loop {
+            match ipc_rx.try_recv() {
+                // v1 and v2 announce the same monitor; keep the fuller v2,
+                // the one with an id even when its description is empty
+                Ok(HyprEvent::MonitorAdded(monitor)) => {
+                    match added.iter_mut().find(|m| m.name == monitor.name) {
+                        Some(seen) if monitor.id.is_some() => *seen = monitor,
+                        Some(_) => {}
+                        None => added.push(monitor),
                     }
                 }
-                DialogType::ConfirmApply { started, .. } => {
-                    let elapsed = started.elapsed().as_secs() as u8;
-                    let remaining = 15u8.saturating_sub(elapsed);
-                    render_confirm_apply_dialog(frame, remaining);
-                }
-                DialogType::ConfirmQuit => {
-                    render_confirm_quit_dialog(frame);
+                Ok(HyprEvent::MonitorRemoved(name)) => {
+                    app.on_monitor_removed(&name);
+                    frames.invalidate();
                 }
-                DialogType::NewWorkspace => {
-                    render_input_dialog(
-                        frame,
-                        "New Workspace",
-                        &app.input_buffer,
-                        "Enter workspace name:",
-                    );
+                // Windows opening in a burst cost one hyprctl call
+                Ok(HyprEvent::WorkspacesChanged) => workspaces_changed = true,
+                Ok(HyprEvent::MonitorFocused(name)) => {
+                    app.focused_monitor = Some(name);
+                    frames.invalidate();
                 }
-                DialogType::RenameWorkspace => {
-                    render_input_dialog(
-                        frame,
-                        "Rename Workspace",
-                        &app.input_buffer,
-                        "Enter new name:",
-                    );
-                }
-                DialogType::DeleteWorkspace => {
-                    render_input_dialog(
-                        frame,
-                        "Delete Workspace",
-                        "",
-                        &format!(
-                            "Delete '{}'? Press Y to confirm",
-                            app.current_workspace_name()
-                        ),
-                    );
+                Err(TryRecvError::Disconnected) => {
+                    // The listener thread ends when Hyprland closes the socket
+                    if app.ipc_connected {
+                        app.ipc_connected = false;
+                        app.toasts.warn(tr("IPC: lost connection to Hyprland"));
+                    }
+                    break;
                 }
-                DialogType::None => {}
+                Err(TryRecvError::Empty) => break,
             }
-        })?;
+        }
+        for monitor in &added {
+            let _ = app.on_monitor_added(monitor);
+            frames.invalidate();
+        }
+        if workspaces_changed {
+            app.refresh_workspaces();
+            frames.invalidate();
+        }
+        app.expire_apply_countdown();
+
+        // Render UI, keeping what was drawn where for mouse events
+        if frames.should_draw(Instant::now()) {
+            terminal.borrow_mut().draw(|frame| {
+                app.hits = ui::render(frame, &app);
+                if ascii {
+                    ui::ascii::asciify(frame.buffer_mut());
+                }
+            })?;
+        }
 
         // Handle input
-        if event::poll(Duration::from_millis(100))? {
+        if event::poll(frames.timeout(Instant::now()))? {
+            frames.invalidate();
             match event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
                     if let InputResult::Quit = handle_key(&mut app, key.code, key.modifiers) {
                         break;
                     }
                 }
+                Event::Paste(text) => handle_paste(&mut app, &text),
                 Event::Mouse(mouse) => {
-                    let size = terminal.size()?;
-                    if let InputResult::Quit = handle_mouse(
-                        &mut app,
-                        mouse.kind,
-                        mouse.column,
-                        mouse.row,
-                        size.width,
-                        size.height,
-                    ) {
+                    let result = handle_mouse(&mut app, mouse.kind, mouse.column, mouse.row);
+                    if let InputResult::Quit = result {
                         break;
                     }
                 }
                 _ => {}
             }
+            app.identify_selection_change();
         }
     }
 
+    let _ = app.session().save();
     Ok(())
 }
@@ -0,0 +1,209 @@
+//! Prometheus metrics for `hyprmon daemon`: counted from its
+//! [`DaemonEvent`]s and served on a local `/metrics` endpoint or written for
+//! node_exporter's textfile collector.
+
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::daemon::DaemonEvent;
+
+/// What the daemon has done since it started.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Metrics {
+    pub applies: u64,
+    pub apply_errors: u64,
+    pub hotplugs: u64,
+    pub match_failures: u64,
+    /// How long the last apply took, in seconds.
+    pub last_apply_seconds: Option<f64>,
+    /// Unix seconds of the last apply.
+    pub last_apply_at: Option<u64>,
+    /// Name and id of the workspace applied last.
+    pub active: Option<(String, String)>,
+}
+
+impl Metrics {
+    /// Count `event`, which happened at unix seconds `time`. Dry runs count
+    /// as applies too; they are what the daemon would have done.
+    pub fn record(&mut self, event: &DaemonEvent, time: u64) {
+        match event {
+            DaemonEvent::HotplugDetected { .. } => self.hotplugs += 1,
+            DaemonEvent::ProfileApplied { workspace, id, seconds, .. } => {
+                self.applies += 1;
+                self.last_apply_seconds = Some(*seconds);
+                self.last_apply_at = Some(time);
+                self.active = Some((workspace.clone(), id.clone()));
+            }
+            DaemonEvent::MatchFailed { .. } => self.match_failures += 1,
+            DaemonEvent::ApplyError { .. } => self.apply_errors += 1,
+        }
+    }
+
+    /// The Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(out, "# HELP hyprmon_{name} {help}");
+            let _ = writeln!(out, "# TYPE hyprmon_{name} {kind}");
+            for (labels, value) in samples {
+                let _ = writeln!(out, "hyprmon_{name}{labels} {value}");
+            }
+        };
+        let one = |value: String| [(String::new(), value)];
+        metric("applies_total", "counter", "Workspaces applied.", &one(self.applies.to_string()));
+        metric(
+            "apply_errors_total",
+            "counter",
+            "Failed applies and Hyprland config errors.",
+            &one(self.apply_errors.to_string()),
+        );
+        metric(
+            "hotplug_events_total",
+            "counter",
+            "Monitor hotplugs handled.",
+            &one(self.hotplugs.to_string()),
+        );
+        metric(
+            "match_failures_total",
+            "counter",
+            "Times no saved workspace fit the connected monitors.",
+            &one(self.match_failures.to_string()),
+        );
+        if let Some(seconds) = self.last_apply_seconds {
+            let help = "Duration of the last apply.";
+            metric("last_apply_duration_seconds", "gauge", help, &one(format!("{seconds:.3}")));
+        }
+        if let Some(at) = self.last_apply_at {
+            let help = "Unix time of the last apply.";
+            metric("last_apply_timestamp_seconds", "gauge", help, &one(at.to_string()));
+        }
+        if let Some((name, id)) = &self.active {
+            let labels = format!("{{workspace=\"{}\",id=\"{}\"}}", escape(name), escape(id));
+            let help = "The workspace applied last, as labels.";
+            metric("active_profile", "gauge", help, &[(labels, "1".to_string())]);
+        }
+        out
+    }
+}
+
+/// A label value with `\`, `"` and newlines escaped.
+fn escape(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', "\\\"").replace('\n', r"\n")
+}
+
+/// Write `metrics` to `path` for node_exporter's textfile collector, through
+/// a temporary file so it never reads half a file.
+pub fn write_textfile(path: &Path, metrics: &Metrics) -> Result<()> {
+    let tmp = path.with_extension("prom.tmp");
+    std::fs::write(&tmp, metrics.render())?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Serve `metrics` at `http://addr/metrics` from a thread of its own.
+/// Returns the address bound, which tells the port when `addr` asked for 0.
+pub fn serve(addr: &str, metrics: Arc<Mutex<Metrics>>) -> Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let bound = listener.local_addr()?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &metrics);
+        }
+    });
+    Ok(bound)
+}
+
+/// Answer one request: the metrics on `GET /metrics`, 404 for anything else.
+fn respond(mut stream: TcpStream, metrics: &Mutex<Metrics>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    // Up to the end of the headers; the request line may arrive in pieces.
+    let (mut request, mut len) = ([0u8; 1024], 0);
+    while len < request.len() && !request[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+        match stream.read(&mut request[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    let request = String::from_utf8_lossy(&request[..len]);
+    let mut words = request.split_whitespace();
+    let (status, body) = match (words.next(), words.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            ("200 OK", metrics.lock().map(|m| m.render()).unwrap_or_default())
+        }
+        _ => ("404 Not Found", "Try /metrics\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(workspace: &str) -> DaemonEvent {
+        DaemonEvent::ProfileApplied {
+            workspace: workspace.into(),
+            id: "42".into(),
+            dry_run: false,
+            seconds: 0.25,
+        }
+    }
+
+    #[test]
+    fn counts_events_and_renders_the_exposition_format() {
+        let mut metrics = Metrics::default();
+        assert!(metrics.render().contains("hyprmon_applies_total 0\n"));
+        assert!(!metrics.render().contains("active_profile"));
+
+        metrics.record(&DaemonEvent::HotplugDetected { monitors: vec![] }, 10);
+        metrics.record(&applied("Desk \"big\""), 11);
+        metrics.record(&DaemonEvent::ApplyError { message: "oops".into() }, 12);
+        metrics.record(&DaemonEvent::MatchFailed { monitors: vec![] }, 13);
+        let text = metrics.render();
+        for line in [
+            "# TYPE hyprmon_applies_total counter",
+            "hyprmon_applies_total 1",
+            "hyprmon_apply_errors_total 1",
+            "hyprmon_hotplug_events_total 1",
+            "hyprmon_match_failures_total 1",
+            "hyprmon_last_apply_duration_seconds 0.250",
+            "hyprmon_last_apply_timestamp_seconds 11",
+            r#"hyprmon_active_profile{workspace="Desk \"big\"",id="42"} 1"#,
+        ] {
+            assert!(text.lines().any(|l| l == line), "{line} missing from\n{text}");
+        }
+    }
+
+    #[test]
+    fn serves_metrics_over_http_and_writes_a_textfile() {
+        let metrics = Arc::new(Mutex::new(Metrics::default()));
+        metrics.lock().unwrap().record(&applied("Desk"), 1);
+        let addr = serve("127.0.0.1:0", metrics.clone()).unwrap();
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut reply = String::new();
+            stream.read_to_string(&mut reply).unwrap();
+            reply
+        };
+        let reply = get("/metrics");
+        assert!(reply.starts_with("HTTP/1.1 200 OK\r\n"), "{reply}");
+        assert!(reply.ends_with(&metrics.lock().unwrap().render()));
+        assert!(get("/").starts_with("HTTP/1.1 404"));
+
+        let path = std::env::temp_dir().join(format!("hyprmon_{}.prom", std::process::id()));
+        write_textfile(&path, &metrics.lock().unwrap()).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("hyprmon_applies_total 1"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,6 +1,13 @@
-use anyhow::Result;
-use serde::Deserialize;
-use std::process::Command;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::edid::Edid;
+use crate::exec::Executor;
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -19,9 +26,66 @@ pub struct HyprMonitor {
     pub transform: u32,
     pub available_modes: Vec<String>,
     pub focused: bool,
+    #[serde(default)]
+    pub serial: String,
+    /// Millimetres; 0 when the EDID does not say.
+    #[serde(default)]
+    pub physical_width: u32,
+    #[serde(default)]
+    pub physical_height: u32,
+    #[serde(default)]
+    pub dpms_status: Option<bool>,
+    #[serde(default)]
+    pub vrr: Option<bool>,
+}
+
+/// Read-only facts about one output for the details dialog. Everything past
+/// `available_modes` only comes from hyprctl and is unknown otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorDetails {
+    pub name: String,
+    pub description: String,
+    pub make: String,
+    pub model: String,
+    pub current_mode: String,
+    pub available_modes: Vec<String>,
+    pub serial: String,
+    /// Width and height in millimetres.
+    pub physical_size: Option<(u32, u32)>,
+    pub dpms: Option<bool>,
+    pub vrr: Option<bool>,
+    /// The EDID from `/sys/class/drm`, decoded; `None` when it cannot be read.
+    pub edid: Option<Edid>,
+}
+
+impl MonitorDetails {
+    /// What the live config alone can tell, for when hyprctl is unavailable.
+    pub fn from_config(monitor: &MonitorConfig) -> Self {
+        Self {
+            name: monitor.name.clone(),
+            description: monitor.description.clone(),
+            make: monitor.make.clone(),
+            model: monitor.model.clone(),
+            current_mode: format!("{}@{:.2}Hz", monitor.resolution, monitor.refresh_rate),
+            available_modes: monitor.available_modes.clone(),
+            serial: String::new(),
+            physical_size: None,
+            dpms: None,
+            vrr: None,
+            edid: None,
+        }
+    }
+
+    /// Diagonal in inches and horizontal pixel density, when the size is known.
+    pub fn diagonal_and_dpi(&self) -> Option<(f64, f64)> {
+        let (w, h) = self.physical_size?;
+        let px: f64 = self.current_mode.split(['x', '@']).next()?.parse().ok()?;
+        let diagonal = (w as f64).hypot(h as f64) / 25.4;
+        Some((diagonal, px / (w as f64 / 25.4)))
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MonitorConfig {
     pub name: String,
     pub description: String,
@@ -35,6 +99,64 @@ pub struct MonitorConfig {
     pub rotation: Rotation,
     pub is_primary: bool,
     pub available_modes: Vec<String>,
+    /// EDID serial; empty when the monitor does not report one.
+    pub serial: String,
+    /// What tells this monitor apart in the database; see
+    /// [`crate::config::MonitorDatabase::get_monitor_key`].
+    pub key_by: KeyBy,
+    /// Hyprland mode keyword applied instead of `resolution@refresh_rate`,
+    /// which then only show the mode it picks.
+    pub mode_preset: Option<ModePreset>,
+}
+
+/// Hyprland's `highres` / `highrr` mode keywords: "the best mode this panel
+/// offers" rather than a fixed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModePreset {
+    HighRes,
+    HighRr,
+}
+
+impl ModePreset {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            ModePreset::HighRes => "highres",
+            ModePreset::HighRr => "highrr",
+        }
+    }
+
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        [ModePreset::HighRes, ModePreset::HighRr].into_iter().find(|p| p.keyword() == keyword)
+    }
+
+    /// The mode Hyprland picks from `modes` (`WxH@RHz`): the most pixels for
+    /// `highres`, the highest refresh rate for `highrr`, the other breaking ties.
+    pub fn pick(self, modes: &[String]) -> Option<(String, f64)> {
+        let parsed = modes.iter().filter_map(|mode| {
+            let (res, rate) = mode.split_once('@')?;
+            let (w, h) = res.trim().split_once('x')?;
+            let pixels = w.parse::<u64>().ok()? * h.parse::<u64>().ok()?;
+            let rate: f64 = rate.trim().trim_end_matches("Hz").parse().ok()?;
+            Some((res.trim(), pixels, rate))
+        });
+        let best = parsed.max_by(|a, b| match self {
+            ModePreset::HighRes => a.1.cmp(&b.1).then(a.2.total_cmp(&b.2)),
+            ModePreset::HighRr => a.2.total_cmp(&b.2).then(a.1.cmp(&b.1)),
+        })?;
+        Some((best.0.to_string(), best.2))
+    }
+}
+
+/// How a monitor is keyed in the database. Identical models share a
+/// description, so once two are connected they need a serial or connector key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyBy {
+    #[default]
+    Description,
+    Serial,
+    Connector,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -91,11 +213,253 @@ impl MonitorConfig {
             self.model.clone()
         }
     }
+
+    /// This monitor's current layout as a one-off `monitor=` rule for
+    /// `hyprctl keyword`, at refresh rate `rate` and with VRR on or off.
+    pub fn keyword_rule(&self, rate: f64, vrr: bool) -> String {
+        let mut rule = format!(
+            "{},{}@{rate:.2},{}x{},{}",
+            self.name,
+            self.resolution,
+            self.position_x,
+            self.position_y,
+            crate::config::format_scale(self.scale)
+        );
+        if self.rotation != Rotation::Normal {
+            rule.push_str(&format!(",transform,{}", self.rotation.transform()));
+        }
+        rule.push_str(if vrr { ",vrr,1" } else { ",vrr,0" });
+        rule
+    }
+
+    /// Refresh rates `available_modes` lists at `resolution`, highest first.
+    pub fn rates_at(&self, resolution: &str) -> Vec<f64> {
+        let mut rates: Vec<f64> = self
+            .available_modes
+            .iter()
+            .filter_map(|mode| mode.split_once('@'))
+            .filter(|(res, _)| res.trim() == resolution.trim())
+            .filter_map(|(_, rate)| rate.trim().trim_end_matches("Hz").parse().ok())
+            .collect();
+        rates.sort_by(|a, b| b.total_cmp(a));
+        rates
+    }
+
+    /// Whether the resolution and refresh rate are a mode the monitor
+    /// advertises, rates compared as the dropdown rounds them. A monitor
+    /// without a mode list, or following a preset, has nothing to check.
+    pub fn mode_advertised(&self) -> bool {
+        self.available_modes.is_empty()
+            || self.mode_preset.is_some()
+            || self
+                .rates_at(&self.resolution)
+                .iter()
+                .any(|rate| (rate - self.refresh_rate).abs() < 0.5)
+    }
+
+    /// After a resolution change: keep the refresh rate if the resolution
+    /// offers it, else switch to the offered rate nearest to it (the higher
+    /// one on a tie). Returns the new rate when it changed.
+    pub fn fit_refresh_rate(&mut self) -> Option<f64> {
+        if self.mode_advertised() {
+            return None;
+        }
+        let old = self.refresh_rate;
+        let nearest = self
+            .rates_at(&self.resolution)
+            .into_iter()
+            .min_by(|a, b| (a - old).abs().total_cmp(&(b - old).abs()))?;
+        self.refresh_rate = nearest;
+        Some(nearest)
+    }
+}
+
+pub fn fetch_monitors(exec: &dyn Executor) -> Result<Vec<MonitorConfig>> {
+    parse_monitors(&hyprctl(exec, &["monitors", "-j"])?)
+}
+
+/// Width and height in millimetres of each output whose EDID gives them, by
+/// connector.
+pub fn fetch_physical_sizes(exec: &dyn Executor) -> Result<Vec<(String, (u32, u32))>> {
+    let hypr_monitors: Vec<HyprMonitor> =
+        serde_json::from_slice(&hyprctl(exec, &["monitors", "-j"])?)?;
+    Ok(hypr_monitors
+        .into_iter()
+        .filter(|m| m.physical_width > 0 && m.physical_height > 0)
+        .map(|m| (m.name, (m.physical_width, m.physical_height)))
+        .collect())
+}
+
+/// Details of the output called `name`, straight from `hyprctl monitors -j`.
+pub fn fetch_monitor_details(exec: &dyn Executor, name: &str) -> Result<MonitorDetails> {
+    parse_monitor_details(&hyprctl(exec, &["monitors", "-j"])?, name)
+}
+
+pub fn parse_monitor_details(json: &[u8], name: &str) -> Result<MonitorDetails> {
+    let hypr_monitors: Vec<HyprMonitor> = serde_json::from_slice(json)?;
+    let Some(m) = hypr_monitors.into_iter().find(|m| m.name == name) else {
+        bail!("{name} is no longer connected");
+    };
+    let physical_size = (m.physical_width > 0 && m.physical_height > 0)
+        .then_some((m.physical_width, m.physical_height));
+    Ok(MonitorDetails {
+        current_mode: format!("{}x{}@{:.2}Hz", m.width, m.height, m.refresh_rate),
+        description: m
+            .description
+            .strip_suffix(&format!(" ({})", m.name))
+            .unwrap_or(&m.description)
+            .to_string(),
+        name: m.name,
+        make: m.make,
+        model: m.model,
+        available_modes: m.available_modes,
+        serial: m.serial,
+        physical_size,
+        dpms: m.dpms_status,
+        vrr: m.vrr,
+        edid: None,
+    })
+}
+
+/// A Hyprland workspace as `hyprctl workspaces -j` reports it.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HyprWorkspace {
+    pub id: i32,
+    pub name: String,
+    /// Connector of the monitor it is on.
+    pub monitor: String,
+    pub windows: u32,
+}
+
+pub fn fetch_workspaces(exec: &dyn Executor) -> Result<Vec<HyprWorkspace>> {
+    parse_workspaces(&hyprctl(exec, &["workspaces", "-j"])?)
+}
+
+/// Regular workspaces in id order. Special (scratchpad) ones have negative
+/// ids and only visit a monitor, so they are left out.
+pub fn parse_workspaces(json: &[u8]) -> Result<Vec<HyprWorkspace>> {
+    let mut workspaces: Vec<HyprWorkspace> = serde_json::from_slice(json)?;
+    workspaces.retain(|ws| ws.id > 0);
+    workspaces.sort_by_key(|ws| ws.id);
+    Ok(workspaces)
 }
 
-pub fn fetch_monitors() -> Result<Vec<MonitorConfig>> {
-    let output = Command::new("hyprctl").args(["monitors", "-j"]).output()?;
-    parse_monitors(&output.stdout)
+/// The workspaces on `monitor`, e.g. `1(3) 2 5(1)`: names with their window
+/// counts, empty ones bare.
+pub fn workspaces_on(workspaces: &[HyprWorkspace], monitor: &str) -> String {
+    let labels: Vec<String> = workspaces
+        .iter()
+        .filter(|ws| ws.monitor == monitor)
+        .map(|ws| match ws.windows {
+            0 => ws.name.clone(),
+            n => format!("{}({n})", ws.name),
+        })
+        .collect();
+    labels.join(" ")
+}
+
+/// Give the monitor called `name` Hyprland's focus: the cursor moves there
+/// and new windows open on it.
+pub fn focus_monitor(exec: &dyn Executor, name: &str) -> Result<()> {
+    dispatch(exec, &["focusmonitor", name])
+}
+
+/// Turn the monitor called `name` on or off (DPMS). It keeps its place in
+/// the layout either way.
+pub fn set_dpms(exec: &dyn Executor, name: &str, on: bool) -> Result<()> {
+    dispatch(exec, &["dpms", if on { "on" } else { "off" }, name])
+}
+
+/// Swap the workspaces shown on monitors `a` and `b`, e.g. after swapping
+/// two displays on the desk.
+pub fn swap_active_workspaces(exec: &dyn Executor, a: &str, b: &str) -> Result<()> {
+    dispatch(exec, &["swapactiveworkspaces", a, b])
+}
+
+/// Run a `hyprctl dispatch`; Hyprland answers "ok" or says what went wrong.
+fn dispatch(exec: &dyn Executor, args: &[&str]) -> Result<()> {
+    let args: Vec<&str> = std::iter::once("dispatch").chain(args.iter().copied()).collect();
+    expect_ok(&hyprctl(exec, &args)?)
+}
+
+/// Change a monitor on the fly with `hyprctl keyword monitor <rule>`. The
+/// change lasts until the next reload and is never written to a file.
+pub fn set_monitor_rule(exec: &dyn Executor, rule: &str) -> Result<()> {
+    expect_ok(&hyprctl(exec, &["keyword", "monitor", rule])?)
+}
+
+/// [`set_monitor_rule`] each of `rules` in order, waiting `pause` between
+/// them. Every rule is tried, so one monitor that refuses does not keep the
+/// others from being set; the first refusal is returned.
+pub fn set_monitor_rules(exec: &dyn Executor, rules: &[String], pause: Duration) -> Result<()> {
+    let mut first_error = None;
+    for (i, rule) in rules.iter().enumerate() {
+        if i > 0 && !pause.is_zero() {
+            thread::sleep(pause);
+        }
+        if let Err(e) = set_monitor_rule(exec, rule) {
+            first_error.get_or_insert(e);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+fn expect_ok(reply: &[u8]) -> Result<()> {
+    match String::from_utf8_lossy(reply).trim() {
+        "ok" => Ok(()),
+        reply => bail!("{reply}"),
+    }
+}
+
+/// Monitor events this soon after our own `hyprctl reload` are taken as its
+/// echo, not a hotplug: Hyprland may re-create outputs while it re-reads the
+/// config, and applying again on those would only reload once more.
+pub const RELOAD_ECHO: Duration = Duration::from_secs(2);
+
+/// Ask Hyprland to re-read its config after `monitors.conf` was rewritten.
+pub fn reload_hyprland(exec: &dyn Executor) -> Result<()> {
+    expect_ok(&hyprctl(exec, &["reload"])?)
+}
+
+/// A problem Hyprland found in its config on the last reload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    /// File and 1-based line it points at, when it names one.
+    pub file: Option<(String, usize)>,
+    pub message: String,
+    /// The offending line, filled in by callers that know the file.
+    pub source: Option<String>,
+}
+
+/// What `hyprctl configerrors` reports after a reload; empty when all is
+/// well or hyprctl cannot be asked.
+pub fn config_errors(exec: &dyn Executor) -> Vec<ConfigError> {
+    hyprctl(exec, &["configerrors", "-j"])
+        .map(|stdout| parse_config_errors(&stdout))
+        .unwrap_or_default()
+}
+
+/// Parse `hyprctl configerrors -j`: a list of strings, `[""]` when there are
+/// none, each of the form "Config error in file <path> at line <n>: <what>".
+pub fn parse_config_errors(json: &[u8]) -> Vec<ConfigError> {
+    let reports: Vec<String> = serde_json::from_slice(json).unwrap_or_default();
+    reports
+        .iter()
+        .flat_map(|report| report.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let located = line.strip_prefix("Config error in file ").and_then(|rest| {
+                let (path, rest) = rest.split_once(" at line ")?;
+                let (number, message) = rest.split_once(": ")?;
+                Some(((path.to_string(), number.parse().ok()?), message.to_string()))
+            });
+            match located {
+                Some((file, message)) => ConfigError { file: Some(file), message, source: None },
+                None => ConfigError { file: None, message: line.to_string(), source: None },
+            }
+        })
+        .collect()
 }
 
 /// Parse `hyprctl monitors -j` output into sorted [`MonitorConfig`]s. Split from
@@ -124,6 +488,9 @@ pub fn parse_monitors(json: &[u8]) -> Result<Vec<MonitorConfig>> {
                 rotation: Rotation::from_transform(m.transform as u8),
                 is_primary: m.focused,
                 available_modes: m.available_modes.clone(),
+                serial: m.serial.trim().to_string(),
+                key_by: KeyBy::Description,
+            mode_preset: None,
             }
         })
         .collect();
@@ -139,24 +506,149 @@ pub fn parse_monitors(json: &[u8]) -> Result<Vec<MonitorConfig>> {
     Ok(monitors)
 }
 
-pub fn identify_monitors(monitors: &[MonitorConfig]) {
-    for (i, monitor) in monitors.iter().enumerate() {
-        let msg = format!("Monitor {}: {}", i + 1, monitor.display_name());
+/// Colors monitors are drawn in, both on screen by identify and in the
+/// arrangement boxes and settings titles. See [`monitor_colors`].
+pub const IDENTIFY_COLORS: [(u8, u8, u8); 6] = [
+    (0x4f, 0xc3, 0xf7),
+    (0xff, 0xb7, 0x4d),
+    (0x81, 0xc7, 0x84),
+    (0xf0, 0x62, 0x92),
+    (0xba, 0x68, 0xc8),
+    (0xff, 0xf1, 0x76),
+];
+
+pub fn identify_color(idx: usize) -> (u8, u8, u8) {
+    IDENTIFY_COLORS[idx % IDENTIFY_COLORS.len()]
+}
+
+/// Index into [`IDENTIFY_COLORS`] for each monitor, by its key. A hash of
+/// the key picks the color, so a monitor keeps it whatever port it is on or
+/// where it sits; when two would share one, the later key in sort order takes
+/// the next free color.
+pub fn monitor_colors(keys: &[String]) -> Vec<usize> {
+    let count = IDENTIFY_COLORS.len();
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+    let mut colors = vec![0; keys.len()];
+    let mut taken = vec![false; count];
+    for i in order {
+        if taken.iter().all(|&t| t) {
+            taken = vec![false; count];
+        }
+        let mut color = (fnv1a(&keys[i]) % count as u64) as usize;
+        while taken[color] {
+            color = (color + 1) % count;
+        }
+        taken[color] = true;
+        colors[i] = color;
+    }
+    colors
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ u64::from(b)).wrapping_mul(0x100_0000_01b3))
+}
+
+/// One monitor to flash: which output, what to show, in which color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifyTarget {
+    pub name: String,
+    pub message: String,
+    pub color: String,
+}
 
-        // Use hyprctl notify with specific formatting
-        // Icon types: 0=warning, 1=info, 2=hint, 3=error, 4=confused, 5=ok
-        let _ = Command::new("hyprctl")
-            .args([
-                "notify",
-                "5",           // OK icon (checkmark)
-                "3000",        // 3 seconds
-                "rgb(00ff00)", // Green color
-                &format!("fontsize:40 {}", msg),
-            ])
-            .spawn();
+/// Targets for `monitors`, or only for monitor `only` when given. Numbers
+/// match the arrangement boxes, and `colors` (from [`monitor_colors`]) their
+/// borders.
+pub fn identify_targets(
+    monitors: &[MonitorConfig],
+    colors: &[usize],
+    only: Option<usize>,
+) -> Vec<IdentifyTarget> {
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| only.is_none() || only == Some(*i))
+        .map(|(i, monitor)| {
+            let (r, g, b) = identify_color(colors.get(i).copied().unwrap_or(i));
+            IdentifyTarget {
+                name: monitor.name.clone(),
+                message: format!("Monitor {}: {}", i + 1, monitor.display_name()),
+                color: format!("rgb({r:02x}{g:02x}{b:02x})"),
+            }
+        })
+        .collect()
+}
+
+struct IdentifyRequest {
+    exec: Arc<dyn Executor>,
+    targets: Vec<IdentifyTarget>,
+    duration: Duration,
+}
+
+static IDENTIFY: OnceLock<Mutex<Sender<IdentifyRequest>>> = OnceLock::new();
+
+/// Flash each target's number on its own monitor for `duration`. `hyprctl
+/// notify` draws on the focused monitor, so focus visits each one in turn
+/// and then returns. Runs in the background; a newer call cuts an older one
+/// short.
+pub fn identify_monitors(
+    exec: Arc<dyn Executor>,
+    targets: Vec<IdentifyTarget>,
+    duration: Duration,
+) {
+    let sender = IDENTIFY.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || identify_worker(rx));
+        Mutex::new(tx)
+    });
+    if let Ok(sender) = sender.lock() {
+        let _ = sender.send(IdentifyRequest { exec, targets, duration });
     }
 }
 
+fn identify_worker(rx: Receiver<IdentifyRequest>) {
+    let mut next = rx.recv().ok();
+    while let Some(request) = next.take() {
+        let exec = &*request.exec;
+        let home = focused_monitor(exec);
+        let ms = request.duration.as_millis().to_string();
+        for target in &request.targets {
+            let _ = hyprctl(exec, &["dispatch", "focusmonitor", &target.name]);
+            // Icon types: -1=none, 0=warning, 1=info, 2=hint, 3=error, 4=confused, 5=ok
+            let text = format!("fontsize:40 {}", target.message);
+            let _ = hyprctl(exec, &["notify", "-1", &ms, &target.color, &text]);
+            match rx.recv_timeout(request.duration) {
+                Ok(newer) => {
+                    let _ = hyprctl(exec, &["dismissnotify"]);
+                    next = Some(newer);
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        if let Some(home) = home {
+            let _ = hyprctl(exec, &["dispatch", "focusmonitor", &home]);
+        }
+        if next.is_none() {
+            next = rx.recv().ok();
+        }
+    }
+}
+
+fn hyprctl(exec: &dyn Executor, args: &[&str]) -> io::Result<Vec<u8>> {
+    exec.output("hyprctl", args)
+}
+
+/// Connector of the monitor that has focus right now.
+pub fn focused_monitor(exec: &dyn Executor) -> Option<String> {
+    let stdout = hyprctl(exec, &["monitors", "-j"]).ok()?;
+    let monitors: Vec<HyprMonitor> = serde_json::from_slice(&stdout).ok()?;
+    monitors.into_iter().find(|m| m.focused).map(|m| m.name)
+}
+
 #[cfg(test)]
 impl MonitorConfig {
     /// Build a representative monitor for tests across modules.
@@ -178,8 +670,19 @@ impl MonitorConfig {
                 "1920x1080@144.00Hz".into(),
                 "2560x1440@60.00Hz".into(),
             ],
+            serial: String::new(),
+            key_by: KeyBy::Description,
+            mode_preset: None,
         }
     }
+
+    /// An Acme monitor `x` pixels from the left, as the scenario and
+    /// snapshot tests lay them out.
+    pub fn acme(name: &str, model: &str, resolution: &str, x: i32) -> Self {
+        let mut monitor = Self::for_test(name, "Acme", model, resolution);
+        monitor.position_x = x;
+        monitor
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +728,9 @@ mod tests {
             rotation: Rotation::Normal,
             is_primary: false,
             available_modes: vec![],
+            serial: String::new(),
+            key_by: KeyBy::Description,
+            mode_preset: None,
         }
     }
 
@@ -263,4 +769,107 @@ mod tests {
         let m = parse_monitors(json).unwrap();
         assert!(m[0].is_primary);
     }
+
+    #[test]
+    fn identify_targets_number_and_color_like_the_tui() {
+        let monitors = [mc("eDP-1", "X"), mc("DP-1", "U2720Q")];
+        let all = identify_targets(&monitors, &[2, 0], None);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].name, "DP-1");
+        assert_eq!(all[1].message, "Monitor 2: U2720Q");
+        assert_eq!(all[1].color, "rgb(4fc3f7)");
+        assert_eq!(all[0].color, "rgb(81c784)");
+        assert_eq!(identify_targets(&monitors, &[2, 0], Some(1)), all[1..]);
+        assert_eq!(identify_color(IDENTIFY_COLORS.len()), identify_color(0));
+    }
+
+    #[test]
+    fn monitor_colors_follow_the_key_not_the_order() {
+        let keys: Vec<String> = ["Dell U2720Q", "LG 27GL850", "BOE 0x095F", "AOC Q27G2"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let colors = monitor_colors(&keys);
+        let mut distinct = colors.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), keys.len());
+        let reversed: Vec<String> = keys.iter().rev().cloned().collect();
+        let back: Vec<usize> = monitor_colors(&reversed).into_iter().rev().collect();
+        assert_eq!(back, colors);
+        let many: Vec<String> = (0..8).map(|i| format!("M{i}")).collect();
+        assert_eq!(monitor_colors(&many).len(), 8);
+    }
+
+    #[test]
+    fn parse_workspaces_skips_special_ones_and_labels_per_monitor() {
+        let json = br#"[
+            {"id":2,"name":"2","monitor":"DP-1","monitorID":1,"windows":0,"hasfullscreen":false},
+            {"id":-98,"name":"special:term","monitor":"DP-1","monitorID":1,"windows":1},
+            {"id":1,"name":"1","monitor":"DP-1","monitorID":1,"windows":3},
+            {"id":7,"name":"mail","monitor":"eDP-1","monitorID":0,"windows":1}
+        ]"#;
+        let workspaces = parse_workspaces(json).unwrap();
+        assert_eq!(workspaces.iter().map(|ws| ws.id).collect::<Vec<_>>(), [1, 2, 7]);
+        assert_eq!(workspaces_on(&workspaces, "DP-1"), "1(3) 2");
+        assert_eq!(workspaces_on(&workspaces, "eDP-1"), "mail(1)");
+        assert_eq!(workspaces_on(&workspaces, "HDMI-A-1"), "");
+    }
+
+    #[test]
+    fn refresh_rates_follow_the_resolution() {
+        let mut m = MonitorConfig::for_test("DP-1", "Dell", "U27", "1920x1080");
+        m.available_modes.push("1920x1080@120.00Hz".into());
+        assert_eq!(m.rates_at("1920x1080"), [144.0, 120.0, 60.0]);
+        m.refresh_rate = 59.95;
+        assert!(m.mode_advertised());
+        assert_eq!(m.fit_refresh_rate(), None);
+
+        m.refresh_rate = 100.0;
+        assert!(!m.mode_advertised());
+        assert_eq!(m.fit_refresh_rate(), Some(120.0));
+        m.refresh_rate = 132.0; // a tie goes to the higher rate
+        assert_eq!(m.fit_refresh_rate(), Some(144.0));
+
+        m.resolution = "1280x720".into(); // not offered at all: left alone
+        assert_eq!(m.fit_refresh_rate(), None);
+        assert!(!m.mode_advertised());
+    }
+
+    #[test]
+    fn parse_config_errors_locates_each_error() {
+        assert!(parse_config_errors(br#"[""]"#).is_empty());
+        assert!(parse_config_errors(b"not json").is_empty());
+        let report = "Config error in file /home/u/.config/hypr/monitors.conf at line 4: \
+                      invalid scale\nsomething odd";
+        let json = serde_json::to_vec(&[report]).unwrap();
+        let errors = parse_config_errors(&json);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].file,
+            Some(("/home/u/.config/hypr/monitors.conf".to_string(), 4))
+        );
+        assert_eq!(errors[0].message, "invalid scale");
+        assert_eq!((errors[1].file.as_ref(), errors[1].message.as_str()), (None, "something odd"));
+    }
+
+    #[test]
+    fn parse_monitor_details_reads_hardware_fields() {
+        let json = br#"[{"name":"DP-1","description":"Dell U2720Q ABC123 (DP-1)","make":"Dell","model":"U2720Q","serial":"ABC123","width":3840,"height":2160,"refreshRate":60.0,"x":0,"y":0,"scale":1.5,"transform":0,"availableModes":["3840x2160@60.00Hz"],"focused":true,"physicalWidth":600,"physicalHeight":340,"dpmsStatus":true,"vrr":false}]"#;
+        let d = parse_monitor_details(json, "DP-1").unwrap();
+        assert_eq!(d.description, "Dell U2720Q ABC123");
+        assert_eq!(d.serial, "ABC123");
+        assert_eq!(d.physical_size, Some((600, 340)));
+        assert_eq!((d.dpms, d.vrr), (Some(true), Some(false)));
+        assert_eq!(d.current_mode, "3840x2160@60.00Hz");
+        let (diagonal, dpi) = d.diagonal_and_dpi().unwrap();
+        assert_eq!((diagonal.round(), dpi.round()), (27.0, 163.0));
+        assert!(parse_monitor_details(json, "HDMI-A-1").is_err());
+
+        // Without hyprctl only what the live config knows is shown
+        let fallback = MonitorDetails::from_config(&mc("eDP-1", "X"));
+        assert_eq!(fallback.physical_size, None);
+        assert_eq!(fallback.diagonal_and_dpi(), None);
+        assert_eq!(fallback.current_mode, "1920x1080@60.00Hz");
+    }
 }
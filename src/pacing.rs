@@ -0,0 +1,111 @@
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TICK_MS: u64 = 100;
+pub const DEFAULT_FPS: u32 = 60;
+
+/// How often the TUI wakes up and how often it may redraw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pacing {
+    /// Longest wait for input; timers (apply countdown, toasts) advance per tick.
+    pub tick_rate: Duration,
+    /// Shortest time between two redraws, so bursts of input such as a drag
+    /// coalesce into one frame.
+    pub frame_time: Duration,
+}
+
+impl Pacing {
+    pub fn new(tick_ms: u64, fps: u32) -> Self {
+        Self {
+            tick_rate: Duration::from_millis(tick_ms),
+            frame_time: Duration::from_secs(1) / fps.max(1),
+        }
+    }
+}
+
+impl Default for Pacing {
+    fn default() -> Self {
+        Self::new(DEFAULT_TICK_MS, DEFAULT_FPS)
+    }
+}
+
+/// Decides when the event loop redraws: once per tick, and after input no
+/// sooner than a frame after the last draw.
+pub struct FrameLimiter {
+    pacing: Pacing,
+    last_tick: Instant,
+    last_draw: Option<Instant>,
+    dirty: bool,
+}
+
+impl FrameLimiter {
+    pub fn new(pacing: Pacing, now: Instant) -> Self {
+        Self {
+            pacing,
+            last_tick: now,
+            last_draw: None,
+            dirty: true,
+        }
+    }
+
+    /// Something on screen changed; draw it when the frame limit allows.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether to draw at `now`. A draw is assumed to follow a `true`.
+    pub fn should_draw(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.last_tick) >= self.pacing.tick_rate {
+            self.last_tick = now;
+            self.dirty = true;
+        }
+        let too_soon = matches!(
+            self.last_draw,
+            Some(t) if now.duration_since(t) < self.pacing.frame_time
+        );
+        if self.dirty && !too_soon {
+            self.dirty = false;
+            self.last_draw = Some(now);
+            return true;
+        }
+        false
+    }
+
+    /// How long to wait for input at `now`: until the next tick, or sooner
+    /// when a held-back frame becomes due.
+    pub fn timeout(&self, now: Instant) -> Duration {
+        let tick = (self.last_tick + self.pacing.tick_rate).saturating_duration_since(now);
+        match self.last_draw {
+            Some(t) if self.dirty => {
+                tick.min((t + self.pacing.frame_time).saturating_duration_since(now))
+            }
+            _ => tick,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_redraws_at_most_once_per_frame() {
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        let mut limiter = FrameLimiter::new(Pacing::new(100, 50), t0);
+        assert!(limiter.should_draw(t0));
+        assert!(!limiter.should_draw(t0 + ms(1)));
+        assert_eq!(limiter.timeout(t0 + ms(1)), ms(99));
+
+        // A burst of drag events inside one 20 ms frame draws once
+        limiter.invalidate();
+        assert!(!limiter.should_draw(t0 + ms(5)));
+        assert_eq!(limiter.timeout(t0 + ms(5)), ms(15));
+        limiter.invalidate();
+        assert!(limiter.should_draw(t0 + ms(20)));
+        assert!(!limiter.should_draw(t0 + ms(45)));
+
+        // Idle, the tick alone keeps countdowns moving
+        assert!(limiter.should_draw(t0 + ms(100)));
+        assert_eq!(limiter.timeout(t0 + ms(130)), ms(70));
+    }
+}
@@ -0,0 +1,293 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::app::{App, CUSTOM_OPTION};
+use crate::config::MonitorDatabase;
+use crate::i18n::tr_fill;
+use crate::input::{handle_main_key, InputResult};
+use crate::state::{MainTab, SettingField};
+
+/// What running a palette entry does.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteCommand {
+    /// Press `key` in the main view, on `tab` if one is given.
+    Key {
+        tab: Option<MainTab>,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    },
+    ApplyWorkspace(usize),
+    /// Pick dropdown option `option` for `field` of live monitor `monitor`.
+    SetField {
+        monitor: usize,
+        field: SettingField,
+        option: usize,
+    },
+    MakePrimary(usize),
+    ToggleManaged(usize),
+    ShowDetails(usize),
+    /// Copy the arrangement on the current tab as an SVG or ASCII diagram.
+    CopyDiagram { svg: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub command: PaletteCommand,
+}
+
+/// Main-view actions by label, run through the same key handler as the
+/// keyboard so they behave identically.
+const KEY_ACTIONS: &[(&str, Option<MainTab>, char)] = &[
+    ("Apply live layout", Some(MainTab::Live), 'a'),
+    ("Apply selected workspace", Some(MainTab::Saved), 'a'),
+    ("Save live layout as…", Some(MainTab::Live), 'S'),
+    ("Undo", Some(MainTab::Live), 'u'),
+    ("Refresh monitors", Some(MainTab::Live), 'r'),
+    ("Identify monitors", Some(MainTab::Live), 'i'),
+    ("Focus monitor in Hyprland", Some(MainTab::Live), 'w'),
+    ("Swap workspaces with another monitor…", Some(MainTab::Live), 'X'),
+    ("Nickname the selected monitor…", Some(MainTab::Live), 'n'),
+    ("Game mode on / off", Some(MainTab::Live), 'b'),
+    ("Safe mode: preferred modes, 100%, side by side", None, '!'),
+    ("Use the monitor's suggested preset", Some(MainTab::Live), 's'),
+    ("Compare live with saved", None, 'v'),
+    ("Edit fallback rule", None, 'f'),
+    ("Show history", None, 't'),
+    ("Show messages", None, 'g'),
+    ("Copy generated config", None, 'y'),
+    ("Show keybindings", None, '?'),
+    ("New workspace", Some(MainTab::Saved), 'n'),
+    ("Rename workspace", Some(MainTab::Saved), 'r'),
+    ("Duplicate workspace", Some(MainTab::Saved), 'c'),
+    ("Delete workspace", Some(MainTab::Saved), 'd'),
+    ("Edit workspace notes", Some(MainTab::Saved), 'o'),
+    ("Pin / unpin workspace", Some(MainTab::Saved), 'p'),
+    ("Toggle manual-only", Some(MainTab::Saved), 'm'),
+    ("Turn monitor off / on in this workspace (DPMS)", Some(MainTab::Saved), 'w'),
+    ("Load workspace into Live", Some(MainTab::Saved), 'e'),
+    ("Import monitors from another workspace…", Some(MainTab::Saved), 'i'),
+    ("Quit", None, 'q'),
+];
+
+/// Everything the palette can do right now, in display order.
+pub fn palette_entries(app: &App) -> Vec<PaletteEntry> {
+    let key = |tab, code, modifiers| PaletteCommand::Key {
+        tab,
+        code,
+        modifiers,
+    };
+    let mut out: Vec<PaletteEntry> = KEY_ACTIONS
+        .iter()
+        .map(|&(label, tab, c)| PaletteEntry {
+            label: label.to_string(),
+            command: key(tab, KeyCode::Char(c), KeyModifiers::NONE),
+        })
+        .collect();
+    for (label, tab, c) in [
+        ("Redo", Some(MainTab::Live), 'r'),
+        ("Lock / unlock active workspace", None, 'l'),
+        ("Pause / resume auto-apply on hotplug", None, 'p'),
+        ("View generated config", None, 'g'),
+        ("Restore an earlier monitors.conf…", None, 'b'),
+        ("Scale every monitor by its pixel density", Some(MainTab::Live), 'd'),
+        ("Presentation: mirror the laptop on every screen / restore", None, 'o'),
+        ("Projector: force the monitor to 1920x1080@60 / restore", Some(MainTab::Live), 'f'),
+    ] {
+        out.push(PaletteEntry {
+            label: label.to_string(),
+            command: key(tab, KeyCode::Char(c), KeyModifiers::CONTROL),
+        });
+    }
+
+    for (label, svg) in [
+        ("Copy arrangement as ASCII diagram", false),
+        ("Copy arrangement as SVG", true),
+    ] {
+        out.push(PaletteEntry {
+            label: label.to_string(),
+            command: PaletteCommand::CopyDiagram { svg },
+        });
+    }
+
+    for (idx, ws) in app.monitor_db.workspaces.iter().enumerate() {
+        out.push(PaletteEntry {
+            label: format!("Apply workspace {}", ws.name),
+            command: PaletteCommand::ApplyWorkspace(idx),
+        });
+    }
+
+    for (idx, monitor) in app.monitors.iter().enumerate() {
+        if !monitor.is_primary {
+            out.push(PaletteEntry {
+                label: format!("Make {} primary", monitor.name),
+                command: PaletteCommand::MakePrimary(idx),
+            });
+        }
+        out.push(PaletteEntry {
+            label: format!("Show details of {}", monitor.name),
+            command: PaletteCommand::ShowDetails(idx),
+        });
+        let key = MonitorDatabase::get_monitor_key(monitor);
+        let verb = if app.monitor_db.is_managed(&key) { "Unmanage" } else { "Manage" };
+        out.push(PaletteEntry {
+            label: format!("{verb} {}", monitor.name),
+            command: PaletteCommand::ToggleManaged(idx),
+        });
+        for field in SettingField::all() {
+            let name = field.label().trim_end_matches(':').to_lowercase();
+            for (option, value) in App::dropdown_options_for(monitor, field).iter().enumerate() {
+                let label = if value == CUSTOM_OPTION {
+                    format!("Type a custom {name} for {}", monitor.name)
+                } else {
+                    format!("Set {name} of {} to {value}", monitor.name)
+                };
+                out.push(PaletteEntry {
+                    label,
+                    command: PaletteCommand::SetField {
+                        monitor: idx,
+                        field,
+                        option,
+                    },
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Entries matching `query`, best first. An empty query lists everything.
+pub fn palette_matches(app: &App, query: &str) -> Vec<PaletteEntry> {
+    let mut scored: Vec<(i32, PaletteEntry)> = palette_entries(app)
+        .into_iter()
+        .filter_map(|entry| Some((fuzzy_score(query, &entry.label)?, entry)))
+        .collect();
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Score `text` against `query` as a case-insensitive subsequence (spaces in
+/// the query are ignored). Runs and word starts score higher, skipped
+/// characters lower; `None` when some query character is missing.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (next..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if found > 0 && found == next {
+            score += 4;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - next).min(5) as i32;
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// Run `command` as if the user had done it by hand.
+pub fn run_palette_command(app: &mut App, command: PaletteCommand) -> InputResult {
+    match command {
+        PaletteCommand::Key {
+            tab,
+            code,
+            modifiers,
+        } => {
+            if let Some(tab) = tab {
+                app.switch_tab(tab);
+            }
+            return handle_main_key(app, code, modifiers);
+        }
+        PaletteCommand::ApplyWorkspace(idx) => {
+            if let Err(e) = app.apply_workspace_at(idx) {
+                app.toasts.error(tr_fill("Error: {}", &[&e]));
+            }
+        }
+        PaletteCommand::SetField {
+            monitor,
+            field,
+            option,
+        } => {
+            app.switch_tab(MainTab::Live);
+            app.selected_monitor = monitor;
+            app.selected_setting = SettingField::all()
+                .iter()
+                .position(|f| *f == field)
+                .unwrap_or(0);
+            app.dropdown_selection = option;
+            app.apply_dropdown_selection();
+        }
+        PaletteCommand::MakePrimary(idx) => {
+            app.switch_tab(MainTab::Live);
+            app.selected_monitor = idx;
+            app.toggle_primary();
+        }
+        PaletteCommand::ToggleManaged(idx) => {
+            app.switch_tab(MainTab::Live);
+            app.selected_monitor = idx;
+            app.toggle_selected_monitor_managed();
+        }
+        PaletteCommand::ShowDetails(idx) => {
+            app.switch_tab(MainTab::Live);
+            app.selected_monitor = idx;
+            app.open_monitor_details();
+        }
+        PaletteCommand::CopyDiagram { svg } => app.copy_diagram(svg),
+    }
+    InputResult::Continue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::MonitorConfig;
+
+    fn app() -> App {
+        App::for_test(vec![
+            MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080"),
+            MonitorConfig::for_test("DP-2", "Dell", "U2720Q", "3840x2160"),
+        ])
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_runs_and_word_starts() {
+        assert!(fuzzy_score("xyz", "Apply live layout").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        let tight = fuzzy_score("scale", "Set scale of DP-2 to 150%").unwrap();
+        let loose = fuzzy_score("scale", "Show keybindings and live").unwrap_or(i32::MIN);
+        assert!(tight > loose);
+        assert!(fuzzy_score("SCALE dp2", "Set scale of DP-2 to 150%").is_some());
+    }
+
+    #[test]
+    fn matches_rank_the_intended_action_first() {
+        let a = app();
+        let top = |q: &str| palette_matches(&a, q)[0].label.clone();
+        assert_eq!(top("scale dp2 150"), "Set scale of DP-2 to 150%");
+        assert_eq!(top("apply default"), "Apply workspace Default");
+        assert_eq!(top("unmanage dp"), "Unmanage DP-2");
+        assert_eq!(palette_matches(&a, "").len(), palette_entries(&a).len());
+    }
+
+    #[test]
+    fn running_commands_changes_live_state() {
+        let mut a = app();
+        a.main_tab = MainTab::Saved;
+        let cmd = palette_matches(&a, "scale dp2 150")[0].command;
+        run_palette_command(&mut a, cmd);
+        assert_eq!(a.main_tab, MainTab::Live);
+        assert_eq!(a.monitors[1].scale, 1.5);
+        assert!(a.has_changes);
+
+        let cmd = palette_matches(&a, "make dp-2 primary")[0].command;
+        run_palette_command(&mut a, cmd);
+        assert!(a.monitors[1].is_primary);
+
+        let cmd = palette_matches(&a, "new workspace")[0].command;
+        run_palette_command(&mut a, cmd);
+        assert_eq!(a.main_tab, MainTab::Saved);
+        assert_eq!(a.dialog, crate::state::DialogType::NewWorkspace);
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::Result;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::app::{App, CUSTOM_OPTION};
+use crate::i18n::tr_fill;
+use crate::palette::{run_palette_command, PaletteCommand};
+use crate::state::{DialogType, SettingField};
+use crate::ui::config_error_lines;
+
+/// How long an apply waits for "y" before rolling back, as in the TUI.
+const CONFIRM_SECS: u64 = 15;
+
+const MAIN_MENU: &[&str] = &[
+    "List monitors",
+    "Change a monitor setting",
+    "Make a monitor primary",
+    "Undo last change",
+    "Apply changes",
+    "Apply a saved workspace",
+    "Identify monitors",
+    "Quit",
+];
+
+/// `hyprmon --plain`: numbered menus on stdin/stdout instead of the
+/// full-screen grid, so screen readers only ever see plain lines.
+pub fn run() -> Result<()> {
+    let mut app = App::new()?;
+    let (tx, rx) = mpsc::channel();
+    // Lines arrive on a channel so the apply confirmation can time out
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(|line| line.ok()) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    Prompt::new(&mut app, rx, io::stdout().lock()).main_menu()
+}
+
+struct Prompt<'a, W: Write> {
+    app: &'a mut App,
+    input: Receiver<String>,
+    out: W,
+    /// Messages already printed from `app.toasts`.
+    shown: usize,
+    /// Input has ended; every further question reads as "go back".
+    eof: bool,
+}
+
+impl<'a, W: Write> Prompt<'a, W> {
+    fn new(app: &'a mut App, input: Receiver<String>, out: W) -> Self {
+        let shown = app.toasts.history().len();
+        Self {
+            app,
+            input,
+            out,
+            shown,
+            eof: false,
+        }
+    }
+
+    fn main_menu(&mut self) -> Result<()> {
+        writeln!(self.out, "hyprmon plain mode. Enter a number; an empty line goes back.")?;
+        loop {
+            self.print_messages()?;
+            writeln!(self.out, "{}", self.summary())?;
+            let Some(choice) = self.choose("Main menu", MAIN_MENU)? else {
+                if self.eof {
+                    return Ok(());
+                }
+                continue;
+            };
+            match MAIN_MENU[choice] {
+                "List monitors" => self.list_monitors()?,
+                "Change a monitor setting" => self.change_setting()?,
+                "Make a monitor primary" => {
+                    if let Some(idx) = self.choose_monitor()? {
+                        run_palette_command(self.app, PaletteCommand::MakePrimary(idx));
+                    }
+                }
+                "Undo last change" => self.app.undo(),
+                "Apply changes" => {
+                    let result = self.app.save_and_apply();
+                    self.after_apply(result)?;
+                }
+                "Apply a saved workspace" => {
+                    let names: Vec<String> =
+                        self.app.monitor_db.workspaces.iter().map(|ws| ws.name.clone()).collect();
+                    if let Some(idx) = self.choose("Workspace", &names)? {
+                        let result = self.app.apply_workspace_at(idx);
+                        self.after_apply(result)?;
+                    }
+                }
+                "Identify monitors" => {
+                    self.app.identify();
+                    writeln!(self.out, "Each monitor shows its number in turn.")?;
+                }
+                _ => {
+                    if self.confirm_quit()? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// One line about the session, read out before every menu.
+    fn summary(&self) -> String {
+        let changes = if self.app.has_changes { "unapplied changes" } else { "no changes" };
+        format!(
+            "Workspace {}, {} monitor(s), {changes}.",
+            self.app.current_workspace_name(),
+            self.app.monitors.len()
+        )
+    }
+
+    fn list_monitors(&mut self) -> Result<()> {
+        for (i, m) in self.app.monitors.iter().enumerate() {
+            let mut line = format!(
+                "{}. {} ({}): {} at {:.0} Hz, scale {:.0}%, {}, position {},{}",
+                i + 1,
+                m.name,
+                self.app.monitor_db.label(m),
+                m.resolution,
+                m.refresh_rate,
+                m.scale * 100.0,
+                m.rotation.as_str(),
+                m.position_x,
+                m.position_y
+            );
+            if m.is_primary {
+                line.push_str(", primary");
+            }
+            if self.app.monitor_changed(i) {
+                line.push_str(", changed");
+            }
+            writeln!(self.out, "{line}")?;
+        }
+        Ok(())
+    }
+
+    fn change_setting(&mut self) -> Result<()> {
+        let Some(monitor) = self.choose_monitor()? else {
+            return Ok(());
+        };
+        let fields: Vec<SettingField> = SettingField::all()
+            .into_iter()
+            .filter(|f| *f != SettingField::Primary)
+            .collect();
+        let labels: Vec<String> = fields
+            .iter()
+            .map(|f| {
+                let value = crate::compare::field_value(&self.app.monitors[monitor], *f);
+                format!("{} {value}", f.label())
+            })
+            .collect();
+        let Some(field) = self.choose("Setting", &labels)? else {
+            return Ok(());
+        };
+        let field = fields[field];
+        if !field.is_position() {
+            let options = App::dropdown_options_for(&self.app.monitors[monitor], field);
+            let Some(option) = self.choose(field.label().trim_end_matches(':'), &options)? else {
+                return Ok(());
+            };
+            if options[option] != CUSTOM_OPTION {
+                run_palette_command(
+                    self.app,
+                    PaletteCommand::SetField {
+                        monitor,
+                        field,
+                        option,
+                    },
+                );
+                return Ok(());
+            }
+        }
+        // Positions and custom values are typed; asked again until valid
+        loop {
+            let Some(answer) = self.ask(&format!("{} ", field.label()))? else {
+                return Ok(());
+            };
+            match self.app.set_setting_from_text(monitor, field, &answer) {
+                Ok(()) => return Ok(()),
+                Err(e) => writeln!(self.out, "Not valid: {e}.")?,
+            }
+        }
+    }
+
+    fn choose_monitor(&mut self) -> Result<Option<usize>> {
+        let names: Vec<String> = self
+            .app
+            .monitors
+            .iter()
+            .map(|m| format!("{} ({})", m.name, self.app.monitor_db.label(m)))
+            .collect();
+        self.choose("Monitor", &names)
+    }
+
+    /// Print `options` numbered from 1 and read a choice until it is valid.
+    /// `None` on an empty line or at end of input.
+    fn choose<S: AsRef<str>>(&mut self, title: &str, options: &[S]) -> Result<Option<usize>> {
+        writeln!(self.out, "{title}:")?;
+        for (i, option) in options.iter().enumerate() {
+            writeln!(self.out, "  {}) {}", i + 1, option.as_ref())?;
+        }
+        loop {
+            let Some(answer) = self.ask("Number: ")? else {
+                return Ok(None);
+            };
+            match answer.parse::<usize>() {
+                Ok(n) if (1..=options.len()).contains(&n) => return Ok(Some(n - 1)),
+                _ => writeln!(self.out, "Not a choice; enter 1 to {}.", options.len())?,
+            }
+        }
+    }
+
+    /// Next non-blank answer, or `None` for an empty line or end of input.
+    fn ask(&mut self, prompt: &str) -> Result<Option<String>> {
+        write!(self.out, "{prompt}")?;
+        self.out.flush()?;
+        let Ok(line) = self.input.recv() else {
+            self.eof = true;
+            writeln!(self.out)?;
+            return Ok(None);
+        };
+        let line = line.trim();
+        Ok((!line.is_empty()).then(|| line.to_string()))
+    }
+
+    /// Keep an apply only when "y" arrives in time, like the TUI countdown.
+    fn after_apply(&mut self, result: Result<()>) -> Result<()> {
+        if let Err(e) = result {
+            self.app.toasts.error(tr_fill("Error: {}", &[&e]));
+            return Ok(());
+        }
+        if matches!(self.app.dialog, DialogType::FixScale { .. }) {
+            writeln!(self.out, "Hyprland would round these scales:")?;
+            for line in self.app.scale_fix_lines() {
+                writeln!(self.out, "  {line}")?;
+            }
+            let answer = self.ask("Use the fixed scales? Type y to fix, n to apply as typed: ")?;
+            self.app.dialog = DialogType::None;
+            let result = match answer.map(|a| a.to_lowercase()).as_deref() {
+                Some("y") => {
+                    self.app.fix_scales();
+                    self.app.save_and_apply_as_is()
+                }
+                Some("n") => self.app.save_and_apply_as_is(),
+                _ => return Ok(()),
+            };
+            return self.after_apply(result);
+        }
+        if matches!(self.app.dialog, DialogType::ConfigErrors { .. }) {
+            self.print_messages()?;
+            for line in config_error_lines(self.app) {
+                writeln!(self.out, "  {line}")?;
+            }
+            self.app.start_apply_countdown();
+        }
+        if !matches!(self.app.dialog, DialogType::ConfirmApply { .. }) {
+            return Ok(()); // nothing was applied
+        }
+        self.print_messages()?;
+        for change in &self.app.applied_changes {
+            writeln!(self.out, "  {}", change.summary())?;
+        }
+        for (name, why) in &self.app.apply_mismatches {
+            writeln!(self.out, "  ! {name}: {why}")?;
+        }
+        write!(self.out, "Keep these settings? Type y within {CONFIRM_SECS} seconds: ")?;
+        self.out.flush()?;
+        let keep = match self.input.recv_timeout(Duration::from_secs(CONFIRM_SECS)) {
+            Ok(answer) => answer.trim().eq_ignore_ascii_case("y"),
+            Err(RecvTimeoutError::Timeout) => {
+                writeln!(self.out)?;
+                false
+            }
+            Err(RecvTimeoutError::Disconnected) => false,
+        };
+        if keep {
+            self.app.confirm_changes();
+        } else {
+            self.app.revert_changes();
+            let _ = self.app.save_and_apply_as_is();
+            self.app.dialog = DialogType::None;
+        }
+        Ok(())
+    }
+
+    fn confirm_quit(&mut self) -> Result<bool> {
+        if !self.app.has_changes {
+            return Ok(true);
+        }
+        let answer = self.ask("Quit without applying your changes? Type y to quit: ")?;
+        Ok(self.eof || answer.is_some_and(|a| a.eq_ignore_ascii_case("y")))
+    }
+
+    /// Status messages raised since the last call, one per line.
+    fn print_messages(&mut self) -> Result<()> {
+        let history = self.app.toasts.history();
+        for toast in history.iter().skip(self.shown) {
+            writeln!(self.out, "{}: {}", toast.level.as_str(), toast.text)?;
+        }
+        self.shown = history.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::MonitorConfig;
+
+    /// Run the prompt over `lines` and return what it printed.
+    fn script(app: &mut App, lines: &[&str]) -> String {
+        let (tx, rx) = mpsc::channel();
+        for line in lines {
+            tx.send(line.to_string()).unwrap();
+        }
+        drop(tx);
+        let mut out = Vec::new();
+        Prompt::new(app, rx, &mut out).main_menu().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn app() -> App {
+        let mut primary = MonitorConfig::for_test("eDP-1", "N", "M", "1920x1080");
+        primary.is_primary = true;
+        let dell = MonitorConfig::for_test("DP-2", "Dell", "U2720Q", "1920x1080");
+        App::for_test(vec![primary, dell])
+    }
+
+    #[test]
+    fn lists_monitors_as_plain_lines() {
+        let mut a = app();
+        let out = script(&mut a, &["1", "8"]);
+        assert!(out.contains("  1) List monitors"), "{out}");
+        assert!(out.contains("1. eDP-1 (Laptop): 1920x1080 at 60 Hz, scale 100%, Landscape"));
+        assert!(out.contains("2. DP-2 (U2720Q)"));
+        assert!(out.contains("no changes."));
+    }
+
+    #[test]
+    fn numbered_menus_change_settings_and_undo() {
+        let mut a = app();
+        // Change → monitor 2 → Scale → 125%; bad input is asked again
+        let out = script(&mut a, &["2", "2", "9", "3", "2", "8", "y"]);
+        assert!(out.contains("Not a choice; enter 1 to 6."), "{out}");
+        assert_eq!(a.monitors[1].scale, 1.25);
+        assert!(out.contains("unapplied changes"));
+        assert!(out.contains("Quit without applying"));
+
+        let mut a = app();
+        script(&mut a, &["2", "2", "3", "2", "4", "3", "2", "8"]);
+        assert_eq!(a.monitors[1].scale, 1.0); // undone
+        assert!(a.monitors[1].is_primary);
+        assert!(a.has_changes); // the quit prompt got end of input
+
+        // Positions are typed: monitor 2 → Y Position → -1440
+        let out = script(&mut a, &["2", "2", "6", "up", "-1440", "8"]);
+        assert!(out.contains("Not valid: \"up\" is not a whole number."), "{out}");
+        assert_eq!(a.monitors[1].position_y, -1440);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User preferences from `~/.config/hyprmon/config.toml`. Unlike the monitor
+/// database this file is hand-written and never saved back by hyprmon.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Prefs {
+    /// Built-in theme name; see `ui::theme::THEMES`.
+    pub theme: Option<String>,
+    /// Interface language code, e.g. `vi`; see `i18n::LANGUAGES`. Unset means
+    /// the locale from `LC_ALL` / `LC_MESSAGES` / `LANG`.
+    pub language: Option<String>,
+    /// Role → color overrides on top of the theme, e.g. `accent = "#ff8800"`.
+    pub colors: BTreeMap<String, String>,
+    /// Action name → keys, e.g. `left = ["Left", "n"]`. See `keymap.rs`.
+    pub keys: BTreeMap<String, KeyList>,
+    pub identify: IdentifyPrefs,
+}
+
+/// The `[identify]` table: how monitor numbers are flashed on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdentifyPrefs {
+    /// How long each monitor shows its number, in milliseconds.
+    pub duration: u64,
+    /// Flash a monitor whenever the Live selection moves to it.
+    pub on_select: bool,
+}
+
+impl Default for IdentifyPrefs {
+    fn default() -> Self {
+        Self {
+            duration: 1500,
+            on_select: false,
+        }
+    }
+}
+
+/// One key (`up = "w"`) or several (`up = ["Up", "w"]`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> &[String] {
+        match self {
+            KeyList::One(key) => std::slice::from_ref(key),
+            KeyList::Many(keys) => keys,
+        }
+    }
+}
+
+impl Prefs {
+    pub fn path() -> PathBuf {
+        dirs::home_dir().unwrap().join(".config/hyprmon/config.toml")
+    }
+
+    /// Read the preferences; a missing file means all defaults.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).with_context(|| format!("{}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_and_list_keys_and_rejects_unknown_sections() {
+        let prefs: Prefs =
+            toml::from_str("[keys]\nup = \"w\"\ndown = [\"Down\", \"s\"]\n").unwrap();
+        assert_eq!(prefs.keys["up"].keys(), ["w"]);
+        assert_eq!(prefs.keys["down"].keys(), ["Down", "s"]);
+        assert!(toml::from_str::<Prefs>("[colours]\n").is_err());
+        assert!(Prefs::load_from(Path::new("/nonexistent/config.toml"))
+            .unwrap()
+            .keys
+            .is_empty());
+    }
+
+    #[test]
+    fn identify_table_fills_in_defaults() {
+        assert_eq!(Prefs::default().identify, IdentifyPrefs::default());
+        let prefs: Prefs = toml::from_str("[identify]\non_select = true\n").unwrap();
+        assert!(prefs.identify.on_select);
+        assert_eq!(prefs.identify.duration, 1500);
+        assert!(toml::from_str::<Prefs>("[identify]\nseconds = 2\n").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Presentation mode, for meeting rooms: the laptop panel mirrored onto every
+//! other output, all at the largest resolution they share so nothing is
+//! letterboxed or scaled twice. Like game mode it only uses `hyprctl
+//! keyword`, so nothing is saved and reloading monitors.conf brings the
+//! workspace back.
+//!
+//! For a projector whose EDID lies there is also a plain override: one
+//! output forced to [`PROJECTOR_MODE`].
+
+use crate::monitor::MonitorConfig;
+
+/// How presentation mode sets up the connected monitors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mirror {
+    /// Connector of the monitor shown everywhere.
+    pub source: String,
+    /// The resolution all of them run, when they list one in common.
+    pub resolution: Option<String>,
+    /// `hyprctl keyword monitor` rules, the source's first.
+    pub rules: Vec<String>,
+}
+
+/// Mirror the laptop panel (or, without one, the first monitor) onto the
+/// others. `None` with fewer than two monitors.
+pub fn plan(monitors: &[MonitorConfig]) -> Option<Mirror> {
+    if monitors.len() < 2 {
+        return None;
+    }
+    let source = monitors.iter().find(|m| m.name.starts_with("eDP")).unwrap_or(&monitors[0]);
+    let all: Vec<&MonitorConfig> = monitors.iter().collect();
+    let resolution = common_resolution(&all);
+    let mode = |monitor: &MonitorConfig| match &resolution {
+        Some(res) => format!("{res}@{:.2}", rate_near_60(monitor, res)),
+        None => "preferred".to_string(),
+    };
+    // Without a shared mode the panel stays as it is and the others scale it
+    let mut rules = Vec::new();
+    if resolution.is_some() {
+        rules.push(format!("{},{},0x0,1", source.name, mode(source)));
+    }
+    for other in monitors.iter().filter(|m| m.name != source.name) {
+        rules.push(format!("{},{},auto,1,mirror,{}", other.name, mode(other), source.name));
+    }
+    Some(Mirror { source: source.name.clone(), resolution, rules })
+}
+
+/// The mode practically every projector and capture box really takes.
+pub const PROJECTOR_MODE: &str = "1920x1080@60";
+
+/// `hyprctl keyword monitor` rule forcing `monitor` to [`PROJECTOR_MODE`] at
+/// 100%, where it is now.
+pub fn projector_rule(monitor: &MonitorConfig) -> String {
+    format!(
+        "{},{PROJECTOR_MODE},{}x{},1",
+        monitor.name, monitor.position_x, monitor.position_y
+    )
+}
+
+/// The largest resolution (by pixels) every one of `monitors` lists.
+pub fn common_resolution(monitors: &[&MonitorConfig]) -> Option<String> {
+    let (first, rest) = monitors.split_first()?;
+    resolutions(first)
+        .into_iter()
+        .filter(|res| rest.iter().all(|m| resolutions(m).contains(res)))
+        .max_by_key(|res| pixels(res))
+}
+
+fn resolutions(monitor: &MonitorConfig) -> Vec<String> {
+    monitor
+        .available_modes
+        .iter()
+        .filter_map(|mode| mode.split_once('@'))
+        .map(|(res, _)| res.trim().to_string())
+        .collect()
+}
+
+fn pixels(resolution: &str) -> u64 {
+    let (w, h) = resolution.split_once('x').unwrap_or_default();
+    w.parse::<u64>().unwrap_or(0) * h.parse::<u64>().unwrap_or(0)
+}
+
+/// Projectors and capture boxes take 60 Hz best: the rate at `resolution`
+/// nearest it.
+fn rate_near_60(monitor: &MonitorConfig, resolution: &str) -> f64 {
+    monitor
+        .rates_at(resolution)
+        .into_iter()
+        .min_by(|a, b| (a - 60.0).abs().total_cmp(&(b - 60.0).abs()))
+        .unwrap_or(60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, modes: &[&str]) -> MonitorConfig {
+        let mut monitor = MonitorConfig::for_test(name, "M", name, "1920x1080");
+        monitor.available_modes = modes.iter().map(|m| m.to_string()).collect();
+        monitor
+    }
+
+    #[test]
+    fn mirrors_the_panel_at_the_largest_shared_resolution() {
+        let projector = monitor("HDMI-A-1", &["1920x1080@60.00Hz", "1280x720@60.00Hz"]);
+        let tv = monitor("DP-2", &["3840x2160@60.00Hz", "1920x1080@59.94Hz", "1920x1080@120.00Hz"]);
+        let panel = monitor("eDP-1", &["2560x1600@165.00Hz", "1920x1080@165.00Hz"]);
+        let mirror = plan(&[projector, tv, panel.clone()]).unwrap();
+        assert_eq!(mirror.source, "eDP-1");
+        assert_eq!(mirror.resolution.as_deref(), Some("1920x1080"));
+        assert_eq!(
+            mirror.rules,
+            [
+                "eDP-1,1920x1080@165.00,0x0,1",
+                "HDMI-A-1,1920x1080@60.00,auto,1,mirror,eDP-1",
+                "DP-2,1920x1080@59.94,auto,1,mirror,eDP-1",
+            ]
+        );
+        assert_eq!(plan(&[panel]), None);
+    }
+
+    #[test]
+    fn without_a_shared_resolution_the_others_scale_the_panel() {
+        let panel = monitor("eDP-1", &["2880x1800@120.00Hz"]);
+        let projector = monitor("HDMI-A-1", &["1024x768@60.00Hz"]);
+        let mirror = plan(&[panel, projector]).unwrap();
+        assert_eq!(mirror.resolution, None);
+        assert_eq!(mirror.rules, ["HDMI-A-1,preferred,auto,1,mirror,eDP-1"]);
+    }
+
+    #[test]
+    fn the_projector_rule_ignores_what_the_edid_offers() {
+        let mut projector = monitor("HDMI-A-1", &["4096x2160@24.00Hz"]);
+        projector.position_x = 2560;
+        projector.scale = 2.0;
+        assert_eq!(projector_rule(&projector), "HDMI-A-1,1920x1080@60,2560x0,1");
+    }
+}
@@ -0,0 +1,269 @@
+//! Known-good settings per monitor model: the scale that suits it, a mode
+//! that works, and notes on quirks and overclocks. A set ships with hyprmon
+//! (`presets.json`); `hyprmon presets --fetch` downloads a newer one, which
+//! is read first. Matching monitors get them as suggestions, never applied
+//! on their own.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::dry_run::Writes;
+use crate::exec::Executor;
+use crate::monitor::MonitorConfig;
+
+const BUNDLED: &str = include_str!("../presets.json");
+
+/// Where `--fetch` gets the current set by default: this repository's copy.
+pub const PRESETS_URL: &str =
+    "https://raw.githubusercontent.com/ChauVanLoc01/hyprmon/main/presets.json";
+
+/// Suggested settings for one monitor model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    /// Make and model as Hyprland reports them; matched ignoring case.
+    pub make: String,
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl Preset {
+    pub fn matches(&self, monitor: &MonitorConfig) -> bool {
+        let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+        same(&self.make, &monitor.make) && same(&self.model, &monitor.model)
+    }
+
+    /// The settings, e.g. `150%, 3840x2160@60 Hz`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(scale) = self.scale {
+            parts.push(format!("{:.0}%", scale * 100.0));
+        }
+        match (&self.resolution, self.refresh_rate) {
+            (Some(res), Some(rate)) => parts.push(format!("{res}@{rate:.0} Hz")),
+            (Some(res), None) => parts.push(res.clone()),
+            (None, Some(rate)) => parts.push(format!("{rate:.0} Hz")),
+            (None, None) => {}
+        }
+        parts.join(", ")
+    }
+
+    /// Set `monitor` up as suggested. A resolution the monitor does not list
+    /// is left out, and a rate it does not list becomes the nearest one it
+    /// does, as overclocks depend on the cable. Returns whether anything
+    /// changed.
+    pub fn apply_to(&self, monitor: &mut MonitorConfig) -> bool {
+        let before = monitor.clone();
+        if let Some(res) = &self.resolution {
+            if monitor.available_modes.is_empty() || !monitor.rates_at(res).is_empty() {
+                monitor.resolution = res.clone();
+                monitor.mode_preset = None;
+            }
+        }
+        if let Some(rate) = self.refresh_rate {
+            let nearest = monitor
+                .rates_at(&monitor.resolution)
+                .into_iter()
+                .min_by(|a, b| (a - rate).abs().total_cmp(&(b - rate).abs()));
+            if let Some(rate) = nearest.or(monitor.available_modes.is_empty().then_some(rate)) {
+                monitor.refresh_rate = rate;
+                monitor.mode_preset = None;
+            }
+        }
+        monitor.fit_refresh_rate();
+        if let Some(scale) = self.scale {
+            monitor.scale = scale;
+        }
+        *monitor != before
+    }
+
+    /// Whether `monitor` already runs as suggested.
+    pub fn is_followed_by(&self, monitor: &MonitorConfig) -> bool {
+        !self.apply_to(&mut monitor.clone())
+    }
+}
+
+/// Scales [`density_scale`] picks from.
+const SCALE_STEPS: &[f64] = &[1.0, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0];
+
+/// Pixels per inch that look right at 100% from a desk; a laptop panel is
+/// seen from closer, so it takes more before it is scaled up.
+const DESK_DPI: f64 = 110.0;
+const LAPTOP_DPI: f64 = 125.0;
+
+/// A starting scale for a `resolution` panel `size_mm` wide and high: 100%
+/// for a 24" 1080p, 150% for a 27" 4K, 125% for a 14" 1080p laptop. Rounded
+/// to a quarter step, then to one Hyprland takes as is. `None` without a
+/// size or a resolution to go by.
+pub fn density_scale(name: &str, resolution: &str, size_mm: (u32, u32)) -> Option<f64> {
+    let width: f64 = resolution.trim().split_once('x')?.0.parse().ok()?;
+    if size_mm.0 == 0 || width <= 0.0 {
+        return None;
+    }
+    let dpi = width / (size_mm.0 as f64 / 25.4);
+    let target = if name.starts_with("eDP") { LAPTOP_DPI } else { DESK_DPI };
+    let step = SCALE_STEPS
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - dpi / target).abs().total_cmp(&(b - dpi / target).abs()))?;
+    Some(crate::config::fixed_scale(resolution, step).unwrap_or(step))
+}
+
+/// The presets hyprmon knows, fetched ones first.
+#[derive(Debug, Clone, Default)]
+pub struct Presets(Vec<Preset>);
+
+impl Presets {
+    /// Only the set shipped with hyprmon.
+    pub fn bundled() -> Self {
+        Self(parse(BUNDLED).expect("presets.json is valid"))
+    }
+
+    /// The fetched set, if any, then the bundled one. A fetched file that
+    /// cannot be read is an error, so it is reported rather than ignored.
+    pub fn load() -> Result<Self> {
+        let mut presets = Self::bundled();
+        let path = path();
+        if path.exists() {
+            let text = std::fs::read_to_string(&path)?;
+            let fetched = parse(&text).with_context(|| format!("in {}", path.display()))?;
+            presets.0.splice(0..0, fetched);
+        }
+        Ok(presets)
+    }
+
+    /// The preset for `monitor`'s model.
+    pub fn find(&self, monitor: &MonitorConfig) -> Option<&Preset> {
+        self.0.iter().find(|preset| preset.matches(monitor))
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<Preset>> {
+    Ok(serde_json::from_str(text)?)
+}
+
+/// `~/.config/hyprmon/presets.json`, where fetched presets are kept.
+pub fn path() -> PathBuf {
+    dirs::home_dir().unwrap().join(".config/hyprmon/presets.json")
+}
+
+/// Download the preset set at `url` with `curl` and keep it for [`Presets::load`].
+/// Returns how many presets it has; only http(s) URLs are accepted.
+pub fn fetch(exec: &dyn Executor, writes: &Writes, url: &str) -> Result<usize> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        bail!("{url} is not an http(s) URL");
+    }
+    let body = exec.output("curl", &["-fsSL", "--max-time", "30", "--", url])?;
+    if writes.dry_run() {
+        println!("would write {}", path().display());
+        return Ok(0);
+    }
+    let text = String::from_utf8(body).context("not UTF-8")?;
+    let presets = parse(&text).with_context(|| format!("{url} is not a preset list"))?;
+    if presets.is_empty() {
+        bail!("{url} lists no presets");
+    }
+    writes.file(&path(), &text)?;
+    Ok(presets.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::{DryRun, Replay};
+    use crate::monitor::{KeyBy, Rotation};
+
+    fn monitor(make: &str, model: &str, modes: &[&str]) -> MonitorConfig {
+        MonitorConfig {
+            name: "DP-1".into(),
+            description: format!("{make} {model}"),
+            make: make.into(),
+            model: model.into(),
+            resolution: "2560x1440".into(),
+            refresh_rate: 60.0,
+            position_x: 0,
+            position_y: 0,
+            scale: 1.0,
+            rotation: Rotation::Normal,
+            is_primary: false,
+            available_modes: modes.iter().map(|m| m.to_string()).collect(),
+            serial: String::new(),
+            key_by: KeyBy::Description,
+            mode_preset: None,
+        }
+    }
+
+    #[test]
+    fn bundled_presets_match_by_make_and_model() {
+        let presets = Presets::bundled();
+        let dell = monitor("Dell Inc.", " dell u2720q", &[]);
+        let preset = presets.find(&dell).unwrap();
+        assert_eq!(preset.summary(), "150%, 3840x2160@60 Hz");
+        assert!(presets.find(&monitor("Dell Inc.", "DELL U2723QE", &[])).is_none());
+    }
+
+    #[test]
+    fn density_scale_goes_by_pixels_per_inch() {
+        assert_eq!(density_scale("DP-1", "1920x1080", (531, 299)), Some(1.0)); // 24"
+        assert_eq!(density_scale("DP-1", "3840x2160", (597, 336)), Some(1.5)); // 27"
+        assert_eq!(density_scale("DP-1", "2560x1440", (597, 336)), Some(1.0));
+        assert_eq!(density_scale("eDP-1", "1920x1080", (309, 174)), Some(1.25)); // 14"
+        assert_eq!(density_scale("eDP-1", "2880x1800", (302, 189)), Some(2.0));
+        // 150% does not divide 2256x1504 evenly, so Hyprland's nearest
+        assert_eq!(density_scale("eDP-1", "2256x1504", (285, 190)), Some(188.0 / 120.0));
+        assert_eq!(density_scale("HDMI-A-1", "1920x1080", (0, 0)), None);
+    }
+
+    #[test]
+    fn applying_keeps_to_the_modes_a_monitor_lists() {
+        let preset = Preset {
+            make: "Dell Inc.".into(),
+            model: "AW3423DW".into(),
+            resolution: Some("3440x1440".into()),
+            refresh_rate: Some(174.96),
+            scale: Some(1.0),
+            notes: Vec::new(),
+        };
+        // Over HDMI the overclocked rate is not offered: the nearest one is
+        let modes = ["3440x1440@99.98Hz", "3440x1440@59.97Hz", "2560x1440@59.95Hz"];
+        let mut hdmi = monitor("Dell Inc.", "AW3423DW", &modes);
+        assert!(!preset.is_followed_by(&hdmi));
+        assert!(preset.apply_to(&mut hdmi));
+        assert_eq!((hdmi.resolution.as_str(), hdmi.refresh_rate), ("3440x1440", 99.98));
+        assert!(preset.is_followed_by(&hdmi));
+
+        let mut dp = monitor("Dell Inc.", "AW3423DW", &["3440x1440@174.96Hz"]);
+        preset.apply_to(&mut dp);
+        assert_eq!(dp.refresh_rate, 174.96);
+    }
+
+    #[test]
+    fn fetch_checks_the_download_before_keeping_it() {
+        let url = "https://example.com/presets.json";
+        let curl = format!("curl -fsSL --max-time 30 -- {url}");
+        let replay = Replay::default().answer(&curl, "<html>not found</html>");
+        assert!(fetch(&replay, &Writes::new(false), url).is_err());
+        replay.set(&curl, "[]");
+        assert!(fetch(&replay, &Writes::new(false), url).is_err());
+
+        let dry = DryRun(Replay::default());
+        assert_eq!(fetch(&dry, &Writes::new(true), url).unwrap(), 0);
+        assert!(dry.0.calls().is_empty());
+    }
+
+    #[test]
+    fn fetch_takes_only_http_urls() {
+        let replay = Replay::default();
+        for url in ["-o/tmp/x https://example.com", "file:///etc/passwd", "example.com"] {
+            assert!(fetch(&replay, &Writes::new(false), url).is_err());
+        }
+        assert!(replay.calls().is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+//! Safe mode, the escape hatch for an experiment that left the screens black
+//! or unreadable: every monitor, disabled ones too, at its preferred mode,
+//! 100% scale and unrotated, side by side. It is generated from the monitors
+//! Hyprland knows each time, set at once with `hyprctl keyword` and written
+//! to monitors.conf so a reload keeps it. Applying a workspace leaves it.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::{splice_managed_block, FileChange};
+use crate::exec::Executor;
+use crate::monitor::set_monitor_rules;
+
+/// Heads the safe block in monitors.conf.
+const HEADER: &str = "# Safe mode (hyprmon panic): apply a workspace to leave it";
+
+/// Connectors of every monitor Hyprland knows, disabled ones included.
+pub fn monitor_names(exec: &dyn Executor) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct Named {
+        name: String,
+    }
+    let json = exec.output("hyprctl", &["monitors", "all", "-j"])?;
+    let monitors: Vec<Named> = serde_json::from_slice(&json)?;
+    Ok(monitors.into_iter().map(|m| m.name).collect())
+}
+
+/// The `monitor=` rules of safe mode for `names`, then one for any monitor
+/// plugged in later. `auto` lines them up left to right.
+pub fn rules(names: &[String]) -> Vec<String> {
+    let mut rules: Vec<String> =
+        names.iter().map(|name| format!("{name},preferred,auto,1,transform,0")).collect();
+    rules.push(",preferred,auto,1".to_string());
+    rules
+}
+
+/// Safe mode as the monitors.conf block.
+pub fn block(names: &[String]) -> String {
+    let mut out = format!("{HEADER}\n");
+    for rule in rules(names) {
+        out += &format!("monitor={rule}\n");
+    }
+    out
+}
+
+/// Set safe mode in Hyprland now. Every rule is tried, so one monitor that
+/// refuses does not keep the others dark; the first refusal is returned.
+pub fn set(exec: &dyn Executor, names: &[String]) -> Result<()> {
+    set_monitor_rules(exec, &rules(names), Duration::ZERO)
+}
+
+/// Writing safe mode into the hyprmon block of the config at `path`, keeping
+/// the usual copies of the previous file.
+pub fn conf_changes(path: &Path, names: &[String]) -> Vec<FileChange> {
+    let mut changes = FileChange::backups_of(path);
+    let existing = std::fs::read_to_string(path).unwrap_or_default();
+    let content = splice_managed_block(&existing, &block(names));
+    changes.push(FileChange::Write { path: path.to_path_buf(), content });
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::Replay;
+
+    #[test]
+    fn sets_every_monitor_even_when_one_refuses() {
+        let all = r#"[{"name":"eDP-1","disabled":true},{"name":"DP-2"}]"#;
+        let replay = Replay::default()
+            .answer("hyprctl monitors all -j", all)
+            .answer("hyprctl keyword monitor eDP-1,preferred,auto,1,transform,0", "ok")
+            .answer("hyprctl keyword monitor DP-2,preferred,auto,1,transform,0", "bad mode")
+            .answer("hyprctl keyword monitor ,preferred,auto,1", "ok");
+        let names = monitor_names(&replay).unwrap();
+        assert_eq!(names, ["eDP-1", "DP-2"]);
+        assert_eq!(set(&replay, &names).unwrap_err().to_string(), "bad mode");
+        assert_eq!(replay.calls().len(), 4);
+    }
+
+    #[test]
+    fn replaces_only_the_hyprmon_block() {
+        let path = std::env::temp_dir().join(format!("hyprmon_safe_{}.conf", std::process::id()));
+        let old = splice_managed_block("", "monitor=DP-2,3840x2160@240,0x0,3");
+        std::fs::write(&path, format!("source = extra.conf\n{old}")).unwrap();
+        let changes = conf_changes(&path, &["DP-2".to_string()]);
+        assert!(matches!(changes[0], FileChange::Backup { .. }));
+        let FileChange::Write { content, .. } = &changes[2] else {
+            panic!("{changes:?}");
+        };
+        assert!(content.starts_with("source = extra.conf\n"));
+        assert!(content.contains("monitor=DP-2,preferred,auto,1,transform,0\n"));
+        assert!(!content.contains("3840x2160@240"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,398 @@
+//! Scripted end-to-end runs: an `App` on a fake Hyprland, fed key presses,
+//! mouse gestures and hotplug events the way `main.rs` feeds them, and judged
+//! by the monitors.conf it ends up writing.
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::App;
+use crate::config::MonitorDatabase;
+use crate::exec::Replay;
+use crate::history::HistoryLog;
+use crate::hypr_ipc::AddedMonitor;
+use crate::input::{handle_key, handle_mouse, handle_paste, InputResult};
+use crate::keymap::parse_key;
+use crate::monitor::MonitorConfig;
+use crate::state::DialogType;
+use crate::ui::Hit;
+
+const WIDTH: u16 = 140;
+const HEIGHT: u16 = 44;
+
+/// One scripted session. Files live in the temp dir under `tag` and are
+/// removed on drop.
+pub struct Scenario {
+    pub app: App,
+    /// What `hyprctl` answers and has been asked.
+    pub hyprctl: Arc<Replay>,
+    /// The monitors the fake Hyprland has plugged in, in its order.
+    connected: Vec<MonitorConfig>,
+    files: Vec<PathBuf>,
+    quit: bool,
+}
+
+impl Scenario {
+    /// Start hyprmon with `monitors` plugged in and an empty database.
+    pub fn new(tag: &str, monitors: Vec<MonitorConfig>) -> Self {
+        Self::with_db(tag, monitors, MonitorDatabase::default())
+    }
+
+    /// Start with `db` as the saved workspaces, e.g. to script auto-matching.
+    pub fn with_db(tag: &str, monitors: Vec<MonitorConfig>, mut db: MonitorDatabase) -> Self {
+        let files: Vec<PathBuf> = ["json", "conf", "jsonl"]
+            .iter()
+            .map(|ext| {
+                let file = format!("hyprmon_scenario_{tag}_{}.{ext}", std::process::id());
+                std::env::temp_dir().join(file)
+            })
+            .collect();
+        for file in &files {
+            let _ = std::fs::remove_file(file);
+        }
+        db.set_config_path(files[0].clone());
+        let hyprctl = Arc::new(
+            Replay::default()
+                .answer("hyprctl reload", "ok")
+                .answer("hyprctl configerrors -j", "[\"\"]")
+                .answer("hyprctl workspaces -j", "[]"),
+        );
+        let mut app = App::for_test(monitors.clone());
+        app.monitor_db = db;
+        app.hypr_conf_path = files[1].clone();
+        app.history_log = HistoryLog::at(files[2].clone());
+        app.exec = hyprctl.clone();
+        let scenario = Self { app, hyprctl, connected: monitors, files, quit: false };
+        scenario.announce();
+        scenario
+    }
+
+    /// Press each key in turn, written as in the keymap config (`a`,
+    /// `Ctrl+g`, `Shift+Left`, `Enter`, …).
+    pub fn keys(&mut self, keys: &[&str]) -> &mut Self {
+        for key in keys {
+            let (code, modifiers) = parse_key(key).unwrap();
+            let result = handle_key(&mut self.app, code, modifiers);
+            self.after_input(result);
+        }
+        self
+    }
+
+    /// Type `text` into whatever entry is open, as a bracketed paste.
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        handle_paste(&mut self.app, text);
+        self.after_input(InputResult::Continue);
+        self
+    }
+
+    /// Left-click the middle of whatever was drawn for `hit`.
+    pub fn click(&mut self, hit: Hit) -> &mut Self {
+        let (col, row) = self.center(hit);
+        self.mouse(MouseEventKind::Down(MouseButton::Left), col, row);
+        self.mouse(MouseEventKind::Up(MouseButton::Left), col, row)
+    }
+
+    /// Press the left button over `hit` and move `cols` columns sideways
+    /// without letting go.
+    pub fn grab(&mut self, hit: Hit, cols: i16) -> &mut Self {
+        let (col, row) = self.center(hit);
+        self.mouse(MouseEventKind::Down(MouseButton::Left), col, row);
+        self.mouse(MouseEventKind::Drag(MouseButton::Left), col.saturating_add_signed(cols), row)
+    }
+
+    /// Let go of the left button over the arrangement, `cols` columns right
+    /// of where monitor box `i` is drawn now.
+    pub fn release(&mut self, i: usize, cols: i16) -> &mut Self {
+        let (col, row) = self.center(Hit::Monitor(i));
+        let col = col.saturating_add_signed(cols);
+        self.mouse(MouseEventKind::Drag(MouseButton::Left), col, row);
+        self.mouse(MouseEventKind::Up(MouseButton::Left), col, row)
+    }
+
+    pub fn mouse(&mut self, kind: MouseEventKind, col: u16, row: u16) -> &mut Self {
+        self.draw();
+        let result = handle_mouse(&mut self.app, kind, col, row);
+        self.after_input(result);
+        self
+    }
+
+    /// Plug `monitor` in: Hyprland lists it and announces it over IPC.
+    pub fn plug(&mut self, monitor: MonitorConfig) -> &mut Self {
+        let added = AddedMonitor {
+            id: Some(self.connected.len() as u32),
+            name: monitor.name.clone(),
+            description: Some(monitor.description.clone()),
+        };
+        self.connected.push(monitor);
+        self.announce();
+        let _ = self.app.on_monitor_added(&added);
+        self
+    }
+
+    /// Unplug the monitor called `name`.
+    pub fn unplug(&mut self, name: &str) -> &mut Self {
+        self.connected.retain(|m| m.name != name);
+        self.announce();
+        self.app.on_monitor_removed(name);
+        self
+    }
+
+    /// Let `secs` seconds pass, as far as the keep-or-revert countdown and
+    /// the last reload can tell.
+    pub fn wait(&mut self, secs: u64) -> &mut Self {
+        let secs = Duration::from_secs(secs);
+        if let DialogType::ConfirmApply { started, .. } = &mut self.app.dialog {
+            *started -= secs;
+        }
+        if let Some(reloaded_at) = &mut self.app.reloaded_at {
+            *reloaded_at -= secs;
+        }
+        self.app.expire_apply_countdown();
+        self
+    }
+
+    /// The `monitor=` lines of monitors.conf as last written.
+    pub fn written(&self) -> Vec<String> {
+        let conf = std::fs::read_to_string(&self.app.hypr_conf_path).unwrap_or_default();
+        conf.lines().filter(|l| l.starts_with("monitor=")).map(String::from).collect()
+    }
+
+    /// The `monitor=` lines an apply would write right now.
+    pub fn pending(&self) -> Vec<String> {
+        let conf = self.app.pending_config();
+        conf.lines().filter(|l| l.starts_with("monitor=")).map(String::from).collect()
+    }
+
+    /// How many times Hyprland was asked to reload.
+    pub fn reloads(&self) -> usize {
+        self.hyprctl.calls().iter().filter(|c| *c == "hyprctl reload").count()
+    }
+
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    fn after_input(&mut self, result: InputResult) {
+        self.quit |= matches!(result, InputResult::Quit);
+        self.app.identify_selection_change();
+        // Draw every step so rendering is exercised against each state
+        self.draw();
+    }
+
+    fn draw(&mut self) {
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+        let mut hits = Default::default();
+        terminal.draw(|frame| hits = crate::ui::render(frame, &self.app)).unwrap();
+        self.app.hits = hits;
+    }
+
+    fn center(&mut self, hit: Hit) -> (u16, u16) {
+        self.draw();
+        let Rect { x, y, width, height } =
+            self.app.hits.rect(hit).unwrap_or_else(|| panic!("{hit:?} is not on screen"));
+        (x + width / 2, y + height / 2)
+    }
+
+    /// Make `hyprctl monitors -j` list what is plugged in now.
+    fn announce(&self) {
+        let monitors: Vec<serde_json::Value> = self
+            .connected
+            .iter()
+            .map(|m| {
+                let (width, height) = m.resolution.split_once('x').unwrap();
+                serde_json::json!({
+                    "name": m.name,
+                    "description": m.description,
+                    "make": m.make,
+                    "model": m.model,
+                    "serial": m.serial,
+                    "width": width.parse::<u32>().unwrap(),
+                    "height": height.parse::<u32>().unwrap(),
+                    "refreshRate": m.refresh_rate,
+                    "x": m.position_x,
+                    "y": m.position_y,
+                    "scale": m.scale,
+                    "transform": m.rotation.transform(),
+                    "availableModes": m.available_modes,
+                    "focused": m.is_primary,
+                })
+            })
+            .collect();
+        self.hyprctl.set("hyprctl monitors -j", serde_json::to_vec(&monitors).unwrap());
+    }
+}
+
+impl Drop for Scenario {
+    fn drop(&mut self) {
+        for file in &self.files {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{DialogButton, DragState, MainTab, SettingField};
+
+    fn desk() -> Vec<MonitorConfig> {
+        vec![
+            MonitorConfig::acme("eDP-1", "Panel", "1920x1080", 0),
+            MonitorConfig::acme("DP-1", "Wide", "2560x1440", 1920),
+        ]
+    }
+
+    #[test]
+    fn keep_an_apply_and_the_file_stays() {
+        let mut s = Scenario::new("keep", desk());
+        s.keys(&["L", "a"]);
+        assert!(matches!(s.app.dialog, DialogType::ConfirmApply { .. }));
+        s.keys(&["y"]).wait(60);
+        assert_eq!(
+            s.written(),
+            [
+                "monitor=desc:Acme Wide,2560x1440@60.00,0x0,1",
+                "monitor=eDP-1,1920x1080@60.00,2560x0,1",
+                "monitor=,preferred,auto,1",
+            ]
+        );
+        assert_eq!(s.reloads(), 1);
+        assert_eq!(s.app.dialog, DialogType::None);
+    }
+
+    #[test]
+    fn an_unanswered_apply_reverts_when_the_countdown_runs_out() {
+        let mut s = Scenario::new("countdown", desk());
+        s.keys(&["a", "y"]);
+        let before = s.written();
+        s.keys(&["L", "a"]).wait(5);
+        assert!(matches!(s.app.dialog, DialogType::ConfirmApply { .. }));
+        assert_ne!(s.written(), before);
+        s.wait(15);
+        assert_eq!(s.app.dialog, DialogType::None);
+        assert_eq!(s.written(), before);
+        assert_eq!(s.app.monitors[0].name, "eDP-1");
+        assert_eq!(s.reloads(), 3);
+    }
+
+    #[test]
+    fn dragging_a_monitor_reorders_what_apply_writes() {
+        let mut s = Scenario::new("drag", desk());
+        s.grab(Hit::Monitor(0), 10).release(1, 2);
+        assert!(s.app.has_changes);
+        assert_eq!(s.pending()[0], "monitor=desc:Acme Wide,2560x1440@60.00,0x0,1");
+        s.keys(&["a", "y"]);
+        assert_eq!(s.written(), s.pending());
+    }
+
+    #[test]
+    fn dragging_a_workspace_tab_reorders_and_saves_the_workspaces() {
+        let mut db = MonitorDatabase::default();
+        db.add_workspace("Desk").unwrap();
+        db.add_workspace("Couch").unwrap();
+        let mut s = Scenario::with_db("tab_drag", desk(), db);
+        s.click(Hit::MainTab(MainTab::Saved));
+        let names = |s: &Scenario| -> Vec<String> {
+            s.app.monitor_db.workspaces.iter().map(|ws| ws.name.clone()).collect()
+        };
+
+        // Dragged off the tab row, the tab goes back
+        let (col, row) = s.center(Hit::WorkspaceTab(0));
+        s.grab(Hit::WorkspaceTab(0), 0).mouse(MouseEventKind::Drag(MouseButton::Left), col, 30);
+        assert_eq!(s.app.drag_state, DragState::None);
+        s.mouse(MouseEventKind::Up(MouseButton::Left), col, row);
+        assert_eq!(names(&s), ["Default", "Desk", "Couch"]);
+
+        let (col, row) = s.center(Hit::WorkspaceTab(2));
+        s.grab(Hit::WorkspaceTab(0), 0).mouse(MouseEventKind::Drag(MouseButton::Left), col, row);
+        assert_eq!(s.app.drag_state, DragState::Workspace { idx: 0, over: 2 });
+        s.mouse(MouseEventKind::Up(MouseButton::Left), col, row);
+        assert_eq!(names(&s), ["Desk", "Couch", "Default"]);
+        assert_eq!(s.app.current_workspace_name(), "Default");
+        let saved = std::fs::read_to_string(&s.files[0]).unwrap();
+        let at = |name: &str| saved.find(&format!("\"{name}\"")).unwrap();
+        assert!(at("Desk") < at("Couch") && at("Couch") < at("Default"));
+    }
+
+    #[test]
+    fn a_hotplug_in_the_middle_of_a_drag_drops_the_drag() {
+        let mut s = Scenario::new("hotplug_drag", desk());
+        s.keys(&["a", "y"]).wait(5).grab(Hit::Monitor(0), 10);
+        assert!(matches!(s.app.drag_state, DragState::Dragging { .. }));
+        s.plug(MonitorConfig::acme("HDMI-A-1", "Side", "1920x1080", 4480));
+        assert_eq!(s.app.drag_state, DragState::None);
+        s.release(2, 0);
+        assert_eq!(s.app.drag_state, DragState::None);
+
+        let names: Vec<&str> = s.app.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["eDP-1", "DP-1", "HDMI-A-1"]);
+        // Saved monitors are back in place, so the new one is applied right away
+        assert!(matches!(s.app.dialog, DialogType::ConfirmApply { .. }));
+        assert!(s.written().contains(&"monitor=desc:Acme Side,1920x1080@60.00,4480x0,1".into()));
+    }
+
+    #[test]
+    fn unplugging_after_an_apply_packs_the_row_again() {
+        let mut s = Scenario::new("unplug", desk());
+        s.plug(MonitorConfig::acme("HDMI-A-1", "Side", "1920x1080", 4480));
+        s.keys(&["a", "y"]);
+        assert_eq!(s.written().len(), 4);
+
+        s.unplug("DP-1").keys(&["a", "y"]);
+        assert_eq!(s.app.monitors.len(), 2);
+        // The unplugged monitor keeps its saved line for when it comes back
+        let written = s.written();
+        assert_eq!(written[0], "monitor=eDP-1,1920x1080@60.00,0x0,1");
+        assert!(written.contains(&"monitor=desc:Acme Side,1920x1080@60.00,1920x0,1".into()));
+        assert!(written.iter().any(|l| l.starts_with("monitor=desc:Acme Wide,")));
+    }
+
+    #[test]
+    fn a_typed_scale_is_fixed_to_hyprlands_before_it_is_written() {
+        let mut s = Scenario::new("typed_scale", desk());
+        let scale = SettingField::all().iter().position(|f| *f == SettingField::Scale).unwrap();
+        s.keys(&["l", "Tab"]).click(Hit::SettingValue(scale));
+        assert_eq!(s.app.dialog, DialogType::EditDropdown);
+        s.keys(&["End", "Enter", "Ctrl+a"]).paste("150").keys(&["Enter", "a"]);
+        assert_eq!(s.app.dialog, DialogType::FixScale { focus: DialogButton::Yes });
+        s.keys(&["y", "y"]);
+        assert_eq!(s.written()[1], "monitor=desc:Acme Wide,2560x1440@60.00,1920x0,1.60");
+        assert!(!s.quit());
+    }
+
+    #[test]
+    fn replugging_a_saved_monitor_sets_only_its_line() {
+        let mut s = Scenario::new("replug", desk());
+        s.keys(&["a", "y"]).wait(5);
+        let reloads = s.reloads();
+        let rule = "keyword monitor desc:Acme Wide,2560x1440@60.00,1920x0,1";
+        s.hyprctl.set(&format!("hyprctl {rule}"), "ok");
+
+        s.unplug("DP-1").plug(MonitorConfig::acme("DP-1", "Wide", "2560x1440", 0));
+        assert!(s.hyprctl.calls().contains(&format!("hyprctl {rule}")));
+        assert_eq!(s.reloads(), reloads);
+        assert_eq!(s.app.dialog, DialogType::None);
+        assert_eq!(s.app.monitors[1].position_x, 1920);
+        assert!(!s.app.out_of_sync());
+        assert_eq!(s.app.toasts.last_text(), "Acme Wide connected - applied its saved settings");
+    }
+
+    #[test]
+    fn a_paused_auto_apply_leaves_a_plugged_in_monitor_alone() {
+        let mut s = Scenario::new("paused", desk());
+        s.keys(&["a", "y"]).wait(5).unplug("DP-1").keys(&["Ctrl+p"]);
+        assert!(!s.app.auto_apply);
+        let calls = s.hyprctl.calls().len();
+
+        s.plug(MonitorConfig::acme("DP-1", "Wide", "2560x1440", 0));
+        // Only asked what was plugged in
+        assert_eq!(s.hyprctl.calls()[calls..], ["hyprctl monitors -j"]);
+        assert_eq!(s.app.dialog, DialogType::None);
+        let toast = "Acme Wide connected - auto-apply paused, press a to apply";
+        assert_eq!(s.app.toasts.last_text(), toast);
+        s.keys(&["Ctrl+p"]);
+        assert!(s.app.auto_apply);
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A daily local-time window such as `23:00-07:00`, stored in minutes since
+/// midnight. Windows whose end is before their start wrap past midnight; equal
+/// ends cover the whole day. Serialized as the `HH:MM-HH:MM` string.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl TimeRange {
+    pub fn parse(text: &str) -> Result<Self> {
+        let Some((start, end)) = text.trim().split_once('-') else {
+            bail!("expected HH:MM-HH:MM, got '{text}'");
+        };
+        Ok(Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+
+    /// Whether `minute` (since local midnight) falls inside the window.
+    pub fn contains(&self, minute: u16) -> bool {
+        match self.start.cmp(&self.end) {
+            std::cmp::Ordering::Less => (self.start..self.end).contains(&minute),
+            std::cmp::Ordering::Greater => minute >= self.start || minute < self.end,
+            std::cmp::Ordering::Equal => true,
+        }
+    }
+}
+
+fn parse_time(text: &str) -> Result<u16> {
+    let text = text.trim();
+    let parsed = text
+        .split_once(':')
+        .and_then(|(h, m)| Some((h.parse::<u16>().ok()?, m.parse::<u16>().ok()?)));
+    match parsed {
+        Some((h, m)) if h < 24 && m < 60 && text.len() <= 5 => Ok(h * 60 + m),
+        _ => bail!("invalid time '{text}', expected HH:MM"),
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start / 60,
+            self.start % 60,
+            self.end / 60,
+            self.end % 60
+        )
+    }
+}
+
+impl TryFrom<String> for TimeRange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value)
+    }
+}
+
+impl From<TimeRange> for String {
+    fn from(range: TimeRange) -> Self {
+        range.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_displays_ranges() {
+        let r = TimeRange::parse("23:00-7:30").unwrap();
+        assert_eq!((r.start, r.end), (23 * 60, 7 * 60 + 30));
+        assert_eq!(r.to_string(), "23:00-07:30");
+        assert!(TimeRange::parse("23:00").is_err());
+        assert!(TimeRange::parse("24:00-01:00").is_err());
+        assert!(TimeRange::parse("10:60-11:00").is_err());
+    }
+
+    #[test]
+    fn contains_handles_midnight_wrap() {
+        let day = TimeRange::parse("08:00-18:00").unwrap();
+        assert!(day.contains(8 * 60));
+        assert!(!day.contains(18 * 60));
+        let night = TimeRange::parse("23:00-07:00").unwrap();
+        assert!(night.contains(23 * 60 + 30));
+        assert!(night.contains(60));
+        assert!(!night.contains(12 * 60));
+        assert!(TimeRange::parse("00:00-00:00").unwrap().contains(720));
+    }
+
+    #[test]
+    fn serializes_as_string() {
+        let r = TimeRange::parse("23:00-07:00").unwrap();
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, "\"23:00-07:00\"");
+        assert_eq!(serde_json::from_str::<TimeRange>(&json).unwrap(), r);
+        assert!(serde_json::from_str::<TimeRange>("\"nope\"").is_err());
+    }
+}
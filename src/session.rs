@@ -0,0 +1,81 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::state::{FocusPanel, MainTab};
+
+/// Where the UI was left at quit, restored on the next start. Things are
+/// remembered by id or name so a reordered workspace list or monitor row
+/// still lands on the same one.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tab: Option<MainTab>,
+    pub focus: Option<FocusPanel>,
+    /// Workspace browsed on the Saved tab, by id (older files: by name).
+    pub workspace: Option<String>,
+    /// Selected live monitor, by connector.
+    pub monitor: Option<String>,
+    /// Selected monitor of the browsed workspace, by position.
+    pub saved_monitor: usize,
+}
+
+impl Session {
+    /// `$XDG_STATE_HOME/hyprmon/session.json` (`~/.local/state/...` by default).
+    pub fn path() -> PathBuf {
+        dirs::state_dir()
+            .unwrap_or_else(|| dirs::home_dir().unwrap().join(".local/state"))
+            .join("hyprmon/session.json")
+    }
+
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    /// The stored session; missing or unreadable files give the defaults, as
+    /// losing the last position is never worth an error.
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_tolerates_bad_files() {
+        let path = std::env::temp_dir().join(format!("hyprmon_session_{}", std::process::id()));
+        let session = Session {
+            tab: Some(MainTab::Saved),
+            focus: Some(FocusPanel::Settings),
+            workspace: Some("Desk".into()),
+            monitor: Some("DP-2".into()),
+            saved_monitor: 1,
+        };
+        session.save_to(&path).unwrap();
+        assert_eq!(Session::load_from(&path), session);
+        assert!(fs::read_to_string(&path).unwrap().contains("\"tab\": \"saved\""));
+
+        fs::write(&path, "{not json").unwrap();
+        assert_eq!(Session::load_from(&path), Session::default());
+        let _ = fs::remove_file(&path);
+        assert_eq!(Session::load_from(&path), Session::default());
+    }
+}
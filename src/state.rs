@@ -1,12 +1,15 @@
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MainTab {
     Live,
     Saved,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FocusPanel {
     Arrangement,
     Settings,
@@ -18,6 +21,8 @@ pub enum SettingField {
     RefreshRate,
     Scale,
     Rotation,
+    PositionX,
+    PositionY,
     Primary,
 }
 
@@ -28,30 +33,204 @@ impl SettingField {
             SettingField::RefreshRate,
             SettingField::Scale,
             SettingField::Rotation,
+            SettingField::PositionX,
+            SettingField::PositionY,
             SettingField::Primary,
         ]
     }
 
+    /// The heading it is listed under in the Live settings panel.
+    pub fn group(&self) -> SettingGroup {
+        match self {
+            SettingField::Resolution | SettingField::RefreshRate => SettingGroup::Mode,
+            SettingField::Scale
+            | SettingField::Rotation
+            | SettingField::PositionX
+            | SettingField::PositionY => SettingGroup::Geometry,
+            SettingField::Primary => SettingGroup::Advanced,
+        }
+    }
+
+    /// Typed in as a number rather than picked from a dropdown.
+    pub fn is_position(&self) -> bool {
+        matches!(self, SettingField::PositionX | SettingField::PositionY)
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             SettingField::Resolution => "Resolution:",
             SettingField::RefreshRate => "Refresh Rate:",
             SettingField::Scale => "Scale:",
             SettingField::Rotation => "Rotation:",
+            SettingField::PositionX => "X Position:",
+            SettingField::PositionY => "Y Position:",
             SettingField::Primary => "Primary:",
         }
     }
 }
 
+/// Headings the Live settings are listed under; each can be collapsed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingGroup {
+    Mode,
+    Geometry,
+    Color,
+    Advanced,
+}
+
+impl SettingGroup {
+    pub fn all() -> [SettingGroup; 4] {
+        [SettingGroup::Mode, SettingGroup::Geometry, SettingGroup::Color, SettingGroup::Advanced]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingGroup::Mode => "Mode",
+            SettingGroup::Geometry => "Geometry",
+            SettingGroup::Color => "Color",
+            SettingGroup::Advanced => "Advanced",
+        }
+    }
+
+    /// Indexes into `SettingField::all()` of the settings under it. A group
+    /// without any (Color, for now) is not shown.
+    pub fn fields(&self) -> Vec<usize> {
+        SettingField::all()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.group() == *self)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DialogType {
     None,
-    ConfirmApply { countdown: u8, started: Instant },
-    ConfirmQuit,
+    /// Keep-or-revert prompt after an apply. In this and the other yes/no
+    /// dialogs `focus` is the highlighted button, which Enter presses.
+    ConfirmApply { countdown: u8, started: Instant, focus: DialogButton },
+    ConfirmQuit { focus: DialogButton },
     EditDropdown,
     NewWorkspace,
     RenameWorkspace,
-    DeleteWorkspace,
+    DuplicateWorkspace,
+    DeleteWorkspace { focus: DialogButton },
+    /// Confirm removing the highlighted monitor from the selected workspace.
+    DeleteMonitor { focus: DialogButton },
+    /// Pick the workspace to capture the Live layout into (`dropdown_selection`
+    /// is the highlighted row).
+    SaveAs,
+    /// Pick monitor entries of other workspaces to copy into the selected
+    /// one (`dropdown_selection` is the highlighted row, `App::import_picks`
+    /// the ticked ones).
+    ImportMonitors,
+    /// Pick the live monitor to swap active workspaces with
+    /// (`dropdown_selection` is the highlighted row).
+    SwapWorkspaces,
+    /// Identical monitors share a database key: pick serial or connector keys
+    /// (`dropdown_selection` is the highlighted row), then the Live apply, or
+    /// with `saved` the Saved one, goes ahead.
+    TellApart { saved: bool },
+    /// Some scales would be rounded by Hyprland: Yes switches them to the
+    /// values it accepts before applying, No applies them as typed.
+    FixScale { focus: DialogButton },
+    /// Hyprland's monitors changed outside hyprmon (`App::external_changes`):
+    /// Yes adopts the change into the active workspace, No re-applies the
+    /// workspace over it.
+    Reconcile { focus: DialogButton },
+    /// Pick a kept monitors.conf version to restore (`App::backups`;
+    /// `dropdown_selection` is the highlighted row).
+    Rollback,
+    /// Name entry for "Save as…" into a brand-new workspace.
+    SaveAsNew,
+    /// Free-text note for the selected workspace.
+    EditNotes,
+    /// Type a value for the selected setting into `text_input`: a custom
+    /// resolution or scale, or an X/Y position.
+    EditSetting,
+    /// Edit the `monitor=,…` rule for unknown monitors (empty removes it).
+    EditFallback,
+    /// Nickname for the selected Live monitor (empty removes it).
+    EditNickname,
+    /// Live-vs-saved diff; `dropdown_selection` is the highlighted row.
+    Compare,
+    /// Errors Hyprland reported after an apply; `scroll` is the first
+    /// visible line. Closing it moves on to the keep-or-revert prompt.
+    ConfigErrors { scroll: usize },
+    /// Read-only view of the monitors.conf an apply would write
+    /// (`App::config_preview`); `scroll` is the first visible line.
+    ConfigPreview { scroll: usize },
+    /// Applied-change log viewer; `scroll` is the first visible line.
+    History { scroll: usize },
+    /// Status message history; `scroll` is the first visible line.
+    Messages { scroll: usize },
+    /// Hardware facts about `App::monitor_details`; `scroll` is the first
+    /// visible line.
+    MonitorDetails { scroll: usize },
+    /// Full keybinding reference; `scroll` is the first visible line.
+    Help { scroll: usize },
+    /// `:` palette; `input_buffer` is the query and `dropdown_selection` the
+    /// highlighted match.
+    CommandPalette,
+}
+
+impl DialogType {
+    /// The highlighted button of a yes/no dialog; `None` for other dialogs.
+    pub fn focused_button(&self) -> Option<DialogButton> {
+        match *self {
+            Self::ConfirmApply { focus, .. }
+            | Self::ConfirmQuit { focus }
+            | Self::DeleteWorkspace { focus }
+            | Self::DeleteMonitor { focus }
+            | Self::FixScale { focus }
+            | Self::Reconcile { focus } => Some(focus),
+            _ => None,
+        }
+    }
+
+    /// Highlight the other button of a yes/no dialog.
+    pub fn toggle_button(&mut self) {
+        if let Self::ConfirmApply { focus, .. }
+        | Self::ConfirmQuit { focus }
+        | Self::DeleteWorkspace { focus }
+        | Self::DeleteMonitor { focus }
+        | Self::FixScale { focus }
+        | Self::Reconcile { focus } = self
+        {
+            *focus = match *focus {
+                DialogButton::Yes => DialogButton::No,
+                _ => DialogButton::Yes,
+            };
+        }
+    }
+}
+
+/// A clickable button drawn in a dialog; its hitbox is recorded on `App` each
+/// frame so mouse clicks land regardless of terminal size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DialogButton {
+    Yes,
+    No,
+    Ok,
+    Cancel,
+}
+
+/// How far a monitor got in the last apply, shown on its box until the
+/// layout is edited or applied again.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApplyStatus {
+    /// Due to be applied, and not yet re-read by Hyprland.
+    Queued,
+    /// Being set on its own by an apply step, pause included.
+    Setting,
+    /// Hyprland reloaded with it, and has not been or could not be asked
+    /// what it runs.
+    Applied,
+    /// Hyprland runs it as asked.
+    Verified,
+    /// Hyprland refused it or runs something else, and why.
+    Failed(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -64,6 +243,9 @@ pub enum DragState {
         current_x: u16,
         current_y: u16,
     },
+    /// Workspace tab `idx` held down on the Saved tab, now over tab `over`;
+    /// a release moves it there.
+    Workspace { idx: usize, over: usize },
 }
 
 #[cfg(test)]
@@ -73,15 +255,16 @@ mod tests {
     #[test]
     fn setting_field_all_lists_every_variant_in_order() {
         let all = SettingField::all();
-        assert_eq!(all.len(), 5);
+        assert_eq!(all.len(), 7);
         assert_eq!(all[0], SettingField::Resolution);
-        assert_eq!(all[4], SettingField::Primary);
+        assert_eq!(all[4], SettingField::PositionX);
+        assert_eq!(all[6], SettingField::Primary);
     }
 
     #[test]
     fn setting_field_labels_are_distinct_and_nonempty() {
         let labels: Vec<&str> = SettingField::all().iter().map(|f| f.label()).collect();
-        assert_eq!(labels.len(), 5);
+        assert_eq!(labels.len(), 7);
         assert!(labels.iter().all(|l| l.ends_with(':')));
         // all distinct
         let mut sorted = labels.clone();
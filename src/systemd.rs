@@ -0,0 +1,130 @@
+//! Running `hyprmon daemon` as a systemd user service (`Type=notify`):
+//! readiness and watchdog pings over `$NOTIFY_SOCKET`, SIGHUP for
+//! `systemctl reload`, and installing the unit file.
+
+use anyhow::{bail, Result};
+use std::ffi::OsStr;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The unit shipped beside the binary, for the `ExecStart` path below.
+const UNIT: &str = include_str!("../hyprmon.service");
+const UNIT_EXEC: &str = "/usr/bin/hyprmon";
+
+/// Tell systemd about the service's state (`READY=1`, `WATCHDOG=1`, …).
+/// Does nothing outside a `Type=notify` service; returns whether it was sent.
+pub fn notify(state: &str) -> bool {
+    std::env::var_os("NOTIFY_SOCKET").is_some_and(|socket| notify_to(&socket, state))
+}
+
+/// [`notify`] to `socket`: a path, or an abstract name when it starts with `@`.
+fn notify_to(socket: &OsStr, state: &str) -> bool {
+    let Ok(sender) = UnixDatagram::unbound() else {
+        return false;
+    };
+    let sent = match socket.as_bytes().strip_prefix(b"@") {
+        Some(name) => SocketAddr::from_abstract_name(name)
+            .and_then(|addr| sender.send_to_addr(state.as_bytes(), &addr)),
+        None => sender.send_to(state.as_bytes(), socket),
+    };
+    sent.is_ok()
+}
+
+/// How often to send `WATCHDOG=1`: half of `WatchdogSec`, as systemd
+/// suggests. `None` when the watchdog is off or meant for another process.
+pub fn watchdog_interval() -> Option<Duration> {
+    let var = |name| std::env::var(name).ok();
+    watchdog_from(var("WATCHDOG_USEC").as_deref(), var("WATCHDOG_PID").as_deref())
+}
+
+fn watchdog_from(usec: Option<&str>, pid: Option<&str>) -> Option<Duration> {
+    if pid.is_some_and(|pid| pid.parse() != Ok(std::process::id())) {
+        return None;
+    }
+    let usec: u64 = usec?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+/// Run `on_hup` on a thread of its own for every SIGHUP. Call this before
+/// starting other threads: SIGHUP is blocked here and they inherit that, so
+/// only the waiting thread ever sees it.
+pub fn on_sighup(mut on_hup: impl FnMut() + Send + 'static) -> Result<()> {
+    // SAFETY: plain libc calls on a zeroed, then initialised, sigset_t
+    let set = unsafe {
+        let mut set: libc::sigset_t = std::mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGHUP);
+        if libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut()) != 0 {
+            bail!("cannot block SIGHUP");
+        }
+        set
+    };
+    std::thread::spawn(move || loop {
+        let mut signal = 0;
+        // SAFETY: `set` was initialised above and outlives the call
+        if unsafe { libc::sigwait(&set, &mut signal) } == 0 {
+            on_hup();
+        }
+    });
+    Ok(())
+}
+
+/// The user unit, starting `exe`.
+pub fn unit_file(exe: &Path) -> String {
+    UNIT.replace(UNIT_EXEC, &exe.display().to_string())
+}
+
+/// `~/.config/systemd/user/hyprmon.service`.
+pub fn unit_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| dirs::home_dir().unwrap().join(".config"))
+        .join("systemd/user/hyprmon.service")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifies_path_and_abstract_sockets() {
+        let path = std::env::temp_dir().join(format!("hyprmon_notify_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let systemd = UnixDatagram::bind(&path).unwrap();
+        assert!(notify_to(path.as_os_str(), "READY=1"));
+        let mut buf = [0u8; 64];
+        let n = systemd.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+        let _ = std::fs::remove_file(&path);
+
+        let name = format!("hyprmon_notify_{}", std::process::id());
+        let addr = SocketAddr::from_abstract_name(name.as_bytes()).unwrap();
+        let systemd = UnixDatagram::bind_addr(&addr).unwrap();
+        assert!(notify_to(OsStr::new(&format!("@{name}")), "WATCHDOG=1"));
+        let n = systemd.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"WATCHDOG=1");
+
+        assert!(!notify_to(OsStr::new("/nonexistent/hyprmon.sock"), "READY=1"));
+    }
+
+    #[test]
+    fn watchdog_pings_at_half_the_timeout_for_this_process_only() {
+        let me = std::process::id().to_string();
+        assert_eq!(watchdog_from(Some("90000000"), None), Some(Duration::from_secs(45)));
+        assert_eq!(watchdog_from(Some("90000000"), Some(&me)), Some(Duration::from_secs(45)));
+        assert_eq!(watchdog_from(Some("90000000"), Some("1")), None);
+        assert_eq!(watchdog_from(Some("0"), None), None);
+        assert_eq!(watchdog_from(None, None), None);
+    }
+
+    #[test]
+    fn unit_starts_this_binary_as_a_notify_service() {
+        let unit = unit_file(Path::new("/home/me/.cargo/bin/hyprmon"));
+        assert!(unit.contains("ExecStart=/home/me/.cargo/bin/hyprmon daemon\n"));
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains("ExecReload=/bin/kill -HUP $MAINPID\n"));
+        assert!(unit_path().ends_with("systemd/user/hyprmon.service"));
+    }
+}
@@ -0,0 +1,74 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns `s` takes in a terminal: wide CJK and emoji count two, combining
+/// marks none.
+pub fn width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// User-perceived characters in `s`, so "é" spelled with a combining accent
+/// or a flag emoji count as one.
+pub fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// Byte offset of grapheme `idx` in `s`, or its length past the end.
+pub fn grapheme_byte(s: &str, idx: usize) -> usize {
+    s.grapheme_indices(true).nth(idx).map_or(s.len(), |(b, _)| b)
+}
+
+/// The first `max` graphemes of `s`.
+pub fn take_graphemes(s: &str, max: usize) -> &str {
+    &s[..grapheme_byte(s, max)]
+}
+
+/// `s` cut to fit `max` columns, ending in `…` when something was cut.
+/// Never splits a grapheme, so multi-byte names are safe to pass.
+pub fn truncate(s: &str, max: usize) -> String {
+    if width(s) <= max {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for g in s.graphemes(true) {
+        let w = width(g);
+        if used + w + 1 > max {
+            break;
+        }
+        out.push_str(g);
+        used += w;
+    }
+    if max > 0 {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_graphemes_and_columns_not_bytes() {
+        let flag = "🇻🇳";
+        let combined = "e\u{301}";
+        assert_eq!(grapheme_count(flag), 1);
+        assert_eq!(grapheme_count(combined), 1);
+        assert_eq!(width("Büro"), 4);
+        assert_eq!(width("会議室"), 6);
+        assert_eq!(take_graphemes("Büro 🖥️", 2), "Bü");
+        assert_eq!(grapheme_byte(combined, 1), combined.len());
+    }
+
+    #[test]
+    fn truncates_to_columns_without_splitting_characters() {
+        assert_eq!(truncate("Büro", 4), "Büro");
+        assert_eq!(truncate("Büro links", 5), "Büro…");
+        assert_eq!(truncate("会議室モニター", 6), "会議…");
+        assert_eq!(truncate("🇻🇳🇻🇳🇻🇳", 4), "🇻🇳…");
+        assert!(truncate("会議室モニター", 7).ends_with('…'));
+        assert!(width(&truncate("会議室モニター", 7)) <= 7);
+        assert_eq!(truncate("abc", 0), "");
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+use crate::clock;
+
+/// How many toasts are stacked on screen at once; older ones stay in history.
+const MAX_VISIBLE: usize = 3;
+/// Messages kept for the history panel.
+const MAX_HISTORY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// How long a toast of this level stays on screen; problems linger.
+    fn ttl(self) -> Duration {
+        Duration::from_secs(match self {
+            Level::Info => 4,
+            Level::Warn => 6,
+            Level::Error => 10,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: Level,
+    pub text: String,
+    /// Wall-clock time of the last occurrence, for the history panel.
+    pub at: u64,
+    /// Consecutive identical messages folded into this one.
+    pub repeat: usize,
+    shown: Instant,
+}
+
+impl Toast {
+    /// `text`, with a `(×n)` suffix when it was repeated.
+    pub fn label(&self) -> String {
+        if self.repeat > 1 {
+            format!("{} (×{})", self.text, self.repeat)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// Status messages: the newest few pop up as toasts and fade on their own,
+/// all of them stay in a bounded history.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    history: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Level::Info, text.into());
+    }
+
+    pub fn warn(&mut self, text: impl Into<String>) {
+        self.push(Level::Warn, text.into());
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Level::Error, text.into());
+    }
+
+    pub fn push(&mut self, level: Level, text: String) {
+        self.push_at(level, text, Instant::now());
+    }
+
+    fn push_at(&mut self, level: Level, text: String, now: Instant) {
+        if let Some(last) = self.history.last_mut() {
+            if last.level == level && last.text == text {
+                last.repeat += 1;
+                last.shown = now;
+                last.at = clock::now();
+                return;
+            }
+        }
+        if self.history.len() == MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(Toast {
+            level,
+            text,
+            at: clock::now(),
+            repeat: 1,
+            shown: now,
+        });
+    }
+
+    /// Toasts still on screen at `now`, oldest first.
+    pub fn visible_at(&self, now: Instant) -> Vec<&Toast> {
+        let start = self.history.len().saturating_sub(MAX_VISIBLE);
+        self.history[start..]
+            .iter()
+            .filter(|t| now.duration_since(t.shown) < t.level.ttl())
+            .collect()
+    }
+
+    pub fn visible(&self) -> Vec<&Toast> {
+        self.visible_at(Instant::now())
+    }
+
+    /// Every message still kept, oldest first.
+    pub fn history(&self) -> &[Toast] {
+        &self.history
+    }
+
+    /// Text of the most recent message, or "" when there was none.
+    #[cfg(test)]
+    pub fn last_text(&self) -> &str {
+        self.history.last().map_or("", |t| t.text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_expire_by_level_but_stay_in_history() {
+        let mut toasts = Toasts::default();
+        let t0 = Instant::now();
+        toasts.push_at(Level::Info, "saved".into(), t0);
+        toasts.push_at(Level::Error, "boom".into(), t0);
+        assert_eq!(toasts.visible_at(t0).len(), 2);
+        let later = t0 + Duration::from_secs(5);
+        let left: Vec<&str> = toasts.visible_at(later).iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(left, ["boom"]);
+        assert!(toasts.visible_at(t0 + Duration::from_secs(11)).is_empty());
+        assert_eq!(toasts.history().len(), 2);
+        assert_eq!(toasts.last_text(), "boom");
+    }
+
+    #[test]
+    fn repeats_fold_and_only_the_newest_few_show() {
+        let mut toasts = Toasts::default();
+        let t0 = Instant::now();
+        toasts.push_at(Level::Warn, "Nothing to undo.".into(), t0);
+        toasts.push_at(Level::Warn, "Nothing to undo.".into(), t0 + Duration::from_secs(5));
+        assert_eq!(toasts.history().len(), 1);
+        assert_eq!(toasts.history()[0].label(), "Nothing to undo. (×2)");
+        assert_eq!(toasts.visible_at(t0 + Duration::from_secs(8)).len(), 1);
+
+        for i in 0..MAX_HISTORY + 5 {
+            toasts.push_at(Level::Info, format!("m{i}"), t0);
+        }
+        assert_eq!(toasts.history().len(), MAX_HISTORY);
+        assert_eq!(toasts.visible_at(t0).len(), MAX_VISIBLE);
+        assert_eq!(toasts.visible_at(t0)[0].text, format!("m{}", MAX_HISTORY + 2));
+    }
+}
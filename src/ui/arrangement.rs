@@ -1,37 +1,163 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use super::{BOX_GAP, BOX_HEIGHT, BOX_WIDTH};
+use super::hit::{Hit, HitMap};
+use super::settings::CHANGED_MARK;
+use super::theme::theme;
+use super::{BoxStrip, BOX_HEIGHT, BOX_WIDTH};
 use crate::app::App;
-use crate::state::{DragState, FocusPanel};
+use crate::config::MonitorDatabase;
+use crate::i18n::tr;
+use crate::monitor::{workspaces_on, MonitorConfig};
+use crate::state::{ApplyStatus, DragState, FocusPanel};
+use crate::text;
 
-pub fn render_arrangement_panel(frame: &mut Frame, area: Rect, app: &App) {
+const HINT: &str =
+    "Drag to move | ←→/hl Select | Shift+←→/HL Reorder | ⏎ Details | P Primary | I Identify";
+/// Marks monitors whose settings share one database entry, until told apart.
+const DUPLICATE_MARK: &str = "⚠";
+const DUPLICATE_HINT: &str =
+    "⚠ Identical monitors share saved settings | A Choose how to tell them apart";
+
+/// Border of the drop slot while dragging, and of saved monitors that are not
+/// plugged in.
+const DASHED: symbols::border::Set = symbols::border::Set {
+    top_left: "╭",
+    top_right: "╮",
+    bottom_left: "╰",
+    bottom_right: "╯",
+    vertical_left: "┆",
+    vertical_right: "┆",
+    horizontal_top: "┄",
+    horizontal_bottom: "┄",
+};
+
+/// What a monitor box can show besides its settings. Each state has a glyph
+/// in the box's top border and its own border, and the legend row under the
+/// boxes names the ones present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum BoxState {
+    /// Turned off with DPMS by the workspace.
+    Off,
+    /// Showing another monitor's picture, in presentation mode.
+    Mirrored,
+    /// Saved in the workspace but not plugged in.
+    Offline,
+    /// Left to hyprland.conf (`M`).
+    Unmanaged,
+}
+
+impl BoxState {
+    pub(super) fn glyph(self) -> &'static str {
+        match self {
+            Self::Off => "◐",
+            Self::Mirrored => "⧉",
+            Self::Offline => "○",
+            Self::Unmanaged => "⊘",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Off => tr("off"),
+            Self::Mirrored => tr("mirrored"),
+            Self::Offline => tr("not connected"),
+            Self::Unmanaged => tr("unmanaged"),
+        }
+    }
+
+    /// Border of an unselected box in this state; selection keeps the double one.
+    pub(super) fn border_set(self) -> symbols::border::Set<'static> {
+        match self {
+            Self::Mirrored => symbols::border::ROUNDED,
+            Self::Offline => DASHED,
+            Self::Off | Self::Unmanaged => symbols::border::PLAIN,
+        }
+    }
+
+    /// `color`, the monitor's own, as a box in this state draws it.
+    pub(super) fn style(self, color: Style) -> Style {
+        match self {
+            Self::Off => color.add_modifier(Modifier::DIM),
+            Self::Mirrored => color,
+            Self::Offline | Self::Unmanaged => theme().muted(),
+        }
+    }
+}
+
+/// State of live monitor `monitor`: mirrored while presenting, else off when
+/// the workspace keeps it dark (`off`, connectors), else unmanaged.
+fn live_state(app: &App, monitor: &MonitorConfig, off: &[String]) -> Option<BoxState> {
+    let mirrored = app.presenting.as_ref().is_some_and(|source| *source != monitor.name);
+    if mirrored {
+        Some(BoxState::Mirrored)
+    } else if off.contains(&monitor.name) {
+        Some(BoxState::Off)
+    } else if !app.monitor_db.is_managed(&MonitorDatabase::get_monitor_key(monitor)) {
+        Some(BoxState::Unmanaged)
+    } else {
+        None
+    }
+}
+
+/// Bottom border label for how a monitor fared in the last apply.
+fn apply_status_line(status: &ApplyStatus) -> Line<'static> {
+    let (glyph, label, style) = match status {
+        ApplyStatus::Queued => ("…", tr("queued"), theme().muted()),
+        ApplyStatus::Setting => ("◌", tr("setting…"), theme().warn()),
+        ApplyStatus::Applied => ("●", tr("applied"), theme().accent()),
+        ApplyStatus::Verified => ("✓", tr("verified"), theme().ok()),
+        ApplyStatus::Failed(_) => ("✗", tr("failed"), theme().danger().bold()),
+    };
+    Line::styled(format!(" {glyph} {label} "), style).centered()
+}
+
+/// One row naming the glyph of each of `states` present, at `y` in `inner`.
+pub(super) fn render_legend(frame: &mut Frame, inner: Rect, y: u16, states: &[BoxState]) {
+    let mut states = states.to_vec();
+    states.sort();
+    states.dedup();
+    let mut spans = Vec::new();
+    for state in states {
+        if !spans.is_empty() {
+            spans.push(Span::raw("   "));
+        }
+        spans.push(Span::styled(state.glyph(), state.style(theme().text())));
+        spans.push(Span::styled(format!(" {}", state.label()), theme().muted()));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
+        Rect::new(inner.x, y, inner.width, 1),
+    );
+}
+
+pub fn render_arrangement_panel(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    hits.push(area, Hit::Arrangement);
     let is_focused = app.focus_panel == FocusPanel::Arrangement;
     let border_style = if is_focused {
-        Style::default().fg(Color::Cyan)
+        theme().accent()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme().muted()
     };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
-        .title(" Monitor Arrangement ");
+        .title(tr(" Monitor Arrangement "));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
     if app.monitors.is_empty() {
-        let text = Paragraph::new("No monitors detected.").alignment(Alignment::Center);
+        let text = Paragraph::new(tr("No monitors detected.")).alignment(Alignment::Center);
         frame.render_widget(text, inner);
         return;
     }
 
-    let total_monitors = app.monitors.len();
-    let total_width = (BOX_WIDTH * total_monitors as u16) + (BOX_GAP * (total_monitors as u16 - 1));
-    let base_start_x = inner.x + (inner.width.saturating_sub(total_width)) / 2;
+    let strip = BoxStrip::new(inner, app.monitors.len(), app.selected_monitor);
+    strip.push_hits(inner, hits);
     let base_start_y = inner.y + (inner.height.saturating_sub(BOX_HEIGHT)) / 2;
 
     // Calculate drag offsets
@@ -46,61 +172,119 @@ pub fn render_arrangement_panel(frame: &mut Frame, area: Rect, app: &App) {
             current_x as i16 - start_x as i16,
             current_y as i16 - start_y as i16,
         ),
-        DragState::None => (0, 0),
+        _ => (0, 0),
+    };
+    let dragged = match app.drag_state {
+        DragState::Dragging { monitor_idx, .. } => Some(monitor_idx),
+        _ => None,
     };
+    let target = app.drag_target();
+    let colors = app.monitor_colors();
+    let off: Vec<String> = app
+        .monitor_db
+        .dpms_plan(&app.monitors)
+        .into_iter()
+        .filter(|(_, on)| !on)
+        .map(|(name, _)| name)
+        .collect();
+    let mut states = Vec::new();
+
+    // The dragged box goes last so it floats above the rest
+    let order = (0..app.monitors.len())
+        .filter(|&i| Some(i) != dragged)
+        .chain(dragged);
+    for i in order {
+        let Some(base_x) = strip.x_of(i) else {
+            continue;
+        };
+        let is_dragging = Some(i) == dragged;
 
-    for (i, monitor) in app.monitors.iter().enumerate() {
-        let base_x = base_start_x + (i as u16 * (BOX_WIDTH + BOX_GAP));
-
-        let (x, y) = match app.drag_state {
-            DragState::Dragging { monitor_idx, .. } if monitor_idx == i => {
-                let new_x = (base_x as i16 + drag_offset_x).max(inner.x as i16) as u16;
-                let new_y = (base_start_y as i16 + drag_offset_y).max(inner.y as i16) as u16;
-                (
-                    new_x.min(inner.x + inner.width - BOX_WIDTH),
-                    new_y.min(inner.y + inner.height - BOX_HEIGHT),
-                )
+        let (x, y) = if is_dragging {
+            // Slot where a release now would drop it
+            if let Some((slot, drop_x)) = target {
+                if let Some(ghost_x) = strip.x_of(slot) {
+                    let ghost = Rect::new(ghost_x, base_start_y, BOX_WIDTH, BOX_HEIGHT);
+                    render_ghost(frame, ghost, drop_x);
+                }
             }
-            _ => (base_x, base_start_y),
+            let new_x = (base_x as i16 + drag_offset_x).max(inner.x as i16) as u16;
+            let new_y = (base_start_y as i16 + drag_offset_y).max(inner.y as i16) as u16;
+            (
+                new_x.min(inner.right().saturating_sub(BOX_WIDTH)),
+                new_y.min(inner.bottom().saturating_sub(BOX_HEIGHT)),
+            )
+        } else {
+            (base_x, base_start_y)
+        };
+        // Monitors the drop would push one slot over
+        let displaced = match (dragged, target) {
+            (Some(from), Some((to, _))) => i != from && (from.min(to)..=from.max(to)).contains(&i),
+            _ => false,
         };
 
         let is_selected = i == app.selected_monitor;
-        let is_dragging =
-            matches!(app.drag_state, DragState::Dragging { monitor_idx, .. } if monitor_idx == i);
         let monitor_area = Rect::new(x, y, BOX_WIDTH, BOX_HEIGHT);
+        let monitor = &app.monitors[i];
+        let state = live_state(app, monitor, &off);
+        states.extend(state);
 
         let border_type = if is_selected {
             symbols::border::DOUBLE
         } else {
-            symbols::border::PLAIN
+            state.map_or(symbols::border::PLAIN, BoxState::border_set)
         };
 
+        let color = match state {
+            Some(state) => state.style(theme().monitor_color(colors[i])),
+            None => theme().monitor_color(colors[i]),
+        };
         let style = if is_dragging {
-            Style::default().fg(Color::Green).bold()
+            theme().ok().bold()
+        } else if displaced {
+            theme().muted().add_modifier(Modifier::DIM)
         } else if is_selected {
-            Style::default().fg(Color::Yellow)
+            color.bold()
         } else {
-            Style::default().fg(Color::White)
+            color
         };
 
-        let block = Block::default()
+        if is_dragging {
+            frame.render_widget(Clear, monitor_area);
+        }
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_set(border_type)
             .border_style(style);
+        if app.monitor_changed(i) {
+            block = block.title(Line::styled(CHANGED_MARK, theme().warn()).right_aligned());
+        }
+        if app.key_collides(i) {
+            block = block.title(Line::styled(DUPLICATE_MARK, theme().danger()));
+        }
+        if let Some(state) = state {
+            block = block.title(Line::styled(state.glyph(), style));
+        }
+        if let Some(status) = app.apply_status_of(&monitor.name) {
+            block = block.title_bottom(apply_status_line(status));
+        }
 
         frame.render_widget(block, monitor_area);
 
-        // Monitor number + primary indicator
+        // Monitor number + primary and Hyprland focus indicators
         let primary_mark = if monitor.is_primary { "*" } else { " " };
-        let number_label = format!("{}{}", primary_mark, i + 1);
+        let focused = app.focused_monitor.as_deref() == Some(monitor.name.as_str());
+        let focus_mark = if focused { " ◉" } else { "" };
+        let number_label = format!("{}{}{}", primary_mark, i + 1, focus_mark);
         let number_area = Rect::new(x + 1, y + 1, BOX_WIDTH - 2, 1);
 
         let label_style = if is_dragging {
-            Style::default().fg(Color::Green).bold()
+            theme().ok().bold()
+        } else if displaced {
+            style
         } else if is_selected {
-            Style::default().fg(Color::Yellow).bold()
+            color.bold()
         } else {
-            Style::default()
+            color
         };
 
         frame.render_widget(
@@ -111,42 +295,76 @@ pub fn render_arrangement_panel(frame: &mut Frame, area: Rect, app: &App) {
         );
 
         // Monitor name
-        let name = monitor.display_name();
-        let display_name = if name.len() > (BOX_WIDTH - 2) as usize {
-            format!("{}…", &name[..(BOX_WIDTH as usize - 3)])
-        } else {
-            name
-        };
+        let name = app.monitor_db.label(monitor);
+        let display_name = text::truncate(&name, (BOX_WIDTH - 2) as usize);
 
         let name_area = Rect::new(x + 1, y + 2, BOX_WIDTH - 2, 1);
         frame.render_widget(
             Paragraph::new(display_name)
-                .style(Style::default().fg(Color::Cyan))
+                .style(if displaced { style } else { theme().accent() })
                 .alignment(Alignment::Center),
             name_area,
         );
 
-        // Resolution
+        // Resolution, or what the monitor shows instead of its own
+        let resolution = match (state, &app.presenting) {
+            (Some(BoxState::Mirrored), Some(source)) => format!("= {source}"),
+            (Some(BoxState::Unmanaged), _) => tr("unmanaged").to_string(),
+            _ => monitor.resolution.clone(),
+        };
         let res_area = Rect::new(x + 1, y + 3, BOX_WIDTH - 2, 1);
         frame.render_widget(
-            Paragraph::new(monitor.resolution.as_str())
-                .style(Style::default().fg(Color::DarkGray))
+            Paragraph::new(resolution)
+                .style(theme().muted())
                 .alignment(Alignment::Center),
             res_area,
         );
+
+        // Hyprland workspaces on it, with window counts
+        let workspaces = workspaces_on(&app.hypr_workspaces, &monitor.name);
+        frame.render_widget(
+            Paragraph::new(text::truncate(&workspaces, (BOX_WIDTH - 2) as usize))
+                .style(theme().muted())
+                .alignment(Alignment::Center),
+            Rect::new(x + 1, y + 4, BOX_WIDTH - 2, 1),
+        );
+    }
+
+    strip.render_scroll_marks(frame, inner, base_start_y + BOX_HEIGHT / 2);
+
+    // Legend, over the help row, when there is room under the boxes
+    let legend_y = (inner.y + inner.height).saturating_sub(2);
+    if !states.is_empty() && legend_y >= base_start_y + BOX_HEIGHT {
+        render_legend(frame, inner, legend_y, &states);
     }
 
     // Help text
-    let help = if matches!(app.drag_state, DragState::Dragging { .. }) {
-        "Dragging... Release to set new position."
-    } else {
-        "Drag to move | ←→/hl Select | Shift+←→/HL Reorder | P Primary | I Identify"
+    let help = match target {
+        Some((slot, x)) => format!(
+            "{} {}, x={x} | {}",
+            tr("Drop at slot"),
+            slot + 1,
+            tr("Release to drop, Esc to cancel")
+        ),
+        None if !app.key_collision().is_empty() => tr(DUPLICATE_HINT).to_string(),
+        None => tr(HINT).to_string(),
     };
     let help_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
     frame.render_widget(
         Paragraph::new(help)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::DarkGray)),
+            .style(theme().muted()),
         help_area,
     );
 }
+
+/// Dashed outline of the slot a drag would drop into, labelled with the x
+/// coordinate the monitor would get there.
+fn render_ghost(frame: &mut Frame, area: Rect, drop_x: i32) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(DASHED)
+        .border_style(theme().ok())
+        .title_bottom(Line::from(format!(" x={drop_x} ")).centered());
+    frame.render_widget(block, area);
+}
@@ -0,0 +1,66 @@
+use ratatui::buffer::Buffer;
+
+/// Plain-ASCII stand-in for a glyph the UI draws: box drawing, arrows,
+/// markers and the like. Anything else outside ASCII becomes `?`.
+fn ascii_for(symbol: &str) -> &'static str {
+    match symbol {
+        "─" | "━" | "╌" | "┄" => "-",
+        "═" => "=",
+        "│" | "┃" | "║" | "╎" | "┆" => "|",
+        "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "├" | "┤" | "┬" | "┴" | "┼" | "╔"
+        | "╗" | "╚" | "╝" | "┏" | "┓" | "┗" | "┛" => "+",
+        "▸" | "▶" | "→" | "›" | "»" => ">",
+        "◀" | "←" | "‹" | "«" => "<",
+        "↑" | "▲" => "^",
+        "↓" | "▼" => "v",
+        "█" | "■" | "▐" | "▌" => "#",
+        "•" | "●" | "★" | "∗" | "✦" => "*",
+        "…" => ".",
+        "×" | "✗" => "x",
+        "✓" => "+",
+        "—" | "–" => "-",
+        "″" | "“" | "”" => "\"",
+        "‘" | "’" => "'",
+        "·" => ".",
+        "⇧" => "S",
+        "⌥" => "A",
+        "⊘" => "/",
+        "⚙" => "%",
+        "◉" => "@",
+        "⚠" => "!",
+        "⏎" => "E",
+        _ => "?",
+    }
+}
+
+/// Rewrite every cell of a drawn frame to plain ASCII, for `--ascii`.
+/// Done once on the finished buffer so no widget has to know about it.
+pub fn asciify(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        if !cell.symbol().is_ascii() {
+            let symbol = ascii_for(cell.symbol());
+            cell.set_symbol(symbol);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        widgets::{Block, Borders, Widget},
+    };
+
+    #[test]
+    fn borders_and_markers_become_ascii() {
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buffer = Buffer::empty(area);
+        Block::default().borders(Borders::ALL).title("▸ a…").render(area, &mut buffer);
+        asciify(&mut buffer);
+        let text: String = buffer.content.iter().map(|c| c.symbol()).collect();
+        assert!(text.is_ascii(), "{text}");
+        assert!(text.starts_with("+> a.------+"), "{text}");
+        assert_eq!(ascii_for("漢"), "?");
+    }
+}
@@ -1,130 +1,1190 @@
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use unicode_segmentation::UnicodeSegmentation;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState,
+    },
 };
 
 use super::centered_rect;
-use crate::app::App;
+use super::hit::{Hit, HitMap};
+use super::settings::selected_setting_y;
+use super::theme::theme;
+use crate::app::{App, OTHER_RES};
+use crate::history::MonitorChange;
+use crate::i18n::{tr, tr_fill};
+use crate::monitor::workspaces_on;
+use crate::state::{DialogButton, SettingField};
+use crate::text;
+
+/// Button hitboxes drawn this frame, in screen coordinates.
+pub type ButtonHitboxes = Vec<(Rect, DialogButton)>;
+
+const YES_NO: [(&str, DialogButton); 2] =
+    [("[Y] Yes", DialogButton::Yes), ("[N] No", DialogButton::No)];
+const ADOPT_REAPPLY: [(&str, DialogButton); 2] =
+    [("[Y] Adopt", DialogButton::Yes), ("[N] Re-apply", DialogButton::No)];
+const OK_CANCEL: [(&str, DialogButton); 2] =
+    [("[OK]", DialogButton::Ok), ("[Cancel]", DialogButton::Cancel)];
+
+/// Draw `buttons`, translated, centered on the single-row `row`, four
+/// columns apart, and return where each one landed. The `focus` button, which
+/// Enter presses, is drawn highlighted.
+fn render_buttons(
+    frame: &mut Frame,
+    row: Rect,
+    buttons: &[(&'static str, DialogButton)],
+    focus: Option<DialogButton>,
+) -> ButtonHitboxes {
+    let total: u16 = buttons.iter().map(|(label, _)| text::width(tr(label)) as u16).sum::<u16>()
+        + 4 * buttons.len().saturating_sub(1) as u16;
+    let mut x = row.x + row.width.saturating_sub(total) / 2;
+    let mut hitboxes = Vec::new();
+    for &(label, button) in buttons {
+        let label = tr(label);
+        let width = text::width(label) as u16;
+        let rect = Rect::new(x, row.y, width, 1).intersection(row);
+        let style = if focus == Some(button) {
+            theme().selection().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            theme().button()
+        };
+        frame.render_widget(Paragraph::new(label).style(style), rect);
+        hitboxes.push((rect, button));
+        x += width + 4;
+    }
+    hitboxes
+}
+
+/// Row `offset` of `inner`, or its last row when the dialog was squeezed.
+fn row_of(inner: Rect, offset: u16) -> Rect {
+    let y = inner.y + offset.min(inner.height.saturating_sub(1));
+    Rect::new(inner.x, y, inner.width, 1)
+}
+
+/// Most options a dropdown shows at once; the rest scroll.
+pub const DROPDOWN_ROWS: usize = 8;
+
+/// Index of the first match shown when `matches` options scroll through
+/// `rows` rows with match `pos` highlighted. The highlight stays centered
+/// once the list scrolls, so this needs no stored state.
+fn dropdown_offset(pos: usize, matches: usize, rows: usize) -> usize {
+    pos.saturating_sub(rows / 2).min(matches.saturating_sub(rows))
+}
 
-pub fn render_dropdown(frame: &mut Frame, area: Rect, app: &App) {
+/// Where the settings dropdown goes inside the settings panel `area`: below
+/// the selected row, aligned with the value column, clamped to fit. `None`
+/// when there is nothing to pick.
+/// Sized for the unfiltered options so typing doesn't make it jump.
+fn dropdown_rect(area: Rect, app: &App) -> Option<Rect> {
     let options = app.get_dropdown_options();
     if options.is_empty() {
-        return;
+        return None;
     }
 
-    let height = (options.len() + 2).min(10) as u16;
-    let width = options.iter().map(|s| s.len()).max().unwrap_or(10) as u16 + 6;
+    let height = (options.len().min(DROPDOWN_ROWS) as u16 + 2).min(area.height);
+    let labels = options.iter().map(|s| text::width(&app.dropdown_label(s)));
+    let width = labels.max().unwrap_or(10) as u16 + 6;
+    let width = width.max(20).min(area.width); // Minimum width for readability
 
     // Position dropdown BELOW the selected setting row, aligned with value column
     let x = area.x + 18; // Align with value column (after label)
-    let y = area.y + 3 + app.selected_setting as u16; // One row below the setting
+    let y = selected_setting_y(area, app) + 1; // One row below the setting
 
-    let dropdown_area = Rect::new(
-        x.min(area.x + area.width - width),
-        y.min(area.y + area.height - height),
-        width.max(20), // Minimum width for readability
+    Some(Rect::new(
+        x.min(area.right() - width),
+        y.min(area.bottom() - height),
+        width,
         height,
-    );
+    ))
+}
+
+pub fn render_dropdown(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    let Some(dropdown_area) = dropdown_rect(area, app) else {
+        return;
+    };
+    hits.push(dropdown_area, Hit::Dropdown);
+    let options = app.get_dropdown_options();
+    let matches = app.dropdown_matches();
+    let rows = dropdown_area.height.saturating_sub(2) as usize;
+    let pos = matches.iter().position(|&i| i == app.dropdown_selection);
+    let offset = dropdown_offset(pos.unwrap_or(0), matches.len(), rows);
 
     // Only clear the exact dropdown area
     frame.render_widget(Clear, dropdown_area);
 
-    let items: Vec<ListItem> = options
+    let title = if app.input_buffer.is_empty() {
+        tr(" Select ").to_string()
+    } else {
+        format!(" /{}█ ", app.input_buffer)
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(title);
+    if SettingField::all()[app.selected_setting] == SettingField::Resolution {
+        let hint = if app.hide_exotic_modes { tr(" Tab: all ") } else { tr(" Tab: fewer ") };
+        block = block.title_bottom(Line::from(hint).right_aligned());
+    }
+    let inner = block.inner(dropdown_area);
+    frame.render_widget(block, dropdown_area);
+
+    if matches.is_empty() {
+        frame.render_widget(Paragraph::new(tr(" No match")).style(theme().muted()), inner);
+        return;
+    }
+
+    for (k, &i) in matches.iter().skip(offset).take(rows).enumerate() {
+        let row = Rect::new(inner.x, inner.y + k as u16, inner.width, 1);
+        hits.push(row.intersection(inner), Hit::DropdownOption(i));
+    }
+    let items: Vec<ListItem> = matches
         .iter()
-        .enumerate()
-        .map(|(i, opt)| {
+        .skip(offset)
+        .take(rows)
+        .map(|&i| {
             let style = if i == app.dropdown_selection {
-                Style::default().bg(Color::Cyan).fg(Color::Black)
+                theme().selection()
+            } else if options[i].contains(OTHER_RES) {
+                // Needs another resolution
+                theme().muted()
             } else {
                 Style::default()
             };
-            ListItem::new(format!(" {} ", opt)).style(style)
+            ListItem::new(format!(" {} ", app.dropdown_label(&options[i]))).style(style)
         })
         .collect();
+    frame.render_widget(List::new(items), inner);
 
-    let list = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(" Select "),
-    );
-
-    let mut state = ListState::default();
-    state.select(Some(app.dropdown_selection));
-    frame.render_stateful_widget(list, dropdown_area, &mut state);
+    if matches.len() > rows {
+        let mut state = ScrollbarState::new(matches.len() - rows).position(offset);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            dropdown_area.inner(Margin::new(0, 1)),
+            &mut state,
+        );
+    }
 }
 
-pub fn render_confirm_apply_dialog(frame: &mut Frame, countdown: u8) {
-    let area = centered_rect(50, 7, frame.area());
+/// Keep-or-revert prompt after an apply, listing what changed on each
+/// monitor above the question so it can be checked before the countdown ends,
+/// then, in warning colors, where Hyprland did not do as asked.
+pub fn render_confirm_apply_dialog(
+    frame: &mut Frame,
+    countdown: u8,
+    changes: &[MonitorChange],
+    mismatches: &[(String, String)],
+    focus: DialogButton,
+) -> ButtonHitboxes {
+    let mut lines: Vec<String> = if changes.is_empty() {
+        vec![tr("Re-applied; no settings changed.").to_string()]
+    } else {
+        changes.iter().map(MonitorChange::summary).collect()
+    };
+    let warn_from = lines.len();
+    if !mismatches.is_empty() {
+        lines.push(tr("Hyprland runs these otherwise:").to_string());
+        lines.extend(mismatches.iter().map(|(name, why)| format!("⚠ {name}: {why}")));
+    }
+    let max_lines = (frame.area().height as usize).saturating_sub(10).max(1);
+    if lines.len() > max_lines {
+        let hidden = lines.len() - max_lines + 1;
+        lines.truncate(max_lines - 1);
+        lines.push(tr("… and {} more").replace("{}", &hidden.to_string()));
+    }
+    let area = centered_rect(60, lines.len() as u16 + 8, frame.area());
 
     frame.render_widget(Clear, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(" Confirm ");
+        .border_style(theme().warn())
+        .title(tr(" Confirm "));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    for (i, line) in lines.iter().enumerate() {
+        let style = if i >= warn_from { theme().warn() } else { theme().muted() };
+        frame.render_widget(
+            Paragraph::new(text::truncate(line, inner.width as usize)).style(style),
+            row_of(inner, i as u16),
+        );
+    }
+    let question = lines.len() as u16 + 1;
     let text = format!(
-        "Do you want to keep these changes?\n\n[Y] Yes    [N] No\n\nAuto-revert in {} seconds",
-        countdown
+        "{}\n\n\n\n{}",
+        tr("Do you want to keep these changes?"),
+        tr("Auto-revert in {} seconds").replace("{}", &countdown.to_string())
     );
 
     frame.render_widget(
         Paragraph::new(text)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White)),
-        inner,
+            .style(theme().text()),
+        Rect::new(inner.x, inner.y + question, inner.width, inner.height.saturating_sub(question)),
     );
+    render_buttons(frame, row_of(inner, question + 2), &YES_NO, Some(focus))
 }
 
-pub fn render_confirm_quit_dialog(frame: &mut Frame) {
+/// Pre-apply prompt listing the scales Hyprland would round, each with the
+/// value it would use instead.
+pub fn render_fix_scale_dialog(
+    frame: &mut Frame,
+    fixes: &[String],
+    focus: DialogButton,
+) -> ButtonHitboxes {
+    let area = centered_rect(60, fixes.len() as u16 + 7, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().warn())
+        .title(tr(" Scale Not Accepted "));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let question = tr("Hyprland needs a whole-pixel logical size and would round:");
+    frame.render_widget(
+        Paragraph::new(text::truncate(question, inner.width as usize)).style(theme().text()),
+        row_of(inner, 0),
+    );
+    for (i, line) in fixes.iter().enumerate() {
+        frame.render_widget(
+            Paragraph::new(text::truncate(line, inner.width as usize)).style(theme().muted()),
+            row_of(inner, i as u16 + 1),
+        );
+    }
+    let row = fixes.len() as u16 + 2;
+    frame.render_widget(
+        Paragraph::new(tr("Use the fixed scales? No applies them as typed."))
+            .alignment(Alignment::Center)
+            .style(theme().text()),
+        row_of(inner, row),
+    );
+    render_buttons(frame, row_of(inner, row + 2), &YES_NO, Some(focus))
+}
+
+/// Hyprland's monitors changed outside hyprmon: what changed, and whether to
+/// keep it in the workspace or put the workspace back.
+pub fn render_reconcile_dialog(
+    frame: &mut Frame,
+    changes: &[MonitorChange],
+    workspace: &str,
+    focus: DialogButton,
+) -> ButtonHitboxes {
+    let area = centered_rect(64, changes.len() as u16 + 8, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().warn())
+        .title(tr(" Changed Outside hyprmon "));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let heading = tr("Hyprland's monitors changed since hyprmon last looked:");
+    frame.render_widget(
+        Paragraph::new(text::truncate(heading, inner.width as usize)).style(theme().text()),
+        row_of(inner, 0),
+    );
+    for (i, change) in changes.iter().enumerate() {
+        frame.render_widget(
+            Paragraph::new(text::truncate(&change.summary(), inner.width as usize))
+                .style(theme().muted()),
+            row_of(inner, i as u16 + 1),
+        );
+    }
+    let row = changes.len() as u16 + 2;
+    let question = tr("Adopt it into {}, or re-apply {} over it?").replace("{}", workspace);
+    frame.render_widget(
+        Paragraph::new(text::truncate(&question, inner.width as usize))
+            .alignment(Alignment::Center)
+            .style(theme().text()),
+        row_of(inner, row),
+    );
+    render_buttons(frame, row_of(inner, row + 2), &ADOPT_REAPPLY, Some(focus))
+}
+
+pub fn render_confirm_quit_dialog(frame: &mut Frame, focus: DialogButton) -> ButtonHitboxes {
     let area = centered_rect(50, 6, frame.area());
 
     frame.render_widget(Clear, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
-        .title(" Warning ");
+        .border_style(theme().danger())
+        .title(tr(" Warning "));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let text = "You have unsaved changes.\nAre you sure you want to quit?\n\n[Y] Yes    [N] No";
+    let text = tr("You have unsaved changes.\nAre you sure you want to quit?");
 
     frame.render_widget(
         Paragraph::new(text)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White)),
+            .style(theme().text()),
         inner,
     );
+    render_buttons(frame, row_of(inner, 3), &YES_NO, Some(focus))
+}
+
+/// Checks the text of a `TextInput` on Enter; the error is shown in the
+/// dialog and the dialog stays open.
+pub type Validator = fn(&str) -> anyhow::Result<()>;
+
+/// One line of editable text behind an input dialog: the cursor and an
+/// optional selection (both counted in graphemes, so an accented letter or
+/// an emoji moves and deletes as one), which characters may be typed, and
+/// the error from the last failed submit.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    text: String,
+    cursor: usize,
+    /// The other end of the selection, when there is one.
+    anchor: Option<usize>,
+    max_chars: usize,
+    accepts: fn(char) -> bool,
+    validator: Option<Validator>,
+    error: Option<String>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new("", usize::MAX)
+    }
+}
+
+impl TextInput {
+    /// `text` with the cursor after it, holding at most `max_chars` graphemes.
+    pub fn new(text: &str, max_chars: usize) -> Self {
+        let mut input = Self {
+            text: String::new(),
+            cursor: 0,
+            anchor: None,
+            max_chars,
+            accepts: |c| !c.is_control(),
+            validator: None,
+            error: None,
+        };
+        input.set_text(text);
+        input
+    }
+
+    /// Only let characters through that `accepts` allows, the text so far
+    /// included.
+    pub fn accepting(mut self, accepts: fn(char) -> bool) -> Self {
+        self.accepts = accepts;
+        let text = std::mem::take(&mut self.text);
+        self.set_text(&text);
+        self
+    }
+
+    /// Check the text with `validator` before `submit` hands it out.
+    pub fn validated(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Selected graphemes as a range, empty selections excluded.
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        let anchor = self.anchor.filter(|&a| a != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    /// Replace the whole text, cursor at the end. The filters still apply.
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        self.cursor = 0;
+        self.anchor = None;
+        self.error = None;
+        self.insert(text);
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text("");
+    }
+
+    /// Select the whole text, so typing replaces it.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.len();
+    }
+
+    fn byte_at(&self, idx: usize) -> usize {
+        text::grapheme_byte(&self.text, idx)
+    }
+
+    fn len(&self) -> usize {
+        text::grapheme_count(&self.text)
+    }
+
+    /// Remove the selection; whether there was one.
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.anchor = None;
+            return false;
+        };
+        let (start, end) = (self.byte_at(range.start), self.byte_at(range.end));
+        self.text.replace_range(start..end, "");
+        self.cursor = range.start;
+        self.anchor = None;
+        true
+    }
+
+    /// Type or paste `text` at the cursor, over the selection. Characters the
+    /// input does not accept are dropped, and so is whatever does not fit.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let room = self.max_chars.saturating_sub(self.len());
+        let accepted: String = text.chars().filter(|&c| (self.accepts)(c)).collect();
+        let typed = text::take_graphemes(&accepted, room);
+        let end = self.byte_at(self.cursor) + typed.len();
+        self.text.insert_str(end - typed.len(), typed);
+        // A combining mark joins the grapheme before it, so count again
+        self.cursor = text::grapheme_count(&self.text[..end]).min(self.len());
+        self.error = None;
+    }
+
+    fn move_to(&mut self, pos: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = pos.min(self.len());
+    }
+
+    /// Edit with `code`: typing, Backspace/Delete, ←/→/Home/End (Shift
+    /// selects), Ctrl+A selects everything and Ctrl+U clears. Returns
+    /// whether the key was used, so Enter, Esc and Tab fall through.
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let select = modifiers.contains(KeyModifiers::SHIFT);
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        match code {
+            KeyCode::Char('a') if ctrl => self.select_all(),
+            KeyCode::Char('u') if ctrl => self.clear(),
+            KeyCode::Char(c) if !ctrl && !modifiers.contains(KeyModifiers::ALT) => {
+                self.insert(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Backspace | KeyCode::Delete => {
+                if !self.delete_selection() {
+                    let at = match code {
+                        KeyCode::Backspace if self.cursor > 0 => self.cursor - 1,
+                        KeyCode::Delete if self.cursor < self.len() => self.cursor,
+                        _ => return true,
+                    };
+                    let (start, end) = (self.byte_at(at), self.byte_at(at + 1));
+                    self.text.replace_range(start..end, "");
+                    self.cursor = at;
+                }
+                self.error = None;
+            }
+            KeyCode::Left => {
+                let to = match self.selection() {
+                    Some(range) if !select => range.start,
+                    _ => self.cursor.saturating_sub(1),
+                };
+                self.move_to(to, select);
+            }
+            KeyCode::Right => {
+                let to = match self.selection() {
+                    Some(range) if !select => range.end,
+                    _ => self.cursor + 1,
+                };
+                self.move_to(to, select);
+            }
+            KeyCode::Home => self.move_to(0, select),
+            KeyCode::End => self.move_to(usize::MAX, select),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Show `error` under the text until the next edit, for checks that
+    /// need more than the text itself (e.g. a name already in use).
+    pub fn set_error(&mut self, error: impl std::fmt::Display) {
+        self.error = Some(error.to_string());
+    }
+
+    /// The text, if it passes the validator; otherwise the error is kept
+    /// for the dialog to show.
+    pub fn submit(&mut self) -> Option<String> {
+        match self.validator.map_or(Ok(()), |validate| validate(&self.text)) {
+            Ok(()) => Some(self.text.clone()),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                None
+            }
+        }
+    }
+}
+
+/// The input line of a `TextInput` in `width` columns: `> ` then the text,
+/// scrolled to keep the cursor in view, with the selection highlighted and
+/// the cursor as a reversed cell (a block past the end).
+pub fn text_input_line(input: &TextInput, width: u16) -> Line<'static> {
+    let graphemes: Vec<&str> = input.text().graphemes(true).collect();
+    let room = (width as usize).saturating_sub(2).max(1);
+    // Scroll back from the cursor cell until the next grapheme would not fit
+    let mut start = input.cursor().min(graphemes.len());
+    let mut used = graphemes.get(start).map_or(1, |g| text::width(g));
+    while start > 0 && used + text::width(graphemes[start - 1]) <= room {
+        start -= 1;
+        used += text::width(graphemes[start]);
+    }
+    let selection = input.selection().unwrap_or(0..0);
+    let mut spans = vec![Span::styled("> ", theme().accent())];
+    let mut used = 0;
+    for (i, g) in graphemes.iter().enumerate().skip(start) {
+        used += text::width(g);
+        if used > room {
+            break;
+        }
+        let style = if selection.contains(&i) {
+            theme().selection()
+        } else if i == input.cursor() && selection.is_empty() {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            theme().text()
+        };
+        spans.push(Span::styled(g.to_string(), style));
+    }
+    if input.cursor() == graphemes.len() {
+        spans.push(Span::styled("█", theme().text()));
+    }
+    Line::from(spans)
 }
 
-pub fn render_input_dialog(frame: &mut Frame, title: &str, input: &str, hint: &str) {
-    let area = centered_rect(50, 5, frame.area());
+pub fn render_input_dialog(
+    frame: &mut Frame,
+    title: &str,
+    input: &TextInput,
+    hint: &str,
+) -> ButtonHitboxes {
+    let area = centered_rect(50, 7, frame.area());
 
     frame.render_widget(Clear, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(format!(" {} ", title));
+        .border_style(theme().accent())
+        .title(format!(" {} ", title))
+        .title_bottom(Line::from(tr(" Enter Confirm | Esc Cancel ")).centered());
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let text = format!(
-        "{}\n\n> {}█\n\n{}",
-        hint, input, "Enter to confirm | Esc to cancel"
+    frame.render_widget(
+        Paragraph::new(hint.to_string())
+            .alignment(Alignment::Center)
+            .style(theme().text()),
+        row_of(inner, 0),
     );
+    frame.render_widget(
+        Paragraph::new(text_input_line(input, inner.width)).alignment(Alignment::Center),
+        row_of(inner, 2),
+    );
+    if let Some(error) = input.error() {
+        frame.render_widget(
+            Paragraph::new(error.to_string())
+                .alignment(Alignment::Center)
+                .style(theme().danger()),
+            row_of(inner, 3),
+        );
+    }
+    render_buttons(frame, row_of(inner, 4), &OK_CANCEL, None)
+}
+
+/// Yes/No question such as "Delete 'Docked'?".
+pub fn render_question_dialog(
+    frame: &mut Frame,
+    title: &str,
+    question: &str,
+    focus: DialogButton,
+) -> ButtonHitboxes {
+    let area = centered_rect(50, 6, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().danger())
+        .title(format!(" {} ", title));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
     frame.render_widget(
-        Paragraph::new(text)
+        Paragraph::new(question)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White)),
+            .style(theme().text()),
         inner,
     );
+    render_buttons(frame, row_of(inner, 2), &YES_NO, Some(focus))
+}
+
+/// Lines shown by the history viewer: every entry's summary, newest first.
+pub fn history_lines(app: &App) -> Vec<String> {
+    app.history_entries
+        .iter()
+        .flat_map(|entry| entry.lines())
+        .collect()
+}
+
+pub fn render_history_dialog(frame: &mut Frame, app: &App, scroll: usize) {
+    let height = frame.area().height.saturating_sub(4).max(5);
+    let area = centered_rect(80, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(tr(" Change History "))
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = history_lines(app);
+    if lines.is_empty() {
+        frame.render_widget(
+            Paragraph::new(tr("No changes recorded yet."))
+                .alignment(Alignment::Center)
+                .style(theme().muted()),
+            inner,
+        );
+        return;
+    }
+
+    let text: Vec<Line> = lines
+        .iter()
+        .skip(scroll)
+        .map(|l| {
+            if l.starts_with(' ') {
+                Line::from(l.as_str())
+            } else {
+                Line::from(l.as_str()).style(theme().warn().bold())
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+/// Lines of the config error list: each error flush left, the generated
+/// line it points at indented beneath it.
+pub fn config_error_lines(app: &App) -> Vec<String> {
+    let mut lines = Vec::new();
+    for error in &app.config_errors {
+        lines.push(match &error.file {
+            Some((path, line)) => {
+                let file = Path::new(path).file_name().map(|f| f.to_string_lossy());
+                format!("{}:{line}: {}", file.unwrap_or_default(), error.message)
+            }
+            None => error.message.clone(),
+        });
+        if let (Some((_, line)), Some(source)) = (&error.file, &error.source) {
+            lines.push(format!("  {line:>4} │ {source}"));
+        }
+    }
+    lines
+}
+
+/// Scrollable list of what Hyprland rejected after an apply, offending
+/// generated lines highlighted.
+pub fn render_config_errors_dialog(frame: &mut Frame, app: &App, scroll: usize) {
+    let height = frame.area().height.saturating_sub(4).max(5);
+    let area = centered_rect(80, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().danger())
+        .title(tr(" Hyprland Config Errors "))
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Keep or revert ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let text: Vec<Line> = config_error_lines(app)
+        .into_iter()
+        .skip(scroll)
+        .map(|l| {
+            if l.starts_with(' ') {
+                Line::from(l).style(theme().danger().bold())
+            } else {
+                Line::from(l).style(theme().text())
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+/// Read-only view of the monitors.conf an apply would write; lines that are
+/// not on disk yet are highlighted.
+pub fn render_config_preview_dialog(frame: &mut Frame, app: &App, scroll: usize) {
+    let height = frame.area().height.saturating_sub(4).max(5);
+    let area = centered_rect(90, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let changed = app.config_preview.iter().filter(|l| l.starts_with('+')).count();
+    let title = format!(
+        " {} ({}) ",
+        tr("Generated monitors.conf"),
+        tr("{} lines differ from disk").replace("{}", &changed.to_string())
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(title)
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let text: Vec<Line> = app
+        .config_preview
+        .iter()
+        .skip(scroll)
+        .map(|l| {
+            if l.starts_with('+') {
+                Line::from(l.as_str()).style(theme().warn().bold())
+            } else if l.trim_start().starts_with('#') {
+                Line::from(l.as_str()).style(theme().muted())
+            } else {
+                Line::from(l.as_str())
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+/// Details dialog text: headings flush left, facts indented beneath them.
+pub fn monitor_details_lines(app: &App) -> Vec<String> {
+    let Some(d) = &app.monitor_details else {
+        return Vec::new();
+    };
+    let or_unknown = |s: &str| if s.is_empty() { tr("unknown").to_string() } else { s.to_string() };
+    let on_off = |state: Option<bool>| match state {
+        Some(true) => tr("on"),
+        Some(false) => tr("off"),
+        None => tr("unknown"),
+    };
+    // Padded by display width, which `{:<14}` does not know
+    let fact = |label: &'static str, value: &str| {
+        let label = tr(label);
+        format!("  {label}{}{value}", " ".repeat(14usize.saturating_sub(text::width(label)).max(1)))
+    };
+    let size = match (d.physical_size, d.diagonal_and_dpi()) {
+        (Some((w, h)), Some((diagonal, dpi))) => {
+            format!("{w} × {h} mm ({diagonal:.1}″, {dpi:.0} DPI)")
+        }
+        (Some((w, h)), None) => format!("{w} × {h} mm"),
+        (None, _) => tr("unknown").to_string(),
+    };
+    let workspaces = match workspaces_on(&app.hypr_workspaces, &d.name) {
+        none if none.is_empty() => tr("none").to_string(),
+        labels => format!("{labels}  {}", tr("(windows in parentheses)")),
+    };
+    let mut lines = vec![
+        format!("{} — {}", d.name, or_unknown(&d.description)),
+        fact("Connector", &d.name),
+        fact("Make", &or_unknown(&d.make)),
+        fact("Model", &or_unknown(&d.model)),
+        fact("Serial", &or_unknown(&d.serial)),
+        fact("Physical size", &size),
+        fact("Current mode", &d.current_mode),
+        fact("DPMS", on_off(d.dpms)),
+        fact("VRR", on_off(d.vrr)),
+        fact("Workspaces", &workspaces),
+    ];
+    let live = app.monitors.iter().find(|m| m.name == d.name);
+    match live.and_then(|m| app.presets.find(m)) {
+        Some(preset) => {
+            let summary = format!("{}  {}", preset.summary(), tr("(s uses it)"));
+            lines.push(fact("Preset", &summary));
+            lines.extend(preset.notes.iter().map(|note| format!("  {:<14}{note}", "")));
+        }
+        None => lines.push(fact("Preset", tr("none known"))),
+    }
+    lines.push(tr_fill("Modes ({})", &[&d.available_modes.len()]));
+    // "2560x1440@59.95Hz" → ("2560x1440", 59.95), so 60Hz and 60.00Hz agree
+    let parse = |mode: &str| {
+        let (res, rate) = mode.split_once('@')?;
+        Some((res.to_string(), rate.trim_end_matches("Hz").parse::<f64>().ok()?))
+    };
+    let current = parse(&d.current_mode);
+    lines.extend(d.available_modes.iter().map(|mode| {
+        let is_current = match (parse(mode), &current) {
+            (Some((res, rate)), Some((cur_res, cur_rate))) => {
+                res == *cur_res && (rate - cur_rate).abs() < 0.01
+            }
+            _ => false,
+        };
+        format!("  {} {mode}", if is_current { "•" } else { " " })
+    }));
+    lines.push("EDID".to_string());
+    match &d.edid {
+        Some(edid) => lines.extend(edid.report(&d.available_modes)),
+        None => lines.push(format!("  {}", tr("not readable from /sys/class/drm"))),
+    }
+    lines
+}
+
+/// Scrollable hardware facts about one monitor.
+pub fn render_monitor_details_dialog(frame: &mut Frame, app: &App, scroll: usize) {
+    let height = frame.area().height.saturating_sub(4).max(5);
+    let area = centered_rect(70, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(tr(" Monitor Details "))
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let text: Vec<Line> = monitor_details_lines(app)
+        .into_iter()
+        .skip(scroll)
+        .map(|l| {
+            if l.starts_with(' ') {
+                Line::from(l)
+            } else {
+                Line::from(l).style(theme().warn().bold())
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+/// Live-vs-saved comparison: one row per deviating field, highlighted row
+/// taken from `dropdown_selection`.
+pub fn render_compare_dialog(frame: &mut Frame, app: &App) {
+    let rows = app.compare_rows();
+    let height = (rows.len() as u16 + 5).clamp(7, frame.area().height);
+    let area = centered_rect(70, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(format!(" Live vs '{}' ", app.active_workspace_name()))
+        .title_bottom(
+            Line::from(" ↑↓ Select | > Live→Saved | < Saved→Live | Esc Close ").centered(),
+        );
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(tr("Live matches the saved workspace."))
+                .alignment(Alignment::Center)
+                .style(theme().ok()),
+            inner,
+        );
+        return;
+    }
+
+    let header = ListItem::new(format!(
+        " {:<24} {:<14} {:<16} {:<16}",
+        "Monitor", "Field", "Live", "Saved"
+    ))
+    .style(theme().muted().bold());
+    let items: Vec<ListItem> = std::iter::once(header)
+        .chain(rows.iter().enumerate().map(|(i, diff)| {
+            let field = diff
+                .field
+                .map(|f| f.label().trim_end_matches(':'))
+                .unwrap_or("(whole)");
+            let style = if i == app.dropdown_selection {
+                theme().selection()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(
+                " {:<24} {:<14} {:<16} {:<16}",
+                text::truncate(&diff.key, 24),
+                field,
+                diff.live,
+                diff.saved
+            ))
+            .style(style)
+        }))
+        .collect();
+    frame.render_widget(List::new(items), inner);
+}
+
+/// Kept monitors.conf versions on top, what restoring the highlighted one
+/// would change below: `+` lines it brings back, `-` lines it drops.
+pub fn render_rollback_dialog(frame: &mut Frame, app: &App) {
+    let height = frame.area().height.saturating_sub(4).max(7);
+    let area = centered_rect(80, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(format!(" {} ", tr("Earlier monitors.conf")))
+        .title_bottom(Line::from(tr(" ↑↓ Pick | Enter Restore | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let options = app.rollback_options();
+    let list_rows = (options.len() as u16).min(inner.height / 3).max(1);
+    let [list_area, diff_area] =
+        Layout::vertical([Constraint::Length(list_rows + 1), Constraint::Min(0)]).areas(inner);
+
+    let items: Vec<ListItem> = options
+        .iter()
+        .map(|opt| ListItem::new(format!(" {opt} ")))
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme().selection())
+        .block(Block::default().borders(Borders::BOTTOM).border_style(theme().muted()));
+    let mut state = ListState::default();
+    state.select(Some(app.dropdown_selection));
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    let diff = app.rollback_diff();
+    if diff.is_empty() {
+        let same = Paragraph::new(tr("Same as the current file.")).style(theme().ok());
+        frame.render_widget(same, diff_area);
+        return;
+    }
+    let text: Vec<Line> = diff
+        .iter()
+        .map(|l| {
+            let style = if l.starts_with('+') { theme().warn() } else { theme().danger() };
+            Line::from(l.as_str()).style(style)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(text), diff_area);
+}
+
+/// Centered single-choice list (e.g. "Save as…" workspace picker).
+pub fn render_picker_dialog(frame: &mut Frame, title: &str, options: &[String], selected: usize) {
+    render_list_dialog(frame, title, options, selected, " Enter Select | Esc Cancel ");
+}
+
+/// [`render_picker_dialog`] for lists where Space ticks several rows.
+pub fn render_checklist_dialog(
+    frame: &mut Frame,
+    title: &str,
+    options: &[String],
+    selected: usize,
+) {
+    render_list_dialog(frame, title, options, selected, " Space Pick | Enter Import | Esc Cancel ");
+}
+
+fn render_list_dialog(
+    frame: &mut Frame,
+    title: &str,
+    options: &[String],
+    selected: usize,
+    footer: &'static str,
+) {
+    let height = (options.len() as u16 + 4).min(frame.area().height);
+    let area = centered_rect(40, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = options
+        .iter()
+        .enumerate()
+        .map(|(i, opt)| {
+            let style = if i == selected {
+                theme().selection()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(" {} ", opt)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme().accent())
+            .title(format!(" {} ", title))
+            .title_bottom(Line::from(tr(footer)).centered()),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// `:` command palette: the query on top, the best matches below it.
+pub fn render_command_palette(frame: &mut Frame, app: &App) {
+    let matches = crate::palette::palette_matches(app, &app.input_buffer);
+    let screen = frame.area();
+    let width = 60.min(screen.width);
+    let height = (matches.len() as u16 + 5).clamp(6, 16).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + screen.height.saturating_sub(height) / 4,
+        width,
+        height,
+    );
+
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(tr(" Command Palette "))
+        .title_bottom(Line::from(tr(" ↑↓ Select | Enter Run | Esc Close ")).centered());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(": ", theme().accent()),
+            Span::raw(format!("{}█", app.input_buffer)),
+        ])),
+        rows[0],
+    );
+
+    if matches.is_empty() {
+        let empty = Paragraph::new(tr(" No matching command")).style(theme().muted());
+        frame.render_widget(empty, rows[1]);
+        return;
+    }
+    let items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let style = if i == app.dropdown_selection {
+                theme().selection()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(" {} ", entry.label)).style(style)
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(app.dropdown_selection));
+    frame.render_stateful_widget(List::new(items), rows[1], &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) {
+        assert!(input.handle_key(code, modifiers));
+    }
+
+    #[test]
+    fn text_input_edits_at_the_cursor_and_over_the_selection() {
+        let mut input = TextInput::new("Dockd", 8);
+        key(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert_eq!((input.text(), input.cursor()), ("Docked", 5));
+
+        key(&mut input, KeyCode::Home, KeyModifiers::SHIFT);
+        assert_eq!(input.selection(), Some(0..5));
+        input.insert("Büro "); // typed or pasted over the selection
+        assert_eq!((input.text(), input.cursor()), ("Büro d", 5));
+        key(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.text(), "Büro");
+
+        input.insert(" 1234567"); // only what fits the cap
+        assert_eq!(input.text(), "Büro 123");
+        key(&mut input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        key(&mut input, KeyCode::Right, KeyModifiers::NONE); // collapses to the end
+        assert_eq!((input.selection(), input.cursor()), (None, 8));
+        key(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(input.text(), "");
+        assert!(!input.handle_key(KeyCode::Enter, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn text_input_filters_characters_and_keeps_the_validation_error() {
+        let mut input = TextInput::new("12a", 6)
+            .accepting(|c| c.is_ascii_digit())
+            .validated(|s| match s.len() {
+                0..=2 => anyhow::bail!("too short"),
+                _ => Ok(()),
+            });
+        assert_eq!(input.text(), "12");
+        assert_eq!(input.submit(), None);
+        assert_eq!(input.error(), Some("too short"));
+        input.insert("x3");
+        assert_eq!(input.error(), None); // editing clears it
+        assert_eq!(input.submit().as_deref(), Some("123"));
+    }
+
+    #[test]
+    fn text_input_moves_and_deletes_whole_graphemes() {
+        let mut input = TextInput::new("Cafe\u{301} 🇻🇳", 7);
+        assert_eq!(input.cursor(), 6);
+        key(&mut input, KeyCode::Backspace, KeyModifiers::NONE); // the whole flag
+        assert_eq!(input.text(), "Cafe\u{301} ");
+        key(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        key(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+        assert_eq!(input.selection(), Some(3..4)); // "é" with its accent
+        input.insert("🖥️🖥️🖥️");
+        assert_eq!(input.text(), "Caf🖥️🖥️🖥️ ");
+        input.insert("x"); // at the cap of 7
+        assert_eq!((input.text().ends_with("️ "), input.cursor()), (true, 6));
+
+        let mut input = TextInput::new("Cafe", 8);
+        input.insert("\u{301}"); // joins the grapheme before it
+        assert_eq!((input.text(), input.cursor()), ("Cafe\u{301}", 4));
+    }
+
+    #[test]
+    fn input_line_scrolls_by_display_width() {
+        let input = TextInput::new("会議室モニター", 20);
+        assert_eq!(text_input_line(&input, 9).to_string(), "> ニター█");
+        assert_eq!(text_input_line(&input, 40).to_string(), "> 会議室モニター█");
+    }
+
+    #[test]
+    fn input_line_scrolls_to_keep_the_cursor_in_view() {
+        let mut input = TextInput::new("abcdefghij", 20);
+        assert_eq!(text_input_line(&input, 8).to_string(), "> fghij█");
+        key(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(text_input_line(&input, 8).to_string(), "> abcdef");
+        assert_eq!(text_input_line(&input, 40).to_string(), "> abcdefghij");
+    }
 }
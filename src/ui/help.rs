@@ -1,15 +1,20 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use super::theme::theme;
 use crate::app::App;
-use crate::state::MainTab;
+use crate::clock;
+use crate::i18n::tr;
+use crate::state::{FocusPanel, MainTab};
 
 pub fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(theme().muted())
+        .title(status_line(app))
+        .title(focus_hint(app));
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -22,72 +27,336 @@ pub fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), inner);
 }
 
+/// Status segment on the help bar's top border: active workspace, connected
+/// monitors, unsaved changes, IPC state, paused auto-apply and game mode while
+/// they are on, and when the workspace was last applied.
+pub(super) fn status_line(app: &App) -> Line<'static> {
+    let sep = || Span::styled(" │ ", sep_style());
+    let count = app.monitors.len();
+    let workspace = app.monitor_db.current_workspace();
+    let applied = match workspace.and_then(|ws| ws.last_applied) {
+        Some(t) => format!("{} {}", tr("applied"), clock::format_local(t)),
+        None => tr("never applied").to_string(),
+    };
+    let mut spans = vec![
+        Span::raw(" "),
+        Span::styled(
+            workspace.map(|ws| ws.name.clone()).unwrap_or_default(),
+            theme().saved().add_modifier(Modifier::BOLD),
+        ),
+        sep(),
+        Span::styled(
+            format!("{count} {}", tr(if count == 1 { "monitor" } else { "monitors" })),
+            desc_style(),
+        ),
+        sep(),
+        if app.has_changes {
+            Span::styled(tr("● unsaved"), theme().warn())
+        } else {
+            Span::styled(tr("no changes"), sep_style())
+        },
+        sep(),
+        if app.ipc_connected {
+            Span::styled("IPC ✓", theme().ok())
+        } else {
+            Span::styled("IPC ✗", theme().danger())
+        },
+    ];
+    if !app.auto_apply {
+        spans.push(sep());
+        spans.push(Span::styled(tr("auto-apply paused"), theme().warn()));
+    }
+    if let Some(name) = &app.game_mode {
+        spans.push(sep());
+        spans.push(Span::styled(format!("{} {name}", tr("game mode")), theme().warn()));
+    }
+    if let Some(name) = &app.presenting {
+        spans.push(sep());
+        spans.push(Span::styled(format!("{} {name}", tr("presenting")), theme().warn()));
+    }
+    if let Some(name) = &app.projector {
+        spans.push(sep());
+        spans.push(Span::styled(format!("{name} {}", tr("forced 1080p60")), theme().warn()));
+    }
+    spans.extend([sep(), Span::styled(applied, sep_style()), Span::raw(" ")]);
+    Line::from(spans)
+}
+
+/// Right end of the help bar's top border: the panel the arrow-less keys
+/// (Enter, Tab) act on, and what Enter does there.
+pub(super) fn focus_hint(app: &App) -> Line<'static> {
+    let (panel, enter) = match app.focus_panel {
+        FocusPanel::Arrangement => (tr("Arrangement"), tr("Details")),
+        FocusPanel::Settings => (tr("Settings"), tr("Change")),
+    };
+    let mut spans = vec![
+        Span::styled(format!(" {} ", tr("Focus:")), sep_style()),
+        Span::styled(panel, theme().accent().add_modifier(Modifier::BOLD)),
+    ];
+    if app.main_tab == MainTab::Live {
+        spans.push(Span::styled(format!(" · ⏎ {enter}"), desc_style()));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans).right_aligned()
+}
+
+/// Every keybinding, grouped by the context it applies in. Keep in sync with
+/// `input.rs` and the README tables.
+const HELP_SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Everywhere",
+        &[
+            ("?", "Show this help"),
+            (":", "Command palette (fuzzy search over all actions)"),
+            ("1 / 2", "Switch to the Live / Saved tab"),
+            ("Tab", "Switch between Arrangement and Settings panels"),
+            ("Alt+1…9", "Apply the Nth saved workspace"),
+            ("a", "Apply (with a 15s confirm / revert countdown)"),
+            ("Ctrl+l", "Lock / unlock the active workspace"),
+            ("Ctrl+p", "Pause / resume auto-apply on hotplug (this session)"),
+            ("!", "Safe mode: every monitor at its preferred mode, 100%, side by side"),
+            ("Ctrl+o", "Presentation: mirror the laptop on every screen / restore"),
+            ("t", "History of applied changes"),
+            ("g", "Message log (every status message this session)"),
+            ("i", "Identify monitors (number on each screen)"),
+            ("y", "Copy the monitors.conf that a would write"),
+            ("Ctrl+g", "View the monitors.conf that a would write (+ marks changes)"),
+            ("Ctrl+b", "Restore an earlier monitors.conf (each kept with its diff)"),
+            ("r", "Refresh monitor list"),
+            ("q", "Quit"),
+        ],
+    ),
+    (
+        "Live tab",
+        &[
+            ("← →", "Select monitor (from either panel)"),
+            ("Enter", "Monitor details: serial, size, modes, DPMS/VRR (Arrangement)"),
+            ("Shift+← →", "Move monitor left / right"),
+            ("Esc", "Cancel a mouse drag (so does leaving the panel)"),
+            ("↑ ↓", "Select setting (from either panel)"),
+            ("Enter", "Open the dropdown for a setting, or type a position (Settings)"),
+            ("Tab", "In the Resolution dropdown: hide / show modes under 720p or odd shapes"),
+            ("+ / -", "Step the selected setting (or mouse wheel over it)"),
+            ("z", "Fold / unfold the selected setting's group (or click its heading)"),
+            ("p", "Make the selected monitor primary"),
+            ("m", "Toggle whether hyprmon manages the monitor"),
+            ("n", "Nickname the monitor, e.g. Left Dell (shown instead of its model)"),
+            ("w", "Focus the monitor in Hyprland (◉ marks the focused one)"),
+            ("X", "Swap its workspaces with another monitor's"),
+            ("b", "Game mode: top refresh rate + VRR here, VRR off elsewhere; b restores"),
+            ("Ctrl+f", "Projector: force 1920x1080@60 here; Ctrl+f or unplugging restores"),
+            ("s", "Use the known-good preset for the monitor's model (✦ shows one)"),
+            ("Ctrl+d", "Scale every monitor by its pixel density, to refine from"),
+            ("S", "Save the live layout as…"),
+            ("u / Ctrl+r", "Undo / redo layout edits"),
+            ("v", "Compare Live with the active workspace"),
+            ("f", "Edit the fallback rule for unknown monitors"),
+        ],
+    ),
+    (
+        "Saved tab",
+        &[
+            ("[ / ]", "Previous / next workspace"),
+            ("{ / }", "Move workspace left / right (or drag its tab)"),
+            ("p", "Pin / unpin workspace"),
+            ("n", "New workspace"),
+            ("R", "Rename workspace"),
+            ("c", "Duplicate workspace"),
+            ("D", "Delete workspace"),
+            ("x / Del", "Remove the selected monitor"),
+            ("i", "Import monitors from another workspace (Space picks several)"),
+            ("e", "Load workspace into Live as unsaved edits"),
+            ("o", "Edit workspace notes"),
+            ("m", "Toggle manual-only"),
+            ("w", "Turn the selected monitor off / on when applying (DPMS)"),
+        ],
+    ),
+    (
+        "Dialogs",
+        &[
+            ("Enter / Esc", "Confirm / cancel input and pickers"),
+            ("y / n", "Answer confirmations (Keep changes? Delete? Quit?)"),
+            ("y / n", "Changed outside hyprmon: adopt it / re-apply the workspace"),
+            ("Tab / ← →", "Confirmations: highlight Yes or No (Enter presses it)"),
+            ("Tab", "Next suggested name (New workspace, Save as new)"),
+            ("↑ ↓ / j k", "Move in lists, scroll history and help"),
+            ("> / <", "Compare: save live value / pull saved value"),
+            ("PgUp / PgDn", "Dropdown: page through long lists (Home / End jump)"),
+            ("typing", "Dropdown: filter options (Backspace edits, Esc clears)"),
+            ("← → Home End", "Text fields: move (Shift selects, Ctrl+A all, Ctrl+U clears)"),
+        ],
+    ),
+];
+
+/// Lines of the `?` overlay: a heading per context, then `key  action` rows.
+pub fn help_overlay_lines() -> Vec<Line<'static>> {
+    let width = HELP_SECTIONS
+        .iter()
+        .flat_map(|(_, keys)| keys.iter())
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for (i, (title, keys)) in HELP_SECTIONS.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        lines.push(Line::styled(tr(title), theme().warn().bold()));
+        for (key, action) in keys.iter() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {key:<width$}  "), key_style()),
+                Span::styled(tr(action), desc_style()),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Full-screen keybinding reference, scrolled to line `scroll`.
+pub fn render_help_overlay(frame: &mut Frame, scroll: usize) {
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(tr(" Keybindings "))
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = help_overlay_lines().into_iter().skip(scroll).collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
 fn key_style() -> Style {
-    Style::default()
-        .fg(Color::Cyan)
-        .add_modifier(Modifier::BOLD)
+    theme().accent().add_modifier(Modifier::BOLD)
 }
 
 fn desc_style() -> Style {
-    Style::default().fg(Color::White)
+    theme().text()
 }
 
 fn sep_style() -> Style {
-    Style::default().fg(Color::DarkGray)
+    theme().muted()
+}
+
+/// Translated description after a key in the help bar.
+fn desc(text: &'static str) -> Span<'static> {
+    Span::styled(format!(" {}", tr(text)), desc_style())
 }
 
 fn create_live_help() -> Vec<Span<'static>> {
     vec![
+        Span::styled("?", key_style()),
+        desc("Help"),
+        Span::styled("  │  ", sep_style()),
         Span::styled("1", key_style()),
         Span::styled("/", sep_style()),
         Span::styled("2", key_style()),
-        Span::styled(" Tab", desc_style()),
+        desc("Tab"),
         Span::styled("  │  ", sep_style()),
         Span::styled("←→", key_style()),
-        Span::styled(" Select", desc_style()),
+        desc("Select"),
         Span::styled("  ", sep_style()),
         Span::styled("⇧←→", key_style()),
-        Span::styled(" Move", desc_style()),
+        desc("Move"),
         Span::styled("  │  ", sep_style()),
         Span::styled("P", key_style()),
-        Span::styled(" Primary", desc_style()),
+        desc("Primary"),
+        Span::styled("  ", sep_style()),
+        Span::styled("M", key_style()),
+        desc("Manage"),
         Span::styled("  ", sep_style()),
         Span::styled("I", key_style()),
-        Span::styled(" Identify", desc_style()),
+        desc("Identify"),
         Span::styled("  │  ", sep_style()),
         Span::styled("R", key_style()),
-        Span::styled(" Refresh", desc_style()),
+        desc("Refresh"),
         Span::styled("  ", sep_style()),
         Span::styled("A", key_style()),
-        Span::styled(" Apply", desc_style()),
+        desc("Apply"),
+        Span::styled("  ", sep_style()),
+        Span::styled("⇧S", key_style()),
+        desc("Save as"),
+        Span::styled("  ", sep_style()),
+        Span::styled("F", key_style()),
+        desc("Fallback"),
+        Span::styled("  ", sep_style()),
+        Span::styled("T", key_style()),
+        desc("History"),
+        Span::styled("  ", sep_style()),
+        Span::styled("V", key_style()),
+        desc("Compare"),
+        Span::styled("  ", sep_style()),
+        Span::styled("U", key_style()),
+        Span::styled("/", sep_style()),
+        Span::styled("^R", key_style()),
+        desc("Undo/Redo"),
         Span::styled("  │  ", sep_style()),
         Span::styled("Q", key_style()),
-        Span::styled(" Quit", desc_style()),
+        desc("Quit"),
     ]
 }
 
 fn create_saved_help() -> Vec<Span<'static>> {
     vec![
+        Span::styled("?", key_style()),
+        desc("Help"),
+        Span::styled("  │  ", sep_style()),
         Span::styled("1", key_style()),
         Span::styled("/", sep_style()),
         Span::styled("2", key_style()),
-        Span::styled(" Tab", desc_style()),
+        desc("Tab"),
         Span::styled("  │  ", sep_style()),
         Span::styled("[", key_style()),
         Span::styled("/", sep_style()),
         Span::styled("]", key_style()),
-        Span::styled(" Workspace", desc_style()),
+        desc("Workspace"),
+        Span::styled("  ", sep_style()),
+        Span::styled("{", key_style()),
+        Span::styled("/", sep_style()),
+        Span::styled("}", key_style()),
+        desc("Reorder"),
+        Span::styled("  ", sep_style()),
+        Span::styled("P", key_style()),
+        desc("Pin"),
         Span::styled("  │  ", sep_style()),
         Span::styled("N", key_style()),
-        Span::styled(" New", desc_style()),
+        desc("New"),
         Span::styled("  ", sep_style()),
         Span::styled("R", key_style()),
-        Span::styled(" Rename", desc_style()),
+        desc("Rename"),
+        Span::styled("  ", sep_style()),
+        Span::styled("C", key_style()),
+        desc("Copy"),
+        Span::styled("  ", sep_style()),
+        Span::styled("O", key_style()),
+        desc("Notes"),
         Span::styled("  ", sep_style()),
         Span::styled("D", key_style()),
-        Span::styled(" Delete", desc_style()),
+        desc("Delete"),
+        Span::styled("  ", sep_style()),
+        Span::styled("X", key_style()),
+        desc("Remove monitor"),
+        Span::styled("  │  ", sep_style()),
+        Span::styled("A", key_style()),
+        desc("Apply"),
+        Span::styled("  ", sep_style()),
+        Span::styled("⌥1-9", key_style()),
+        desc("Apply Nth"),
+        Span::styled("  ", sep_style()),
+        Span::styled("E", key_style()),
+        desc("Edit in Live"),
+        Span::styled("  ", sep_style()),
+        Span::styled("M", key_style()),
+        desc("Manual-only"),
+        Span::styled("  ", sep_style()),
+        Span::styled("^L", key_style()),
+        desc("Lock"),
         Span::styled("  │  ", sep_style()),
         Span::styled("Q", key_style()),
-        Span::styled(" Quit", desc_style()),
+        desc("Quit"),
     ]
 }
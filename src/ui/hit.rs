@@ -0,0 +1,98 @@
+use ratatui::layout::{Position, Rect};
+
+use super::dialogs::ButtonHitboxes;
+use crate::state::{DialogButton, FocusPanel, MainTab, SettingGroup};
+
+/// What a click on part of the screen means. Rendering tags every clickable
+/// rect with one of these, so mouse handling never re-derives the layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hit {
+    /// Either half of the main tab row.
+    MainTab(MainTab),
+    /// Either half of the compact-mode panel switcher.
+    Panel(FocusPanel),
+    /// The arrangement panel, border included; drags stay inside it.
+    Arrangement,
+    /// The column of monitor box `i` in the arrangement strip.
+    Monitor(usize),
+    /// The Saved tab's row of workspace tabs; drags of a tab stay inside it.
+    WorkspaceTabs,
+    /// Workspace tab `i`: a click selects it, a drag moves it.
+    WorkspaceTab(usize),
+    /// The settings panel, border included.
+    Settings,
+    /// A settings group heading: a click folds or unfolds it.
+    SettingGroup(SettingGroup),
+    /// The row of setting `i` (index into `SettingField::all()`).
+    Setting(usize),
+    /// The value of Live setting `i`, or Primary's checkbox: a click edits it.
+    SettingValue(usize),
+    /// The open dropdown, border included.
+    Dropdown,
+    /// Dropdown option `i` (index into the unfiltered options).
+    DropdownOption(usize),
+    Button(DialogButton),
+}
+
+/// Clickable rects of the last frame drawn, in drawing order.
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    regions: Vec<(Rect, Hit)>,
+}
+
+impl HitMap {
+    /// Tag `rect` with `hit`; empty rects (clipped away) are skipped.
+    pub fn push(&mut self, rect: Rect, hit: Hit) {
+        if !rect.is_empty() {
+            self.regions.push((rect, hit));
+        }
+    }
+
+    /// Record the buttons a dialog drew.
+    pub fn push_buttons(&mut self, buttons: ButtonHitboxes) {
+        for (rect, button) in buttons {
+            self.push(rect, Hit::Button(button));
+        }
+    }
+
+    /// What is under (`col`, `row`): the region drawn last wins, so a dialog
+    /// covers the panels below and a setting's value its row.
+    pub fn at(&self, col: u16, row: u16) -> Option<Hit> {
+        let pos = Position::new(col, row);
+        self.regions.iter().rev().find(|(rect, _)| rect.contains(pos)).map(|&(_, hit)| hit)
+    }
+
+    /// Whether (`col`, `row`) lies in a region tagged `hit`, covered or not.
+    pub fn within(&self, col: u16, row: u16, hit: Hit) -> bool {
+        let pos = Position::new(col, row);
+        self.regions.iter().any(|&(rect, h)| h == hit && rect.contains(pos))
+    }
+
+    /// Where `hit` was drawn, if it was.
+    #[cfg(test)]
+    pub fn rect(&self, hit: Hit) -> Option<Rect> {
+        self.regions.iter().find(|&&(_, h)| h == hit).map(|&(rect, _)| rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_regions_cover_earlier_ones() {
+        let mut hits = HitMap::default();
+        hits.push(Rect::new(0, 0, 20, 10), Hit::Settings);
+        hits.push(Rect::new(0, 2, 20, 1), Hit::Setting(0));
+        hits.push(Rect::new(10, 2, 10, 1), Hit::SettingValue(0));
+        hits.push(Rect::new(5, 5, 0, 3), Hit::Dropdown); // clipped away
+        assert_eq!(hits.at(12, 2), Some(Hit::SettingValue(0)));
+        assert_eq!(hits.at(3, 2), Some(Hit::Setting(0)));
+        assert_eq!(hits.at(5, 5), Some(Hit::Settings));
+        assert_eq!(hits.at(30, 2), None);
+        assert!(hits.within(12, 2, Hit::Settings));
+        assert!(!hits.within(12, 3, Hit::Setting(0)));
+        assert_eq!(hits.rect(Hit::Setting(0)), Some(Rect::new(0, 2, 20, 1)));
+        assert_eq!(hits.rect(Hit::Dropdown), None);
+    }
+}
@@ -1,23 +1,41 @@
 mod arrangement;
+pub mod ascii;
 mod dialogs;
 mod help;
+mod hit;
 mod saved;
 pub mod settings;
+#[cfg(test)]
+mod snapshot;
 mod tabs;
+pub mod theme;
+mod toasts;
 
 pub use arrangement::render_arrangement_panel;
 pub use dialogs::{
-    render_confirm_apply_dialog, render_confirm_quit_dialog, render_dropdown, render_input_dialog,
+    config_error_lines, history_lines, render_command_palette, render_compare_dialog,
+    render_config_errors_dialog, render_config_preview_dialog, render_confirm_apply_dialog,
+    render_checklist_dialog, render_confirm_quit_dialog,
+    render_dropdown, render_fix_scale_dialog, render_history_dialog, render_reconcile_dialog,
+    render_rollback_dialog,
+    monitor_details_lines, render_input_dialog, render_monitor_details_dialog, render_picker_dialog,
+    render_question_dialog, TextInput, DROPDOWN_ROWS,
 };
-pub use help::render_help_bar;
+pub use help::{help_overlay_lines, render_help_bar, render_help_overlay};
+pub use hit::{Hit, HitMap};
 pub use saved::{render_saved_arrangement_panel, render_saved_settings_panel};
 pub use settings::render_settings_panel;
-pub use tabs::{render_main_tabs, render_workspace_tabs};
+pub use tabs::{render_main_tabs, render_panel_switcher, render_too_small, render_workspace_tabs};
+pub use toasts::{message_lines, render_messages_dialog, render_toasts};
+
+use ratatui::{prelude::*, widgets::Paragraph};
 
-use ratatui::prelude::*;
+use crate::app::App;
+use crate::state::{DialogType, FocusPanel, MainTab, SettingField};
 
 pub fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
     let width = r.width * percent_x / 100;
+    let height = height.min(r.height);
     let x = r.x + (r.width - width) / 2;
     let y = r.y + (r.height - height) / 2;
     Rect::new(x, y, width, height)
@@ -28,12 +46,394 @@ pub const BOX_WIDTH: u16 = 18;
 pub const BOX_HEIGHT: u16 = 6;
 pub const BOX_GAP: u16 = 3;
 
+/// Columns kept free on each side for the "◀ +n" / "+n ▶" scroll marks.
+const SCROLL_MARK_WIDTH: u16 = 5;
+
+/// The row of monitor boxes in an arrangement panel. When they don't all fit
+/// in the panel, a window of them is shown, centered on the selected monitor,
+/// with marks for how many are hidden on either side. Stateless, so there is
+/// no scroll offset to track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxStrip {
+    /// Index of the leftmost visible box.
+    pub first: usize,
+    /// Number of visible boxes.
+    pub count: usize,
+    pub total: usize,
+    /// Column of the leftmost visible box.
+    pub start_x: u16,
+}
+
+impl BoxStrip {
+    /// Lay out `total` boxes across `inner` (a panel's inside area).
+    pub fn new(inner: Rect, total: usize, selected: usize) -> Self {
+        let slot = (BOX_WIDTH + BOX_GAP) as usize;
+        let span = |n: usize| (n * slot).saturating_sub(BOX_GAP as usize) as u16;
+        let (first, count) = if span(total) <= inner.width {
+            (0, total)
+        } else {
+            let room = inner.width.saturating_sub(2 * SCROLL_MARK_WIDTH) as usize;
+            let count = ((room + BOX_GAP as usize) / slot).clamp(1, total);
+            let first = selected.saturating_sub(count / 2).min(total - count);
+            (first, count)
+        };
+        Self {
+            first,
+            count,
+            total,
+            start_x: inner.x + inner.width.saturating_sub(span(count)) / 2,
+        }
+    }
+
+    /// Left column of box `i`, or `None` when it is scrolled out of view.
+    pub fn x_of(&self, i: usize) -> Option<u16> {
+        (self.first..self.first + self.count)
+            .contains(&i)
+            .then(|| self.start_x + (i - self.first) as u16 * (BOX_WIDTH + BOX_GAP))
+    }
+
+    /// Tag each visible box's column of `inner`, so a click above or below
+    /// a box still picks it.
+    pub fn push_hits(&self, inner: Rect, hits: &mut HitMap) {
+        for i in self.first..self.first + self.count {
+            if let Some(x) = self.x_of(i) {
+                let column = Rect::new(x, inner.y, BOX_WIDTH, inner.height);
+                hits.push(column.intersection(inner), Hit::Monitor(i));
+            }
+        }
+    }
+
+    /// Draw "◀ +n" / "+n ▶" at the edges of `inner` on row `y` for hidden boxes.
+    pub fn render_scroll_marks(&self, frame: &mut Frame, inner: Rect, y: u16) {
+        let hidden_right = self.total - self.first - self.count;
+        let style = theme::theme().warn();
+        if self.first > 0 {
+            let mark = format!("◀ +{}", self.first);
+            frame.render_widget(
+                Paragraph::new(mark).style(style),
+                Rect::new(inner.x, y, SCROLL_MARK_WIDTH.min(inner.width), 1),
+            );
+        }
+        if hidden_right > 0 {
+            let mark = format!("+{} ▶", hidden_right);
+            let width = SCROLL_MARK_WIDTH.min(inner.width);
+            frame.render_widget(
+                Paragraph::new(mark)
+                    .style(style)
+                    .alignment(Alignment::Right),
+                Rect::new(inner.right() - width, y, width, 1),
+            );
+        }
+    }
+}
+
+/// Below this size only the focused panel is shown, with a switcher row.
+pub const COMPACT_WIDTH: u16 = 80;
+pub const COMPACT_HEIGHT: u16 = 24;
+/// Below this size only a "terminal too small" notice is drawn.
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 15;
+
+/// Where the main view's parts go on a terminal of a given size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MainLayout {
+    pub tabs: Rect,
+    /// Arrangement / Settings switcher row, only in compact mode.
+    pub switcher: Option<Rect>,
+    /// `None` when the panel is hidden in compact mode.
+    pub arrangement: Option<Rect>,
+    pub settings: Option<Rect>,
+    pub help: Rect,
+}
+
+impl MainLayout {
+    /// Lay out `area`; `None` when it is below [`MIN_WIDTH`]×[`MIN_HEIGHT`].
+    pub fn new(area: Rect, focus: FocusPanel) -> Option<Self> {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            return None;
+        }
+        if area.width >= COMPACT_WIDTH && area.height >= COMPACT_HEIGHT {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3), // Main tabs (3 rows for box)
+                    Constraint::Percentage(38),
+                    Constraint::Percentage(43),
+                    Constraint::Length(3),
+                ])
+                .split(area);
+            return Some(Self {
+                tabs: chunks[0],
+                switcher: None,
+                arrangement: Some(chunks[1]),
+                settings: Some(chunks[2]),
+                help: chunks[3],
+            });
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(area);
+        let panel = Some(chunks[2]);
+        Some(Self {
+            tabs: chunks[0],
+            switcher: Some(chunks[1]),
+            arrangement: panel.filter(|_| focus == FocusPanel::Arrangement),
+            settings: panel.filter(|_| focus == FocusPanel::Settings),
+            help: chunks[3],
+        })
+    }
+
+    /// Everything between the tabs and the help bar.
+    pub fn body(&self) -> Rect {
+        let top = self.tabs.bottom();
+        Rect::new(self.tabs.x, top, self.tabs.width, self.help.y.saturating_sub(top))
+    }
+}
+
+/// Draw the whole screen for `app`: the main view, then any dialog on top.
+/// Returns what was drawn where, for mouse events until the next frame.
+pub fn render(frame: &mut Frame, app: &App) -> HitMap {
+    let mut hits = HitMap::default();
+    let layout = MainLayout::new(frame.area(), app.focus_panel);
+    if let Some(layout) = layout {
+        render_main_tabs(frame, layout.tabs, app, &mut hits);
+        if let Some(switcher) = layout.switcher {
+            render_panel_switcher(frame, switcher, app, &mut hits);
+        }
+
+        match app.main_tab {
+            MainTab::Live => {
+                if let Some(area) = layout.arrangement {
+                    render_arrangement_panel(frame, area, app, &mut hits);
+                }
+                if let Some(area) = layout.settings {
+                    render_settings_panel(frame, area, app, &mut hits);
+                }
+            }
+            MainTab::Saved => {
+                if let Some(area) = layout.arrangement {
+                    // Split arrangement area for workspace tabs
+                    let saved_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([
+                            Constraint::Length(2), // Workspace tabs
+                            Constraint::Min(0),    // Arrangement
+                        ])
+                        .split(area);
+
+                    // A click on the workspace tabs focuses the panel too
+                    hits.push(saved_chunks[0], Hit::Arrangement);
+                    render_workspace_tabs(frame, saved_chunks[0], app, &mut hits);
+                    render_saved_arrangement_panel(frame, saved_chunks[1], app, &mut hits);
+                }
+                if let Some(area) = layout.settings {
+                    render_saved_settings_panel(frame, area, app, &mut hits);
+                }
+            }
+        }
+
+        render_help_bar(frame, layout.help, app);
+        render_toasts(frame, layout.body(), app);
+    } else {
+        render_too_small(frame);
+    }
+
+    // Render dialogs on top
+    let buttons = match app.dialog {
+        DialogType::EditDropdown => {
+            let settings = layout.and_then(|l| l.settings);
+            if let (MainTab::Live, Some(area)) = (app.main_tab, settings) {
+                render_dropdown(frame, area, app, &mut hits);
+            }
+            Vec::new()
+        }
+        DialogType::ConfirmApply { started, focus, .. } => {
+            let elapsed = started.elapsed().as_secs() as u8;
+            let remaining = 15u8.saturating_sub(elapsed);
+            let (changes, mismatches) = (&app.applied_changes, &app.apply_mismatches);
+            render_confirm_apply_dialog(frame, remaining, changes, mismatches, focus)
+        }
+        DialogType::ConfirmQuit { focus } => render_confirm_quit_dialog(frame, focus),
+        DialogType::FixScale { focus } => {
+            render_fix_scale_dialog(frame, &app.scale_fix_lines(), focus)
+        }
+        DialogType::Reconcile { focus } => {
+            let workspace = app.current_workspace_name();
+            render_reconcile_dialog(frame, &app.external_changes, &workspace, focus)
+        }
+        DialogType::NewWorkspace => render_input_dialog(
+            frame,
+            "New Workspace",
+            &app.text_input,
+            "Enter workspace name (Tab: next suggestion):",
+        ),
+        DialogType::RenameWorkspace => {
+            render_input_dialog(frame, "Rename Workspace", &app.text_input, "Enter new name:")
+        }
+        DialogType::DuplicateWorkspace => render_input_dialog(
+            frame,
+            "Duplicate Workspace",
+            &app.text_input,
+            &format!("Name for copy of '{}':", app.current_workspace_name()),
+        ),
+        DialogType::DeleteWorkspace { focus } => render_question_dialog(
+            frame,
+            "Delete Workspace",
+            &format!("Delete '{}'?", app.current_workspace_name()),
+            focus,
+        ),
+        DialogType::DeleteMonitor { focus } => render_question_dialog(
+            frame,
+            "Remove Monitor",
+            &format!(
+                "Remove {} from '{}'?",
+                app.selected_saved_monitor_key().unwrap_or_default(),
+                app.current_workspace_name()
+            ),
+            focus,
+        ),
+        DialogType::SaveAs => {
+            render_picker_dialog(
+                frame,
+                "Save Live Layout As",
+                &app.save_as_options(),
+                app.dropdown_selection,
+            );
+            Vec::new()
+        }
+        DialogType::ImportMonitors => {
+            render_checklist_dialog(
+                frame,
+                &format!("Import Into '{}'", app.current_workspace_name()),
+                &app.import_options(),
+                app.dropdown_selection,
+            );
+            Vec::new()
+        }
+        DialogType::TellApart { .. } => {
+            let choices: Vec<String> =
+                app.key_choices().into_iter().map(|(_, label)| label).collect();
+            render_picker_dialog(
+                frame,
+                "Identical Monitors: Tell Apart",
+                &choices,
+                app.dropdown_selection,
+            );
+            Vec::new()
+        }
+        DialogType::SwapWorkspaces => {
+            render_picker_dialog(
+                frame,
+                "Swap Workspaces With",
+                &app.swap_options(),
+                app.dropdown_selection,
+            );
+            Vec::new()
+        }
+        DialogType::Rollback => {
+            render_rollback_dialog(frame, app);
+            Vec::new()
+        }
+        DialogType::SaveAsNew => render_input_dialog(
+            frame,
+            "Save As New Workspace",
+            &app.text_input,
+            "Enter workspace name (Tab: next suggestion):",
+        ),
+        DialogType::EditNotes => render_input_dialog(
+            frame,
+            "Workspace Notes",
+            &app.text_input,
+            &format!("What is '{}' for?", app.current_workspace_name()),
+        ),
+        DialogType::EditSetting => {
+            let name = app.current_monitor().map_or("", |m| m.name.as_str());
+            let (title, hint) = match SettingField::all()[app.selected_setting] {
+                SettingField::Resolution => (
+                    "Custom Resolution".to_string(),
+                    format!("Width x height for {name}, e.g. 2560x1080:"),
+                ),
+                SettingField::Scale => (
+                    "Custom Scale".to_string(),
+                    format!("Scale of {name} in percent, e.g. 133:"),
+                ),
+                field => {
+                    let axis = if field == SettingField::PositionY { "Y" } else { "X" };
+                    (
+                        format!("{axis} Position"),
+                        format!("{axis} of {name}'s top-left corner, in logical pixels:"),
+                    )
+                }
+            };
+            render_input_dialog(frame, &title, &app.text_input, &hint)
+        }
+        DialogType::EditNickname => {
+            let name = app.current_monitor().map_or("", |m| m.name.as_str());
+            render_input_dialog(
+                frame,
+                "Monitor Nickname",
+                &app.text_input,
+                &format!("What to call {name}, e.g. Left Dell (empty = none):"),
+            )
+        }
+        DialogType::EditFallback => render_input_dialog(
+            frame,
+            "Fallback Rule",
+            &app.text_input,
+            "monitor=,<mode>,<position>,<scale> (empty = none):",
+        ),
+        DialogType::Compare => {
+            render_compare_dialog(frame, app);
+            Vec::new()
+        }
+        DialogType::ConfigErrors { scroll } => {
+            render_config_errors_dialog(frame, app, scroll);
+            Vec::new()
+        }
+        DialogType::ConfigPreview { scroll } => {
+            render_config_preview_dialog(frame, app, scroll);
+            Vec::new()
+        }
+        DialogType::History { scroll } => {
+            render_history_dialog(frame, app, scroll);
+            Vec::new()
+        }
+        DialogType::Messages { scroll } => {
+            render_messages_dialog(frame, app, scroll);
+            Vec::new()
+        }
+        DialogType::MonitorDetails { scroll } => {
+            render_monitor_details_dialog(frame, app, scroll);
+            Vec::new()
+        }
+        DialogType::Help { scroll } => {
+            render_help_overlay(frame, scroll);
+            Vec::new()
+        }
+        DialogType::CommandPalette => {
+            render_command_palette(frame, app);
+            Vec::new()
+        }
+        DialogType::None => Vec::new(),
+    };
+    hits.push_buttons(buttons);
+    hits
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::App;
+    use crate::history::HistoryEntry;
     use crate::monitor::MonitorConfig;
-    use crate::state::{DialogType, FocusPanel, MainTab};
+    use crate::state::{DialogButton, DialogType, DragState, FocusPanel, MainTab};
     use ratatui::{backend::TestBackend, Terminal};
 
     const W: u16 = 140;
@@ -56,54 +456,525 @@ mod tests {
 
     #[test]
     fn renders_every_panel_without_panicking() {
+        let h = &mut HitMap::default();
         let mut a = app();
-        a.message = "Applied!".into();
+        a.toasts.info("Applied!");
         let r = area();
-        terminal().draw(|f| render_arrangement_panel(f, r, &a)).unwrap();
-        terminal().draw(|f| render_settings_panel(f, r, &a)).unwrap();
-        terminal().draw(|f| render_main_tabs(f, r, &a)).unwrap();
-        terminal().draw(|f| render_workspace_tabs(f, r, &a)).unwrap();
+        terminal().draw(|f| render_arrangement_panel(f, r, &a, h)).unwrap();
+        terminal().draw(|f| render_settings_panel(f, r, &a, h)).unwrap();
+        terminal().draw(|f| render_main_tabs(f, r, &a, h)).unwrap();
+        terminal().draw(|f| render_workspace_tabs(f, r, &a, h)).unwrap();
         terminal().draw(|f| render_help_bar(f, r, &a)).unwrap();
     }
 
+    #[test]
+    fn changed_settings_and_boxes_are_marked() {
+        let mut a = app();
+        let text = |a: &App, render: fn(&mut Frame, Rect, &App, &mut HitMap)| -> String {
+            let mut t = terminal();
+            t.draw(|f| render(f, area(), a, &mut HitMap::default())).unwrap();
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        assert!(!text(&a, render_settings_panel).contains(settings::CHANGED_MARK));
+        assert!(!text(&a, render_arrangement_panel).contains(settings::CHANGED_MARK));
+        a.monitors[0].scale = 1.25;
+        let full = text(&a, render_settings_panel);
+        assert!(full.contains("•Scale:") && full.contains("changed since last apply"), "{full}");
+        assert!(!full.contains("•Resolution:"));
+        assert_eq!(text(&a, render_arrangement_panel).matches(settings::CHANGED_MARK).count(), 1);
+    }
+
+    #[test]
+    fn monitor_boxes_list_their_hyprland_workspaces() {
+        let mut a = app();
+        let json = br#"[{"id":1,"name":"1","monitor":"eDP-1","windows":2},
+            {"id":4,"name":"code","monitor":"HDMI-A-1","windows":0}]"#;
+        a.hypr_workspaces = crate::monitor::parse_workspaces(json).unwrap();
+        let mut t = terminal();
+        t.draw(|f| render_arrangement_panel(f, area(), &a, &mut HitMap::default())).unwrap();
+        let text: String = t.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains(" 1(2) ") && text.contains(" code "), "{text}");
+        a.monitor_details = Some(crate::monitor::MonitorDetails::from_config(&a.monitors[0]));
+        let lines = monitor_details_lines(&a);
+        assert!(lines.contains(&"  Workspaces    1(2)  (windows in parentheses)".to_string()));
+    }
+
+    #[test]
+    fn the_hyprland_focused_monitor_is_marked() {
+        let mut a = app();
+        let text = |a: &App| -> String {
+            let mut t = terminal();
+            t.draw(|f| render_arrangement_panel(f, area(), a, &mut HitMap::default())).unwrap();
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        assert!(!text(&a).contains('◉'));
+        a.focused_monitor = Some("HDMI-A-1".into());
+        let full = text(&a);
+        assert!(full.contains(" 2 ◉") && full.matches('◉').count() == 1, "{full}");
+    }
+
+    #[test]
+    fn identical_monitors_are_flagged() {
+        let twin = |name: &str| MonitorConfig::for_test(name, "Dell", "U2720Q", "2560x1440");
+        let a = App::for_test(vec![twin("DP-1"), twin("DP-2")]);
+        let mut t = terminal();
+        t.draw(|f| render_arrangement_panel(f, area(), &a, &mut HitMap::default())).unwrap();
+        let text: String = t.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert_eq!(text.matches('⚠').count(), 3, "{text}"); // both boxes and the hint
+        assert!(text.contains("Identical monitors share saved settings"));
+    }
+
+    #[test]
+    fn dragging_shows_a_ghost_where_the_monitor_will_land() {
+        let h = &mut HitMap::default();
+        let mut a = app();
+        a.drag_state = DragState::Dragging {
+            monitor_idx: 0,
+            start_x: 40,
+            start_y: 5,
+            current_x: 40 + BOX_WIDTH + BOX_GAP,
+            current_y: 5 + BOX_HEIGHT, // off the row, so the ghost shows
+        };
+        let mut t = terminal();
+        t.draw(|f| render_arrangement_panel(f, area(), &a, h)).unwrap();
+        let text: String = t.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("┆") && text.contains("Drop at slot 2, x=2560 |"), "{text}");
+        assert!(text.contains(" x=2560 ┄") && text.contains("MP275Q"), "{text}");
+
+        // Under half a box the ghost stays in the monitor's own slot
+        a.drag_state = DragState::Dragging {
+            monitor_idx: 0,
+            start_x: 40,
+            start_y: 5,
+            current_x: 45,
+            current_y: 5,
+        };
+        t.draw(|f| render_arrangement_panel(f, area(), &a, h)).unwrap();
+        let text: String = t.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains(" x=0 ") && text.contains("Drop at slot 1, x=0 |"), "{text}");
+    }
+
+    #[test]
+    fn ascii_mode_has_a_stand_in_for_every_glyph_drawn() {
+        let mut a = app();
+        a.monitors[0].scale = 1.25; // changed markers
+        a.toasts.warn("Nothing to undo.");
+        let mut t = terminal();
+        a.dialog = DialogType::Help { scroll: 0 };
+        let frame = t.draw(|f| drop(render(f, &a))).unwrap();
+        let before = frame.buffer.clone();
+        let mut after = before.clone();
+        ascii::asciify(&mut after);
+        for (b, c) in before.content.iter().zip(&after.content) {
+            assert!(c.symbol().is_ascii());
+            assert!(b.symbol().is_ascii() || c.symbol() != "?", "no stand-in for {}", b.symbol());
+        }
+    }
+
+    #[test]
+    fn monitor_details_mark_the_current_mode() {
+        let mut a = app();
+        a.monitors[0].refresh_rate = 144.0;
+        a.monitor_details = Some(crate::monitor::MonitorDetails::from_config(&a.monitors[0]));
+        let lines = monitor_details_lines(&a);
+        assert!(lines.contains(&"  Serial        unknown".to_string()), "{lines:?}");
+        assert!(lines.contains(&"  Workspaces    none".to_string()), "{lines:?}");
+        assert!(lines.contains(&"  • 1920x1080@144.00Hz".to_string()), "{lines:?}");
+        assert!(lines.contains(&"    1920x1080@60.00Hz".to_string()), "{lines:?}");
+        terminal().draw(|f| render_monitor_details_dialog(f, &a, 2)).unwrap();
+    }
+
+    #[test]
+    fn dropdown_shows_filter_and_scrollbar() {
+        let h = &mut HitMap::default();
+        let mut m = MonitorConfig::for_test("DP-1", "D", "M", "3000x1000");
+        m.available_modes = (1..=30).map(|i| format!("{}x1000@60Hz", 3000 - i * 10)).collect();
+        let mut a = App::for_test(vec![m]);
+        a.dialog = DialogType::EditDropdown;
+        a.dropdown_selection = 20;
+        let r = Rect::new(0, 0, 60, 20);
+        let mut t = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        let text = |t: &Terminal<TestBackend>| -> String {
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        t.draw(|f| render_dropdown(f, r, &a, h)).unwrap();
+        let full = text(&t);
+        assert!(full.contains("2790x1000") && !full.contains("2990x1000"), "{full}");
+        assert!(full.contains('║'), "scrollbar track missing: {full}");
+
+        a.input_buffer = "298".into();
+        t.draw(|f| render_dropdown(f, r, &a, h)).unwrap();
+        assert!(text(&t).contains("/298█"));
+        a.input_buffer = "zz".into();
+        t.draw(|f| render_dropdown(f, r, &a, h)).unwrap();
+        assert!(text(&t).contains("No match"));
+    }
+
+    #[test]
+    fn box_strip_scrolls_to_keep_the_selection_visible() {
+        let inner = Rect::new(1, 1, 78, 8);
+        let all = BoxStrip::new(inner, 3, 0);
+        assert_eq!((all.first, all.count), (0, 3));
+        assert_eq!(all.x_of(0), Some(1 + (78 - 60) / 2));
+
+        // 78 - 10 mark columns fits three 21-column slots
+        let strip = BoxStrip::new(inner, 7, 5);
+        assert_eq!((strip.first, strip.count), (4, 3));
+        assert_eq!(strip.x_of(3), None);
+        let x = strip.x_of(5).unwrap();
+        let mut hits = HitMap::default();
+        strip.push_hits(inner, &mut hits);
+        assert_eq!(hits.at(x + BOX_WIDTH - 1, 1), Some(Hit::Monitor(5)));
+        assert_eq!(hits.at(x + BOX_WIDTH, 8), None); // the gap
+        assert_eq!(hits.at(x, 9), None); // below the panel
+        assert_eq!(BoxStrip::new(inner, 7, 0).first, 0);
+        assert_eq!(BoxStrip::new(inner, 7, 6).first, 4);
+        assert_eq!(BoxStrip::new(Rect::new(0, 0, 10, 8), 2, 1).count, 1);
+    }
+
+    #[test]
+    fn many_monitors_scroll_with_marks() {
+        let h = &mut HitMap::default();
+        let monitors: Vec<MonitorConfig> = (1..=7)
+            .map(|i| MonitorConfig::for_test(&format!("DP-{i}"), "Dell", &format!("M{i}"), "4K"))
+            .collect();
+        let mut a = App::for_test(monitors.clone());
+        for m in &monitors {
+            a.monitor_db.update_monitor(m);
+        }
+        a.refresh_saved_monitors();
+        a.selected_monitor = 3;
+        a.saved_selected_monitor = 6;
+        let r = Rect::new(0, 0, 80, 12);
+        let mut t = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        let text = |t: &Terminal<TestBackend>| -> String {
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        t.draw(|f| render_arrangement_panel(f, r, &a, h)).unwrap();
+        let live = text(&t);
+        assert!(live.contains("◀ +2") && live.contains("+2 ▶"), "{live}");
+        assert!(live.contains("M4") && !live.contains("M1"));
+        t.draw(|f| render_saved_arrangement_panel(f, r, &a, h)).unwrap();
+        let saved = text(&t);
+        assert!(saved.contains("◀ +4") && !saved.contains("▶"), "{saved}");
+    }
+
+    #[test]
+    fn layout_collapses_to_one_panel_then_gives_up() {
+        let full = MainLayout::new(area(), FocusPanel::Settings).unwrap();
+        assert!(full.switcher.is_none() && full.arrangement.is_some() && full.settings.is_some());
+
+        let small = Rect::new(0, 0, 60, 20);
+        let compact = MainLayout::new(small, FocusPanel::Settings).unwrap();
+        assert_eq!(compact.switcher.map(|r| r.height), Some(1));
+        assert!(compact.arrangement.is_none());
+        assert_eq!(compact.settings.unwrap().height, 20 - 3 - 1 - 3);
+        let compact = MainLayout::new(small, FocusPanel::Arrangement).unwrap();
+        assert!(compact.arrangement.is_some() && compact.settings.is_none());
+        assert_eq!(compact.body(), Rect::new(0, 3, 60, 14));
+
+        let narrow = Rect::new(0, 0, MIN_WIDTH - 1, 30);
+        assert!(MainLayout::new(narrow, FocusPanel::Settings).is_none());
+        let short = Rect::new(0, 0, 100, MIN_HEIGHT - 1);
+        assert!(MainLayout::new(short, FocusPanel::Settings).is_none());
+    }
+
+    #[test]
+    fn small_terminals_render_without_panicking() {
+        let mut a = app();
+        a.toasts.info("Applied!");
+        for (w, h) in [(MIN_WIDTH, MIN_HEIGHT), (50, 18), (79, 23), (100, 20), (30, 10)] {
+            for (tab, focus) in [
+                (MainTab::Live, FocusPanel::Arrangement),
+                (MainTab::Live, FocusPanel::Settings),
+                (MainTab::Saved, FocusPanel::Settings),
+            ] {
+                a.main_tab = tab;
+                a.focus_panel = focus;
+                a.dialog = DialogType::EditDropdown;
+                let mut t = Terminal::new(TestBackend::new(w, h)).unwrap();
+                t.draw(|f| drop(render(f, &a))).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn help_bar_border_shows_status() {
+        let mut a = app();
+        a.has_changes = true;
+        a.ipc_connected = true;
+        let mut t = terminal();
+        t.draw(|f| render_help_bar(f, Rect::new(0, 0, W, 3), &a)).unwrap();
+        let top: String =
+            (0..W).map(|x| t.backend().buffer()[(x, 0)].symbol().to_string()).collect();
+        for part in ["Default", "2 monitors", "● unsaved", "IPC ✓", "never applied"] {
+            assert!(top.contains(part), "{part} missing from {top:?}");
+        }
+        assert!(top.contains("Focus: Arrangement · ⏎ Details ┐"), "{top:?}");
+        a.focus_panel = FocusPanel::Settings;
+        assert_eq!(help::focus_hint(&a).to_string(), " Focus: Settings · ⏎ Change ");
+        a.main_tab = MainTab::Saved;
+        assert_eq!(help::focus_hint(&a).to_string(), " Focus: Settings ");
+
+        a.monitor_db.workspaces[0].mark_applied(0);
+        a.ipc_connected = false;
+        let line = help::status_line(&a).to_string();
+        assert!(line.contains("IPC ✗") && line.contains("applied 19"), "{line}");
+    }
+
+    #[test]
+    fn toasts_stack_bottom_right_and_fill_the_message_log() {
+        let mut a = app();
+        terminal().draw(|f| render_messages_dialog(f, &a, 0)).unwrap();
+        a.toasts.info("Applied!");
+        a.toasts.error("Error: boom");
+        let mut t = terminal();
+        t.draw(|f| render_toasts(f, area(), &a)).unwrap();
+        let row = |y: u16| -> String {
+            (0..W).map(|x| t.backend().buffer()[(x, y)].symbol().to_string()).collect()
+        };
+        assert!(row(H - 2).contains("Error: boom"));
+        assert!(row(H - 5).contains("Applied!"));
+
+        let lines = message_lines(&a);
+        assert!(lines[0].1.ends_with("error  Error: boom"));
+        terminal().draw(|f| render_messages_dialog(f, &a, 1)).unwrap();
+    }
+
     #[test]
     fn renders_focused_and_empty_states() {
+        let h = &mut HitMap::default();
         let r = area();
         let mut a = app();
         a.focus_panel = FocusPanel::Settings;
         a.main_tab = MainTab::Saved;
-        terminal().draw(|f| render_settings_panel(f, r, &a)).unwrap();
+        terminal().draw(|f| render_settings_panel(f, r, &a, h)).unwrap();
 
         let empty = App::for_test(vec![]);
-        terminal().draw(|f| render_settings_panel(f, r, &empty)).unwrap();
-        terminal().draw(|f| render_arrangement_panel(f, r, &empty)).unwrap();
+        terminal().draw(|f| render_settings_panel(f, r, &empty, h)).unwrap();
+        terminal().draw(|f| render_arrangement_panel(f, r, &empty, h)).unwrap();
     }
 
     #[test]
     fn renders_saved_panels_with_and_without_monitors() {
+        let h = &mut HitMap::default();
         let r = area();
         let empty = App::for_test(vec![]);
-        terminal().draw(|f| render_saved_arrangement_panel(f, r, &empty)).unwrap();
-        terminal().draw(|f| render_saved_settings_panel(f, r, &empty)).unwrap();
+        terminal().draw(|f| render_saved_arrangement_panel(f, r, &empty, h)).unwrap();
+        terminal().draw(|f| render_saved_settings_panel(f, r, &empty, h)).unwrap();
 
         let mut a = app();
         a.saved_monitors = a.monitors.clone();
-        terminal().draw(|f| render_saved_arrangement_panel(f, r, &a)).unwrap();
-        terminal().draw(|f| render_saved_settings_panel(f, r, &a)).unwrap();
+        terminal().draw(|f| render_saved_arrangement_panel(f, r, &a, h)).unwrap();
+        terminal().draw(|f| render_saved_settings_panel(f, r, &a, h)).unwrap();
+    }
+
+    #[test]
+    fn multibyte_names_are_cut_by_display_width() {
+        let mut a = app();
+        a.monitors[1].model = "Bureau 会議室モニター".to_string();
+        a.saved_monitors = a.monitors.clone();
+        a.monitor_db.add_workspace("Büro 🖥️").unwrap();
+        let text = |render: fn(&mut Frame, Rect, &App, &mut HitMap), a: &App| -> String {
+            let mut t = terminal();
+            t.draw(|f| render(f, area(), a, &mut HitMap::default())).unwrap();
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        // Wide characters fill two cells, the second one blank
+        let cut = "Bureau 会 議 室 モ …";
+        assert!(text(render_arrangement_panel, &a).contains(cut));
+        assert!(text(render_saved_arrangement_panel, &a).contains(cut));
+        let tabs = text(render_workspace_tabs, &a);
+        assert!(tabs.contains("Büro 🖥️"));
+    }
+
+    #[test]
+    fn workspace_tabs_scroll_to_keep_the_selected_one_in_view() {
+        let mut a = app();
+        for i in 1..=30 {
+            a.monitor_db.add_workspace(&format!("Room {i}")).unwrap();
+        }
+        let row = |a: &App| -> String {
+            let mut t = terminal();
+            t.draw(|f| render_workspace_tabs(f, area(), a, &mut HitMap::default())).unwrap();
+            (0..W).map(|x| t.backend().buffer()[(x, 0)].symbol().to_string()).collect()
+        };
+        a.selected_workspace = 0;
+        let first = row(&a);
+        assert!(first.contains("▸ Default") && first.contains(" ▶"), "{first}");
+        assert!(!first.contains('◀') && first.trim_end().ends_with("[+]"), "{first}");
+        a.selected_workspace = 30;
+        let last = row(&a);
+        assert!(last.contains("▸ Room 30") && last.contains("◀ +"), "{last}");
+        assert!(!last.contains('▶') && last.trim_end().ends_with("[+]"), "{last}");
+        a.selected_workspace = 15;
+        let middle = row(&a);
+        assert!(middle.contains("▸ Room 15") && middle.contains('◀') && middle.contains('▶'));
     }
 
     #[test]
     fn renders_dialogs() {
+        let h = &mut HitMap::default();
         let r = area();
         let mut a = app();
         a.dialog = DialogType::EditDropdown;
         a.selected_setting = 0;
-        terminal().draw(|f| render_dropdown(f, r, &a)).unwrap();
-        terminal().draw(|f| render_confirm_apply_dialog(f, 10)).unwrap();
-        terminal().draw(render_confirm_quit_dialog).unwrap();
+        terminal().draw(|f| render_dropdown(f, r, &a, h)).unwrap();
+        terminal().draw(|f| render_history_dialog(f, &a, 0)).unwrap();
+        terminal().draw(|f| render_help_overlay(f, 3)).unwrap();
+        a.input_buffer = "scale".into();
+        terminal().draw(|f| render_command_palette(f, &a)).unwrap();
+        a.input_buffer = "zzzz".into();
+        terminal().draw(|f| render_command_palette(f, &a)).unwrap();
+        terminal().draw(|f| render_compare_dialog(f, &a)).unwrap();
+        a.monitor_db.update_monitor(&a.monitors[0].clone());
+        terminal().draw(|f| render_compare_dialog(f, &a)).unwrap();
         terminal()
-            .draw(|f| render_input_dialog(f, "New Workspace", "typed", "Enter to confirm"))
+            .draw(|f| render_picker_dialog(f, "Save As", &a.save_as_options(), 1))
+            .unwrap();
+    }
+
+    /// Text drawn under each button hitbox.
+    fn button_labels(t: &Terminal<TestBackend>, buttons: &[(Rect, DialogButton)]) -> Vec<String> {
+        buttons
+            .iter()
+            .map(|(r, _)| {
+                (r.x..r.x + r.width)
+                    .map(|x| t.backend().buffer()[(x, r.y)].symbol().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dialog_buttons_report_where_they_are_drawn() {
+        let mut t = terminal();
+        let mut buttons = Vec::new();
+        let yes = DialogButton::Yes;
+        t.draw(|f| buttons = render_confirm_apply_dialog(f, 10, &[], &[], yes)).unwrap();
+        assert_eq!(button_labels(&t, &buttons), ["[Y] Yes", "[N] No"]);
+        assert_eq!(buttons[1].1, DialogButton::No);
+        // The focused button, the one Enter presses, stands out
+        let underlined = |t: &Terminal<TestBackend>, r: Rect| {
+            t.backend().buffer()[(r.x, r.y)].modifier.contains(Modifier::UNDERLINED)
+        };
+        assert!(underlined(&t, buttons[0].0) && !underlined(&t, buttons[1].0));
+
+        t.draw(|f| buttons = render_confirm_quit_dialog(f, DialogButton::No)).unwrap();
+        assert_eq!(button_labels(&t, &buttons), ["[Y] Yes", "[N] No"]);
+
+        let typed = TextInput::new("typed", 20);
+        t.draw(|f| buttons = render_input_dialog(f, "New Workspace", &typed, "Name:")).unwrap();
+        assert_eq!(button_labels(&t, &buttons), ["[OK]", "[Cancel]"]);
+
+        let no = DialogButton::No;
+        t.draw(|f| buttons = render_question_dialog(f, "Delete", "Delete 'A'?", no)).unwrap();
+        assert_eq!(buttons.len(), 2);
+
+        // Squeezed terminals keep the buttons inside the screen
+        let mut small = Terminal::new(TestBackend::new(30, 4)).unwrap();
+        let yes = DialogButton::Yes;
+        small.draw(|f| buttons = render_confirm_apply_dialog(f, 10, &[], &[], yes)).unwrap();
+        assert!(buttons.iter().all(|(r, _)| r.y < 4 && r.x + r.width <= 30));
+    }
+
+    #[test]
+    fn confirm_apply_lists_what_changed_per_monitor() {
+        let text = |t: &Terminal<TestBackend>| -> String {
+            t.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        let before = app().monitors;
+        let mut after = before.clone();
+        after[1].scale = 1.5;
+        after[1].position_x = 2560;
+        let changes = HistoryEntry::between("Desk", &before, &after).changes;
+        let mut t = terminal();
+        let mut buttons = Vec::new();
+        let yes = DialogButton::Yes;
+        t.draw(|f| buttons = render_confirm_apply_dialog(f, 12, &changes, &[], yes)).unwrap();
+        let shown = text(&t);
+        assert!(shown.contains("desc:MSI MP275Q: scale 100% -> 150%, position 0x0 -> 2560x0"));
+        assert!(shown.contains("Auto-revert in 12 seconds"));
+        assert_eq!(button_labels(&t, &buttons), ["[Y] Yes", "[N] No"]);
+
+        t.draw(|f| buttons = render_confirm_apply_dialog(f, 12, &[], &[], yes)).unwrap();
+        assert!(text(&t).contains("Re-applied; no settings changed."));
+
+        let fell_back =
+            [("DP-1".to_string(), "asked 2560x1440@165.00Hz, got 2560x1440@60.00Hz".to_string())];
+        t.draw(|f| buttons = render_confirm_apply_dialog(f, 12, &changes, &fell_back, yes))
             .unwrap();
+        let shown = text(&t);
+        assert!(shown.contains("Hyprland runs these otherwise:"));
+        assert!(shown.contains("⚠ DP-1: asked 2560x1440@165.00Hz, got 2560x1440@60.00Hz"));
+
+        // More monitors than fit leave room for the question and buttons
+        let many = vec![changes[0].clone(); 40];
+        t.draw(|f| buttons = render_confirm_apply_dialog(f, 12, &many, &[], yes)).unwrap();
+        assert!(text(&t).contains("… and 7 more"));
+        assert_eq!(button_labels(&t, &buttons), ["[Y] Yes", "[N] No"]);
+    }
+
+    #[test]
+    fn main_tabs_show_lock_and_modified_badges() {
+        let h = &mut HitMap::default();
+        let mut a = app();
+        a.monitor_db.lock_active = true;
+        a.monitor_db.workspaces[0].manual_only = true;
+        let mut t = terminal();
+        t.draw(|f| render_main_tabs(f, area(), &a, h)).unwrap();
+        let row: String = (0..W)
+            .map(|x| t.backend().buffer()[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(row.trim_end().ends_with("Locked: Default"), "{row}");
+        assert!(!row.contains("[modified]"));
+
+        a.monitor_db.update_monitor(&a.monitors[0].clone());
+        a.hypr_monitors[0].scale = 2.0;
+        let mut t = terminal();
+        t.draw(|f| render_main_tabs(f, area(), &a, h)).unwrap();
+        let row: String = (0..W)
+            .map(|x| t.backend().buffer()[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(row.contains("1 Live [modified]"), "{row}");
+        terminal().draw(|f| render_workspace_tabs(f, area(), &a, h)).unwrap();
+    }
+
+    #[test]
+    fn help_overlay_covers_every_context() {
+        let text: Vec<String> = help_overlay_lines().iter().map(|l| l.to_string()).collect();
+        for heading in ["Everywhere", "Live tab", "Saved tab", "Dialogs"] {
+            assert!(text.iter().any(|l| l == heading), "{heading}");
+        }
+        assert!(text.iter().any(|l| l.contains("Ctrl+l") && l.contains("Lock")));
+    }
+
+    #[test]
+    fn render_records_what_it_drew_where() {
+        let mut a = app();
+        let mut t = terminal();
+        let mut hits = HitMap::default();
+        t.draw(|f| hits = render(f, &a)).unwrap();
+        let layout = MainLayout::new(area(), a.focus_panel).unwrap();
+        let (arr, set) = (layout.arrangement.unwrap(), layout.settings.unwrap());
+        assert_eq!(hits.at(5, 1), Some(Hit::MainTab(MainTab::Live)));
+        assert_eq!(hits.at(W - 5, 1), Some(Hit::MainTab(MainTab::Saved)));
+        assert_eq!(hits.at(1, arr.y + 1), Some(Hit::Arrangement));
+        assert!(hits.rect(Hit::Monitor(1)).is_some());
+        assert_eq!(hits.at(30, set.y + 2), Some(Hit::SettingValue(0)));
+        assert_eq!(hits.at(0, layout.help.y), None);
+
+        // An open dropdown covers the rows below the setting
+        a.dialog = DialogType::EditDropdown;
+        t.draw(|f| hits = render(f, &a)).unwrap();
+        let dd = hits.rect(Hit::Dropdown).unwrap();
+        assert_eq!(hits.at(dd.x + 2, dd.y + 1), Some(Hit::DropdownOption(0)));
+        assert_eq!(hits.at(dd.x, dd.y + 1), Some(Hit::Dropdown));
+
+        a.dialog = DialogType::ConfirmQuit { focus: DialogButton::No };
+        t.draw(|f| hits = render(f, &a)).unwrap();
+        let no = hits.rect(Hit::Button(DialogButton::No)).unwrap();
+        assert_eq!(hits.at(no.x, no.y), Some(Hit::Button(DialogButton::No)));
     }
 
     #[test]
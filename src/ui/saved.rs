@@ -3,23 +3,42 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use super::{BOX_GAP, BOX_HEIGHT, BOX_WIDTH};
+use super::arrangement::{render_legend, BoxState};
+use super::hit::{Hit, HitMap};
+use super::theme::theme;
+use super::{BoxStrip, BOX_HEIGHT, BOX_WIDTH};
 use crate::app::App;
+use crate::config::MonitorDatabase;
+use crate::i18n::tr;
 use crate::state::{FocusPanel, MainTab, SettingField};
+use crate::text;
 
-pub fn render_saved_arrangement_panel(frame: &mut Frame, area: Rect, app: &App) {
+pub fn render_saved_arrangement_panel(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    hits: &mut HitMap,
+) {
+    hits.push(area, Hit::Arrangement);
     let is_focused = app.focus_panel == FocusPanel::Arrangement && app.main_tab == MainTab::Saved;
     let border_style = if is_focused {
-        Style::default().fg(Color::Magenta)
+        theme().saved()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme().muted()
     };
 
     let ws_name = app.current_workspace_name();
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
         .title(format!(" Saved Monitors - {} ", ws_name));
+    if let Some(ws) = app.monitor_db.workspaces.get(app.selected_workspace) {
+        block = block.title_bottom(
+            Line::from(format!(" {} ", ws.summary()))
+                .style(theme().muted())
+                .centered(),
+        );
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -27,53 +46,77 @@ pub fn render_saved_arrangement_panel(frame: &mut Frame, area: Rect, app: &App)
     if app.saved_monitors.is_empty() {
         let text = Paragraph::new("No monitors saved in this workspace.\nSwitch to Live panel and Apply to save current monitors.")
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::DarkGray));
+            .style(theme().muted());
         frame.render_widget(text, inner);
         return;
     }
 
-    let total_monitors = app.saved_monitors.len();
-    let total_width =
-        (BOX_WIDTH * total_monitors as u16) + (BOX_GAP * (total_monitors as u16).saturating_sub(1));
-    let start_x = inner.x + (inner.width.saturating_sub(total_width)) / 2;
+    let ws_dpms_off = app
+        .monitor_db
+        .workspaces
+        .get(app.selected_workspace)
+        .map(|ws| ws.dpms_off.as_slice())
+        .unwrap_or_default();
+    let strip = BoxStrip::new(inner, app.saved_monitors.len(), app.saved_selected_monitor);
+    let colors = app.saved_monitor_colors();
+    let connected: Vec<String> =
+        app.monitors.iter().map(MonitorDatabase::get_monitor_key).collect();
+    let mut states = Vec::new();
+    strip.push_hits(inner, hits);
     let start_y = inner.y + (inner.height.saturating_sub(BOX_HEIGHT)) / 2;
 
     for (i, monitor) in app.saved_monitors.iter().enumerate() {
-        let x = start_x + (i as u16 * (BOX_WIDTH + BOX_GAP));
+        let Some(x) = strip.x_of(i) else {
+            continue;
+        };
         let y = start_y;
 
         let is_selected = i == app.saved_selected_monitor;
         let monitor_area = Rect::new(x, y, BOX_WIDTH, BOX_HEIGHT);
 
+        // Saved monitors are named by their database key
+        let state = if !connected.contains(&monitor.name) {
+            Some(BoxState::Offline)
+        } else if ws_dpms_off.contains(&monitor.name) {
+            Some(BoxState::Off)
+        } else if !app.monitor_db.is_managed(&monitor.name) {
+            Some(BoxState::Unmanaged)
+        } else {
+            None
+        };
+        states.extend(state);
+
         let border_type = if is_selected {
             symbols::border::DOUBLE
         } else {
-            symbols::border::PLAIN
+            state.map_or(symbols::border::PLAIN, BoxState::border_set)
         };
 
-        let style = if is_selected {
-            Style::default().fg(Color::Magenta)
-        } else {
-            Style::default().fg(Color::White)
+        let color = match state {
+            Some(state) => state.style(theme().monitor_color(colors[i])),
+            None => theme().monitor_color(colors[i]),
         };
+        let style = if is_selected { color.bold() } else { color };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_set(border_type)
             .border_style(style);
+        if let Some(state) = state {
+            block = block.title(Line::styled(state.glyph(), style));
+        }
 
         frame.render_widget(block, monitor_area);
 
-        // Monitor number + primary indicator
+        // Monitor number + primary indicator, and whether applying turns it off
         let primary_mark = if monitor.is_primary { "*" } else { " " };
-        let number_label = format!("{}{}", primary_mark, i + 1);
+        let mut number_label = format!("{}{}", primary_mark, i + 1);
+        if ws_dpms_off.contains(&monitor.name) {
+            number_label += " off";
+        }
         let number_area = Rect::new(x + 1, y + 1, BOX_WIDTH - 2, 1);
 
-        let label_style = if is_selected {
-            Style::default().fg(Color::Magenta).bold()
-        } else {
-            Style::default()
-        };
+        let label_style = style;
 
         frame.render_widget(
             Paragraph::new(number_label)
@@ -83,17 +126,13 @@ pub fn render_saved_arrangement_panel(frame: &mut Frame, area: Rect, app: &App)
         );
 
         // Monitor name
-        let name = monitor.display_name();
-        let display_name = if name.len() > (BOX_WIDTH - 2) as usize {
-            format!("{}…", &name[..(BOX_WIDTH as usize - 3)])
-        } else {
-            name
-        };
+        let name = app.monitor_db.label(monitor);
+        let display_name = text::truncate(&name, (BOX_WIDTH - 2) as usize);
 
         let name_area = Rect::new(x + 1, y + 2, BOX_WIDTH - 2, 1);
         frame.render_widget(
             Paragraph::new(display_name)
-                .style(Style::default().fg(Color::Cyan))
+                .style(theme().accent())
                 .alignment(Alignment::Center),
             name_area,
         );
@@ -102,29 +141,37 @@ pub fn render_saved_arrangement_panel(frame: &mut Frame, area: Rect, app: &App)
         let res_area = Rect::new(x + 1, y + 3, BOX_WIDTH - 2, 1);
         frame.render_widget(
             Paragraph::new(monitor.resolution.as_str())
-                .style(Style::default().fg(Color::DarkGray))
+                .style(theme().muted())
                 .alignment(Alignment::Center),
             res_area,
         );
     }
 
+    strip.render_scroll_marks(frame, inner, start_y + BOX_HEIGHT / 2);
+
+    let legend_y = (inner.y + inner.height).saturating_sub(2);
+    if !states.is_empty() && legend_y >= start_y + BOX_HEIGHT {
+        render_legend(frame, inner, legend_y, &states);
+    }
+
     // Help text
-    let help = "←→/hl Select | Edit settings below";
+    let help = "←→/hl Select | A Apply this workspace";
     let help_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
     frame.render_widget(
         Paragraph::new(help)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::DarkGray)),
+            .style(theme().muted()),
         help_area,
     );
 }
 
-pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
+pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    hits.push(area, Hit::Settings);
     let is_focused = app.focus_panel == FocusPanel::Settings && app.main_tab == MainTab::Saved;
     let border_style = if is_focused {
-        Style::default().fg(Color::Magenta)
+        theme().saved()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme().muted()
     };
 
     let monitor = match app.saved_monitors.get(app.saved_selected_monitor) {
@@ -133,17 +180,18 @@ pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .border_style(border_style)
-                .title(" Saved Settings ");
+                .title(tr(" Saved Settings "));
             frame.render_widget(block, area);
             return;
         }
     };
 
-    let title = format!(" Saved Settings - {} ", monitor.display_name());
+    let title = format!(" Saved Settings - {} ", app.monitor_db.label(monitor));
+    let color = app.saved_monitor_colors()[app.saved_selected_monitor];
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
-        .title(title);
+        .title(Line::styled(title, theme().monitor_color(color).bold()));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -156,7 +204,7 @@ pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
         let cursor = if is_selected { ">" } else { " " };
 
         let style = if is_selected {
-            Style::default().fg(Color::Magenta).bold()
+            theme().saved().bold()
         } else {
             Style::default()
         };
@@ -166,10 +214,9 @@ pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
                 y += 1;
                 let checkbox = if monitor.is_primary { "[x]" } else { "[ ]" };
                 let line = format!(" {} {} Primary monitor", cursor, checkbox);
-                frame.render_widget(
-                    Paragraph::new(line).style(style),
-                    Rect::new(inner.x, y, inner.width, 1),
-                );
+                let row = Rect::new(inner.x, y, inner.width, 1);
+                frame.render_widget(Paragraph::new(line).style(style), row);
+                hits.push(row.intersection(inner), Hit::Setting(i));
             }
             _ => {
                 let value = match field {
@@ -177,14 +224,15 @@ pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
                     SettingField::RefreshRate => format!("{:.0} Hz", monitor.refresh_rate),
                     SettingField::Scale => format!("{:.0}%", monitor.scale * 100.0),
                     SettingField::Rotation => monitor.rotation.as_str().to_string(),
+                    SettingField::PositionX => monitor.position_x.to_string(),
+                    SettingField::PositionY => monitor.position_y.to_string(),
                     _ => String::new(),
                 };
 
-                let line = format!(" {} {:<14} {:<14}", cursor, field.label(), value);
-                frame.render_widget(
-                    Paragraph::new(line).style(style),
-                    Rect::new(inner.x, y, inner.width, 1),
-                );
+                let line = format!(" {} {:<14} {:<14}", cursor, tr(field.label()), value);
+                let row = Rect::new(inner.x, y, inner.width, 1);
+                frame.render_widget(Paragraph::new(line).style(style), row);
+                hits.push(row.intersection(inner), Hit::Setting(i));
             }
         }
         y += 1;
@@ -193,8 +241,21 @@ pub fn render_saved_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
     // Note about editing
     y += 1;
     frame.render_widget(
-        Paragraph::new(" Note: Saved configs are read-only. Edit in Live panel.")
-            .style(Style::default().fg(Color::DarkGray).italic()),
+        Paragraph::new(tr(" Note: Saved configs are read-only. Edit in Live panel."))
+            .style(theme().muted().italic()),
         Rect::new(inner.x, y, inner.width, 1),
     );
+
+    if let Some(ws) = app.monitor_db.workspaces.get(app.selected_workspace) {
+        y += 1;
+        let mut rules = ws.match_rules.describe();
+        if !ws.schedule.is_empty() {
+            rules = format!("{rules} · at {}", ws.schedule_label());
+        }
+        frame.render_widget(
+            Paragraph::new(format!(" Auto-match: {rules}"))
+                .style(theme().muted()),
+            Rect::new(inner.x, y, inner.width, 1),
+        );
+    }
 }
@@ -1,17 +1,26 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 
+use super::hit::{Hit, HitMap};
+use super::theme::theme;
 use crate::app::App;
-use crate::state::{FocusPanel, SettingField};
+use crate::config;
+use crate::i18n::tr;
+use crate::state::{FocusPanel, SettingField, SettingGroup};
+use crate::text;
 
-pub fn render_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
+/// Marks settings (and monitor boxes) that differ from what was last applied.
+pub const CHANGED_MARK: &str = "•";
+
+pub fn render_settings_panel(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    hits.push(area, Hit::Settings);
     let is_focused = app.focus_panel == FocusPanel::Settings;
     let border_style = if is_focused {
-        Style::default().fg(Color::Cyan)
+        theme().accent()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme().muted()
     };
 
     let monitor = match app.current_monitor() {
@@ -20,128 +29,290 @@ pub fn render_settings_panel(frame: &mut Frame, area: Rect, app: &App) {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .border_style(border_style)
-                .title(" Settings ");
+                .title(tr(" Settings "));
             frame.render_widget(block, area);
             return;
         }
     };
 
     let title = format!(
-        " Settings for Monitor {} ({}) ",
+        " {} {} ({}) ",
+        tr("Settings for Monitor"),
         app.selected_monitor + 1,
-        monitor.display_name()
+        app.monitor_db.label(monitor)
     );
+    let color = app.monitor_colors()[app.selected_monitor];
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
-        .title(title);
+        .title(Line::styled(title, theme().monitor_color(color).bold()));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
     let fields = SettingField::all();
-    let mut y = inner.y + 1;
+    let rows = settings_rows(app);
+    let height = list_height(inner, app) as usize;
+    let selected = selected_row(app, &rows);
+    let offset = settings_offset(selected, rows.len(), height);
+    let mut y = inner.y;
 
-    for (i, field) in fields.iter().enumerate() {
-        let is_selected = i == app.selected_setting && is_focused;
+    for (k, &row) in rows.iter().enumerate().skip(offset).take(height) {
+        let is_selected = k == selected && is_focused;
         let cursor = if is_selected { ">" } else { " " };
 
         let style = if is_selected {
-            Style::default().fg(Color::Yellow).bold()
+            theme().warn().bold()
         } else {
             Style::default()
         };
+        let i = match row {
+            SettingsRow::Group(group) => {
+                let collapsed = app.collapsed_groups.contains(&group);
+                let fold = if collapsed { "▸" } else { "▼" };
+                let line = Line::from(vec![
+                    format!("{cursor}{fold} ").into(),
+                    Span::styled(tr(group.label()), theme().accent().bold()),
+                ]);
+                let row = Rect::new(inner.x, y, inner.width, 1);
+                frame.render_widget(Paragraph::new(line).style(style), row);
+                hits.push(row.intersection(inner), Hit::SettingGroup(group));
+                y += 1;
+                continue;
+            }
+            SettingsRow::Field(i) => i,
+        };
+        let field = &fields[i];
+        let changed = app.setting_changed(app.selected_monitor, *field);
+        let mark = Span::styled(if changed { CHANGED_MARK } else { " " }, theme().warn());
 
         match field {
             SettingField::Primary => {
-                y += 1; // Extra spacing
                 let checkbox = if monitor.is_primary { "[x]" } else { "[ ]" };
-                let line = format!(" {} {} Set as primary monitor", cursor, checkbox);
-                frame.render_widget(
-                    Paragraph::new(line).style(style),
-                    Rect::new(inner.x, y, inner.width, 1),
-                );
+                let label = format!("{} {}", checkbox, tr("Set as primary monitor"));
+                let row = Rect::new(inner.x, y, inner.width, 1);
+                // The checkbox and its label toggle, after " >•"
+                let toggle = Rect::new(row.x + 3, y, text::width(&label) as u16, 1);
+                let line = Line::from(vec![format!(" {}", cursor).into(), mark, label.into()]);
+                frame.render_widget(Paragraph::new(line).style(style), row);
+                hits.push(row.intersection(inner), Hit::Setting(i));
+                hits.push(toggle.intersection(inner), Hit::SettingValue(i));
             }
             _ => {
                 let value = match field {
-                    SettingField::Resolution => monitor.resolution.clone(),
-                    SettingField::RefreshRate => format!("{:.0} Hz", monitor.refresh_rate),
-                    SettingField::Scale => format!("{:.0}%", monitor.scale * 100.0),
+                    SettingField::Resolution => match monitor.mode_preset {
+                        Some(preset) => format!("{} {}", preset.keyword(), monitor.resolution),
+                        None => monitor.resolution.clone(),
+                    },
+                    SettingField::RefreshRate => {
+                        let rate = format!("{:.0} Hz", monitor.refresh_rate);
+                        match monitor.mode_preset {
+                            Some(preset) => format!("{} {rate}", preset.keyword()),
+                            // Not a mode the monitor lists for this resolution
+                            None if !monitor.mode_advertised() => format!("{rate} ⚠"),
+                            None => rate,
+                        }
+                    }
+                    SettingField::Scale => {
+                        let percent = format!("{:.0}%", monitor.scale * 100.0);
+                        // Hyprland would round it; the apply offers the fix
+                        match config::fixed_scale(&monitor.resolution, monitor.scale) {
+                            Some(_) => format!("{percent} ⚠"),
+                            None => percent,
+                        }
+                    }
                     SettingField::Rotation => monitor.rotation.as_str().to_string(),
+                    SettingField::PositionX => monitor.position_x.to_string(),
+                    SettingField::PositionY => monitor.position_y.to_string(),
                     _ => String::new(),
                 };
 
-                // Format: " > Label:          Value          [Change]"
-                let label = field.label();
-                let line = format!(" {} {:<14} {:<14} [Change]", cursor, label, value);
-                frame.render_widget(
-                    Paragraph::new(line).style(style),
-                    Rect::new(inner.x, y, inner.width, 1),
-                );
+                // Format: " >•Label:          Value          [Change]"
+                let label = tr(field.label());
+                let line = Line::from(vec![
+                    format!(" {}", cursor).into(),
+                    mark,
+                    format!("{:<14} {:<14} {}", label, value, tr("[Change]")).into(),
+                ]);
+                let row = Rect::new(inner.x, y, inner.width, 1);
+                frame.render_widget(Paragraph::new(line).style(style), row);
+                // Value and [Change] both open the editor
+                let value_x = row.x + 4 + text::width(label).max(14) as u16;
+                let value = Rect::new(value_x, y, row.right().saturating_sub(value_x), 1);
+                hits.push(row.intersection(inner), Hit::Setting(i));
+                hits.push(value.intersection(inner), Hit::SettingValue(i));
             }
         }
         y += 1;
     }
 
-    // Status message
-    if !app.message.is_empty() {
+    if rows.len() > height {
+        let mut state = ScrollbarState::new(rows.len() - height).position(offset);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            Rect::new(inner.x, inner.y, inner.width, height as u16),
+            &mut state,
+        );
+    }
+
+    if any_changed(app) && y + 1 < inner.bottom() {
         y += 1;
         frame.render_widget(
-            Paragraph::new(app.message.as_str()).style(Style::default().fg(Color::Green)),
-            Rect::new(inner.x + 1, y, inner.width - 2, 1),
+            Paragraph::new(format!(" {CHANGED_MARK} {}", tr("changed since last apply")))
+                .style(theme().muted()),
+            Rect::new(inner.x, y, inner.width, 1),
         );
     }
+    if let Some(preset) = app.suggested_preset(app.selected_monitor) {
+        if y + 1 < inner.bottom() {
+            let (label, uses) = (tr("Suggested:"), tr("s uses it"));
+            let hint = format!(" ✦ {label} {} ({uses})", preset.summary());
+            frame.render_widget(
+                Paragraph::new(hint).style(theme().accent()),
+                Rect::new(inner.x, y + 1, inner.width, 1),
+            );
+        }
+    }
 }
 
-/// Returns the row index for each setting field (for mouse click detection)
-#[allow(dead_code)]
-pub fn get_setting_row(setting_index: usize, panel_start_y: usize) -> usize {
-    // Row calculation: panel_start_y + 2 (border + padding) + setting_index
-    // Primary has an extra row of spacing before it
-    if setting_index == 4 {
-        // Primary field
-        panel_start_y + 2 + setting_index + 1
-    } else {
-        panel_start_y + 2 + setting_index
-    }
+/// One line of the Live settings list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsRow {
+    Group(SettingGroup),
+    /// Index into `SettingField::all()`.
+    Field(usize),
 }
 
-/// Converts a row position to a setting index, returns None if not on a setting
-pub fn row_to_setting(row: usize, panel_start_y: usize) -> Option<usize> {
-    if row < panel_start_y + 2 {
-        return None;
+/// The settings list: every group that has settings under its heading,
+/// the settings left out while it is collapsed.
+pub fn settings_rows(app: &App) -> Vec<SettingsRow> {
+    let mut rows = Vec::new();
+    for group in SettingGroup::all() {
+        let fields = group.fields();
+        if fields.is_empty() {
+            continue;
+        }
+        rows.push(SettingsRow::Group(group));
+        if !app.collapsed_groups.contains(&group) {
+            rows.extend(fields.into_iter().map(SettingsRow::Field));
+        }
     }
+    rows
+}
 
-    let relative_row = row - panel_start_y - 2;
-
-    // Account for extra spacing before Primary checkbox
-    if relative_row <= 3 {
-        Some(relative_row)
-    } else if relative_row == 5 {
-        // Primary checkbox (after spacing)
-        Some(4)
+/// The row showing the selected setting: its own, or its group's heading
+/// while the group is collapsed.
+fn selected_row(app: &App, rows: &[SettingsRow]) -> usize {
+    let group = SettingField::all()[app.selected_setting].group();
+    let target = if app.collapsed_groups.contains(&group) {
+        SettingsRow::Group(group)
     } else {
-        None
-    }
+        SettingsRow::Field(app.selected_setting)
+    };
+    rows.iter().position(|&row| row == target).unwrap_or(0)
+}
+
+/// First row shown when `rows` rows scroll through `height` lines: just far
+/// enough down to keep the selected row in view, so it needs no stored state.
+fn settings_offset(selected: usize, rows: usize, height: usize) -> usize {
+    (selected + 1).saturating_sub(height).min(rows.saturating_sub(height))
+}
+
+fn any_changed(app: &App) -> bool {
+    SettingField::all().iter().any(|f| app.setting_changed(app.selected_monitor, *f))
+}
+
+/// Lines the list gets inside the panel's `inner` area: all but the
+/// footnote's two while something changed.
+fn list_height(inner: Rect, app: &App) -> u16 {
+    let footnote = if any_changed(app) { 2 } else { 0 };
+    inner.height.saturating_sub(footnote)
+}
+
+/// Screen row of the selected setting inside the settings panel `area`, for
+/// the dropdown to open under.
+pub fn selected_setting_y(area: Rect, app: &App) -> u16 {
+    let inner = area.inner(Margin::new(1, 1));
+    let rows = settings_rows(app);
+    let height = list_height(inner, app) as usize;
+    let selected = selected_row(app, &rows);
+    let offset = settings_offset(selected, rows.len(), height);
+    inner.y + (selected - offset) as u16
 }
 
 #[cfg(test)]
 mod tests {
-    use super::row_to_setting;
+    use super::*;
+    use crate::monitor::MonitorConfig;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn hits_for(app: &App, panel: Rect) -> HitMap {
+        let mut hits = HitMap::default();
+        let mut t = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        t.draw(|f| render_settings_panel(f, panel, app, &mut hits)).unwrap();
+        hits
+    }
+
+    fn app() -> App {
+        App::for_test(vec![MonitorConfig::for_test("DP-1", "Dell", "U27", "2560x1440")])
+    }
+
+    fn hits_at(panel: Rect) -> HitMap {
+        hits_for(&app(), panel)
+    }
+
+    #[test]
+    fn registers_a_row_per_setting_under_group_headings() {
+        let hits = hits_at(Rect::new(0, 10, 80, 14));
+        assert_eq!(hits.at(2, 11), Some(Hit::SettingGroup(SettingGroup::Mode)));
+        assert_eq!(hits.at(2, 12), Some(Hit::Setting(0))); // Resolution
+        assert_eq!(hits.at(2, 13), Some(Hit::Setting(1)));
+        assert_eq!(hits.at(2, 14), Some(Hit::SettingGroup(SettingGroup::Geometry)));
+        for i in 0..4 {
+            assert_eq!(hits.at(2, 15 + i), Some(Hit::Setting(2 + i as usize))); // Scale…Y
+        }
+        assert_eq!(hits.at(2, 19), Some(Hit::SettingGroup(SettingGroup::Advanced)));
+        assert_eq!(hits.at(1, 20), Some(Hit::Setting(6))); // Primary
+        assert_eq!(hits.at(4, 20), Some(Hit::SettingValue(6))); // its checkbox
+        assert_eq!(hits.at(10, 12), Some(Hit::Setting(0))); // the label
+        assert_eq!(hits.at(19, 12), Some(Hit::SettingValue(0)));
+        assert_eq!(hits.at(60, 12), Some(Hit::SettingValue(0))); // [Change]
+        assert_eq!(hits.at(2, 21), Some(Hit::Settings)); // below the list
+        assert_eq!(hits.at(85, 12), None);
+    }
 
     #[test]
-    fn maps_regular_setting_rows() {
-        let panel_start = 10;
-        assert_eq!(row_to_setting(panel_start + 2, panel_start), Some(0)); // Resolution
-        assert_eq!(row_to_setting(panel_start + 3, panel_start), Some(1)); // Refresh Rate
-        assert_eq!(row_to_setting(panel_start + 4, panel_start), Some(2)); // Scale
-        assert_eq!(row_to_setting(panel_start + 5, panel_start), Some(3)); // Rotation
+    fn rows_cut_off_by_a_short_panel_are_not_clickable() {
+        let hits = hits_at(Rect::new(0, 10, 80, 6));
+        assert_eq!(hits.rect(Hit::Setting(1)), Some(Rect::new(1, 13, 78, 1)));
+        assert_eq!(hits.rect(Hit::Setting(2)), None);
+        assert_eq!(hits.rect(Hit::Setting(6)), None);
     }
 
     #[test]
-    fn skips_spacing_and_maps_primary_row() {
-        let panel_start = 10;
-        assert_eq!(row_to_setting(panel_start + 6, panel_start), None); // spacing row
-        assert_eq!(row_to_setting(panel_start + 7, panel_start), Some(4)); // Primary
+    fn the_list_scrolls_to_the_selection_and_folds_groups() {
+        let panel = Rect::new(0, 10, 80, 6);
+        let mut a = app();
+        a.focus_panel = FocusPanel::Settings;
+        a.selected_setting = 6; // Primary, the last row
+        let hits = hits_for(&a, panel);
+        assert_eq!(hits.rect(Hit::Setting(4)), Some(Rect::new(1, 11, 78, 1)));
+        assert_eq!(hits.at(2, 13), Some(Hit::SettingGroup(SettingGroup::Advanced)));
+        assert_eq!(hits.rect(Hit::Setting(6)), Some(Rect::new(1, 14, 78, 1)));
+        assert_eq!(selected_setting_y(panel, &a), 14);
+
+        // Folding Geometry leaves its heading standing in for its settings
+        a.selected_setting = 3;
+        a.toggle_setting_group(SettingGroup::Geometry);
+        assert_eq!(a.selected_setting, 2);
+        let hits = hits_for(&a, panel);
+        assert_eq!(hits.at(2, 14), Some(Hit::SettingGroup(SettingGroup::Geometry)));
+        assert_eq!(hits.rect(Hit::Setting(2)), None);
+        assert_eq!(selected_setting_y(panel, &a), 14);
+        assert_eq!(a.setting_stops(), [0, 1, 2, 6]);
+        a.step_setting(1);
+        assert_eq!(a.selected_setting, 6);
     }
 }
@@ -0,0 +1,187 @@
+//! Whole-screen render tests: each draws the app in a representative state
+//! and compares the text on screen with `snapshots/<name>.txt`. After an
+//! intended layout change, rerun with `UPDATE_SNAPSHOTS=1` to rewrite them and
+//! review the diff like any other change.
+
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::path::PathBuf;
+use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::App;
+use crate::config::MonitorDatabase;
+use crate::monitor::{MonitorConfig, Rotation};
+use crate::state::{ApplyStatus, DialogButton, DialogType, DragState, FocusPanel, MainTab};
+
+/// The screen as lines of text, trailing blanks trimmed. Styles are left
+/// out; the theme has tests of its own.
+fn screen_text(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in 0..buffer.area.width {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = symbol.width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn assert_snapshot(name: &str, app: &App, width: u16, height: u16) {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| drop(super::render(frame, app))).unwrap();
+    let actual = screen_text(terminal.backend().buffer());
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!("no snapshot {}; run with UPDATE_SNAPSHOTS=1 to record it", path.display());
+    };
+    if actual != expected {
+        let row = actual.lines().zip(expected.lines()).position(|(a, e)| a != e);
+        panic!(
+            "{name} differs from its snapshot (first at row {}); rerun with \
+             UPDATE_SNAPSHOTS=1 if intended\n--- expected\n{expected}--- actual\n{actual}",
+            row.unwrap_or(actual.lines().count().min(expected.lines().count())),
+        );
+    }
+}
+
+fn one() -> Vec<MonitorConfig> {
+    let mut laptop = MonitorConfig::acme("eDP-1", "Panel", "1920x1080", 0);
+    laptop.is_primary = true;
+    vec![laptop]
+}
+
+fn two() -> Vec<MonitorConfig> {
+    let mut monitors = one();
+    monitors.push(MonitorConfig::acme("DP-1", "Wide", "2560x1440", 1920));
+    monitors
+}
+
+/// Two landscape monitors either side of a portrait one, plus a TV.
+fn four() -> Vec<MonitorConfig> {
+    let mut monitors = two();
+    let mut portrait = MonitorConfig::acme("DP-2", "Tall", "2560x1440", 4480);
+    portrait.rotation = Rotation::Left;
+    monitors.push(portrait);
+    let mut tv = MonitorConfig::acme("HDMI-A-1", "TV", "1920x1080", 5920);
+    tv.scale = 2.0;
+    monitors.push(tv);
+    monitors
+}
+
+#[test]
+fn live_with_one_two_and_four_monitors() {
+    assert_snapshot("live_one", &App::for_test(one()), 140, 44);
+    assert_snapshot("live_two", &App::for_test(two()), 140, 44);
+    assert_snapshot("live_four", &App::for_test(four()), 140, 44);
+}
+
+#[test]
+fn live_while_dragging() {
+    let mut app = App::for_test(two());
+    app.drag_state = DragState::Dragging {
+        monitor_idx: 0,
+        start_x: 40,
+        start_y: 8,
+        current_x: 90,
+        current_y: 8,
+    };
+    assert_snapshot("live_dragging", &app, 140, 44);
+}
+
+#[test]
+fn live_with_off_unmanaged_and_mirrored_monitors() {
+    let mut app = App::for_test(four());
+    let mut db = MonitorDatabase::default();
+    for monitor in &app.monitors {
+        db.update_monitor(monitor);
+    }
+    db.workspaces[0].dpms_off.push(MonitorDatabase::get_monitor_key(&app.monitors[3]));
+    db.toggle_managed(&MonitorDatabase::get_monitor_key(&app.monitors[2]));
+    app.monitor_db = db;
+    assert_snapshot("live_states", &app, 140, 44);
+
+    app.presenting = Some("eDP-1".to_string());
+    assert_snapshot("live_presenting", &app, 140, 44);
+}
+
+#[test]
+fn live_after_a_partly_failed_apply() {
+    let mut app = App::for_test(four());
+    app.apply_status = vec![
+        ("eDP-1".to_string(), ApplyStatus::Verified),
+        ("DP-1".to_string(), ApplyStatus::Failed("scale asked 1.25, got 1.00".to_string())),
+        ("DP-2".to_string(), ApplyStatus::Applied),
+        ("HDMI-A-1".to_string(), ApplyStatus::Queued),
+    ];
+    assert_snapshot("live_apply_status", &app, 140, 44);
+}
+
+#[test]
+fn settings_with_an_edit_and_a_folded_group() {
+    let mut app = App::for_test(two());
+    app.focus_panel = FocusPanel::Settings;
+    app.selected_monitor = 1;
+    app.monitors[1].scale = 1.25;
+    app.has_changes = true;
+    app.collapsed_groups.push(crate::state::SettingGroup::Advanced);
+    assert_snapshot("settings_edited", &app, 140, 44);
+}
+
+#[test]
+fn saved_tab_with_two_workspaces() {
+    let mut db = MonitorDatabase::default();
+    for monitor in &two() {
+        db.update_monitor(monitor);
+    }
+    db.add_workspace("Desk").unwrap();
+    // Creation times are shown in local time; leave them out
+    for workspace in &mut db.workspaces {
+        workspace.created_at = None;
+    }
+    let mut app = App::for_test(two());
+    app.monitor_db = db;
+    app.main_tab = MainTab::Saved;
+    app.refresh_saved_monitors();
+    assert_snapshot("saved_two_workspaces", &app, 140, 44);
+}
+
+#[test]
+fn dialogs_over_the_live_tab() {
+    let mut app = App::for_test(two());
+    app.dialog = DialogType::ConfirmApply {
+        countdown: 15,
+        started: Instant::now(),
+        focus: DialogButton::Yes,
+    };
+    assert_snapshot("dialog_confirm_apply", &app, 140, 44);
+
+    app.focus_panel = FocusPanel::Settings;
+    app.open_dropdown();
+    assert_snapshot("dialog_resolution_dropdown", &app, 140, 44);
+
+    app.dialog = DialogType::Help { scroll: 0 };
+    assert_snapshot("dialog_help", &app, 140, 44);
+}
+
+#[test]
+fn small_and_tiny_terminals() {
+    assert_snapshot("compact_80x24", &App::for_test(two()), 80, 24);
+    assert_snapshot("too_small_30x8", &App::for_test(two()), 30, 8);
+}
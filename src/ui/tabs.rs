@@ -1,40 +1,57 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use super::hit::{Hit, HitMap};
+use super::theme::theme;
+use super::{MIN_HEIGHT, MIN_WIDTH};
 use crate::app::App;
-use crate::state::MainTab;
+use crate::i18n::tr;
+use crate::state::{DragState, FocusPanel, MainTab};
+use crate::text;
 
-pub fn render_main_tabs(frame: &mut Frame, area: Rect, app: &App) {
+pub fn render_main_tabs(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
     let is_live = app.main_tab == MainTab::Live;
+    // Either half of the row picks the tab drawn on that side
+    let half = area.width / 2;
+    hits.push(Rect::new(area.x, area.y, half, area.height), Hit::MainTab(MainTab::Live));
+    let right = Rect::new(area.x + half, area.y, area.width - half, area.height);
+    hits.push(right, Hit::MainTab(MainTab::Saved));
     
     // Calculate positions for centered tabs
     let live_text = " 1 Live ";
+    // Hyprland drifted from the active workspace (e.g. a manual hyprctl call)
+    let badge = if app.out_of_sync() { tr("[modified] ") } else { "" };
     let saved_text = " 2 Saved ";
     let gap = 2;
-    let total_width = live_text.len() + saved_text.len() + gap + 4; // +4 for borders
+    let badge_width = text::width(badge);
+    let total_width = live_text.len() + badge_width + saved_text.len() + gap + 4; // +4 for borders
     let start_x = area.x + (area.width.saturating_sub(total_width as u16)) / 2;
     
     // Live tab box
-    let live_width = live_text.len() as u16 + 2;
+    let live_width = (live_text.len() + badge_width) as u16 + 2;
+    let live_line = Line::from(vec![
+        Span::raw(live_text),
+        Span::styled(badge, theme().warn()),
+    ]);
     let live_area = Rect::new(start_x, area.y, live_width, 3);
     
     if is_live {
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(theme().accent());
         frame.render_widget(block, live_area);
         frame.render_widget(
-            Paragraph::new(live_text)
-                .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            Paragraph::new(live_line)
+                .style(theme().accent().add_modifier(Modifier::BOLD))
                 .alignment(Alignment::Center),
             Rect::new(live_area.x + 1, live_area.y + 1, live_area.width - 2, 1),
         );
     } else {
         frame.render_widget(
-            Paragraph::new(live_text)
-                .style(Style::default().fg(Color::DarkGray))
+            Paragraph::new(live_line)
+                .style(theme().muted())
                 .alignment(Alignment::Center),
             Rect::new(live_area.x + 1, live_area.y + 1, live_area.width - 2, 1),
         );
@@ -48,71 +65,200 @@ pub fn render_main_tabs(frame: &mut Frame, area: Rect, app: &App) {
     if !is_live {
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Magenta));
+            .border_style(theme().saved());
         frame.render_widget(block, saved_area);
         frame.render_widget(
             Paragraph::new(saved_text)
-                .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+                .style(theme().saved().add_modifier(Modifier::BOLD))
                 .alignment(Alignment::Center),
             Rect::new(saved_area.x + 1, saved_area.y + 1, saved_area.width - 2, 1),
         );
     } else {
         frame.render_widget(
             Paragraph::new(saved_text)
-                .style(Style::default().fg(Color::DarkGray))
+                .style(theme().muted())
                 .alignment(Alignment::Center),
             Rect::new(saved_area.x + 1, saved_area.y + 1, saved_area.width - 2, 1),
         );
     }
+
+    // Lock indicator, right-aligned on the tab row
+    if app.monitor_db.lock_active {
+        let text = format!("{} {} ", tr("Locked:"), app.active_workspace_name());
+        let width = (text::width(&text) as u16).min(area.width);
+        frame.render_widget(
+            Paragraph::new(text).style(theme().warn()),
+            Rect::new(area.x + area.width - width, area.y + 1, width, 1),
+        );
+    }
 }
 
-pub fn render_workspace_tabs(frame: &mut Frame, area: Rect, app: &App) {
-    let mut spans: Vec<Span> = vec![Span::styled(
-        " Workspaces: ",
-        Style::default().fg(Color::DarkGray),
-    )];
+/// Columns kept for the "◀ +n " / " +n ▶" marks of workspace tabs scrolled
+/// out of view.
+const TAB_MARK_WIDTH: usize = 6;
+
+/// The range of `widths` (tab widths, two columns apart) shown in `room`
+/// columns: all of them when they fit, else as many as fit next to marks on
+/// both sides, grown around `selected` so the active tab is always in view.
+fn visible_tabs(widths: &[usize], selected: usize, room: usize) -> std::ops::Range<usize> {
+    let span = |range: &std::ops::Range<usize>| {
+        widths[range.clone()].iter().sum::<usize>() + 2 * range.len().saturating_sub(1)
+    };
+    if widths.is_empty() || span(&(0..widths.len())) <= room {
+        return 0..widths.len();
+    }
+    let room = room.saturating_sub(2 * TAB_MARK_WIDTH);
+    let selected = selected.min(widths.len() - 1);
+    let mut range = selected..selected + 1;
+    // Grow a tab to the right, then one to the left, and so on
+    let mut right_first = true;
+    loop {
+        let right = (range.end < widths.len()).then(|| range.start..range.end + 1);
+        let left = (range.start > 0).then(|| range.start - 1..range.end);
+        let (first, second) = if right_first { (right, left) } else { (left, right) };
+        match first.into_iter().chain(second).find(|grown| span(grown) <= room) {
+            Some(grown) => range = grown,
+            None => return range,
+        }
+        right_first = !right_first;
+    }
+}
 
+pub fn render_workspace_tabs(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    // A dragged tab is drawn like a dragged monitor box, and the one it
+    // would drop on underlined
+    let (dragged, over) = match app.drag_state {
+        DragState::Workspace { idx, over } => (Some(idx), Some(over).filter(|&o| o != idx)),
+        _ => (None, None),
+    };
+    let mut tabs: Vec<Vec<Span>> = Vec::new();
     for (i, ws) in app.monitor_db.workspaces.iter().enumerate() {
         let is_selected = i == app.selected_workspace;
+        let mut spans = Vec::new();
 
-        if i > 0 {
-            spans.push(Span::styled("  ", Style::default()));
+        if ws.pinned {
+            spans.push(Span::styled("★", theme().warn()));
+        }
+        if ws.manual_only {
+            spans.push(Span::styled("⊘", theme().muted()));
+        }
+        if app.monitor_db.is_bundled(i) {
+            spans.push(Span::styled("⚙", theme().muted()));
         }
 
         if is_selected {
-            spans.push(Span::styled("▸ ", Style::default().fg(Color::Magenta)));
+            spans.push(Span::styled("▸ ", theme().saved()));
             spans.push(Span::styled(
                 ws.name.clone(),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
+                theme().text().add_modifier(Modifier::BOLD),
             ));
         } else {
             spans.push(Span::styled(
                 ws.name.clone(),
-                Style::default().fg(Color::DarkGray),
+                theme().muted(),
             ));
         }
+        if dragged == Some(i) {
+            spans = spans.into_iter().map(|s| s.style(theme().ok().bold())).collect();
+        } else if over == Some(i) {
+            spans = spans.into_iter().map(|s| s.underlined()).collect();
+        }
+        tabs.push(spans);
+    }
+
+    let block = Block::default()
+        .borders(Borders::BOTTOM)
+        .border_style(theme().muted());
+    let inner = block.inner(area);
+
+    // The [+] button after the tabs takes six columns
+    let label = tr(" Workspaces: ");
+    let widths: Vec<usize> = tabs
+        .iter()
+        .map(|spans| spans.iter().map(|span| text::width(&span.content)).sum())
+        .collect();
+    let room = (inner.width as usize).saturating_sub(text::width(label) + 6);
+    let shown = visible_tabs(&widths, app.selected_workspace, room);
+    let hidden_right = tabs.len() - shown.end;
+
+    let mut spans: Vec<Span> = vec![Span::styled(label, theme().muted())];
+    if shown.start > 0 {
+        spans.push(Span::styled(format!("◀ +{} ", shown.start), theme().warn()));
+    }
+    hits.push(inner, Hit::WorkspaceTabs);
+    let mut x = inner.x as usize + spans.iter().map(|s| text::width(&s.content)).sum::<usize>();
+    for (i, tab) in tabs.into_iter().enumerate().skip(shown.start).take(shown.len()) {
+        if i > shown.start {
+            spans.push(Span::styled("  ", Style::default()));
+            x += 2;
+        }
+        let tab_area = Rect::new(x as u16, inner.y, widths[i] as u16, 1);
+        hits.push(tab_area.intersection(inner), Hit::WorkspaceTab(i));
+        x += widths[i];
+        spans.extend(tab);
+    }
+    if hidden_right > 0 {
+        spans.push(Span::styled(format!(" +{hidden_right} ▶"), theme().warn()));
     }
 
     // Add [+] button
     spans.push(Span::styled("   ", Style::default()));
-    spans.push(Span::styled("[", Style::default().fg(Color::DarkGray)));
+    spans.push(Span::styled("[", theme().muted()));
     spans.push(Span::styled(
         "+",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
+        theme().ok().add_modifier(Modifier::BOLD),
     ));
-    spans.push(Span::styled("]", Style::default().fg(Color::DarkGray)));
+    spans.push(Span::styled("]", theme().muted()));
 
     let line = Line::from(spans);
 
-    let block = Block::default()
-        .borders(Borders::BOTTOM)
-        .border_style(Style::default().fg(Color::DarkGray));
-
-    let inner = block.inner(area);
     frame.render_widget(block, area);
     frame.render_widget(Paragraph::new(line), inner);
 }
+
+/// Compact-mode row naming the two panels, the visible one highlighted.
+/// Clicking either half (or Tab) switches.
+pub fn render_panel_switcher(frame: &mut Frame, area: Rect, app: &App, hits: &mut HitMap) {
+    let tab = |label: &'static str, panel: FocusPanel| {
+        if app.focus_panel == panel {
+            let style = theme().accent().add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            Span::styled(label, style)
+        } else {
+            Span::styled(label, theme().muted())
+        }
+    };
+    let half = area.width / 2;
+    let left = Rect::new(area.x, area.y, half, 1);
+    let right = Rect::new(area.x + half, area.y, area.width - half, 1);
+    frame.render_widget(
+        Paragraph::new(tab(tr(" Arrangement "), FocusPanel::Arrangement))
+            .alignment(Alignment::Center),
+        left,
+    );
+    frame.render_widget(
+        Paragraph::new(tab(tr(" Settings "), FocusPanel::Settings)).alignment(Alignment::Center),
+        right,
+    );
+    hits.push(left, Hit::Panel(FocusPanel::Arrangement));
+    hits.push(right, Hit::Panel(FocusPanel::Settings));
+}
+
+/// Shown instead of the main view when the terminal is below the minimum size.
+pub fn render_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let text = vec![
+        Line::from(tr("Terminal too small")).style(theme().warn().bold()),
+        Line::from(format!(
+            "{}x{}, {} {MIN_WIDTH}x{MIN_HEIGHT}",
+            area.width,
+            area.height,
+            tr("need at least")
+        )),
+        Line::from(tr("q quits")).style(theme().muted()),
+    ];
+    let y = area.y + area.height.saturating_sub(3) / 2;
+    frame.render_widget(
+        Paragraph::new(text).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+        Rect::new(area.x, y, area.width, area.height.min(3)),
+    );
+}
@@ -0,0 +1,250 @@
+use anyhow::{bail, Result};
+use ratatui::prelude::*;
+use std::sync::OnceLock;
+
+use crate::monitor::identify_color;
+use crate::prefs::Prefs;
+
+/// Palette every panel and dialog draws with. `Color::Reset` roles fall back to
+/// modifiers (reverse video, dim) so the monochrome theme stays readable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Live tab, focused borders, key hints, input dialogs.
+    pub accent: Color,
+    /// Saved tab and its workspace strip.
+    pub saved: Color,
+    /// Unfocused borders and secondary text.
+    pub muted: Color,
+    pub text: Color,
+    /// Selected monitor, badges, confirm dialogs, headings.
+    pub warn: Color,
+    /// Primary monitor and status messages.
+    pub ok: Color,
+    /// Destructive confirmations.
+    pub danger: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    pub button_fg: Color,
+    pub button_bg: Color,
+}
+
+/// Names accepted by `theme = "…"` in config.toml.
+pub const THEMES: &[&str] = &["default", "light", "high-contrast", "monochrome"];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            accent: Color::Cyan,
+            saved: Color::Magenta,
+            muted: Color::DarkGray,
+            text: Color::White,
+            warn: Color::Yellow,
+            ok: Color::Green,
+            danger: Color::Red,
+            selection_fg: Color::Black,
+            selection_bg: Color::Cyan,
+            button_fg: Color::Black,
+            button_bg: Color::Gray,
+        }
+    }
+}
+
+impl Theme {
+    pub fn named(name: &str) -> Option<Self> {
+        let base = Self::default();
+        Some(match name {
+            "default" => base,
+            "light" => Self {
+                accent: Color::Blue,
+                muted: Color::Gray,
+                text: Color::Black,
+                warn: Color::Rgb(0xb5, 0x89, 0x00),
+                ok: Color::Rgb(0x2e, 0x7d, 0x32),
+                selection_fg: Color::White,
+                selection_bg: Color::Blue,
+                button_fg: Color::White,
+                button_bg: Color::DarkGray,
+                ..base
+            },
+            "high-contrast" => Self {
+                accent: Color::LightCyan,
+                saved: Color::LightMagenta,
+                muted: Color::Gray,
+                warn: Color::LightYellow,
+                ok: Color::LightGreen,
+                danger: Color::LightRed,
+                selection_bg: Color::LightCyan,
+                button_bg: Color::White,
+                ..base
+            },
+            "monochrome" => Self {
+                accent: Color::Reset,
+                saved: Color::Reset,
+                muted: Color::Reset,
+                text: Color::Reset,
+                warn: Color::Reset,
+                ok: Color::Reset,
+                danger: Color::Reset,
+                selection_fg: Color::Reset,
+                selection_bg: Color::Reset,
+                button_fg: Color::Reset,
+                button_bg: Color::Reset,
+            },
+            _ => return None,
+        })
+    }
+
+    /// The `theme` named in the config (monochrome when unset and `NO_COLOR`
+    /// is set), with any `[colors]` overrides laid on top.
+    pub fn from_prefs(prefs: &Prefs) -> Result<Self> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let name = match &prefs.theme {
+            Some(name) => name.as_str(),
+            None if no_color => "monochrome",
+            None => "default",
+        };
+        let Some(mut theme) = Self::named(name) else {
+            bail!("unknown theme '{name}' (built-in: {})", THEMES.join(", "));
+        };
+        for (role, value) in &prefs.colors {
+            let Ok(color) = value.parse::<Color>() else {
+                bail!("[colors] {role}: '{value}' is not a color name, index or #rrggbb");
+            };
+            *theme.role_mut(role)? = color;
+        }
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Result<&mut Color> {
+        Ok(match role {
+            "accent" => &mut self.accent,
+            "saved" => &mut self.saved,
+            "muted" => &mut self.muted,
+            "text" => &mut self.text,
+            "warn" => &mut self.warn,
+            "ok" => &mut self.ok,
+            "danger" => &mut self.danger,
+            "selection_fg" => &mut self.selection_fg,
+            "selection_bg" => &mut self.selection_bg,
+            "button_fg" => &mut self.button_fg,
+            "button_bg" => &mut self.button_bg,
+            _ => bail!("unknown color role '{role}' in [colors]"),
+        })
+    }
+
+    pub fn accent(&self) -> Style {
+        Style::default().fg(self.accent)
+    }
+
+    pub fn saved(&self) -> Style {
+        Style::default().fg(self.saved)
+    }
+
+    pub fn muted(&self) -> Style {
+        let style = Style::default().fg(self.muted);
+        if self.muted == Color::Reset {
+            style.add_modifier(Modifier::DIM)
+        } else {
+            style
+        }
+    }
+
+    pub fn text(&self) -> Style {
+        Style::default().fg(self.text)
+    }
+
+    pub fn warn(&self) -> Style {
+        Style::default().fg(self.warn)
+    }
+
+    pub fn ok(&self) -> Style {
+        Style::default().fg(self.ok)
+    }
+
+    pub fn danger(&self) -> Style {
+        Style::default().fg(self.danger)
+    }
+
+    /// A monitor's own color (see `App::monitor_colors`), the one identify
+    /// flashes it in; plain text when the theme has no colors.
+    pub fn monitor_color(&self, color: usize) -> Style {
+        if self.text == Color::Reset {
+            return Style::default();
+        }
+        let (r, g, b) = identify_color(color);
+        Style::default().fg(Color::Rgb(r, g, b))
+    }
+
+    /// Highlighted row in lists and dropdowns.
+    pub fn selection(&self) -> Style {
+        reverse_if_plain(self.selection_fg, self.selection_bg)
+    }
+
+    /// Clickable dialog buttons.
+    pub fn button(&self) -> Style {
+        reverse_if_plain(self.button_fg, self.button_bg)
+    }
+}
+
+fn reverse_if_plain(fg: Color, bg: Color) -> Style {
+    if bg == Color::Reset {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default().fg(fg).bg(bg)
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Install the theme for the rest of the process; only the first call counts.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The active theme (the default one until [`set_theme`] is called).
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(toml: &str) -> Prefs {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn every_builtin_theme_resolves() {
+        for name in THEMES {
+            assert!(Theme::named(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::named("default"), Some(Theme::default()));
+        assert!(Theme::named("solarized").is_none());
+    }
+
+    #[test]
+    fn config_picks_theme_and_overrides_roles() {
+        let theme = Theme::from_prefs(&prefs(
+            "theme = \"high-contrast\"\n[colors]\naccent = \"#ff8800\"\ndanger = \"9\"\n",
+        ))
+        .unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.danger, Color::Indexed(9));
+        assert_eq!(theme.saved, Color::LightMagenta);
+
+        assert!(Theme::from_prefs(&prefs("theme = \"nope\"\n")).is_err());
+        assert!(Theme::from_prefs(&prefs("[colors]\nborder = \"red\"\n")).is_err());
+        assert!(Theme::from_prefs(&prefs("[colors]\naccent = \"redish\"\n")).is_err());
+    }
+
+    #[test]
+    fn monochrome_uses_modifiers_instead_of_colors() {
+        let mono = Theme::named("monochrome").unwrap();
+        assert_eq!(mono.selection(), Style::default().add_modifier(Modifier::REVERSED));
+        assert!(mono.muted().add_modifier.contains(Modifier::DIM));
+        assert_eq!(Theme::default().selection().bg, Some(Color::Cyan));
+        assert_eq!(mono.monitor_color(0), Style::default());
+        assert_ne!(Theme::default().monitor_color(0), Theme::default().monitor_color(1));
+    }
+}
@@ -0,0 +1,97 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::theme::theme;
+use crate::app::App;
+use crate::clock;
+use crate::i18n::tr;
+use crate::toast::Level;
+use crate::text;
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::Info => theme().ok(),
+        Level::Warn => theme().warn(),
+        Level::Error => theme().danger(),
+    }
+}
+
+/// Stack the live toasts in the bottom-right corner of `area`, newest lowest.
+pub fn render_toasts(frame: &mut Frame, area: Rect, app: &App) {
+    let mut bottom = area.y + area.height;
+    for toast in app.toasts.visible().into_iter().rev() {
+        if bottom < area.y + 3 {
+            break;
+        }
+        let text = toast.label();
+        let width = (text::width(&text) as u16 + 4).min(area.width / 2).max(12);
+        let rect = Rect::new(
+            area.x + area.width.saturating_sub(width + 1),
+            bottom - 3,
+            width.min(area.width),
+            3,
+        );
+        let style = level_style(toast.level);
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(text).style(style).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(style)
+                    .title(format!(" {} ", toast.level.as_str())),
+            ),
+            rect,
+        );
+        bottom -= 3;
+    }
+}
+
+/// Message history lines, newest first, each tagged with its level.
+pub fn message_lines(app: &App) -> Vec<(Level, String)> {
+    app.toasts
+        .history()
+        .iter()
+        .rev()
+        .map(|t| {
+            let when = clock::format_local(t.at);
+            (t.level, format!("{when}  {:<5}  {}", t.level.as_str(), t.label()))
+        })
+        .collect()
+}
+
+/// Scrollable list of every status message this session.
+pub fn render_messages_dialog(frame: &mut Frame, app: &App, scroll: usize) {
+    let height = frame.area().height.saturating_sub(4).max(5);
+    let area = super::centered_rect(80, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().accent())
+        .title(tr(" Messages "))
+        .title_bottom(Line::from(tr(" ↑↓ Scroll | Esc Close ")).centered());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = message_lines(app);
+    if lines.is_empty() {
+        frame.render_widget(
+            Paragraph::new(tr("No messages yet."))
+                .alignment(Alignment::Center)
+                .style(theme().muted()),
+            inner,
+        );
+        return;
+    }
+
+    let text: Vec<Line> = lines
+        .into_iter()
+        .skip(scroll)
+        .map(|(level, line)| Line::from(line).style(level_style(level)))
+        .collect();
+    frame.render_widget(Paragraph::new(text), inner);
+}
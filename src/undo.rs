@@ -0,0 +1,92 @@
+/// Maximum number of snapshots kept on each side of the history. Layout edits
+/// are small (a handful of monitors), so this is generous without growing
+/// unbounded during a long drag-and-tweak session.
+const MAX_DEPTH: usize = 100;
+
+/// Snapshot-based undo/redo history.
+///
+/// Callers push the state *before* a mutation with [`UndoStack::record`]. Undo
+/// swaps the current state for the last recorded one and stashes the current
+/// state for redo; any new edit invalidates the redo branch.
+#[derive(Debug, Clone)]
+pub struct UndoStack<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T> UndoStack<T> {
+    /// Record `before` as the state to return to on the next undo.
+    pub fn record(&mut self, before: T) {
+        if self.undo.len() == MAX_DEPTH {
+            self.undo.remove(0);
+        }
+        self.undo.push(before);
+        self.redo.clear();
+    }
+
+    /// Step back one edit. Returns the state to restore, or `None` when there is
+    /// nothing to undo (in which case `current` is dropped untouched).
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let prev = self.undo.pop()?;
+        self.redo.push(current);
+        Some(prev)
+    }
+
+    /// Re-apply the most recently undone edit.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo_walks_history_both_ways() {
+        let mut s = UndoStack::default();
+        s.record(1);
+        s.record(2);
+        assert_eq!(s.undo(3), Some(2));
+        assert_eq!(s.undo(2), Some(1));
+        assert_eq!(s.undo(1), None);
+        assert_eq!(s.redo(1), Some(2));
+        assert_eq!(s.redo(2), Some(3));
+        assert_eq!(s.redo(3), None);
+    }
+
+    #[test]
+    fn new_edit_discards_redo_branch_and_depth_is_capped() {
+        let mut s = UndoStack::default();
+        s.record(1);
+        assert_eq!(s.undo(2), Some(1));
+        assert!(!s.redo.is_empty());
+        s.record(1);
+        assert!(s.redo.is_empty());
+
+        let mut capped = UndoStack::default();
+        for i in 0..(MAX_DEPTH + 5) {
+            capped.record(i);
+        }
+        assert_eq!(capped.undo.len(), MAX_DEPTH);
+        assert_eq!(capped.undo[0], 5); // oldest entries dropped first
+        capped.clear();
+        assert!(capped.undo.is_empty());
+    }
+}